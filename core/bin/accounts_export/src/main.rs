@@ -0,0 +1,351 @@
+use anyhow::{bail, ensure, format_err};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    str::FromStr,
+};
+use structopt::StructOpt;
+use zksync_crypto::{
+    convert::FeConvert,
+    params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL},
+    Fr,
+};
+use zksync_state::state::ZkSyncState;
+use zksync_storage::StorageProcessor;
+use zksync_types::{Account, AccountId, Address, BlockNumber, PubKeyHash, TokenId};
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Csv,
+    JsonLines,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "jsonl" => Ok(Self::JsonLines),
+            _ => bail!("Unknown format {}, expected `csv` or `jsonl`", s),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ExportHeader {
+    block_number: BlockNumber,
+    root_hash: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportedAccount {
+    account_id: AccountId,
+    address: Address,
+    pub_key_hash: PubKeyHash,
+    nonce: u32,
+    /// Non-zero balances by the token symbol.
+    balances: BTreeMap<String, String>,
+    /// Identifiers of the NFTs owned by the account.
+    nfts: Vec<TokenId>,
+}
+
+impl ExportedAccount {
+    fn new(
+        account_id: AccountId,
+        account: Account,
+        token_symbols: &HashMap<TokenId, String>,
+    ) -> anyhow::Result<Self> {
+        let mut balances = BTreeMap::new();
+        let mut nfts = Vec::new();
+        for (token_id, balance) in account.get_nonzero_balances() {
+            match token_id.0 {
+                NFT_TOKEN_ID_VAL => {
+                    // Special token used to track the minted NFTs, it's neither a balance nor an NFT.
+                }
+                MIN_NFT_TOKEN_ID..=NFT_TOKEN_ID_VAL => nfts.push(token_id),
+                _ => {
+                    let symbol = token_symbols
+                        .get(&token_id)
+                        .ok_or_else(|| format_err!("Unknown token {}", *token_id))?;
+                    balances.insert(symbol.clone(), balance.0.to_string());
+                }
+            }
+        }
+        nfts.sort_unstable();
+
+        Ok(Self {
+            account_id,
+            address: account.address,
+            pub_key_hash: account.pub_key_hash,
+            nonce: *account.nonce,
+            balances,
+            nfts,
+        })
+    }
+
+    /// CSV doesn't support nested values, so balances and NFTs are joined into a single field,
+    /// e.g. `ETH:1000;USDC:5` and `65536;65537`.
+    fn into_csv_record(self) -> [String; 6] {
+        let balances: Vec<_> = self
+            .balances
+            .into_iter()
+            .map(|(symbol, balance)| format!("{}:{}", symbol, balance))
+            .collect();
+        let nfts: Vec<_> = self.nfts.iter().map(|id| id.to_string()).collect();
+        [
+            self.account_id.to_string(),
+            format!("{:#x}", self.address),
+            self.pub_key_hash.as_hex(),
+            self.nonce.to_string(),
+            balances.join(";"),
+            nfts.join(";"),
+        ]
+    }
+}
+
+const CSV_HEADERS: [&str; 6] = [
+    "account_id",
+    "address",
+    "pub_key_hash",
+    "nonce",
+    "balances",
+    "nfts",
+];
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkSync accounts export tool", author = "Matter Labs")]
+#[structopt(about = "Tool to export the state of all accounts at the finalized block")]
+struct Opt {
+    /// Block to export the state at, must be finalized. The last finalized block is used by default.
+    #[structopt(long)]
+    block: Option<u32>,
+    /// Output format: `csv` or `jsonl`.
+    #[structopt(long, default_value = "csv")]
+    format: Format,
+    /// File to write the accounts to.
+    #[structopt(long, short)]
+    output: PathBuf,
+}
+
+async fn load_state(
+    storage: &mut StorageProcessor<'_>,
+    block: Option<BlockNumber>,
+) -> anyhow::Result<(BlockNumber, ZkSyncState)> {
+    let last_verified_block = storage
+        .chain()
+        .block_schema()
+        .get_last_verified_confirmed_block()
+        .await?;
+    let block = block.unwrap_or(last_verified_block);
+    ensure!(
+        block <= last_verified_block,
+        "Block {} is not finalized yet, the last finalized block is {}",
+        block,
+        last_verified_block
+    );
+
+    // The verified state is stored as is, the state at the earlier block is restored
+    // by reverting the updates of the blocks after it.
+    let (block, accounts) = if block == last_verified_block {
+        storage.chain().state_schema().load_verified_state().await?
+    } else {
+        storage
+            .chain()
+            .state_schema()
+            .load_committed_state(Some(block))
+            .await?
+    };
+    println!("Loaded {} accounts at block {}", accounts.len(), block);
+
+    Ok((block, ZkSyncState::from_acc_map(accounts, block)))
+}
+
+async fn block_root_hash(
+    storage: &mut StorageProcessor<'_>,
+    block: BlockNumber,
+) -> anyhow::Result<Fr> {
+    let stored_block = storage
+        .chain()
+        .block_schema()
+        .get_storage_block(block)
+        .await?
+        .ok_or_else(|| format_err!("No block {} in storage", block))?;
+    Ok(Fr::from_bytes(&stored_block.root_hash)?)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+
+    let mut storage = StorageProcessor::establish_connection().await?;
+    let (block, state) = load_state(&mut storage, opt.block.map(BlockNumber)).await?;
+
+    // The export is only useful if it can be checked against the block.
+    let root_hash = state.root_hash();
+    let expected_root_hash = block_root_hash(&mut storage, block).await?;
+    ensure!(
+        root_hash == expected_root_hash,
+        "Root hash of the restored state 0x{} doesn't match the root hash of block {} 0x{}",
+        root_hash.to_hex(),
+        block,
+        expected_root_hash.to_hex()
+    );
+    let header = ExportHeader {
+        block_number: block,
+        root_hash: format!("0x{}", root_hash.to_hex()),
+    };
+
+    let token_symbols: HashMap<_, _> = storage
+        .tokens_schema()
+        .load_tokens()
+        .await?
+        .into_iter()
+        .map(|(id, token)| (id, token.symbol))
+        .collect();
+
+    let mut account_ids: Vec<_> = state.get_account_addresses().values().copied().collect();
+    account_ids.sort_unstable();
+    // Empty accounts are not a part of the state.
+    let accounts = account_ids
+        .iter()
+        .filter_map(|&account_id| Some((account_id, state.get_account(account_id)?)));
+    let mut accounts_count = 0;
+
+    let mut writer = BufWriter::new(File::create(&opt.output)?);
+    match opt.format {
+        Format::Csv => {
+            // Block information goes to the comment line, so the rest of the file is a regular CSV.
+            writeln!(
+                writer,
+                "# block_number={} root_hash={}",
+                header.block_number, header.root_hash
+            )?;
+            let mut csv_writer = csv::Writer::from_writer(writer);
+            csv_writer.write_record(&CSV_HEADERS)?;
+            for (account_id, account) in accounts {
+                let record = ExportedAccount::new(account_id, account, &token_symbols)?;
+                csv_writer.write_record(&record.into_csv_record())?;
+                accounts_count += 1;
+            }
+            csv_writer.flush()?;
+        }
+        Format::JsonLines => {
+            // The first line describes the block, every following line is an account.
+            serde_json::to_writer(&mut writer, &header)?;
+            writeln!(writer)?;
+            for (account_id, account) in accounts {
+                let record = ExportedAccount::new(account_id, account, &token_symbols)?;
+                serde_json::to_writer(&mut writer, &record)?;
+                writeln!(writer)?;
+                accounts_count += 1;
+            }
+            writer.flush()?;
+        }
+    }
+
+    println!(
+        "Exported {} accounts at block {} with root hash {} to {}",
+        accounts_count,
+        header.block_number,
+        header.root_hash,
+        opt.output.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::Nonce;
+
+    fn token_symbols() -> HashMap<TokenId, String> {
+        vec![
+            (TokenId(0), "ETH".to_string()),
+            (TokenId(2), "USDC".to_string()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn account() -> Account {
+        let mut account = Account::default_with_address(&Address::repeat_byte(0x11));
+        account.nonce = Nonce(3);
+        account.set_balance(TokenId(0), 1000u64.into());
+        account.set_balance(TokenId(2), 5u64.into());
+        // Zero balances are not exported.
+        account.set_balance(TokenId(3), 0u64.into());
+        account.set_balance(TokenId(MIN_NFT_TOKEN_ID + 1), 1u64.into());
+        account.set_balance(TokenId(MIN_NFT_TOKEN_ID), 1u64.into());
+        account
+    }
+
+    /// Checks that the balances are exported by the token symbol, the NFTs are exported as a sorted list
+    /// and the special token tracking the minted NFTs is skipped.
+    #[test]
+    fn exported_account() {
+        let mut account = account();
+        account.set_balance(TokenId(NFT_TOKEN_ID_VAL), 2u64.into());
+
+        let exported = ExportedAccount::new(AccountId(7), account, &token_symbols()).unwrap();
+        assert_eq!(exported.account_id, AccountId(7));
+        assert_eq!(exported.address, Address::repeat_byte(0x11));
+        assert_eq!(exported.nonce, 3);
+        let expected_balances: BTreeMap<_, _> = vec![
+            ("ETH".to_string(), "1000".to_string()),
+            ("USDC".to_string(), "5".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(exported.balances, expected_balances);
+        assert_eq!(
+            exported.nfts,
+            vec![TokenId(MIN_NFT_TOKEN_ID), TokenId(MIN_NFT_TOKEN_ID + 1)]
+        );
+    }
+
+    /// Checks that the account can't be exported if it has a balance of an unknown token.
+    #[test]
+    fn unknown_token() {
+        let mut account = account();
+        account.set_balance(TokenId(5), 1u64.into());
+
+        let err = ExportedAccount::new(AccountId(7), account, &token_symbols()).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown token 5");
+    }
+
+    /// Checks that the balances and NFTs are joined into single CSV fields.
+    #[test]
+    fn csv_record() {
+        let account = account();
+        let pub_key_hash = account.pub_key_hash.as_hex();
+
+        let record = ExportedAccount::new(AccountId(7), account, &token_symbols())
+            .unwrap()
+            .into_csv_record();
+        assert_eq!(
+            record,
+            [
+                "7".to_string(),
+                format!("0x{}", "11".repeat(20)),
+                pub_key_hash,
+                "3".to_string(),
+                "ETH:1000;USDC:5".to_string(),
+                "65536;65537".to_string(),
+            ]
+        );
+
+        let empty = ExportedAccount::new(
+            AccountId(8),
+            Account::default_with_address(&Address::zero()),
+            &token_symbols(),
+        )
+        .unwrap()
+        .into_csv_record();
+        assert_eq!(empty[4], "");
+        assert_eq!(empty[5], "");
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use anyhow::{ensure, format_err};
+use structopt::StructOpt;
+use zksync_state::state::ZkSyncState;
+use zksync_storage::StorageProcessor;
+use zksync_types::{BlockNumber, TokenId, NFT};
+
+use crate::replay::{replay_block, ReplayReport};
+
+mod replay;
+
+// TODO: don't use anyhow (ZKS-588)
+/// Loads the committed state after the `block_number` block.
+async fn load_state(
+    storage: &mut StorageProcessor<'_>,
+    block_number: BlockNumber,
+) -> anyhow::Result<ZkSyncState> {
+    let (state_block, accounts) = storage
+        .chain()
+        .state_schema()
+        .load_committed_state(Some(block_number))
+        .await?;
+    // State is reported for the last block with account updates, so it may be one of the previous
+    // blocks if the last ones didn't change any account. It's the state after `block_number` though.
+    ensure!(
+        state_block <= block_number,
+        "State for block {} is not available, loaded state for block {}",
+        block_number,
+        state_block
+    );
+
+    let mut state = ZkSyncState::from_acc_map(accounts, block_number + 1);
+    state.nfts = storage
+        .chain()
+        .state_schema()
+        .load_committed_nft_tokens(Some(block_number))
+        .await?
+        .into_iter()
+        .map(|nft| {
+            let token: NFT = nft.into();
+            (token.id, token)
+        })
+        .collect::<HashMap<TokenId, NFT>>();
+    Ok(state)
+}
+
+// TODO: don't use anyhow (ZKS-588)
+async fn replay_stored_block(
+    storage: &mut StorageProcessor<'_>,
+    state: &mut ZkSyncState,
+    block_number: BlockNumber,
+) -> anyhow::Result<ReplayReport> {
+    let block = storage
+        .chain()
+        .block_schema()
+        .get_block(block_number)
+        .await?
+        .ok_or_else(|| format_err!("No block {} in storage", block_number))?;
+    let stored_updates = storage
+        .chain()
+        .state_schema()
+        .load_state_diff_for_block(block_number)
+        .await?;
+
+    Ok(replay_block(state, &block, &stored_updates))
+}
+
+fn print_report(report: &ReplayReport) {
+    if report.is_ok() {
+        println!(
+            "Block {}: OK, replayed operations: {}, skipped failed transactions: {}",
+            report.block_number, report.replayed_ops, report.skipped_failed_txs
+        );
+        return;
+    }
+
+    println!(
+        "Block {}: MISMATCH, replayed operations: {}, skipped failed transactions: {}",
+        report.block_number, report.replayed_ops, report.skipped_failed_txs
+    );
+    for mismatch in &report.op_mismatches {
+        println!(
+            "  operation #{} ({}): {}",
+            mismatch.block_index, mismatch.hash, mismatch.reason
+        );
+    }
+    for mismatch in &report.block_mismatches {
+        println!("  block: {}", mismatch);
+    }
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkSync block replay tool", author = "Matter Labs")]
+#[structopt(
+    about = "Tool to re-execute stored blocks of zkSync network and compare the result with the stored state"
+)]
+struct Opt {
+    /// First block to replay.
+    #[structopt(long)]
+    from_block: u32,
+    /// Last block to replay, only `from_block` is replayed if not set.
+    #[structopt(long)]
+    to_block: Option<u32>,
+}
+
+// TODO: don't use anyhow (ZKS-588)
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+    let from_block = BlockNumber(opt.from_block);
+    let to_block = BlockNumber(opt.to_block.unwrap_or(opt.from_block));
+    ensure!(*from_block > 0, "Genesis block can't be replayed");
+    ensure!(from_block <= to_block, "Invalid block range");
+
+    let mut storage = StorageProcessor::establish_connection().await?;
+
+    let mut state = load_state(&mut storage, from_block - 1).await?;
+    let mut mismatched_blocks = Vec::new();
+    for block_number in *from_block..=*to_block {
+        let block_number = BlockNumber(block_number);
+        let report = replay_stored_block(&mut storage, &mut state, block_number).await?;
+        print_report(&report);
+
+        if !report.is_ok() {
+            mismatched_blocks.push(block_number);
+            // Replayed state has diverged, continue from the stored one,
+            // so the mismatches are not propagated to the next blocks.
+            if block_number < to_block {
+                state = load_state(&mut storage, block_number).await?;
+            }
+        }
+        state.block_number = block_number + 1;
+    }
+
+    ensure!(
+        mismatched_blocks.is_empty(),
+        "Replayed state doesn't match the stored one for blocks: {:?}",
+        mismatched_blocks
+    );
+    println!("All blocks were replayed successfully");
+    Ok(())
+}
@@ -0,0 +1,290 @@
+//! Re-execution of a stored block on top of the state it was created from.
+
+use std::collections::HashMap;
+
+use zksync_state::state::{OpSuccess, ZkSyncState};
+use zksync_types::{
+    block::{Block, ExecutedOperations},
+    AccountUpdates, BlockNumber, ZkSyncOp,
+};
+
+/// Difference between the stored and the replayed result of a single operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpMismatch {
+    /// Index of the operation in the block.
+    pub block_index: u32,
+    /// Hash of the L2 transaction or Ethereum hash of the priority operation.
+    pub hash: String,
+    pub reason: String,
+}
+
+/// Result of the block replay.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayReport {
+    pub block_number: BlockNumber,
+    /// Amount of successful operations that were executed again.
+    pub replayed_ops: usize,
+    /// Failed transactions don't change the state, so they're not executed again.
+    pub skipped_failed_txs: usize,
+    pub op_mismatches: Vec<OpMismatch>,
+    /// Mismatches of the data that can't be attributed to a single operation,
+    /// e.g. the root hash or collected fees.
+    pub block_mismatches: Vec<String>,
+}
+
+impl ReplayReport {
+    pub fn is_ok(&self) -> bool {
+        self.op_mismatches.is_empty() && self.block_mismatches.is_empty()
+    }
+}
+
+/// Executes successful operations of the `block` on top of the `state`, which must be
+/// the state after the previous block, and compares the outcome with the stored data.
+///
+/// `stored_updates` are the account updates stored for the block, in the order they were applied.
+pub fn replay_block(
+    state: &mut ZkSyncState,
+    block: &Block,
+    stored_updates: &AccountUpdates,
+) -> ReplayReport {
+    let mut report = ReplayReport {
+        block_number: block.block_number,
+        replayed_ops: 0,
+        skipped_failed_txs: 0,
+        op_mismatches: Vec::new(),
+        block_mismatches: Vec::new(),
+    };
+
+    let mut operations = Vec::new();
+    for operation in &block.block_transactions {
+        match operation {
+            ExecutedOperations::PriorityOp(op) => operations.push((op.block_index, operation)),
+            ExecutedOperations::Tx(tx) => match tx.block_index {
+                Some(block_index) if tx.success => operations.push((block_index, operation)),
+                _ => report.skipped_failed_txs += 1,
+            },
+        }
+    }
+    operations.sort_by_key(|(block_index, _)| *block_index);
+
+    let mut updates_offset = 0;
+    let mut fees = Vec::new();
+    let mut priority_ops_count = 0;
+    let mut replayed_ops: HashMap<u32, ZkSyncOp> = HashMap::new();
+
+    for (block_index, operation) in operations {
+        let (hash, result) = match operation {
+            ExecutedOperations::PriorityOp(op) => {
+                priority_ops_count += 1;
+                (
+                    format!("{:?}", op.priority_op.eth_hash),
+                    Ok(state.execute_priority_op(op.priority_op.data.clone())),
+                )
+            }
+            ExecutedOperations::Tx(tx) => (
+                tx.signed_tx.tx.hash().to_string(),
+                state.execute_tx(tx.signed_tx.tx.clone()),
+            ),
+        };
+        report.replayed_ops += 1;
+
+        let mut reasons = Vec::new();
+        match result {
+            Ok(OpSuccess {
+                fee,
+                updates,
+                executed_op,
+            }) => {
+                let stored_op = operation
+                    .get_executed_op()
+                    .expect("Successful operation must have an executed op");
+                let (stored_pubdata, replayed_pubdata) =
+                    (stored_op.public_data(), executed_op.public_data());
+                if stored_pubdata != replayed_pubdata {
+                    reasons.push(format!(
+                        "pubdata mismatch: stored 0x{}, replayed 0x{}",
+                        hex::encode(stored_pubdata),
+                        hex::encode(replayed_pubdata)
+                    ));
+                }
+
+                let stored = stored_updates
+                    .get(updates_offset..updates_offset + updates.len())
+                    .unwrap_or_default();
+                if stored != updates.as_slice() {
+                    reasons.push(format!(
+                        "account updates mismatch: stored {:?}, replayed {:?}",
+                        stored, updates
+                    ));
+                }
+                updates_offset += updates.len();
+
+                fees.extend(fee);
+                replayed_ops.insert(block_index, executed_op);
+            }
+            Err(err) => {
+                reasons.push(format!("operation failed on replay: {}", err));
+            }
+        }
+
+        report
+            .op_mismatches
+            .extend(reasons.into_iter().map(|reason| OpMismatch {
+                block_index,
+                hash: hash.clone(),
+                reason,
+            }));
+    }
+
+    // Fees are applied to the fee account once all the operations are executed.
+    let fee_updates = state.collect_fee(&fees, block.fee_account);
+    let stored = stored_updates.get(updates_offset..).unwrap_or_default();
+    if stored != fee_updates.as_slice() {
+        report.block_mismatches.push(format!(
+            "collected fees mismatch: stored {:?}, replayed {:?}",
+            stored, fee_updates
+        ));
+    }
+
+    let (priority_op_before, priority_op_after) = block.processed_priority_ops;
+    if priority_op_after - priority_op_before != priority_ops_count {
+        report.block_mismatches.push(format!(
+            "processed priority operations mismatch: stored {:?}, replayed {}",
+            block.processed_priority_ops, priority_ops_count
+        ));
+    }
+
+    let root_hash = state.root_hash();
+    if root_hash != block.new_root_hash {
+        report.block_mismatches.push(format!(
+            "root hash mismatch: stored {:?}, replayed {:?}",
+            block.new_root_hash, root_hash
+        ));
+    }
+
+    let mut replayed_block = block.clone();
+    for operation in &mut replayed_block.block_transactions {
+        match operation {
+            ExecutedOperations::PriorityOp(op) => {
+                if let Some(replayed_op) = replayed_ops.remove(&op.block_index) {
+                    op.op = replayed_op;
+                }
+            }
+            ExecutedOperations::Tx(tx) => {
+                if let Some(replayed_op) = tx
+                    .block_index
+                    .filter(|_| tx.success)
+                    .and_then(|block_index| replayed_ops.remove(&block_index))
+                {
+                    tx.op = Some(replayed_op);
+                }
+            }
+        }
+    }
+    if replayed_block.get_eth_public_data() != block.get_eth_public_data() {
+        report
+            .block_mismatches
+            .push("block pubdata mismatch".to_string());
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigUint;
+    use zksync_types::{
+        block::ExecutedPriorityOp, Account, AccountId, AccountUpdate, Deposit, PriorityOp, TokenId,
+        ZkSyncPriorityOp, H160, H256,
+    };
+
+    fn deposit(amount: u32) -> PriorityOp {
+        let address = H160::random();
+        PriorityOp {
+            data: ZkSyncPriorityOp::Deposit(Deposit {
+                from: address,
+                to: address,
+                amount: amount.into(),
+                token: TokenId(0),
+            }),
+            serial_id: 0,
+            deadline_block: 0,
+            eth_hash: H256::zero(),
+            eth_block: 0,
+            eth_block_index: None,
+        }
+    }
+
+    /// Executes a deposit on top of the given state and creates a block from it.
+    fn create_block(state: &ZkSyncState) -> (Block, AccountUpdates) {
+        let mut state = state.clone();
+        let priority_op = deposit(100);
+        let OpSuccess {
+            updates,
+            executed_op,
+            ..
+        } = state.execute_priority_op(priority_op.data.clone());
+
+        let block = Block::new(
+            BlockNumber(1),
+            state.root_hash(),
+            AccountId(0),
+            vec![ExecutedOperations::PriorityOp(Box::new(
+                ExecutedPriorityOp {
+                    priority_op,
+                    op: executed_op,
+                    block_index: 0,
+                    created_at: chrono::Utc::now(),
+                },
+            ))],
+            (0, 1),
+            10,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            0,
+        );
+        (block, updates)
+    }
+
+    fn genesis_state() -> ZkSyncState {
+        let mut state = ZkSyncState::empty();
+        state.insert_account(AccountId(0), Account::default_with_address(&H160::random()));
+        state
+    }
+
+    #[test]
+    fn replay_matches() {
+        let state = genesis_state();
+        let (block, updates) = create_block(&state);
+
+        let report = replay_block(&mut state.clone(), &block, &updates);
+        assert!(report.is_ok(), "{:?}", report);
+        assert_eq!(report.replayed_ops, 1);
+    }
+
+    #[test]
+    fn replay_reports_mismatches() {
+        let state = genesis_state();
+        let (mut block, updates) = create_block(&state);
+
+        // Corrupt the stored balance update of the deposit.
+        let mut corrupted_updates = updates.clone();
+        for (_, update) in &mut corrupted_updates {
+            if let AccountUpdate::UpdateBalance { balance_update, .. } = update {
+                balance_update.2 = BigUint::from(1u32);
+            }
+        }
+        let report = replay_block(&mut state.clone(), &block, &corrupted_updates);
+        assert_eq!(report.op_mismatches.len(), 1);
+        assert_eq!(report.op_mismatches[0].block_index, 0);
+        assert!(report.block_mismatches.is_empty());
+
+        // Corrupt the stored root hash.
+        block.new_root_hash = Default::default();
+        let report = replay_block(&mut state.clone(), &block, &updates);
+        assert!(report.op_mismatches.is_empty());
+        assert_eq!(report.block_mismatches.len(), 1);
+    }
+}
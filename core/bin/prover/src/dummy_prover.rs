@@ -15,7 +15,7 @@ impl ProverConfig for DummyProverConfig {
         let env_config = ZkSyncConfig::from_env();
 
         Self {
-            block_sizes: env_config.chain.state_keeper.block_chunk_sizes,
+            block_sizes: env_config.chain.block_chunk_sizes_to_seal(),
         }
     }
 }
@@ -1,4 +1,5 @@
 // Built-in deps
+use std::collections::HashMap;
 use std::sync::Mutex;
 // Workspace deps
 use zksync_config::ChainConfig;
@@ -14,16 +15,11 @@ use crate::{ProverConfig, ProverImpl};
 use zksync_crypto::franklin_crypto::circuit::test::TestConstraintSystem;
 use zksync_prover_utils::fs_utils::load_precomputed_proofs;
 
-/// We prepare some data before making proof for each block size, so we cache it in case next block
-/// would be of our size
-struct PreparedComputations {
-    block_size: usize,
-    setup: SetupForStepByStepProver,
-}
-
 pub struct PlonkStepByStepProver {
     config: PlonkStepByStepProverConfig,
-    prepared_computations: Mutex<Option<PreparedComputations>>,
+    /// We prepare some data before making proof for each block size, so we cache it per block size
+    /// in case next blocks would be of the same sizes (blocks may be sealed into several sizes).
+    prepared_computations: Mutex<HashMap<usize, SetupForStepByStepProver>>,
     precomputed_sample_proofs: PrecomputedSampleProofs,
 }
 
@@ -45,7 +41,7 @@ impl ProverConfig for PlonkStepByStepProverConfig {
         Self {
             download_setup_from_network: parse_env("MISC_PROVER_DOWNLOAD_SETUP"),
             all_block_sizes: env_config.circuit.supported_block_chunks_sizes,
-            block_sizes: env_config.state_keeper.block_chunk_sizes,
+            block_sizes: env_config.block_chunk_sizes_to_seal(),
             aggregated_proof_sizes_with_setup_pow,
         }
     }
@@ -66,29 +62,28 @@ impl PlonkStepByStepProver {
             println!("number of constraints {}", cs.num_constraints());
             println!("Unsatisfied {:?}", err);
         }
-        let valid_cached_precomp = {
+        let cached_setup = {
             self.prepared_computations
                 .lock()
                 .unwrap()
-                .take()
-                .filter(|p| p.block_size == block_size)
+                .remove(&block_size)
         };
-        let precomp = if let Some(precomp) = valid_cached_precomp {
-            precomp
+        let setup = if let Some(setup) = cached_setup {
+            setup
         } else {
-            let setup = SetupForStepByStepProver::prepare_setup_for_step_by_step_prover(
+            SetupForStepByStepProver::prepare_setup_for_step_by_step_prover(
                 witness.clone(),
                 self.config.download_setup_from_network,
-            )?;
-            PreparedComputations { block_size, setup }
+            )?
         };
 
         let vk = PlonkVerificationKey::read_verification_key_for_main_circuit(block_size)?;
-        let verified_proof = precomp
-            .setup
-            .gen_step_by_step_proof_using_prepared_setup(witness, &vk)?;
+        let verified_proof = setup.gen_step_by_step_proof_using_prepared_setup(witness, &vk)?;
 
-        *self.prepared_computations.lock().unwrap() = Some(precomp);
+        self.prepared_computations
+            .lock()
+            .unwrap()
+            .insert(block_size, setup);
 
         Ok(verified_proof)
     }
@@ -99,7 +94,7 @@ impl PlonkStepByStepProver {
     ) -> anyhow::Result<AggregatedProof> {
         // drop setup cache
         {
-            self.prepared_computations.lock().unwrap().take();
+            self.prepared_computations.lock().unwrap().clear();
         }
 
         let proofs_to_pad = {
@@ -182,7 +177,7 @@ impl ProverImpl for PlonkStepByStepProver {
         assert!(!config.block_sizes.is_empty());
         PlonkStepByStepProver {
             config,
-            prepared_computations: Mutex::new(None),
+            prepared_computations: Mutex::new(HashMap::new()),
             precomputed_sample_proofs: load_precomputed_proofs()
                 .expect("Failed to load precomputed sample proofs"),
         }
@@ -127,6 +127,7 @@ async fn main() -> anyhow::Result<()> {
             vlog::warn!("Stop signal received, shutting down");
         }
     };
+    vlog::shutdown_exporter().await;
 
     Ok(())
 }
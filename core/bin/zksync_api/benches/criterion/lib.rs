@@ -35,6 +35,8 @@ fn get_txs_fee(client: Client, url: String) {
         tx_type: ApiTxFeeTypes::Withdraw,
         address: Address::random(),
         token_like: TokenLike::Id(TokenId(2)), // id of wBTC on localhost
+        sender: None,
+        nonce: None,
     };
 
     let res = client
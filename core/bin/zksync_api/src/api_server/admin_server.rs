@@ -16,8 +16,13 @@ use jsonwebtoken::{decode, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
 
 // Local uses
-use zksync_storage::ConnectionPool;
-use zksync_types::{tokens, Address, TokenId, TokenKind};
+use crate::fee_ticker::fee_formula::{FeeFormulaConfig, FeeFormulaUpdater};
+use zksync_eth_client::{ClientHealth, EthereumGateway};
+use zksync_storage::{tokens::StoreTokenError, ConnectionPool};
+use zksync_types::{
+    tokens::{self, TokenFeePolicy, TokenUpdate},
+    Address, TokenId, TokenKind,
+};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -32,6 +37,8 @@ struct PayloadAuthToken {
 struct AppState {
     secret_auth: String,
     connection_pool: ConnectionPool,
+    fee_formula: FeeFormulaUpdater,
+    eth_gateway: EthereumGateway,
 }
 
 impl AppState {
@@ -57,6 +64,28 @@ struct AddTokenRequest {
     pub decimals: u8,
 }
 
+/// Health of the Ethereum gateway used by the multiplexed client.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct EthGatewayHealth {
+    pub name: String,
+    pub preferred: bool,
+    pub healthy: bool,
+    /// Reason why the gateway has been de-prioritized.
+    pub unhealthy_reason: Option<String>,
+}
+
+impl From<ClientHealth> for EthGatewayHealth {
+    fn from(health: ClientHealth) -> Self {
+        Self {
+            name: health.name,
+            preferred: health.preferred,
+            healthy: health.unhealthy_reason.is_none(),
+            unhealthy_reason: health.unhealthy_reason,
+        }
+    }
+}
+
 struct AuthTokenValidator<'a> {
     decoding_key: DecodingKey<'a>,
 }
@@ -136,6 +165,147 @@ async fn add_token(
     Ok(HttpResponse::Ok().json(token))
 }
 
+/// Changes the token metadata and listing settings. Every change is stored as a new version of the token settings.
+///
+/// Note that the fee token validators cache their decisions, so the change of the fee policy may take effect
+/// with a delay up to `FEE_TICKER_AVAILABLE_LIQUIDITY_SECONDS`, and the API servers reload the token symbol
+/// within a minute. Decimals can be changed only for the tokens that have never been used.
+async fn update_token(
+    data: web::Data<AppState>,
+    token_id: web::Path<u32>,
+    update: web::Json<TokenUpdate>,
+) -> actix_web::Result<HttpResponse> {
+    update_token_settings(&data, TokenId(token_id.into_inner()), update.into_inner()).await
+}
+
+async fn update_token_settings(
+    data: &AppState,
+    token_id: TokenId,
+    update: TokenUpdate,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let settings = storage
+        .tokens_schema()
+        .update_token_settings(token_id, update)
+        .await
+        .map_err(|e| match e {
+            StoreTokenError::TokenAlreadyExistsError(e) | StoreTokenError::TokenInUseError(e) => {
+                actix_web::error::ErrorBadRequest(e)
+            }
+            StoreTokenError::Other(e) => {
+                vlog::warn!("failed to update token settings in progress request: {}", e);
+                actix_web::error::ErrorInternalServerError("storage layer error")
+            }
+        })?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("token not found"))?;
+
+    vlog::info!(
+        "Token {} settings updated to version {}: {:?}",
+        token_id,
+        settings.version,
+        settings
+    );
+    Ok(HttpResponse::Ok().json(settings))
+}
+
+/// Returns all the versions of the token settings, empty if they have never been changed.
+async fn get_token_history(
+    data: web::Data<AppState>,
+    token_id: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let history = storage
+        .tokens_schema()
+        .load_token_settings_history(TokenId(token_id.into_inner()))
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to load token settings in progress request: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    Ok(HttpResponse::Ok().json(history))
+}
+
+/// Returns the tokens accepted to pay fees in regardless of their market volume.
+async fn get_fee_allowlist(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let allowlist = storage
+        .tokens_schema()
+        .load_tokens_by_fee_policy(TokenFeePolicy::Allowed)
+        .await
+        .map_err(|e| {
+            vlog::warn!(
+                "failed to load fee tokens allowlist in progress request: {}",
+                e
+            );
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    Ok(HttpResponse::Ok().json(allowlist))
+}
+
+async fn add_to_fee_allowlist(
+    data: web::Data<AppState>,
+    token_id: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let update = TokenUpdate {
+        fee_policy: Some(TokenFeePolicy::Allowed),
+        ..Default::default()
+    };
+    update_token_settings(&data, TokenId(token_id.into_inner()), update).await
+}
+
+/// Removes the token from the allowlist, so it's checked by the market volume again.
+async fn remove_from_fee_allowlist(
+    data: web::Data<AppState>,
+    token_id: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let update = TokenUpdate {
+        fee_policy: Some(TokenFeePolicy::MarketVolume),
+        ..Default::default()
+    };
+    update_token_settings(&data, TokenId(token_id.into_inner()), update).await
+}
+
+async fn get_fee_formula(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(data.fee_formula.formula()))
+}
+
+/// Replaces the fee formula used by the tickers until the formula file is changed.
+async fn update_fee_formula(
+    data: web::Data<AppState>,
+    formula: web::Json<FeeFormulaConfig>,
+) -> actix_web::Result<HttpResponse> {
+    data.fee_formula.update(formula.into_inner()).map_err(|e| {
+        vlog::warn!("failed to update the fee formula: {}", e);
+        actix_web::error::ErrorBadRequest(e)
+    })?;
+
+    Ok(HttpResponse::Ok().json(data.fee_formula.formula()))
+}
+
+/// Reloads the fee formula from the file, discarding the updates made via API.
+async fn reload_fee_formula(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    data.fee_formula.reload(true).map_err(|e| {
+        vlog::warn!("failed to reload the fee formula: {}", e);
+        actix_web::error::ErrorBadRequest(e)
+    })?;
+
+    Ok(HttpResponse::Ok().json(data.fee_formula.formula()))
+}
+
+/// Returns the health of the Ethereum gateways, empty if the client is not multiplexed.
+async fn get_eth_gateways(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let gateways: Vec<EthGatewayHealth> = data
+        .eth_gateway
+        .clients_health()
+        .into_iter()
+        .map(EthGatewayHealth::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(gateways))
+}
+
 async fn run_server(app_state: AppState, bind_to: SocketAddr) {
     HttpServer::new(move || {
         let auth = HttpAuthentication::bearer(move |req, credentials| async {
@@ -153,6 +323,18 @@ async fn run_server(app_state: AppState, bind_to: SocketAddr) {
             .wrap(auth)
             .app_data(web::Data::new(app_state.clone()))
             .route("/tokens", web::post().to(add_token))
+            .route("/tokens/{id}", web::put().to(update_token))
+            .route("/tokens/{id}/history", web::get().to(get_token_history))
+            .route("/fee_allowlist", web::get().to(get_fee_allowlist))
+            .route("/fee_allowlist/{id}", web::put().to(add_to_fee_allowlist))
+            .route(
+                "/fee_allowlist/{id}",
+                web::delete().to(remove_from_fee_allowlist),
+            )
+            .route("/fee_formula", web::get().to(get_fee_formula))
+            .route("/fee_formula", web::put().to(update_fee_formula))
+            .route("/fee_formula/reload", web::post().to(reload_fee_formula))
+            .route("/eth_gateways", web::get().to(get_eth_gateways))
     })
     .workers(1)
     .bind(&bind_to)
@@ -166,6 +348,8 @@ pub fn start_admin_server(
     bind_to: SocketAddr,
     secret_auth: String,
     connection_pool: zksync_storage::ConnectionPool,
+    fee_formula: FeeFormulaUpdater,
+    eth_gateway: EthereumGateway,
     panic_notify: mpsc::Sender<bool>,
 ) {
     thread::Builder::new()
@@ -176,6 +360,8 @@ pub fn start_admin_server(
                 let app_state = AppState {
                     secret_auth,
                     connection_pool,
+                    fee_formula,
+                    eth_gateway,
                 };
 
                 run_server(app_state, bind_to).await;
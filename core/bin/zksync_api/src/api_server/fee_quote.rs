@@ -0,0 +1,236 @@
+//! Signed fee quotes.
+//!
+//! The quote binds the fee to the transaction type, the recipient address, the fee token,
+//! the sender address, the sender nonce and the expiration time. The quote is signed with
+//! the server secret (HMAC-SHA256), so the server doesn't have to store the issued quotes
+//! to check them. The quote is not replayable since the sender nonce is used by the first
+//! transaction it is accepted for.
+
+// External uses
+use chrono::{DateTime, TimeZone, Utc};
+use hmac::{Hmac, Mac, NewMac};
+use num::BigUint;
+use sha2::Sha256;
+use thiserror::Error;
+// Workspace uses
+use zksync_config::ZkSyncConfig;
+use zksync_types::{Address, FeeQuote, Nonce, TokenId, TxFeeTypes};
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum FeeQuoteError {
+    #[error("fee quote has expired")]
+    Expired,
+    #[error("fee quote is invalid or was issued for another transaction")]
+    InvalidSignature,
+    #[error("provided fee is lower than the quoted one")]
+    FeeBelowQuote,
+    #[error("required fee has grown too much since the quote was issued")]
+    FeeIncreaseTooLarge,
+}
+
+/// Transaction the fee is quoted for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuotedTx {
+    pub tx_type: TxFeeTypes,
+    /// Recipient of the transaction, as used for the fee calculation.
+    pub address: Address,
+    pub token: TokenId,
+    pub sender: Address,
+    pub nonce: Nonce,
+}
+
+#[derive(Debug, Clone)]
+pub struct FeeQuoteSigner {
+    secret: Vec<u8>,
+    validity: chrono::Duration,
+    max_fee_increase_percent: u32,
+}
+
+impl FeeQuoteSigner {
+    pub fn new(config: &ZkSyncConfig) -> Self {
+        Self {
+            secret: config.api.common.fee_quote_secret.clone().into_bytes(),
+            validity: chrono::Duration::seconds(config.api.common.fee_quote_validity_secs as i64),
+            max_fee_increase_percent: config.api.common.fee_quote_max_fee_increase_percent,
+        }
+    }
+
+    /// Issues the quote of the fee which is valid for the configured time.
+    pub fn sign(&self, tx: QuotedTx, total_fee: BigUint) -> FeeQuote {
+        // Expiration time is rounded to seconds, so it's not changed by the serialization.
+        let expires_at = Utc.timestamp((Utc::now() + self.validity).timestamp(), 0);
+        let id = hex::encode(self.mac(tx, &total_fee, expires_at).finalize().into_bytes());
+
+        FeeQuote {
+            id: format!("0x{}", id),
+            total_fee,
+            expires_at,
+        }
+    }
+
+    /// Checks whether the quote allows to accept the transaction paying `provided_fee`
+    /// while the ticker requires `required_fee`.
+    pub fn check(
+        &self,
+        quote: &FeeQuote,
+        tx: QuotedTx,
+        provided_fee: &BigUint,
+        required_fee: &BigUint,
+    ) -> Result<(), FeeQuoteError> {
+        let signature = quote
+            .id
+            .strip_prefix("0x")
+            .and_then(|id| hex::decode(id).ok())
+            .ok_or(FeeQuoteError::InvalidSignature)?;
+        self.mac(tx, &quote.total_fee, quote.expires_at)
+            .verify(&signature)
+            .map_err(|_| FeeQuoteError::InvalidSignature)?;
+
+        if quote.expires_at < Utc::now() {
+            return Err(FeeQuoteError::Expired);
+        }
+        if provided_fee < &quote.total_fee {
+            return Err(FeeQuoteError::FeeBelowQuote);
+        }
+        // The quote doesn't protect from the price changes without bound, e.g. if the token
+        // has crashed, the transaction has to pay the actual fee.
+        if required_fee * BigUint::from(100u32)
+            > &quote.total_fee * BigUint::from(100 + self.max_fee_increase_percent)
+        {
+            return Err(FeeQuoteError::FeeIncreaseTooLarge);
+        }
+        Ok(())
+    }
+
+    fn mac(&self, tx: QuotedTx, total_fee: &BigUint, expires_at: DateTime<Utc>) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_varkey(&self.secret).expect("HMAC can take a key of any size");
+        mac.update(&serde_json::to_vec(&tx.tx_type).expect("fee type serialization failed"));
+        mac.update(tx.address.as_bytes());
+        mac.update(&tx.token.0.to_be_bytes());
+        mac.update(tx.sender.as_bytes());
+        mac.update(&tx.nonce.0.to_be_bytes());
+        mac.update(&expires_at.timestamp().to_be_bytes());
+        mac.update(&total_fee.to_bytes_be());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signer() -> FeeQuoteSigner {
+        FeeQuoteSigner {
+            secret: b"secret".to_vec(),
+            validity: chrono::Duration::seconds(60),
+            max_fee_increase_percent: 20,
+        }
+    }
+
+    fn quoted_tx() -> QuotedTx {
+        QuotedTx {
+            tx_type: TxFeeTypes::Transfer,
+            address: Address::repeat_byte(0x11),
+            token: TokenId(1),
+            sender: Address::repeat_byte(0x22),
+            nonce: Nonce(5),
+        }
+    }
+
+    #[test]
+    fn fee_quote_check() {
+        let signer = signer();
+        let tx = quoted_tx();
+        let quote = signer.sign(tx, 100u32.into());
+
+        let check = |tx, provided_fee: u32, required_fee: u32| {
+            signer.check(&quote, tx, &provided_fee.into(), &required_fee.into())
+        };
+
+        // The quoted fee is accepted while the required fee is within the limit.
+        assert_eq!(check(tx, 100, 100), Ok(()));
+        assert_eq!(check(tx, 100, 120), Ok(()));
+        assert_eq!(check(tx, 100, 121), Err(FeeQuoteError::FeeIncreaseTooLarge));
+        assert_eq!(check(tx, 99, 100), Err(FeeQuoteError::FeeBelowQuote));
+
+        // The quote can't be used for another transaction.
+        let another_txs = vec![
+            QuotedTx {
+                tx_type: TxFeeTypes::Withdraw,
+                ..tx
+            },
+            QuotedTx {
+                address: Address::zero(),
+                ..tx
+            },
+            QuotedTx {
+                token: TokenId(0),
+                ..tx
+            },
+            QuotedTx {
+                sender: Address::zero(),
+                ..tx
+            },
+            QuotedTx {
+                nonce: Nonce(6),
+                ..tx
+            },
+        ];
+        for another_tx in another_txs {
+            assert_eq!(
+                check(another_tx, 100, 100),
+                Err(FeeQuoteError::InvalidSignature)
+            );
+        }
+
+        // Quoted values can't be changed by the user.
+        let mut forged = quote.clone();
+        forged.total_fee = 50u32.into();
+        assert_eq!(
+            signer.check(&forged, tx, &50u32.into(), &100u32.into()),
+            Err(FeeQuoteError::InvalidSignature)
+        );
+        let forged = FeeQuote {
+            expires_at: quote.expires_at + chrono::Duration::hours(1),
+            ..quote.clone()
+        };
+        assert_eq!(
+            signer.check(&forged, tx, &100u32.into(), &100u32.into()),
+            Err(FeeQuoteError::InvalidSignature)
+        );
+        let forged = FeeQuote {
+            id: "0xzz".into(),
+            ..quote
+        };
+        assert_eq!(
+            signer.check(&forged, tx, &100u32.into(), &100u32.into()),
+            Err(FeeQuoteError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn fee_quote_expiration() {
+        let signer = FeeQuoteSigner {
+            validity: chrono::Duration::seconds(-10),
+            ..signer()
+        };
+        let tx = quoted_tx();
+        let quote = signer.sign(tx, 100u32.into());
+
+        assert_eq!(
+            signer.check(&quote, tx, &100u32.into(), &100u32.into()),
+            Err(FeeQuoteError::Expired)
+        );
+    }
+
+    #[test]
+    fn fee_quote_serialization() {
+        let quote = signer().sign(quoted_tx(), 100u32.into());
+        let deserialized: FeeQuote =
+            serde_json::from_str(&serde_json::to_string(&quote).unwrap()).unwrap();
+        assert_eq!(deserialized, quote);
+    }
+}
@@ -11,13 +11,16 @@ use zksync_config::ZkSyncConfig;
 use zksync_eth_client::EthereumGateway;
 use zksync_storage::ConnectionPool;
 // Local uses
-use crate::fee_ticker::TickerRequest;
+use self::rate_limiter::RateLimiter;
+use crate::fee_ticker::{fee_formula::FeeFormulaUpdater, TickerRequest};
 use crate::signature_checker;
 
 mod admin_server;
 mod event_notify;
+mod fee_quote;
 pub mod forced_exit_checker;
 mod helpers;
+mod rate_limiter;
 mod rest;
 pub mod rpc_server;
 mod rpc_subscriptions;
@@ -32,15 +35,18 @@ pub fn start_api_server(
     connection_pool: ConnectionPool,
     panic_notify: mpsc::Sender<bool>,
     ticker_request_sender: mpsc::Sender<TickerRequest>,
+    fee_formula: FeeFormulaUpdater,
     eth_gateway: EthereumGateway,
     config: &ZkSyncConfig,
 ) {
     let (sign_check_sender, sign_check_receiver) = mpsc::channel(32768);
+    let rate_limiter = RateLimiter::new(&config.api.rate_limit);
 
     signature_checker::start_sign_checker_detached(
-        eth_gateway,
+        eth_gateway.clone(),
         sign_check_receiver,
         panic_notify.clone(),
+        config,
     );
 
     rest::start_server_thread_detached(
@@ -50,6 +56,7 @@ pub fn start_api_server(
         panic_notify.clone(),
         ticker_request_sender.clone(),
         sign_check_sender.clone(),
+        rate_limiter.clone(),
         config.clone(),
     );
 
@@ -57,6 +64,7 @@ pub fn start_api_server(
         connection_pool.clone(),
         sign_check_sender.clone(),
         ticker_request_sender.clone(),
+        rate_limiter.clone(),
         panic_notify.clone(),
         config,
     );
@@ -65,6 +73,8 @@ pub fn start_api_server(
         config.api.admin.bind_addr(),
         config.api.admin.secret_auth.clone(),
         connection_pool.clone(),
+        fee_formula,
+        eth_gateway,
         panic_notify.clone(),
     );
 
@@ -72,9 +82,10 @@ pub fn start_api_server(
         connection_pool.clone(),
         sign_check_sender,
         ticker_request_sender,
+        rate_limiter.clone(),
         panic_notify.clone(),
         config,
     );
 
-    web3::start_rpc_server(connection_pool, panic_notify, config);
+    web3::start_rpc_server(connection_pool, rate_limiter, panic_notify, config);
 }
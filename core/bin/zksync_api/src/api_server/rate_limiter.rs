@@ -0,0 +1,750 @@
+//! Token-bucket rate limiter for the public API servers.
+//!
+//! Every incoming request has a cost that depends on the called method. This cost is consumed
+//! from the bucket of the client IP address and, if the request concerns a certain zkSync account,
+//! from the bucket of this account. Buckets are refilled continuously with the configured rate.
+//! Request is rejected if any of the buckets doesn't have enough tokens.
+//!
+//! Client IP address is the peer address. The `X-Forwarded-For` / `X-Real-IP` headers are honoured
+//! only for the requests coming from the configured trusted proxies. JSON RPC and WebSocket servers
+//! don't provide the peer address, so they honour the headers only if trusted proxies are set.
+
+// Built-in uses
+use std::{
+    collections::HashMap,
+    future::Future,
+    net::IpAddr,
+    pin::Pin,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+// External uses
+use actix_web::{dev::ServiceRequest, http::StatusCode, ResponseError};
+use futures::future::{ready, Either};
+use ipnet::IpNet;
+use jsonrpc_core::{Call, ErrorCode, Metadata, Middleware, Output, Params, Value};
+use jsonrpc_pubsub::Session;
+use lru_cache::LruCache;
+use thiserror::Error;
+// Workspace uses
+use zksync_config::configs::api::RateLimit;
+use zksync_types::{AccountId, Address};
+// Local uses
+use super::rpc_server::error::RpcErrorCodes;
+
+/// Error code returned by the web3 server, as recommended by EIP-1474 for the "Limit exceeded" error.
+const WEB3_LIMIT_EXCEEDED_CODE: i64 = -32005;
+
+/// Public API server which the request came to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiServer {
+    Rest,
+    JsonRpc,
+    WebSocket,
+    Web3,
+}
+
+impl ApiServer {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Rest => "rest",
+            Self::JsonRpc => "json_rpc",
+            Self::WebSocket => "ws",
+            Self::Web3 => "web3",
+        }
+    }
+}
+
+/// Identifier of the rate limiting bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    Ip(IpAddr),
+    Address(Address),
+    AccountId(AccountId),
+}
+
+impl RateLimitKey {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Ip(_) => "ip",
+            Self::Address(_) | Self::AccountId(_) => "account",
+        }
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq)]
+#[error("Rate limit exceeded for {kind}, retry later")]
+pub struct RateLimitError {
+    /// Kind of the exhausted bucket: either "ip" or "account".
+    pub kind: &'static str,
+}
+
+impl RateLimitError {
+    pub fn into_rpc_error(self, server: ApiServer) -> jsonrpc_core::Error {
+        let code = match server {
+            ApiServer::Web3 => ErrorCode::ServerError(WEB3_LIMIT_EXCEEDED_CODE),
+            _ => RpcErrorCodes::RateLimitExceeded.into(),
+        };
+        jsonrpc_core::Error {
+            code,
+            message: self.to_string(),
+            data: None,
+        }
+    }
+}
+
+impl ResponseError for RateLimitError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::TOO_MANY_REQUESTS
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BucketParams {
+    capacity: f64,
+    refill_per_second: f64,
+}
+
+impl TokenBucket {
+    fn full(params: BucketParams, now: Instant) -> Self {
+        Self {
+            tokens: params.capacity,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, params: BucketParams, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * params.refill_per_second).min(params.capacity);
+        self.last_refill = now;
+    }
+}
+
+#[derive(Debug)]
+struct RateLimiterInner {
+    ip_params: BucketParams,
+    account_params: BucketParams,
+    default_method_cost: u32,
+    method_costs: HashMap<String, u32>,
+    trusted_proxies: Vec<IpNet>,
+    /// Least recently used buckets are evicted once the configured amount of keys is exceeded.
+    buckets: Mutex<LruCache<RateLimitKey, TokenBucket>>,
+}
+
+impl RateLimiterInner {
+    fn params(&self, key: &RateLimitKey) -> BucketParams {
+        match key {
+            RateLimitKey::Ip(_) => self.ip_params,
+            RateLimitKey::Address(_) | RateLimitKey::AccountId(_) => self.account_params,
+        }
+    }
+
+    fn is_trusted_proxy(&self, ip: &IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|net| net.contains(ip))
+    }
+}
+
+/// Client address as seen by the API server.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClientAddr {
+    /// Address of the connected peer, if the server provides it.
+    pub peer: Option<IpAddr>,
+    /// Value of the `X-Forwarded-For` header.
+    pub forwarded_for: Option<String>,
+    /// Value of the `X-Real-IP` header.
+    pub real_ip: Option<String>,
+}
+
+/// Rate limiter shared by all the public API servers.
+///
+/// The limiter is cheap to clone, all the clones share the same set of buckets.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    inner: Option<Arc<RateLimiterInner>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &RateLimit) -> Self {
+        if !config.enabled {
+            return Self::disabled();
+        }
+
+        let inner = RateLimiterInner {
+            ip_params: BucketParams {
+                capacity: config.ip_bucket_capacity as f64,
+                refill_per_second: config.ip_refill_per_second as f64,
+            },
+            account_params: BucketParams {
+                capacity: config.account_bucket_capacity as f64,
+                refill_per_second: config.account_refill_per_second as f64,
+            },
+            default_method_cost: config.default_method_cost,
+            method_costs: config.method_costs().into_iter().collect(),
+            trusted_proxies: parse_trusted_proxies(&config.trusted_proxies),
+            buckets: Mutex::new(LruCache::new(config.max_tracked_keys)),
+        };
+        Self {
+            inner: Some(Arc::new(inner)),
+        }
+    }
+
+    /// Creates a limiter that accepts every request.
+    pub fn disabled() -> Self {
+        Self { inner: None }
+    }
+
+    /// Returns the cost of the specified method.
+    pub fn method_cost(&self, method: &str) -> u32 {
+        match &self.inner {
+            Some(inner) => inner
+                .method_costs
+                .get(method)
+                .copied()
+                .unwrap_or(inner.default_method_cost),
+            None => 0,
+        }
+    }
+
+    /// Resolves the client IP address.
+    ///
+    /// Forwarded headers are honoured only if the request came from a trusted proxy. Every proxy
+    /// appends the address it has received the request from to `X-Forwarded-For`, so the right-most
+    /// address which doesn't belong to a trusted proxy is the client one.
+    pub fn client_ip(&self, addr: &ClientAddr) -> Option<IpAddr> {
+        let inner = self.inner.as_ref()?;
+        match addr.peer {
+            Some(peer) if !inner.is_trusted_proxy(&peer) => return Some(peer),
+            // The peer is unknown, so the headers can be trusted only if the server
+            // is expected to be reachable through the trusted proxies.
+            None if inner.trusted_proxies.is_empty() => return None,
+            _ => {}
+        }
+
+        if let Some(forwarded_for) = &addr.forwarded_for {
+            let mut client_ip = None;
+            for ip in forwarded_for.rsplit(',') {
+                match IpAddr::from_str(ip.trim()) {
+                    Ok(ip) => {
+                        client_ip = Some(ip);
+                        if !inner.is_trusted_proxy(&ip) {
+                            break;
+                        }
+                    }
+                    // Values to the left of the trusted proxies can't be trusted.
+                    Err(_) => break,
+                }
+            }
+            if client_ip.is_some() {
+                return client_ip;
+            }
+        }
+        addr.real_ip
+            .as_deref()
+            .and_then(|ip| IpAddr::from_str(ip.trim()).ok())
+            .or(addr.peer)
+    }
+
+    /// Checks whether the request to the `method` can be served and consumes the tokens
+    /// from the corresponding buckets if so.
+    pub fn check(
+        &self,
+        server: ApiServer,
+        method: &str,
+        ip: Option<IpAddr>,
+        account: Option<RateLimitKey>,
+    ) -> Result<(), RateLimitError> {
+        self.check_at(server, method, ip, account, Instant::now())
+    }
+
+    fn check_at(
+        &self,
+        server: ApiServer,
+        method: &str,
+        ip: Option<IpAddr>,
+        account: Option<RateLimitKey>,
+        now: Instant,
+    ) -> Result<(), RateLimitError> {
+        let inner = match &self.inner {
+            Some(inner) => inner,
+            None => return Ok(()),
+        };
+        let cost = self.method_cost(method) as f64;
+        let keys: Vec<_> = ip
+            .map(RateLimitKey::Ip)
+            .into_iter()
+            .chain(account)
+            .collect();
+
+        let mut buckets = inner.buckets.lock().unwrap();
+        // Tokens are consumed only if every bucket has enough of them.
+        for key in &keys {
+            let params = inner.params(key);
+            if !buckets.contains_key(key) {
+                buckets.insert(*key, TokenBucket::full(params, now));
+            }
+            let bucket = buckets.get_mut(key).expect("bucket was just inserted");
+            bucket.refill(params, now);
+
+            if bucket.tokens < cost {
+                metrics::increment_counter!(
+                    "api.rate_limit.rejected",
+                    "server" => server.as_str(),
+                    "method" => method.to_string(),
+                    "key" => key.kind()
+                );
+                return Err(RateLimitError { kind: key.kind() });
+            }
+        }
+        for key in &keys {
+            if let Some(bucket) = buckets.get_mut(key) {
+                bucket.tokens -= cost;
+            }
+        }
+        metrics::gauge!("api.rate_limit.tracked_keys", buckets.len() as f64);
+
+        Ok(())
+    }
+}
+
+impl RateLimiter {
+    /// Checks the request to the REST API.
+    ///
+    /// Method is identified by the matched route pattern, and for the `accounts` scope
+    /// the account from the path is rate limited as well.
+    pub fn check_rest_request(&self, req: &ServiceRequest) -> Result<(), RateLimitError> {
+        if self.inner.is_none() {
+            return Ok(());
+        }
+
+        let method = req
+            .match_pattern()
+            .unwrap_or_else(|| req.path().to_string());
+        let header = |name: &str| {
+            req.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+        let ip = self.client_ip(&ClientAddr {
+            peer: req.peer_addr().map(|addr| addr.ip()),
+            forwarded_for: header("x-forwarded-for"),
+            real_ip: header("x-real-ip"),
+        });
+        let account = req
+            .path()
+            .split("/accounts/")
+            .nth(1)
+            .and_then(|rest| rest.split('/').next())
+            .and_then(account_from_path_segment);
+
+        self.check(ApiServer::Rest, &method, ip, account)
+    }
+}
+
+fn account_from_path_segment(segment: &str) -> Option<RateLimitKey> {
+    if let Ok(account_id) = u32::from_str(segment) {
+        return Some(RateLimitKey::AccountId(AccountId(account_id)));
+    }
+    Address::from_str(segment.trim_start_matches("0x"))
+        .ok()
+        .map(RateLimitKey::Address)
+}
+
+/// Parses the addresses and subnets of the trusted proxies.
+///
+/// # Panics
+///
+/// Panics if any of the entries has incorrect format.
+fn parse_trusted_proxies(entries: &[String]) -> Vec<IpNet> {
+    entries
+        .iter()
+        .map(|entry| entry.trim())
+        // Empty list may be deserialized from the environment as a single empty entry.
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            IpNet::from_str(entry)
+                .or_else(|_| IpAddr::from_str(entry).map(IpNet::from))
+                .unwrap_or_else(|err| panic!("Incorrect trusted proxy {}: {}", entry, err))
+        })
+        .collect()
+}
+
+/// Tries to find the zkSync account which the JSON RPC call concerns.
+///
+/// The first parameter is considered: it is either an address itself (e.g. `account_info`,
+/// `eth_getBalance`), a transaction (e.g. `tx_submit`) or a batch of transactions (`submit_txs_batch`).
+pub fn account_from_params(params: &Params) -> Option<RateLimitKey> {
+    let first = match params {
+        Params::Array(values) => values.first()?,
+        _ => return None,
+    };
+    account_from_value(first)
+}
+
+fn account_from_value(value: &Value) -> Option<RateLimitKey> {
+    match value {
+        Value::String(address) => Address::from_str(address.trim_start_matches("0x"))
+            .ok()
+            .map(RateLimitKey::Address),
+        Value::Array(values) => values.first().and_then(account_from_value),
+        Value::Object(object) => {
+            // Elements of `submit_txs_batch` are wrapped into the `{ tx, signature }` object.
+            if let Some(tx) = object.get("tx") {
+                return account_from_value(tx);
+            }
+            ["from", "account"]
+                .iter()
+                .filter_map(|field| object.get(*field))
+                .find_map(account_from_value)
+                .or_else(|| {
+                    object
+                        .get("accountId")
+                        .and_then(Value::as_u64)
+                        .map(|id| RateLimitKey::AccountId(AccountId(id as u32)))
+                })
+        }
+        _ => None,
+    }
+}
+
+/// Metadata that allows the rate limiter to find out the client IP address.
+pub trait ClientIp {
+    fn client_addr(&self) -> ClientAddr;
+}
+
+/// Metadata of the HTTP JSON RPC requests.
+#[derive(Debug, Clone, Default)]
+pub struct RequestMeta {
+    pub addr: ClientAddr,
+}
+
+impl Metadata for RequestMeta {}
+
+impl ClientIp for RequestMeta {
+    fn client_addr(&self) -> ClientAddr {
+        self.addr.clone()
+    }
+}
+
+impl RequestMeta {
+    /// Extracts the forwarded headers of the HTTP request.
+    /// The JSON RPC HTTP server doesn't provide the peer address.
+    pub fn from_http_request(
+        request: &jsonrpc_http_server::hyper::Request<jsonrpc_http_server::hyper::Body>,
+    ) -> Self {
+        let header = |name: &str| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+        Self {
+            addr: ClientAddr {
+                peer: None,
+                forwarded_for: header("x-forwarded-for"),
+                real_ip: header("x-real-ip"),
+            },
+        }
+    }
+}
+
+/// WebSocket sessions don't provide the client address, so for the WebSocket server
+/// the per-IP limit is applied only to the connection handshakes.
+impl ClientIp for Arc<Session> {
+    fn client_addr(&self) -> ClientAddr {
+        ClientAddr::default()
+    }
+}
+
+/// JSON RPC middleware applying the rate limiter to every method call.
+#[derive(Debug, Clone)]
+pub struct RateLimitMiddleware {
+    limiter: RateLimiter,
+    server: ApiServer,
+}
+
+impl RateLimitMiddleware {
+    pub fn new(limiter: RateLimiter, server: ApiServer) -> Self {
+        Self { limiter, server }
+    }
+}
+
+impl<M: Metadata + ClientIp> Middleware<M> for RateLimitMiddleware {
+    type Future = jsonrpc_core::middleware::NoopFuture;
+    type CallFuture = Pin<Box<dyn Future<Output = Option<Output>> + Send>>;
+
+    fn on_call<F, X>(&self, call: Call, meta: M, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, M) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        if let Call::MethodCall(method_call) = &call {
+            let account = account_from_params(&method_call.params);
+            let ip = self.limiter.client_ip(&meta.client_addr());
+            if let Err(err) = self
+                .limiter
+                .check(self.server, &method_call.method, ip, account)
+            {
+                let output = Output::from(
+                    Err(err.into_rpc_error(self.server)),
+                    method_call.id.clone(),
+                    method_call.jsonrpc,
+                );
+                return Either::Left(Box::pin(ready(Some(output))));
+            }
+        }
+
+        Either::Right(next(call, meta))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn config() -> RateLimit {
+        RateLimit {
+            enabled: true,
+            ip_bucket_capacity: 10,
+            ip_refill_per_second: 1,
+            account_bucket_capacity: 4,
+            account_refill_per_second: 2,
+            default_method_cost: 1,
+            method_costs: vec!["get_tx_fee=5".into()],
+            max_tracked_keys: 2,
+            trusted_proxies: vec!["10.0.0.0/8".into(), "127.0.0.1".into()],
+        }
+    }
+
+    #[test]
+    fn method_costs() {
+        let limiter = RateLimiter::new(&config());
+        assert_eq!(limiter.method_cost("get_tx_fee"), 5);
+        assert_eq!(limiter.method_cost("account_info"), 1);
+        assert_eq!(RateLimiter::disabled().method_cost("get_tx_fee"), 0);
+    }
+
+    #[test]
+    fn ip_bucket_is_exhausted_and_refilled() {
+        let limiter = RateLimiter::new(&config());
+        let ip = Some(IpAddr::from([127, 0, 0, 1]));
+        let start = Instant::now();
+
+        assert!(limiter
+            .check_at(ApiServer::JsonRpc, "get_tx_fee", ip, None, start)
+            .is_ok());
+        assert!(limiter
+            .check_at(ApiServer::JsonRpc, "get_tx_fee", ip, None, start)
+            .is_ok());
+        let err = limiter
+            .check_at(ApiServer::JsonRpc, "account_info", ip, None, start)
+            .unwrap_err();
+        assert_eq!(err.kind, "ip");
+
+        // Other clients are not affected.
+        let other_ip = Some(IpAddr::from([127, 0, 0, 2]));
+        assert!(limiter
+            .check_at(ApiServer::JsonRpc, "get_tx_fee", other_ip, None, start)
+            .is_ok());
+
+        // After 5 seconds there are enough tokens for one more expensive call.
+        let later = start + Duration::from_secs(5);
+        assert!(limiter
+            .check_at(ApiServer::JsonRpc, "get_tx_fee", ip, None, later)
+            .is_ok());
+        assert!(limiter
+            .check_at(ApiServer::JsonRpc, "account_info", ip, None, later)
+            .is_err());
+    }
+
+    #[test]
+    fn account_bucket_is_shared_between_ips() {
+        let limiter = RateLimiter::new(&config());
+        let account = Some(RateLimitKey::Address(Address::repeat_byte(1)));
+        let start = Instant::now();
+
+        for i in 0..4 {
+            let ip = Some(IpAddr::from([10, 0, 0, i]));
+            assert!(limiter
+                .check_at(ApiServer::Rest, "account_info", ip, account, start)
+                .is_ok());
+        }
+        let err = limiter
+            .check_at(ApiServer::Rest, "account_info", None, account, start)
+            .unwrap_err();
+        assert_eq!(err.kind, "account");
+    }
+
+    #[test]
+    fn rejected_request_does_not_consume_tokens() {
+        let limiter = RateLimiter::new(&config());
+        let ip = Some(IpAddr::from([127, 0, 0, 1]));
+        let account = Some(RateLimitKey::AccountId(AccountId(1)));
+        let start = Instant::now();
+
+        // Account bucket can't afford the expensive call, so the IP bucket must remain full.
+        assert!(limiter
+            .check_at(ApiServer::JsonRpc, "get_tx_fee", ip, account, start)
+            .is_err());
+        for _ in 0..10 {
+            assert!(limiter
+                .check_at(ApiServer::JsonRpc, "account_info", ip, None, start)
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn disabled_limiter() {
+        let mut config = config();
+        config.enabled = false;
+        let limiter = RateLimiter::new(&config);
+        let ip = Some(IpAddr::from([127, 0, 0, 1]));
+        for _ in 0..100 {
+            assert!(limiter
+                .check(ApiServer::Web3, "eth_getLogs", ip, None)
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn least_recently_used_buckets_are_evicted() {
+        let limiter = RateLimiter::new(&config());
+        let start = Instant::now();
+        let first = Some(IpAddr::from([10, 0, 0, 1]));
+        let second = Some(IpAddr::from([10, 0, 0, 2]));
+        let third = Some(IpAddr::from([10, 0, 0, 3]));
+
+        assert!(limiter
+            .check_at(ApiServer::Rest, "get_tx_fee", first, None, start)
+            .is_ok());
+        assert!(limiter
+            .check_at(ApiServer::Rest, "get_tx_fee", second, None, start)
+            .is_ok());
+        // Make the first bucket the most recently used one and exhaust it.
+        assert!(limiter
+            .check_at(ApiServer::Rest, "get_tx_fee", first, None, start)
+            .is_ok());
+        assert!(limiter
+            .check_at(ApiServer::Rest, "get_tx_fee", third, None, start)
+            .is_ok());
+
+        let mut buckets = limiter.inner.as_ref().unwrap().buckets.lock().unwrap();
+        assert_eq!(buckets.len(), 2);
+        assert!(!buckets.contains_key(&RateLimitKey::Ip(second.unwrap())));
+        drop(buckets);
+
+        // The exhausted bucket was not evicted.
+        assert!(limiter
+            .check_at(ApiServer::Rest, "account_info", first, None, start)
+            .is_err());
+    }
+
+    #[test]
+    fn account_extraction() {
+        let address = Address::repeat_byte(0xab);
+        let address_str = format!("{:?}", address);
+
+        let params = Params::Array(vec![json!(address_str)]);
+        assert_eq!(
+            account_from_params(&params),
+            Some(RateLimitKey::Address(address))
+        );
+
+        let params = Params::Array(vec![json!({ "type": "Transfer", "from": address_str })]);
+        assert_eq!(
+            account_from_params(&params),
+            Some(RateLimitKey::Address(address))
+        );
+
+        let params = Params::Array(vec![json!([
+            { "tx": { "type": "ChangePubKey", "account": address_str, "accountId": 5 } }
+        ])]);
+        assert_eq!(
+            account_from_params(&params),
+            Some(RateLimitKey::Address(address))
+        );
+
+        let params = Params::Array(vec![json!({ "type": "ForcedExit", "accountId": 5 })]);
+        assert_eq!(
+            account_from_params(&params),
+            Some(RateLimitKey::AccountId(AccountId(5)))
+        );
+
+        let params = Params::Array(vec![json!(10)]);
+        assert_eq!(account_from_params(&params), None);
+        assert_eq!(account_from_params(&Params::None), None);
+    }
+
+    #[test]
+    fn account_from_rest_path() {
+        assert_eq!(
+            account_from_path_segment("42"),
+            Some(RateLimitKey::AccountId(AccountId(42)))
+        );
+        assert_eq!(
+            account_from_path_segment("0x0101010101010101010101010101010101010101"),
+            Some(RateLimitKey::Address(Address::repeat_byte(1)))
+        );
+        assert_eq!(account_from_path_segment("latest"), None);
+    }
+
+    #[test]
+    fn client_ip_resolution() {
+        let limiter = RateLimiter::new(&config());
+        let client = IpAddr::from([203, 0, 113, 1]);
+        let proxy = IpAddr::from([10, 1, 2, 3]);
+
+        // Headers set by an untrusted peer are ignored.
+        let addr = ClientAddr {
+            peer: Some(client),
+            forwarded_for: Some("198.51.100.7".into()),
+            real_ip: Some("198.51.100.7".into()),
+        };
+        assert_eq!(limiter.client_ip(&addr), Some(client));
+
+        // Client can't spoof its address by prepending values to the header.
+        let addr = ClientAddr {
+            peer: Some(proxy),
+            forwarded_for: Some("198.51.100.7, 203.0.113.1, 10.0.0.1".into()),
+            real_ip: None,
+        };
+        assert_eq!(limiter.client_ip(&addr), Some(client));
+
+        let addr = ClientAddr {
+            peer: Some(proxy),
+            forwarded_for: None,
+            real_ip: Some("203.0.113.1".into()),
+        };
+        assert_eq!(limiter.client_ip(&addr), Some(client));
+
+        let addr = ClientAddr {
+            peer: Some(proxy),
+            forwarded_for: Some("unknown".into()),
+            real_ip: None,
+        };
+        assert_eq!(limiter.client_ip(&addr), Some(proxy));
+
+        // Without trusted proxies the headers are never honoured.
+        let mut config = config();
+        config.trusted_proxies = vec![String::new()];
+        let limiter = RateLimiter::new(&config);
+        let addr = ClientAddr {
+            peer: None,
+            forwarded_for: Some("203.0.113.1".into()),
+            real_ip: None,
+        };
+        assert_eq!(limiter.client_ip(&addr), None);
+    }
+}
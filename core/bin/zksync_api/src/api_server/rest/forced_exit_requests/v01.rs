@@ -23,8 +23,8 @@ use zksync_config::ZkSyncConfig;
 use zksync_storage::ConnectionPool;
 use zksync_types::{
     forced_exit_requests::{
-        ForcedExitEligibilityResponse, ForcedExitRequest, ForcedExitRequestId,
-        SaveForcedExitRequestQuery,
+        ForcedExitEligibilityResponse, ForcedExitRequest, ForcedExitRequestEvent,
+        ForcedExitRequestId, SaveForcedExitRequestQuery,
     },
     Address, TokenLike,
 };
@@ -195,6 +195,42 @@ pub async fn get_request_by_id(
     }
 }
 
+// Returns the history of the request state changes
+pub async fn get_request_events(
+    data: web::Data<ApiForcedExitRequestsData>,
+    request_id: web::Path<ForcedExitRequestId>,
+) -> JsonResult<Vec<ForcedExitRequestEvent>> {
+    let start = Instant::now();
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(warn_err)
+        .map_err(ApiError::internal)?;
+
+    let mut fe_requests_schema = storage.forced_exit_requests_schema();
+
+    let fe_request_from_db = fe_requests_schema
+        .get_request_by_id(*request_id)
+        .await
+        .map_err(ApiError::internal)?;
+    if fe_request_from_db.is_none() {
+        return Err(ApiError::not_found("Request with such id does not exist"));
+    }
+
+    let events = fe_requests_schema
+        .get_request_events(*request_id)
+        .await
+        .map_err(ApiError::internal)?;
+
+    metrics::histogram!(
+        "api.forced_exit_requests.v01.get_request_events",
+        start.elapsed()
+    );
+    Ok(Json(events))
+}
+
 // Checks if the account is eligible for forced_exit in terms of
 // existing enough time
 pub async fn check_account_eligibility(
@@ -235,6 +271,7 @@ pub fn api_scope(
         scope
             .route("/submit", web::post().to(submit_request))
             .route("/requests/{id}", web::get().to(get_request_by_id))
+            .route("/requests/{id}/events", web::get().to(get_request_events))
             .route(
                 "/checks/eligibility/{account}",
                 web::get().to(check_account_eligibility),
@@ -254,7 +291,7 @@ mod tests {
     use zksync_api_client::rest::client::Client;
     use zksync_config::ForcedExitRequestsConfig;
     use zksync_storage::ConnectionPool;
-    use zksync_types::{Address, TokenId};
+    use zksync_types::{forced_exit_requests::ForcedExitRequestState, Address, TokenId};
 
     use super::*;
     use crate::api_server::{
@@ -451,6 +488,16 @@ mod tests {
         assert_eq!(submit_result.price_in_wei, price_in_wei);
         assert_eq!(submit_result.tokens, tokens);
         assert_eq!(submit_result.target, target);
+        assert_eq!(submit_result.state, ForcedExitRequestState::Created);
+
+        let stored_request = client.get_forced_exit_request(submit_result.id).await?;
+        assert_eq!(stored_request, submit_result);
+
+        let events = client
+            .get_forced_exit_request_events(submit_result.id)
+            .await?;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].state, ForcedExitRequestState::Created);
 
         server.stop().await;
         Ok(())
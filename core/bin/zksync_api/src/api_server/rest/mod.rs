@@ -1,6 +1,9 @@
 use actix_cors::Cors;
-use actix_web::{web, App, HttpResponse, HttpServer};
-use futures::channel::mpsc;
+use actix_web::{dev::Service, web, App, HttpResponse, HttpServer};
+use futures::{
+    channel::mpsc,
+    future::{ready, Either},
+};
 use std::net::SocketAddr;
 use zksync_storage::ConnectionPool;
 use zksync_types::H160;
@@ -10,7 +13,7 @@ use zksync_utils::panic_notify::ThreadPanicNotify;
 use self::v01::api_decl::ApiV01;
 use crate::{fee_ticker::TickerRequest, signature_checker::VerifySignatureRequest};
 
-use super::tx_sender::TxSender;
+use super::{rate_limiter::RateLimiter, tx_sender::TxSender};
 use zksync_config::ZkSyncConfig;
 
 mod forced_exit_requests;
@@ -22,10 +25,12 @@ async fn start_server(
     api_v01: ApiV01,
     fee_ticker: mpsc::Sender<TickerRequest>,
     sign_verifier: mpsc::Sender<VerifySignatureRequest>,
+    rate_limiter: RateLimiter,
     bind_to: SocketAddr,
 ) {
     HttpServer::new(move || {
         let api_v01 = api_v01.clone();
+        let rate_limiter = rate_limiter.clone();
 
         let forced_exit_requests_api_scope =
             forced_exit_requests::api_scope(api_v01.connection_pool.clone(), &api_v01.config);
@@ -37,7 +42,12 @@ async fn start_server(
                 fee_ticker.clone(),
                 &api_v01.config,
             );
-            v02::api_scope(tx_sender, &api_v01.config)
+            v02::api_scope(tx_sender, &api_v01.config).wrap_fn(move |req, srv| {
+                match rate_limiter.check_rest_request(&req) {
+                    Ok(()) => Either::Left(srv.call(req)),
+                    Err(err) => Either::Right(ready(Err(err.into()))),
+                }
+            })
         };
         App::new()
             .wrap(
@@ -75,6 +85,7 @@ pub(super) fn start_server_thread_detached(
     panic_notify: mpsc::Sender<bool>,
     fee_ticker: mpsc::Sender<TickerRequest>,
     sign_verifier: mpsc::Sender<VerifySignatureRequest>,
+    rate_limiter: RateLimiter,
     config: ZkSyncConfig,
 ) {
     std::thread::Builder::new()
@@ -86,7 +97,14 @@ pub(super) fn start_server_thread_detached(
                 let api_v01 = ApiV01::new(connection_pool, contract_address, config.clone());
                 api_v01.spawn_network_status_updater(panic_notify);
 
-                start_server(api_v01, fee_ticker, sign_verifier, listen_addr).await;
+                start_server(
+                    api_v01,
+                    fee_ticker,
+                    sign_verifier,
+                    rate_limiter,
+                    listen_addr,
+                )
+                .await;
             });
         })
         .expect("Api server thread");
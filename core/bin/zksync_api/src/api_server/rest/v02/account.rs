@@ -11,13 +11,15 @@ use actix_web::{web, Scope};
 use zksync_api_types::v02::{
     account::{Account, AccountAddressOrId, AccountState},
     pagination::{
-        parse_query, AccountTxsRequest, ApiEither, Paginated, PaginationQuery, PendingOpsRequest,
+        parse_query, AccountNFTsRequest, AccountTxsRequest, ApiEither, MintedNFTsRequest,
+        Paginated, PaginationQuery, PendingOpsRequest,
     },
+    token::NFT,
     transaction::{Transaction, TxHashSerializeWrapper},
 };
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
 use zksync_storage::{ConnectionPool, StorageProcessor};
-use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, SerialId};
+use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, SerialId, TokenId};
 
 // Local uses
 use super::{
@@ -290,6 +292,40 @@ impl ApiAccountData {
         storage.paginate_checked(&new_query).await
     }
 
+    async fn account_nfts(
+        &self,
+        query: PaginationQuery<ApiEither<TokenId>>,
+        account_id: AccountId,
+    ) -> Result<Paginated<NFT, TokenId>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let new_query = PaginationQuery {
+            from: AccountNFTsRequest {
+                account_id,
+                token_id: query.from,
+            },
+            limit: query.limit,
+            direction: query.direction,
+        };
+        storage.paginate_checked(&new_query).await
+    }
+
+    async fn account_minted_nfts(
+        &self,
+        query: PaginationQuery<ApiEither<TokenId>>,
+        creator_id: AccountId,
+    ) -> Result<Paginated<NFT, TokenId>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let new_query = PaginationQuery {
+            from: MintedNFTsRequest {
+                creator_id,
+                token_id: query.from,
+            },
+            limit: query.limit,
+            direction: query.direction,
+        };
+        storage.paginate_checked(&new_query).await
+    }
+
     /// Pending deposits can be matched only with addresses,
     /// while pending full exits can be matched only with account ids.
     /// If the account isn't created yet it doesn't have an id
@@ -384,6 +420,34 @@ async fn account_pending_txs(
         .into()
 }
 
+async fn account_nfts(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+    web::Query(query): web::Query<PaginationQuery<String>>,
+) -> ApiResult<Paginated<NFT, TokenId>> {
+    let query = api_try!(parse_query(query).map_err(Error::from));
+    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let account_id = api_try!(data
+        .get_id_by_address_or_id(address_or_id)
+        .await
+        .and_then(|id| id.ok_or_else(|| Error::from(InvalidDataError::AccountNotFound))));
+    data.account_nfts(query, account_id).await.into()
+}
+
+async fn account_minted_nfts(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+    web::Query(query): web::Query<PaginationQuery<String>>,
+) -> ApiResult<Paginated<NFT, TokenId>> {
+    let query = api_try!(parse_query(query).map_err(Error::from));
+    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let account_id = api_try!(data
+        .get_id_by_address_or_id(address_or_id)
+        .await
+        .and_then(|id| id.ok_or_else(|| Error::from(InvalidDataError::AccountNotFound))));
+    data.account_minted_nfts(query, account_id).await.into()
+}
+
 pub fn api_scope(
     pool: ConnectionPool,
     tokens: TokenDBCache,
@@ -410,6 +474,11 @@ pub fn api_scope(
             "{account_id_or_address}/transactions/pending",
             web::get().to(account_pending_txs),
         )
+        .route("{account_id_or_address}/nfts", web::get().to(account_nfts))
+        .route(
+            "{account_id_or_address}/nfts/minted",
+            web::get().to(account_minted_nfts),
+        )
 }
 
 #[cfg(test)]
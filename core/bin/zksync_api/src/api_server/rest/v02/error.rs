@@ -39,6 +39,7 @@ pub enum ErrorCode {
     InappropriateFeeToken = 606,
     CommunicationCoreServer = 607,
     Toggle2FAError = 608,
+    FeeQuoteRejected = 609,
     Other = 60_000,
 }
 
@@ -187,6 +188,7 @@ impl ApiError for SubmitError {
             Self::IncorrectTx(_) => ErrorCode::IncorrectTx,
             Self::TxAdd(_) => ErrorCode::TxAddError,
             Self::InappropriateFeeToken => ErrorCode::InappropriateFeeToken,
+            Self::FeeQuote(_) => ErrorCode::FeeQuoteRejected,
             Self::CommunicationCoreServer(_) => ErrorCode::CommunicationCoreServer,
             Self::Internal(_) => ErrorCode::InternalError,
             Self::Toggle2FA(_) => ErrorCode::Toggle2FAError,
@@ -44,7 +44,12 @@ async fn get_tx_fee(
         return Error::from(SubmitError::InappropriateFeeToken).into();
     }
     data.tx_sender
-        .get_txs_fee_in_wei(body.tx_type.into(), body.address, body.token_like)
+        .get_txs_fee_in_wei(
+            body.tx_type.into(),
+            body.address,
+            body.token_like,
+            body.sender.zip(body.nonce),
+        )
         .await
         .map_err(Error::from)
         .map(ApiFee::from)
@@ -100,7 +105,7 @@ mod tests {
         fee::{ApiTxFeeTypes, TxInBatchFeeRequest},
         ApiVersion,
     };
-    use zksync_types::{tokens::TokenLike, Address, TokenId};
+    use zksync_types::{tokens::TokenLike, Address, Nonce, TokenId};
 
     #[actix_rt::test]
     #[cfg_attr(
@@ -140,12 +145,29 @@ mod tests {
         let allowed_token = TokenLike::Id(TokenId(2));
 
         let response = client
-            .get_txs_fee(tx_type, address, allowed_token.clone())
+            .get_txs_fee(tx_type.clone(), address, allowed_token.clone())
             .await?;
         let api_fee: ApiFee = deserialize_response_result(response)?;
         assert_eq!(api_fee.gas_fee, BigUint::from(1u32));
         assert_eq!(api_fee.zkp_fee, BigUint::from(1u32));
         assert_eq!(api_fee.total_fee, BigUint::from(2u32));
+        // The fee is not quoted if the sender is unknown.
+        assert_eq!(api_fee.quote, None);
+
+        let response = client
+            .get_txs_fee_quote(
+                tx_type,
+                address,
+                allowed_token.clone(),
+                Address::repeat_byte(0x11),
+                Nonce(0),
+            )
+            .await?;
+        let api_fee: ApiFee = deserialize_response_result(response)?;
+        // Fee of the single transaction is quoted.
+        let quote = api_fee.quote.expect("fee quote is not issued");
+        assert_eq!(quote.total_fee, api_fee.total_fee);
+        assert!(quote.expires_at > chrono::Utc::now());
 
         let tx = TxInBatchFeeRequest {
             tx_type: ApiTxFeeTypes::Withdraw,
@@ -158,6 +180,7 @@ mod tests {
         assert_eq!(api_batch_fee.gas_fee, BigUint::from(3u32));
         assert_eq!(api_batch_fee.zkp_fee, BigUint::from(3u32));
         assert_eq!(api_batch_fee.total_fee, BigUint::from(6u32));
+        assert_eq!(api_batch_fee.quote, None);
 
         server.stop().await;
         Ok(())
@@ -7,9 +7,10 @@ use zksync_api_types::{
     v02::{
         block::BlockInfo,
         pagination::{
-            AccountTxsRequest, ApiEither, BlockAndTxHash, Paginated, PaginationQuery,
-            PendingOpsRequest,
+            AccountNFTsRequest, AccountTxsRequest, ApiEither, BlockAndTxHash, MintedNFTsRequest,
+            NFTTxsRequest, Paginated, PaginationQuery, PendingOpsRequest,
         },
+        token::NFT,
         transaction::{Transaction, TxHashSerializeWrapper},
     },
     Either,
@@ -262,6 +263,198 @@ impl Paginate<AccountTxsRequest> for StorageProcessor<'_> {
     }
 }
 
+#[async_trait::async_trait]
+impl Paginate<AccountNFTsRequest> for StorageProcessor<'_> {
+    type OutputObj = NFT;
+    type OutputId = TokenId;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<AccountNFTsRequest>,
+    ) -> Result<Paginated<NFT, TokenId>, Error> {
+        let mut transaction = self.start_transaction().await.map_err(Error::storage)?;
+
+        let token_id = match query.from.token_id.inner {
+            Either::Left(token_id) => token_id,
+            Either::Right(_) => {
+                if let Some(token_id) = transaction
+                    .chain()
+                    .account_schema()
+                    .get_account_last_nft_id(query.from.account_id)
+                    .await
+                    .map_err(Error::storage)?
+                {
+                    token_id
+                } else {
+                    return Ok(Paginated::new(
+                        Vec::new(),
+                        Default::default(),
+                        query.limit,
+                        query.direction,
+                        0,
+                    ));
+                }
+            }
+        };
+
+        let page_query = PaginationQuery {
+            from: token_id,
+            limit: query.limit,
+            direction: query.direction,
+        };
+        let nfts = transaction
+            .chain()
+            .account_schema()
+            .load_account_nfts_page(query.from.account_id, &page_query)
+            .await
+            .map_err(Error::storage)?;
+        let count = transaction
+            .chain()
+            .account_schema()
+            .get_account_nfts_count(query.from.account_id)
+            .await
+            .map_err(Error::storage)?;
+        transaction.commit().await.map_err(Error::storage)?;
+
+        Ok(Paginated::new(
+            nfts.into_iter().map(NFT::from).collect(),
+            token_id,
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl Paginate<MintedNFTsRequest> for StorageProcessor<'_> {
+    type OutputObj = NFT;
+    type OutputId = TokenId;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<MintedNFTsRequest>,
+    ) -> Result<Paginated<NFT, TokenId>, Error> {
+        let mut transaction = self.start_transaction().await.map_err(Error::storage)?;
+
+        let token_id = match query.from.token_id.inner {
+            Either::Left(token_id) => token_id,
+            Either::Right(_) => {
+                if let Some(token_id) = transaction
+                    .tokens_schema()
+                    .get_last_minted_nft_id(query.from.creator_id)
+                    .await
+                    .map_err(Error::storage)?
+                {
+                    token_id
+                } else {
+                    return Ok(Paginated::new(
+                        Vec::new(),
+                        Default::default(),
+                        query.limit,
+                        query.direction,
+                        0,
+                    ));
+                }
+            }
+        };
+
+        let page_query = PaginationQuery {
+            from: token_id,
+            limit: query.limit,
+            direction: query.direction,
+        };
+        let nfts = transaction
+            .tokens_schema()
+            .load_minted_nfts_page(query.from.creator_id, &page_query)
+            .await
+            .map_err(Error::storage)?;
+        let count = transaction
+            .tokens_schema()
+            .get_minted_nfts_count(query.from.creator_id)
+            .await
+            .map_err(Error::storage)?;
+        transaction.commit().await.map_err(Error::storage)?;
+
+        Ok(Paginated::new(
+            nfts.into_iter().map(NFT::from).collect(),
+            token_id,
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl Paginate<NFTTxsRequest> for StorageProcessor<'_> {
+    type OutputObj = Transaction;
+    type OutputId = TxHashSerializeWrapper;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<NFTTxsRequest>,
+    ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
+        let mut transaction = self.start_transaction().await.map_err(Error::storage)?;
+
+        let tx_hash = match query.from.tx_hash.inner {
+            Either::Left(tx_hash) => tx_hash,
+            Either::Right(_) => {
+                if let Some(tx_hash) = transaction
+                    .chain()
+                    .operations_ext_schema()
+                    .get_nft_last_tx_hash(query.from.token_id)
+                    .await
+                    .map_err(Error::storage)?
+                {
+                    tx_hash
+                } else {
+                    return Ok(Paginated::new(
+                        Vec::new(),
+                        Default::default(),
+                        query.limit,
+                        query.direction,
+                        0,
+                    ));
+                }
+            }
+        };
+
+        let query = PaginationQuery {
+            from: NFTTxsRequest {
+                token_id: query.from.token_id,
+                tx_hash: ApiEither::from(tx_hash),
+            },
+            limit: query.limit,
+            direction: query.direction,
+        };
+
+        let txs = transaction
+            .chain()
+            .operations_ext_schema()
+            .get_nft_transactions(&query)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::TransactionNotFound))?;
+        let count = transaction
+            .chain()
+            .operations_ext_schema()
+            .get_nft_transactions_count(query.from.token_id)
+            .await
+            .map_err(Error::storage)?;
+
+        transaction.commit().await.map_err(Error::storage)?;
+
+        Ok(Paginated::new(
+            txs,
+            TxHashSerializeWrapper(tx_hash),
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
+
 #[async_trait::async_trait]
 impl Paginate<PendingOpsRequest> for CoreApiClient {
     type OutputObj = Transaction;
@@ -17,8 +17,9 @@ use num::{rational::Ratio, BigUint, FromPrimitive};
 
 // Workspace uses
 use zksync_api_types::v02::{
-    pagination::{parse_query, ApiEither, Paginated, PaginationQuery},
+    pagination::{parse_query, ApiEither, NFTTxsRequest, Paginated, PaginationQuery},
     token::{ApiNFT, ApiToken, TokenPrice},
+    transaction::{Transaction, TxHashSerializeWrapper},
 };
 use zksync_config::ZkSyncConfig;
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
@@ -279,6 +280,27 @@ async fn get_nft_owner(
     ApiResult::Ok(owner_id)
 }
 
+async fn get_nft_transactions(
+    data: web::Data<ApiTokenData>,
+    id: web::Path<TokenId>,
+    web::Query(query): web::Query<PaginationQuery<String>>,
+) -> ApiResult<Paginated<Transaction, TxHashSerializeWrapper>> {
+    if id.0 < MIN_NFT_TOKEN_ID {
+        return Error::from(InvalidDataError::InvalidNFTTokenId).into();
+    }
+    let query = api_try!(parse_query(query).map_err(Error::from));
+    let query = PaginationQuery {
+        from: NFTTxsRequest {
+            token_id: *id,
+            tx_hash: query.from,
+        },
+        limit: query.limit,
+        direction: query.direction,
+    };
+    let mut storage = api_try!(data.pool.access_storage().await.map_err(Error::storage));
+    storage.paginate_checked(&query).await.into()
+}
+
 pub fn api_scope(
     config: &ZkSyncConfig,
     pool: ConnectionPool,
@@ -300,6 +322,7 @@ pub fn api_scope(
         )
         .route("nft/{id}", web::get().to(get_nft))
         .route("nft/{id}/owner", web::get().to(get_nft_owner))
+        .route("nft/{id}/transactions", web::get().to(get_nft_transactions))
 }
 
 #[cfg(test)]
@@ -310,7 +333,7 @@ mod tests {
         SharedData,
     };
     use zksync_api_types::v02::{pagination::PaginationDirection, ApiVersion};
-    use zksync_types::Address;
+    use zksync_types::{tx::TxHash, Address};
 
     async fn is_token_enabled_for_fees(
         storage: &mut StorageProcessor<'_>,
@@ -461,6 +484,30 @@ mod tests {
         };
         assert_eq!(owner_id, expected_owner_id);
 
+        let query = PaginationQuery {
+            from: ApiEither::<TxHash>::from_str("latest").unwrap(),
+            limit: 5,
+            direction: PaginationDirection::Older,
+        };
+        let response = client.nft_txs(&query, nft_id).await?;
+        let txs: Paginated<Transaction, TxHashSerializeWrapper> =
+            deserialize_response_result(response)?;
+        let expected_txs = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .paginate_checked(&PaginationQuery {
+                    from: NFTTxsRequest {
+                        token_id: nft_id,
+                        tx_hash: query.from,
+                    },
+                    limit: query.limit,
+                    direction: query.direction,
+                })
+                .await
+                .map_err(|err| anyhow::anyhow!(err.message))?
+        };
+        assert_eq!(txs.pagination, expected_txs.pagination);
+
         server.stop().await;
         Ok(())
     }
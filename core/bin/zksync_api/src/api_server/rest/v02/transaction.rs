@@ -1,6 +1,7 @@
 //! Transactions part of API implementation.
 
 // Built-in uses
+use std::convert::TryFrom;
 
 // External uses
 use actix_web::{
@@ -11,13 +12,13 @@ use actix_web::{
 // Workspace uses
 use zksync_api_types::{
     v02::transaction::{
-        ApiTxBatch, IncomingTxBatch, L1Receipt, L1Transaction, Receipt, SubmitBatchResponse,
-        Toggle2FA, Toggle2FAResponse, Transaction, TransactionData, TxData, TxHashSerializeWrapper,
-        TxInBlockStatus,
+        ApiTxBatch, IncomingTx, IncomingTxBatch, L1Receipt, L1Transaction, Receipt,
+        SubmitBatchResponse, Toggle2FA, Toggle2FAResponse, Transaction, TransactionData, TxData,
+        TxHashSerializeWrapper, TxInBlockStatus, WithdrawalInfo,
     },
     PriorityOpLookupQuery, TxWithSignature,
 };
-use zksync_types::{tx::TxHash, EthBlockId};
+use zksync_types::{tx::TxHash, EthBlockId, SerialId};
 
 // Local uses
 use super::{error::Error, response::ApiResult};
@@ -67,6 +68,36 @@ impl ApiTransactionData {
         }
     }
 
+    /// Priority operations which are not executed yet can be looked up only by their hashes.
+    async fn priority_op_receipt(&self, serial_id: SerialId) -> Result<Option<Receipt>, Error> {
+        let serial_id = match u32::try_from(serial_id) {
+            Ok(serial_id) => serial_id,
+            Err(_) => return Ok(None),
+        };
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let op = storage
+            .chain()
+            .operations_schema()
+            .get_executed_priority_operation(serial_id)
+            .await
+            .map_err(Error::storage)?;
+        if let Some(op) = op {
+            storage
+                .chain()
+                .operations_ext_schema()
+                .tx_receipt_api_v02(&op.tx_hash)
+                .await
+                .map_err(Error::storage)
+        } else {
+            Ok(None)
+        }
+    }
+
     async fn tx_data(&self, tx_hash: TxHash) -> Result<Option<TxData>, Error> {
         let mut storage = self
             .tx_sender
@@ -113,6 +144,20 @@ impl ApiTransactionData {
         }
     }
 
+    async fn withdrawal_status(&self, tx_hash: TxHash) -> Result<Option<WithdrawalInfo>, Error> {
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        storage
+            .withdrawals_schema()
+            .get_withdrawal(tx_hash)
+            .await
+            .map_err(Error::storage)
+    }
+
     async fn get_batch(&self, batch_hash: TxHash) -> Result<Option<ApiTxBatch>, Error> {
         let mut storage = self
             .tx_sender
@@ -138,6 +183,13 @@ async fn tx_status(
     data.tx_status(*tx_hash).await.into()
 }
 
+async fn priority_op_receipt(
+    data: web::Data<ApiTransactionData>,
+    serial_id: web::Path<SerialId>,
+) -> ApiResult<Option<Receipt>> {
+    data.priority_op_receipt(*serial_id).await.into()
+}
+
 async fn tx_data(
     data: web::Data<ApiTransactionData>,
     tx_hash: web::Path<TxHash>,
@@ -145,13 +197,20 @@ async fn tx_data(
     data.tx_data(*tx_hash).await.into()
 }
 
+async fn withdrawal_status(
+    data: web::Data<ApiTransactionData>,
+    tx_hash: web::Path<TxHash>,
+) -> ApiResult<Option<WithdrawalInfo>> {
+    data.withdrawal_status(*tx_hash).await.into()
+}
+
 async fn submit_tx(
     data: web::Data<ApiTransactionData>,
-    Json(body): Json<TxWithSignature>,
+    Json(body): Json<IncomingTx>,
 ) -> ApiResult<TxHashSerializeWrapper> {
     let tx_hash = data
         .tx_sender
-        .submit_tx(body.tx, body.signature)
+        .submit_tx_with_fee_quote(body.tx, body.signature, body.fee_quote)
         .await
         .map_err(Error::from);
 
@@ -198,8 +257,13 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
         .route("", web::post().to(submit_tx))
         .route("{tx_hash}", web::get().to(tx_status))
         .route("{tx_hash}/data", web::get().to(tx_data))
+        .route("{tx_hash}/withdrawal", web::get().to(withdrawal_status))
         .route("/batches", web::post().to(submit_batch))
         .route("/batches/{batch_hash}", web::get().to(get_batch))
+        .route(
+            "/priorityOps/{serial_id}",
+            web::get().to(priority_op_receipt),
+        )
         .route("/toggle2FA", web::post().to(toggle_2fa))
 }
 
@@ -210,7 +274,7 @@ mod tests {
         api_server::rest::v02::{
             test_utils::{
                 deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier,
-                TestServerConfig, TestTransactions,
+                TestServerConfig, TestTransactions, VERIFIED_OP_SERIAL_ID,
             },
             SharedData,
         },
@@ -424,6 +488,25 @@ mod tests {
         let tx_data: Option<TxData> = deserialize_response_result(response)?;
         assert!(tx_data.is_none());
 
+        let response = client.priority_op_receipt(VERIFIED_OP_SERIAL_ID).await?;
+        let receipt: Option<Receipt> = deserialize_response_result(response)?;
+        let expected_receipt = Receipt::L1(L1Receipt {
+            status: TxInBlockStatus::Finalized,
+            eth_block: EthBlockId(10),
+            rollup_block: Some(BlockNumber(2)),
+            id: VERIFIED_OP_SERIAL_ID,
+        });
+        assert_eq!(receipt, Some(expected_receipt));
+
+        let response = client.priority_op_receipt(u64::MAX).await?;
+        let receipt: Option<Receipt> = deserialize_response_result(response)?;
+        assert!(receipt.is_none());
+
+        // Withdrawals are only known after their blocks are processed by the tracker.
+        let response = client.withdrawal_status(tx_hash).await?;
+        let withdrawal: Option<WithdrawalInfo> = deserialize_response_result(response)?;
+        assert!(withdrawal.is_none());
+
         server.stop().await;
         core_server.stop().await;
         Ok(())
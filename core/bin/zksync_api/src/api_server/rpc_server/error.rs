@@ -10,6 +10,7 @@ pub enum RpcErrorCodes {
     IncorrectTx = 103,
     FeeTooLow = 104,
     InappropriateFeeToken = 105,
+    FeeQuoteRejected = 106,
 
     MissingEthSignature = 200,
     EIP1271SignatureVerificationFail = 201,
@@ -21,6 +22,7 @@ pub enum RpcErrorCodes {
     OperationsLimitReached = 302,
     UnsupportedFastProcessing = 303,
     Toggle2FA = 304,
+    RateLimitExceeded = 305,
 }
 
 impl From<TxAddError> for RpcErrorCodes {
@@ -86,6 +88,11 @@ impl From<SubmitError> for jsonrpc_core::Error {
                 message: inner.to_string(),
                 data: None,
             },
+            SubmitError::FeeQuote(_) => Self {
+                code: RpcErrorCodes::FeeQuoteRejected.into(),
+                message: inner.to_string(),
+                data: None,
+            },
             SubmitError::CommunicationCoreServer(reason) => Self {
                 code: RpcErrorCodes::Other.into(),
                 message: "Error communicating core server".to_string(),
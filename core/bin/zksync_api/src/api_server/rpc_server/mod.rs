@@ -6,7 +6,7 @@ use futures::{
     channel::{mpsc, oneshot},
     SinkExt,
 };
-use jsonrpc_core::{Error, IoHandler, MetaIoHandler, Metadata, Middleware, Result};
+use jsonrpc_core::{Error, MetaIoHandler, Metadata, Middleware, Result};
 use jsonrpc_http_server::ServerBuilder;
 
 // Workspace uses
@@ -36,7 +36,10 @@ pub mod types;
 
 pub use self::rpc_trait::Rpc;
 use self::types::*;
-use super::tx_sender::TxSender;
+use super::{
+    rate_limiter::{ApiServer, RateLimitMiddleware, RateLimiter, RequestMeta},
+    tx_sender::TxSender,
+};
 
 #[derive(Clone)]
 pub struct RpcApp {
@@ -405,6 +408,7 @@ pub fn start_rpc_server(
     connection_pool: ConnectionPool,
     sign_verify_request_sender: mpsc::Sender<VerifySignatureRequest>,
     ticker_request_sender: mpsc::Sender<TickerRequest>,
+    rate_limiter: RateLimiter,
     panic_notify: mpsc::Sender<bool>,
     config: &ZkSyncConfig,
 ) {
@@ -418,10 +422,13 @@ pub fn start_rpc_server(
     );
     std::thread::spawn(move || {
         let _panic_sentinel = ThreadPanicNotify(panic_notify);
-        let mut io = IoHandler::new();
+        let mut io = MetaIoHandler::with_middleware(RateLimitMiddleware::new(
+            rate_limiter,
+            ApiServer::JsonRpc,
+        ));
         rpc_app.extend(&mut io);
 
-        let server = ServerBuilder::new(io)
+        let server = ServerBuilder::with_meta_extractor(io, RequestMeta::from_http_request)
             .threads(super::THREADS_PER_SERVER)
             .start_http(&addr)
             .unwrap();
@@ -15,7 +15,8 @@ use zksync_api_types::{
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 use zksync_types::{
     tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash},
-    AccountId, Address, Fee, Token, TokenId, TokenLike, TotalFee, TxFeeTypes, ZkSyncTx,
+    AccountId, Address, Fee, FeeQuote, Nonce, Token, TokenId, TokenLike, TotalFee, TxFeeTypes,
+    ZkSyncTx,
 };
 // Local uses
 use crate::{api_server::tx_sender::SubmitError, fee_ticker::TokenPriceRequestType};
@@ -119,11 +120,12 @@ impl RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
+        fee_quote: Option<FeeQuote>,
     ) -> Result<TxHash> {
         let start = Instant::now();
         let result = self
             .tx_sender
-            .submit_tx_with_separate_fp(*tx, *signature, fast_processing)
+            .submit_tx_with_separate_fp(*tx, *signature, fast_processing, fee_quote)
             .await
             .map_err(Error::from);
         metrics::histogram!("api.rpc.tx_submit", start.elapsed());
@@ -226,6 +228,7 @@ impl RpcApp {
         tx_type: ApiTxFeeTypes,
         address: Address,
         token: TokenLike,
+        sender: Option<(Address, Nonce)>,
     ) -> Result<Fee> {
         let start = Instant::now();
         let ticker = self.tx_sender.ticker_requests.clone();
@@ -233,11 +236,15 @@ impl RpcApp {
         if !token_allowed {
             return Err(SubmitError::InappropriateFeeToken.into());
         }
-        let result =
-            Self::ticker_request(ticker.clone(), tx_type.into(), address, token.clone()).await?;
+        let tx_type: TxFeeTypes = tx_type.into();
+        let result = Self::ticker_request(ticker.clone(), tx_type, address, token.clone()).await?;
+        let fee = self
+            .tx_sender
+            .with_fee_quote(result.normal_fee, tx_type, address, token, sender)
+            .await?;
 
         metrics::histogram!("api.rpc.get_tx_fee", start.elapsed());
-        Ok(result.normal_fee)
+        Ok(fee)
     }
 
     pub async fn _impl_get_txs_batch_fee_in_wei(
@@ -16,7 +16,7 @@ use zksync_api_types::{
 use zksync_crypto::params::ZKSYNC_VERSION;
 use zksync_types::{
     tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash},
-    AccountId, Address, Fee, Token, TokenId, TokenLike, TotalFee, ZkSyncTx,
+    AccountId, Address, Fee, FeeQuote, Nonce, Token, TokenId, TokenLike, TotalFee, ZkSyncTx,
 };
 
 // Local uses
@@ -48,6 +48,7 @@ pub trait Rpc {
         tx: Box<ZkSyncTx>,
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
+        fee_quote: Option<FeeQuote>,
     ) -> BoxFutureResult<TxHash>;
 
     #[rpc(name = "submit_txs_batch", returns = "Vec<TxHash>")]
@@ -65,12 +66,15 @@ pub trait Rpc {
     fn tokens(&self) -> BoxFutureResult<HashMap<String, Token>>;
 
     // _address argument is left for the backward compatibility.
+    // The fee is quoted only if the sender and the nonce of the transaction are provided.
     #[rpc(name = "get_tx_fee", returns = "Fee")]
     fn get_tx_fee(
         &self,
         tx_type: ApiTxFeeTypes,
         _address: Address,
         token_like: TokenLike,
+        sender: Option<Address>,
+        nonce: Option<Nonce>,
     ) -> BoxFutureResult<Fee>;
 
     // _addresses argument is left for the backward compatibility.
@@ -123,8 +127,9 @@ impl Rpc for RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
+        fee_quote: Option<FeeQuote>,
     ) -> BoxFutureResult<TxHash> {
-        spawn!(self._impl_tx_submit(tx, signature, fast_processing))
+        spawn!(self._impl_tx_submit(tx, signature, fast_processing, fee_quote))
     }
 
     fn submit_txs_batch(
@@ -148,8 +153,10 @@ impl Rpc for RpcApp {
         tx_type: ApiTxFeeTypes,
         address: Address,
         token_like: TokenLike,
+        sender: Option<Address>,
+        nonce: Option<Nonce>,
     ) -> BoxFutureResult<Fee> {
-        spawn!(self._impl_get_tx_fee(tx_type, address, token_like))
+        spawn!(self._impl_get_tx_fee(tx_type, address, token_like, sender.zip(nonce)))
     }
 
     fn get_txs_batch_fee_in_wei(
@@ -7,7 +7,7 @@ use futures::channel::mpsc;
 use jsonrpc_core::{MetaIoHandler, Result};
 use jsonrpc_derive::rpc;
 use jsonrpc_pubsub::{typed::Subscriber, PubSubHandler, Session, SubscriptionId};
-use jsonrpc_ws_server::RequestContext;
+use jsonrpc_ws_server::{ws, RequestContext};
 // Workspace uses
 use zksync_storage::ConnectionPool;
 use zksync_types::{tx::TxHash, ActionType, Address};
@@ -15,6 +15,7 @@ use zksync_types::{tx::TxHash, ActionType, Address};
 use crate::fee_ticker::TickerRequest;
 use crate::{
     api_server::event_notify::{start_sub_notifier, EventNotifierRequest, EventSubscribeRequest},
+    api_server::rate_limiter::{ApiServer, ClientAddr, RateLimitMiddleware, RateLimiter},
     api_server::rpc_server::types::{ETHOpInfoResp, ResponseAccountState, TransactionInfoResp},
     signature_checker::VerifySignatureRequest,
 };
@@ -178,6 +179,7 @@ pub fn start_ws_server(
     db_pool: ConnectionPool,
     sign_verify_request_sender: mpsc::Sender<VerifySignatureRequest>,
     ticker_request_sender: mpsc::Sender<TickerRequest>,
+    rate_limiter: RateLimiter,
     panic_notify: mpsc::Sender<bool>,
     config: &ZkSyncConfig,
 ) {
@@ -202,7 +204,10 @@ pub fn start_ws_server(
     std::thread::spawn(move || {
         let _panic_sentinel = ThreadPanicNotify(panic_notify);
 
-        let mut io = PubSubHandler::new(MetaIoHandler::default());
+        let mut io = PubSubHandler::new(MetaIoHandler::with_middleware(RateLimitMiddleware::new(
+            rate_limiter.clone(),
+            ApiServer::WebSocket,
+        )));
 
         req_rpc_app.extend(&mut io);
 
@@ -214,6 +219,25 @@ pub fn start_ws_server(
             io,
             |context: &RequestContext| Arc::new(Session::new(context.sender())),
         )
+        .request_middleware(move |request: &ws::Request| {
+            // Sessions don't know the client address, so the per-IP limit is applied to handshakes.
+            let header = |name: &str| {
+                request
+                    .header(name)
+                    .and_then(|value| String::from_utf8(value.clone()).ok())
+            };
+            let ip = rate_limiter.client_ip(&ClientAddr {
+                peer: None,
+                forwarded_for: header("x-forwarded-for"),
+                real_ip: header("x-real-ip"),
+            });
+            rate_limiter
+                .check(ApiServer::WebSocket, "ws_connect", ip, None)
+                .err()
+                .map(|err| {
+                    ws::Response::new(429, "Too Many Requests", err.to_string().into_bytes())
+                })
+        })
         .max_connections(1000)
         .start(&addr)
         .expect("Unable to start RPC ws server");
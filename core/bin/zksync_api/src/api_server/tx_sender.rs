@@ -18,6 +18,7 @@ use futures::{
 use itertools::izip;
 use num::{bigint::ToBigInt, BigUint, Zero};
 use thiserror::Error;
+use vlog::Instrument;
 
 // Workspace uses
 use zksync_api_types::{
@@ -31,12 +32,16 @@ use zksync_types::{
         EthBatchSignData, EthBatchSignatures, EthSignData, Order, SignedZkSyncTx, TxEthSignature,
         TxEthSignatureVariant, TxHash,
     },
-    AccountId, Address, BatchFee, Fee, Token, TokenId, TokenLike, TxFeeTypes, ZkSyncTx, H160,
+    AccountId, Address, BatchFee, Fee, FeeQuote, Nonce, Token, TokenId, TokenLike, TxFeeTypes,
+    ZkSyncTx, H160,
 };
 
 // Local uses
 use crate::{
-    api_server::forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+    api_server::{
+        fee_quote::{FeeQuoteError, FeeQuoteSigner, QuotedTx},
+        forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+    },
     core_api_client::CoreApiClient,
     fee_ticker::{ResponseBatchFee, ResponseFee, TickerRequest, TokenPriceRequestType},
     signature_checker::{
@@ -63,6 +68,7 @@ pub struct TxSender {
     /// List of account IDs that do not have to pay fees for operations.
     pub fee_free_accounts: HashSet<AccountId>,
     pub enforce_pubkey_change_fee: bool,
+    pub fee_quotes: FeeQuoteSigner,
     // Limit the number of both transactions and Ethereum signatures per batch.
     pub max_number_of_transactions_per_batch: usize,
     pub max_number_of_authors_per_batch: usize,
@@ -82,6 +88,8 @@ pub enum SubmitError {
     TxAdd(TxAddError),
     #[error("Chosen token is not suitable for paying fees.")]
     InappropriateFeeToken,
+    #[error("Fee quote is not accepted: {0}.")]
+    FeeQuote(FeeQuoteError),
     // Not all TxAddErrors would apply to Toggle2FA, but
     // it is helpful to re-use IncorrectEthSignature and DbError
     #[error("Failed to toggle 2FA: {0}.")]
@@ -163,6 +171,7 @@ impl TxSender {
             tokens: TokenDBCache::new(),
             forced_exit_checker: ForcedExitChecker::new(config),
             enforce_pubkey_change_fee: config.api.common.enforce_pubkey_change_fee,
+            fee_quotes: FeeQuoteSigner::new(config),
             blocks: BlockDetailsCache::new(config.api.common.caches_size),
 
             fee_free_accounts: HashSet::from_iter(config.api.common.fee_free_accounts.clone()),
@@ -291,6 +300,28 @@ impl TxSender {
         order: &Order,
         signature: Option<TxEthSignature>,
     ) -> Result<(), SubmitError> {
+        let order_request = match self.order_verification_request(order, signature).await? {
+            Some(order_request) => order_request,
+            None => return Ok(()),
+        };
+        let (sender, receiever) = oneshot::channel();
+
+        let request = VerifySignatureRequest {
+            data: RequestData::Order(order_request),
+            response: sender,
+        };
+
+        send_verify_request_and_recv(request, self.sign_verify_requests.clone(), receiever).await?;
+        Ok(())
+    }
+
+    /// Prepares the data required to verify the Ethereum signature of the order.
+    /// Returns `None` if the signature of this order doesn't have to be checked.
+    async fn order_verification_request(
+        &self,
+        order: &Order,
+        signature: Option<TxEthSignature>,
+    ) -> Result<Option<OrderRequest>, SubmitError> {
         let signer_type = self
             .get_sender_type(order.account_id)
             .await
@@ -301,12 +332,12 @@ impl TxSender {
                     "Eth signature from CREATE2 account not expected".to_string(),
                 ))
             } else {
-                Ok(())
+                Ok(None)
             };
         }
         if matches!(signer_type, EthAccountType::No2FA) {
             // We don't verify signatures for accounts with no 2FA
-            return Ok(());
+            return Ok(None);
         }
 
         let signature = signature.ok_or(SubmitError::TxAdd(TxAddError::MissingEthSignature))?;
@@ -321,19 +352,12 @@ impl TxSender {
             .get_ethereum_sign_message(&token_sell.symbol, &token_buy.symbol, token_sell.decimals)
             .into_bytes();
         let eth_sign_data = EthSignData { signature, message };
-        let (sender, receiever) = oneshot::channel();
 
-        let request = VerifySignatureRequest {
-            data: RequestData::Order(OrderRequest {
-                order: Box::new(order.clone()),
-                sign_data: eth_sign_data,
-                sender: signer,
-            }),
-            response: sender,
-        };
-
-        send_verify_request_and_recv(request, self.sign_verify_requests.clone(), receiever).await?;
-        Ok(())
+        Ok(Some(OrderRequest {
+            order: Box::new(order.clone()),
+            sign_data: eth_sign_data,
+            sender: signer,
+        }))
     }
 
     // This method is left for RPC API
@@ -343,6 +367,7 @@ impl TxSender {
         mut tx: ZkSyncTx,
         signature: TxEthSignatureVariant,
         fast_processing: Option<bool>,
+        fee_quote: Option<FeeQuote>,
     ) -> Result<TxHash, SubmitError> {
         let fast_processing = fast_processing.unwrap_or(false);
         if fast_processing && !tx.is_withdraw() {
@@ -360,13 +385,37 @@ impl TxSender {
             withdraw.fast = fast_processing;
         }
 
-        self.submit_tx(tx, signature).await
+        self.submit_tx_with_fee_quote(tx, signature, fee_quote)
+            .await
     }
 
     pub async fn submit_tx(
         &self,
         tx: ZkSyncTx,
         signature: TxEthSignatureVariant,
+    ) -> Result<TxHash, SubmitError> {
+        self.submit_tx_with_fee_quote(tx, signature, None).await
+    }
+
+    /// Submits the transaction. If the provided fee is not enough to cover the required fee,
+    /// but the fee has been quoted by the server, the transaction is accepted as long as the quote is valid.
+    pub async fn submit_tx_with_fee_quote(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        fee_quote: Option<FeeQuote>,
+    ) -> Result<TxHash, SubmitError> {
+        let span = vlog::info_span!("tx_sender.submit_tx", tx_hash = %tx.hash().to_string());
+        self.submit_tx_traced(tx, signature, fee_quote)
+            .instrument(span)
+            .await
+    }
+
+    async fn submit_tx_traced(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        fee_quote: Option<FeeQuote>,
     ) -> Result<TxHash, SubmitError> {
         if tx.is_close() {
             return Err(SubmitError::AccountCloseDisabled);
@@ -418,11 +467,32 @@ impl TxSender {
                 .to_bigint()
                 .unwrap()
                 .into();
-            let provided_fee: BigDecimal = provided_fee.to_bigint().unwrap().into();
+            let provided_fee_decimal: BigDecimal = provided_fee.to_bigint().unwrap().into();
             // Scaling the fee required since the price may change between signing the transaction and sending it to the server.
-            let scaled_provided_fee = scale_user_fee_up(provided_fee);
+            let scaled_provided_fee = scale_user_fee_up(provided_fee_decimal);
             if required_fee >= scaled_provided_fee && should_enforce_fee {
-                return Err(SubmitError::TxAdd(TxAddError::TxFeeTooLow));
+                let fee_quote = fee_quote.ok_or(SubmitError::TxAdd(TxAddError::TxFeeTooLow))?;
+                let fee_token = self.token_info_from_id(token).await?;
+                let sender = self
+                    .get_tx_sender(&tx)
+                    .await
+                    .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
+                let quoted_tx = QuotedTx {
+                    tx_type,
+                    address,
+                    token: fee_token.id,
+                    sender,
+                    nonce: tx.nonce(),
+                };
+                self.fee_quotes
+                    .check(
+                        &fee_quote,
+                        quoted_tx,
+                        &provided_fee,
+                        &required_fee_data.normal_fee.total_fee,
+                    )
+                    .map_err(SubmitError::FeeQuote)?;
+                metrics::increment_counter!("api.tx_sender.fee_quotes_honored");
             }
         }
 
@@ -468,6 +538,26 @@ impl TxSender {
         &self,
         txs: Vec<TxWithSignature>,
         eth_signatures: Option<EthBatchSignatures>,
+    ) -> Result<SubmitBatchResponse, SubmitError> {
+        let tx_hashes = txs
+            .iter()
+            .map(|tx| tx.tx.hash().to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let span = vlog::info_span!(
+            "tx_sender.submit_txs_batch",
+            batch_size = txs.len(),
+            tx_hashes = %tx_hashes
+        );
+        self.submit_txs_batch_traced(txs, eth_signatures)
+            .instrument(span)
+            .await
+    }
+
+    async fn submit_txs_batch_traced(
+        &self,
+        txs: Vec<TxWithSignature>,
+        eth_signatures: Option<EthBatchSignatures>,
     ) -> Result<SubmitBatchResponse, SubmitError> {
         // Bring the received signatures into a vector for simplified work.
         let eth_signatures = EthBatchSignatures::api_arg_to_vec(eth_signatures);
@@ -604,16 +694,22 @@ impl TxSender {
             }
         }
 
+        // Signatures of the swap orders are verified together with the rest of the batch.
+        let mut orders = Vec::new();
         for tx in txs.iter() {
             if let ZkSyncTx::Swap(swap) = &tx.tx {
                 if tx.signature.is_single() {
                     return Err(SubmitError::TxAdd(TxAddError::MissingEthSignature));
                 }
                 let signatures = tx.signature.orders_signatures();
-                self.verify_order_eth_signature(&swap.orders.0, signatures.0.clone())
-                    .await?;
-                self.verify_order_eth_signature(&swap.orders.1, signatures.1.clone())
-                    .await?;
+                orders.extend(
+                    self.order_verification_request(&swap.orders.0, signatures.0.clone())
+                        .await?,
+                );
+                orders.extend(
+                    self.order_verification_request(&swap.orders.1, signatures.1.clone())
+                        .await?,
+                );
             }
         }
 
@@ -667,6 +763,7 @@ impl TxSender {
             tx_sender_types,
             batch_sign_data,
             messages_to_sign,
+            orders,
             self.sign_verify_requests.clone(),
         )
         .await?
@@ -691,11 +788,14 @@ impl TxSender {
         })
     }
 
+    /// Calculates the fee for the transaction. If the sender and the nonce of the transaction
+    /// are known, the fee is quoted.
     pub async fn get_txs_fee_in_wei(
         &self,
         tx_type: TxFeeTypes,
         address: Address,
         token: TokenLike,
+        sender: Option<(Address, Nonce)>,
     ) -> Result<Fee, SubmitError> {
         let resp_fee = Self::ticker_request(
             self.ticker_requests.clone(),
@@ -704,7 +804,34 @@ impl TxSender {
             token.clone(),
         )
         .await?;
-        Ok(resp_fee.normal_fee)
+        self.with_fee_quote(resp_fee.normal_fee, tx_type, address, token, sender)
+            .await
+    }
+
+    /// Attaches the signed quote to the fee calculated for the transaction
+    /// with the given sender and nonce.
+    pub(crate) async fn with_fee_quote(
+        &self,
+        mut fee: Fee,
+        tx_type: TxFeeTypes,
+        address: Address,
+        token: TokenLike,
+        sender: Option<(Address, Nonce)>,
+    ) -> Result<Fee, SubmitError> {
+        let (sender, nonce) = match sender {
+            Some(sender) => sender,
+            None => return Ok(fee),
+        };
+        let fee_token = self.token_info_from_id(token).await?;
+        let quoted_tx = QuotedTx {
+            tx_type,
+            address,
+            token: fee_token.id,
+            sender,
+            nonce,
+        };
+        fee.quote = Some(self.fee_quotes.sign(quoted_tx, fee.total_fee.clone()));
+        Ok(fee)
     }
 
     pub async fn get_txs_batch_fee_in_wei(
@@ -944,6 +1071,8 @@ async fn verify_tx_info_message_signature(
 /// Unlike in case of `verify_tx_info_message_signature`, we do not require
 /// every transaction from the batch to be signed. The signature must be obtained
 /// through signing a human-readable message with accordance to zkSync protocol.
+/// Signatures of the swap orders from the batch are verified within the same request.
+#[allow(clippy::too_many_arguments)]
 async fn verify_txs_batch_signature(
     batch: Vec<TxWithSignature>,
     senders: Vec<Address>,
@@ -951,6 +1080,7 @@ async fn verify_txs_batch_signature(
     sender_types: Vec<EthAccountType>,
     batch_sign_data: Option<EthBatchSignData>,
     msgs_to_sign: Vec<Option<Vec<u8>>>,
+    orders: Vec<OrderRequest>,
     req_channel: mpsc::Sender<VerifySignatureRequest>,
 ) -> Result<VerifiedTx, SubmitError> {
     // This hashset holds addresses that have performed a CREATE2 ChangePubKey
@@ -1016,6 +1146,7 @@ async fn verify_txs_batch_signature(
             batch_sign_data,
             senders,
             tokens,
+            orders,
         }),
         response: sender,
     };
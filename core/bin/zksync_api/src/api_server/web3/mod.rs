@@ -1,7 +1,7 @@
 // Built-in uses
 // External uses
 use futures::channel::mpsc;
-use jsonrpc_core::{Error, IoHandler, MetaIoHandler, Metadata, Middleware, Result};
+use jsonrpc_core::{Error, MetaIoHandler, Metadata, Middleware, Result};
 use jsonrpc_http_server::ServerBuilder;
 // Workspace uses
 use zksync_config::ZkSyncConfig;
@@ -9,6 +9,7 @@ use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 // Local uses
 use self::{calls::CallsHelper, logs::LogsHelper, rpc_trait::Web3Rpc};
+use super::rate_limiter::{ApiServer, RateLimitMiddleware, RateLimiter, RequestMeta};
 
 mod calls;
 mod converter;
@@ -60,6 +61,7 @@ impl Web3RpcApp {
 
 pub fn start_rpc_server(
     connection_pool: ConnectionPool,
+    rate_limiter: RateLimiter,
     panic_notify: mpsc::Sender<bool>,
     config: &ZkSyncConfig,
 ) {
@@ -68,10 +70,11 @@ pub fn start_rpc_server(
     let rpc_app = Web3RpcApp::new(connection_pool, config);
     std::thread::spawn(move || {
         let _panic_sentinel = ThreadPanicNotify(panic_notify);
-        let mut io = IoHandler::new();
+        let mut io =
+            MetaIoHandler::with_middleware(RateLimitMiddleware::new(rate_limiter, ApiServer::Web3));
         rpc_app.extend(&mut io);
 
-        let server = ServerBuilder::new(io)
+        let server = ServerBuilder::with_meta_extractor(io, RequestMeta::from_http_request)
             .threads(super::THREADS_PER_SERVER)
             .start_http(&addr)
             .unwrap();
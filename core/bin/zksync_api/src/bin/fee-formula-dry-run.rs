@@ -0,0 +1,80 @@
+//! Shows how the new fee formula would change the fees.
+//!
+//! Fees of every operation type paid in ETH and in the tokens with the risk factors set
+//! are calculated by the current and the new formula for the given gas and ETH prices.
+
+use std::path::{Path, PathBuf};
+
+use num::{traits::Pow, BigUint, Zero};
+use structopt::StructOpt;
+use zksync_api::fee_ticker::{
+    fee_formula::{compare_fees, sample_operations, FeeFormulaConfig},
+    zksync_home_path, TickerConfig,
+};
+use zksync_config::ZkSyncConfig;
+use zksync_utils::{ratio_to_big_decimal, UnsignedRatioSerializeAsDecimal};
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "fee-formula-dry-run",
+    about = "Compares fees calculated by the current and the new fee formula"
+)]
+struct Opt {
+    /// Path to the new fee formula file.
+    #[structopt(long)]
+    new: PathBuf,
+    /// Path to the current fee formula file. The one from the config is used by default.
+    #[structopt(long)]
+    old: Option<PathBuf>,
+    /// Gas price in gwei.
+    #[structopt(long, default_value = "100")]
+    gas_price_gwei: u64,
+    /// Price of ETH in USD.
+    #[structopt(long, default_value = "3000")]
+    eth_price_usd: String,
+}
+
+fn load_ticker_config(config: &ZkSyncConfig, path: &Path) -> anyhow::Result<TickerConfig> {
+    let formula = FeeFormulaConfig::load(path)?;
+    TickerConfig::from_formula(config, &formula)
+}
+
+fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+    let config = ZkSyncConfig::from_env();
+
+    let old_path = opt
+        .old
+        .unwrap_or_else(|| zksync_home_path(&config.ticker.fee_formula_path));
+    let old = load_ticker_config(&config, &old_path)?;
+    let new = load_ticker_config(&config, &opt.new)?;
+
+    let gas_price_wei = BigUint::from(opt.gas_price_gwei) * BigUint::from(10u32).pow(9u32);
+    let eth_price_usd =
+        UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(&opt.eth_price_usd)?;
+
+    println!(
+        "{:<60} {:>6} {:>14} {:>14} {:>10}",
+        "Operation", "Token", "Old fee, $", "New fee, $", "Change"
+    );
+    let samples = sample_operations(&old, &new);
+    for fee in compare_fees(&old, &new, &samples, &gas_price_wei, &eth_price_usd) {
+        let change = if fee.old_fee_usd.is_zero() {
+            "-".to_string()
+        } else {
+            let ratio = ratio_to_big_decimal(&(&fee.new_fee_usd / &fee.old_fee_usd), 4);
+            format!("{}%", ((ratio - 1) * 100).with_scale(2))
+        };
+
+        println!(
+            "{:<60} {:>6} {:>14} {:>14} {:>10}",
+            format!("{:?}", fee.fee_type),
+            *fee.token_id,
+            ratio_to_big_decimal(&fee.old_fee_usd, 6).to_string(),
+            ratio_to_big_decimal(&fee.new_fee_usd, 6).to_string(),
+            change
+        );
+    }
+
+    Ok(())
+}
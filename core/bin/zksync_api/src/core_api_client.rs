@@ -87,7 +87,11 @@ impl CoreApiClient {
     }
 
     async fn get<T: serde::de::DeserializeOwned>(&self, url: &str) -> anyhow::Result<T> {
-        let response = self.client.get(url).send().await?.json().await?;
+        let response = Self::with_trace_headers(self.client.get(url))
+            .send()
+            .await?
+            .json()
+            .await?;
 
         Ok(response)
     }
@@ -97,9 +101,7 @@ impl CoreApiClient {
         url: &str,
         request: impl serde::Serialize,
     ) -> anyhow::Result<T> {
-        let response = self
-            .client
-            .post(url)
+        let response = Self::with_trace_headers(self.client.post(url))
             .json(&request)
             .send()
             .await?
@@ -108,4 +110,12 @@ impl CoreApiClient {
 
         Ok(response)
     }
+
+    /// Attaches the context of the current span, so the Core request is traced as a part of the API request.
+    fn with_trace_headers(mut request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        for (name, value) in vlog::current_trace_headers() {
+            request = request.header(name.as_str(), value);
+        }
+        request
+    }
 }
@@ -0,0 +1,234 @@
+//! Tunable parameters of the fee formula.
+//!
+//! By default fees are calculated from the operation costs estimated via the `gas_price` test.
+//! These costs, the fee factors of the operations and the token risk factors can be overridden
+//! by the JSON file located at `FEE_TICKER_FEE_FORMULA_PATH`, e.g.:
+//!
+//! ```json
+//! {
+//!   "amortizedCostPerChunk": 200,
+//!   "fastProcessingCoeff": 10.0,
+//!   "operations": [
+//!     { "feeType": "Transfer", "gasCost": 1500 },
+//!     { "feeType": { "ChangePubKey": "ECDSA" }, "feeFactor": 0.5 }
+//!   ],
+//!   "tokens": [{ "tokenId": 1, "riskFactor": 2.5 }]
+//! }
+//! ```
+//!
+//! The file is reloaded periodically, it can also be reloaded or replaced via the admin API.
+
+// Built-in deps
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+// External deps
+use anyhow::{ensure, format_err};
+use num::{rational::Ratio, traits::Pow, BigUint};
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+// Workspace deps
+use zksync_config::ZkSyncConfig;
+use zksync_types::{OutputFeeType, TokenId};
+use zksync_utils::UnsignedRatioSerializeAsDecimal;
+// Local deps
+use crate::fee_ticker::{zksync_home_path, TickerConfig};
+
+/// Overrides of the fee formula parameters. Parameters that are not set keep their default values.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FeeFormulaConfig {
+    /// Gas cost per chunk to cover constant cost of commit, execute and prove transactions.
+    pub amortized_cost_per_chunk: Option<u64>,
+    /// Coefficient for the fee price for fast withdrawal requests.
+    pub fast_processing_coeff: Option<f64>,
+    pub operations: Vec<OperationFeeOverride>,
+    pub tokens: Vec<TokenFeeOverride>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationFeeOverride {
+    pub fee_type: OutputFeeType,
+    /// Gas cost of the operation, replaces the estimated one.
+    #[serde(default)]
+    pub gas_cost: Option<u64>,
+    /// Multiplier of the operation fee. Values below 1 subsidize the operation.
+    #[serde(default)]
+    pub fee_factor: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenFeeOverride {
+    pub token_id: TokenId,
+    /// Multiplier of the fees paid in this token.
+    pub risk_factor: f64,
+}
+
+impl FeeFormulaConfig {
+    /// Loads the overrides from the file. Missing file means that there are no overrides.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|err| format_err!("Invalid fee formula file {}: {}", path.display(), err))
+    }
+}
+
+pub(super) fn ratio_from_f64(value: f64) -> anyhow::Result<Ratio<BigUint>> {
+    ensure!(
+        value.is_finite() && value >= 0.0,
+        "Fee formula coefficient must be a non-negative number, got {}",
+        value
+    );
+    UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(&value.to_string())
+}
+
+#[derive(Debug, Default)]
+struct FormulaState {
+    /// Formula used by the tickers.
+    current: FeeFormulaConfig,
+    /// Formula loaded from the file the last time.
+    file: FeeFormulaConfig,
+}
+
+/// Keeps the fee formula used by the tickers up to date.
+#[derive(Debug, Clone)]
+pub struct FeeFormulaUpdater {
+    config: ZkSyncConfig,
+    path: PathBuf,
+    state: Arc<Mutex<FormulaState>>,
+    updates: Arc<watch::Sender<Arc<TickerConfig>>>,
+}
+
+impl FeeFormulaUpdater {
+    /// Loads the fee formula and returns the receiver of its updates for the tickers.
+    pub fn new(
+        config: &ZkSyncConfig,
+    ) -> anyhow::Result<(Self, watch::Receiver<Arc<TickerConfig>>)> {
+        let path = zksync_home_path(&config.ticker.fee_formula_path);
+        if !path.exists() {
+            vlog::warn!(
+                "Fee formula file {} does not exist, default fee formula is used",
+                path.display()
+            );
+        }
+
+        let formula = FeeFormulaConfig::load(&path)?;
+        let ticker_config = TickerConfig::from_formula(config, &formula)?;
+        let (sender, receiver) = watch::channel(Arc::new(ticker_config));
+
+        let updater = Self {
+            config: config.clone(),
+            path,
+            state: Arc::new(Mutex::new(FormulaState {
+                current: formula.clone(),
+                file: formula,
+            })),
+            updates: Arc::new(sender),
+        };
+        Ok((updater, receiver))
+    }
+
+    /// Returns the formula currently used by the tickers.
+    pub fn formula(&self) -> FeeFormulaConfig {
+        self.state.lock().unwrap().current.clone()
+    }
+
+    /// Validates the formula and sends it to the tickers. The file is left unchanged.
+    pub fn update(&self, formula: FeeFormulaConfig) -> anyhow::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        self.apply(&mut state, formula)
+    }
+
+    /// Reloads the formula from the file. Unless `force` is set, the formula is updated only if
+    /// the file has been changed since the last reload, so the updates made via `update` are kept.
+    /// Returns `true` if the formula has been updated.
+    pub fn reload(&self, force: bool) -> anyhow::Result<bool> {
+        let formula = FeeFormulaConfig::load(&self.path)?;
+
+        let mut state = self.state.lock().unwrap();
+        if !force && formula == state.file {
+            return Ok(false);
+        }
+        self.apply(&mut state, formula.clone())?;
+        state.file = formula;
+        Ok(true)
+    }
+
+    pub async fn keep_updated(self, duration_secs: u64) {
+        loop {
+            tokio::time::sleep(Duration::from_secs(duration_secs)).await;
+
+            if let Err(err) = self.reload(false) {
+                vlog::warn!("Failed to reload the fee formula: {}", err);
+            }
+        }
+    }
+
+    fn apply(&self, state: &mut FormulaState, formula: FeeFormulaConfig) -> anyhow::Result<()> {
+        let ticker_config = TickerConfig::from_formula(&self.config, &formula)?;
+        self.updates
+            .send(Arc::new(ticker_config))
+            .map_err(|_| format_err!("Fee tickers have stopped"))?;
+
+        vlog::info!("Fee formula has been updated: {:?}", formula);
+        metrics::increment_counter!("ticker.fee_formula_updates");
+        state.current = formula;
+        Ok(())
+    }
+}
+
+/// Fee of the sample operation calculated by the old and the new formula.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeComparison {
+    pub fee_type: OutputFeeType,
+    pub token_id: TokenId,
+    pub old_fee_usd: Ratio<BigUint>,
+    pub new_fee_usd: Ratio<BigUint>,
+}
+
+/// Sample operations for the dry run: every operation type paid in ETH
+/// and in every token with the risk factor set by any of the formulas.
+pub fn sample_operations(old: &TickerConfig, new: &TickerConfig) -> Vec<(OutputFeeType, TokenId)> {
+    let mut fee_types = new.fee_types();
+    fee_types.sort_by_key(|fee_type| format!("{:?}", fee_type));
+
+    let tokens: BTreeSet<_> = std::iter::once(TokenId(0))
+        .chain(old.risk_factor_tokens())
+        .chain(new.risk_factor_tokens())
+        .collect();
+
+    tokens
+        .into_iter()
+        .flat_map(|token_id| fee_types.iter().map(move |&fee_type| (fee_type, token_id)))
+        .collect()
+}
+
+/// Calculates the fees of the sample operations by both formulas for the given gas and ETH prices.
+/// Unlike the ticker, uses the standard gas cost for fast withdrawals.
+pub fn compare_fees(
+    old: &TickerConfig,
+    new: &TickerConfig,
+    samples: &[(OutputFeeType, TokenId)],
+    gas_price_wei: &BigUint,
+    eth_price_usd: &Ratio<BigUint>,
+) -> Vec<FeeComparison> {
+    let wei_price_usd = eth_price_usd / BigUint::from(10u32).pow(18u32);
+
+    samples
+        .iter()
+        .map(|&(fee_type, token_id)| FeeComparison {
+            fee_type,
+            token_id,
+            old_fee_usd: old.estimate_fee_usd(fee_type, token_id, gas_price_wei, &wei_price_usd),
+            new_fee_usd: new.estimate_fee_usd(fee_type, token_id, gas_price_wei, &wei_price_usd),
+        })
+        .collect()
+}
@@ -2,12 +2,15 @@
 //!
 //! base formula for calculation:
 //! `( zkp cost of chunk * number of chunks + gas price of transaction) * token risk factor / cost of token is usd`
+//!
+//! Parameters of the formula can be tuned without a release, see the `fee_formula` module.
 
 // Built-in deps
 use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::fmt::Display;
 use std::iter::FromIterator;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 // External deps
 use bigdecimal::BigDecimal;
@@ -22,12 +25,17 @@ use num::{
 };
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use tokio::sync::Mutex;
+use tokio::sync::{watch, Mutex};
 use tokio::task::JoinHandle;
 use tokio::time::Instant;
 // Workspace deps
-use zksync_balancer::{Balancer, BuildBalancedItem};
-use zksync_config::{configs::ticker::TokenPriceSource, ZkSyncConfig};
+use zksync_balancer::{
+    balancing_key, BalancedReceiver, BalancedRequest, Balancer, BalancingPolicy, BuildBalancedItem,
+};
+use zksync_config::{
+    configs::ticker::{TickerBalancingPolicy, TokenPriceSource},
+    ZkSyncConfig,
+};
 use zksync_storage::ConnectionPool;
 use zksync_types::{
     tokens::ChangePubKeyFeeTypeArg, tx::ChangePubKeyType, Address, BatchFee, ChangePubKeyOp, Fee,
@@ -39,9 +47,14 @@ use zksync_utils::ratio_to_big_decimal;
 // Local deps
 use crate::fee_ticker::constants::AMORTIZED_COST_PER_CHUNK;
 use crate::fee_ticker::{
+    fee_formula::{ratio_from_f64, FeeFormulaConfig},
     ticker_api::{
-        coingecko::CoinGeckoAPI, coinmarkercap::CoinMarketCapAPI, FeeTickerAPI, TickerApi,
-        CONNECTION_TIMEOUT,
+        coingecko::CoinGeckoAPI,
+        coinmarkercap::CoinMarketCapAPI,
+        median::{BoxedPriceAPI, MedianPriceAPI},
+        static_prices::StaticPriceAPI,
+        uniswap_twap::UniswapTwapAPI,
+        FeeTickerAPI, TickerApi, CONNECTION_TIMEOUT,
     },
     ticker_info::{FeeTickerInfo, TickerInfo},
     validator::{
@@ -53,6 +66,7 @@ use crate::utils::token_db_cache::TokenDBCache;
 use zksync_types::gas_counter::GasCounter;
 
 mod constants;
+pub mod fee_formula;
 mod ticker_api;
 mod ticker_info;
 pub mod validator;
@@ -70,70 +84,108 @@ pub struct GasOperationsCost {
 
 impl GasOperationsCost {
     pub fn from_constants(fast_processing_coeff: f64) -> Self {
+        Self::new(AMORTIZED_COST_PER_CHUNK, fast_processing_coeff)
+    }
+
+    pub fn new(amortized_cost_per_chunk: u64, fast_processing_coeff: f64) -> Self {
+        // Base costs include the default amortized cost of the operation chunks, which is replaced with the given one.
+        let base_cost = |cost: u64, chunks: usize| {
+            cost - AMORTIZED_COST_PER_CHUNK * chunks as u64
+                + amortized_cost_per_chunk * chunks as u64
+        };
+        let withdraw_cost = base_cost(constants::BASE_WITHDRAW_COST, WithdrawOp::CHUNKS);
+        let withdraw_nft_cost = base_cost(constants::BASE_WITHDRAW_NFT_COST, WithdrawNFTOp::CHUNKS);
+
         // We increase gas price for fast withdrawals, since it will induce generating a smaller block
         // size, resulting in us paying more gas than for bigger block.
-        let standard_fast_withdrawal_cost =
-            (constants::BASE_WITHDRAW_COST as f64 * fast_processing_coeff) as u32;
+        let standard_fast_withdrawal_cost = (withdraw_cost as f64 * fast_processing_coeff) as u32;
         let standard_fast_withdrawal_nft_cost =
-            (constants::BASE_WITHDRAW_NFT_COST as f64 * fast_processing_coeff) as u32;
+            (withdraw_nft_cost as f64 * fast_processing_coeff) as u32;
 
         let standard_cost = vec![
             (
                 OutputFeeType::Transfer,
-                constants::BASE_TRANSFER_COST.into(),
+                base_cost(constants::BASE_TRANSFER_COST, TransferOp::CHUNKS).into(),
             ),
-            (OutputFeeType::MintNFT, constants::BASE_MINT_NFT_COST.into()),
             (
-                OutputFeeType::TransferToNew,
-                constants::BASE_TRANSFER_TO_NEW_COST.into(),
+                OutputFeeType::MintNFT,
+                base_cost(constants::BASE_MINT_NFT_COST, MintNFTOp::CHUNKS).into(),
             ),
             (
-                OutputFeeType::Withdraw,
-                constants::BASE_WITHDRAW_COST.into(),
+                OutputFeeType::TransferToNew,
+                base_cost(
+                    constants::BASE_TRANSFER_TO_NEW_COST,
+                    TransferToNewOp::CHUNKS,
+                )
+                .into(),
             ),
+            (OutputFeeType::Withdraw, withdraw_cost.into()),
             (
                 OutputFeeType::FastWithdraw,
                 standard_fast_withdrawal_cost.into(),
             ),
-            (OutputFeeType::Swap, constants::BASE_SWAP_COST.into()),
             (
-                OutputFeeType::WithdrawNFT,
-                constants::BASE_WITHDRAW_NFT_COST.into(),
+                OutputFeeType::Swap,
+                base_cost(constants::BASE_SWAP_COST, SwapOp::CHUNKS).into(),
             ),
+            (OutputFeeType::WithdrawNFT, withdraw_nft_cost.into()),
             (
                 OutputFeeType::FastWithdrawNFT,
                 standard_fast_withdrawal_nft_cost.into(),
             ),
-            (OutputFeeType::MintNFT, constants::BASE_MINT_NFT_COST.into()),
+            (
+                OutputFeeType::MintNFT,
+                base_cost(constants::BASE_MINT_NFT_COST, MintNFTOp::CHUNKS).into(),
+            ),
             (
                 OutputFeeType::ChangePubKey(ChangePubKeyFeeTypeArg::PreContracts4Version {
                     onchain_pubkey_auth: false,
                 }),
-                constants::BASE_OLD_CHANGE_PUBKEY_OFFCHAIN_COST.into(),
+                base_cost(
+                    constants::BASE_OLD_CHANGE_PUBKEY_OFFCHAIN_COST,
+                    ChangePubKeyOp::CHUNKS,
+                )
+                .into(),
             ),
             (
                 OutputFeeType::ChangePubKey(ChangePubKeyFeeTypeArg::PreContracts4Version {
                     onchain_pubkey_auth: true,
                 }),
-                constants::BASE_CHANGE_PUBKEY_ONCHAIN_COST.into(),
+                base_cost(
+                    constants::BASE_CHANGE_PUBKEY_ONCHAIN_COST,
+                    ChangePubKeyOp::CHUNKS,
+                )
+                .into(),
             ),
             (
                 OutputFeeType::ChangePubKey(ChangePubKeyFeeTypeArg::ContractsV4Version(
                     ChangePubKeyType::Onchain,
                 )),
-                constants::BASE_CHANGE_PUBKEY_ONCHAIN_COST.into(),
+                base_cost(
+                    constants::BASE_CHANGE_PUBKEY_ONCHAIN_COST,
+                    ChangePubKeyOp::CHUNKS,
+                )
+                .into(),
             ),
             (
                 OutputFeeType::ChangePubKey(ChangePubKeyFeeTypeArg::ContractsV4Version(
                     ChangePubKeyType::ECDSA,
                 )),
-                constants::BASE_CHANGE_PUBKEY_OFFCHAIN_COST.into(),
+                base_cost(
+                    constants::BASE_CHANGE_PUBKEY_OFFCHAIN_COST,
+                    ChangePubKeyOp::CHUNKS,
+                )
+                .into(),
             ),
             (
                 OutputFeeType::ChangePubKey(ChangePubKeyFeeTypeArg::ContractsV4Version(
                     ChangePubKeyType::CREATE2,
                 )),
-                constants::BASE_CHANGE_PUBKEY_CREATE2_COST.into(),
+                base_cost(
+                    constants::BASE_CHANGE_PUBKEY_CREATE2_COST,
+                    ChangePubKeyOp::CHUNKS,
+                )
+                .into(),
             ),
         ]
         .into_iter()
@@ -148,10 +200,183 @@ pub struct TickerConfig {
     zkp_cost_chunk_usd: Ratio<BigUint>,
     gas_cost_tx: GasOperationsCost,
     tokens_risk_factors: HashMap<TokenId, Ratio<BigUint>>,
+    /// Multipliers of the operation fees, operations without the factor are not scaled.
+    fee_factors: HashMap<OutputFeeType, Ratio<BigUint>>,
     scale_fee_coefficient: Ratio<BigUint>,
+    amortized_cost_per_chunk: u64,
     max_blocks_to_aggregate: u32,
 }
 
+impl TickerConfig {
+    /// Creates the config with the default parameters replaced by the ones set in the fee formula.
+    pub fn from_formula(config: &ZkSyncConfig, formula: &FeeFormulaConfig) -> anyhow::Result<Self> {
+        Self::new(
+            config.ticker.fast_processing_coeff,
+            Ratio::new(
+                BigUint::from(config.ticker.scale_fee_percent),
+                BigUint::from(100u32),
+            ),
+            std::cmp::max(
+                config.chain.state_keeper.max_aggregated_blocks_to_commit,
+                config.chain.state_keeper.max_aggregated_blocks_to_execute,
+            ) as u32,
+            formula,
+        )
+    }
+
+    fn new(
+        fast_processing_coeff: f64,
+        scale_fee_coefficient: Ratio<BigUint>,
+        max_blocks_to_aggregate: u32,
+        formula: &FeeFormulaConfig,
+    ) -> anyhow::Result<Self> {
+        let amortized_cost_per_chunk = formula
+            .amortized_cost_per_chunk
+            .unwrap_or(AMORTIZED_COST_PER_CHUNK);
+        let fast_processing_coeff = formula
+            .fast_processing_coeff
+            .unwrap_or(fast_processing_coeff);
+
+        let mut gas_cost_tx =
+            GasOperationsCost::new(amortized_cost_per_chunk, fast_processing_coeff);
+        let mut fee_factors = HashMap::new();
+        for operation in &formula.operations {
+            if let Some(gas_cost) = operation.gas_cost {
+                anyhow::ensure!(
+                    !matches!(
+                        operation.fee_type,
+                        OutputFeeType::FastWithdraw | OutputFeeType::FastWithdrawNFT
+                    ),
+                    "Gas cost of fast withdrawals depends on the pending blocks and can't be overridden"
+                );
+                gas_cost_tx
+                    .standard_cost
+                    .insert(operation.fee_type, gas_cost.into());
+            }
+            if let Some(fee_factor) = operation.fee_factor {
+                fee_factors.insert(operation.fee_type, ratio_from_f64(fee_factor)?);
+            }
+        }
+
+        let mut tokens_risk_factors = HashMap::new();
+        for token in &formula.tokens {
+            anyhow::ensure!(
+                token.risk_factor > 0.0,
+                "Risk factor of the token {} must be positive",
+                token.token_id
+            );
+            tokens_risk_factors.insert(token.token_id, ratio_from_f64(token.risk_factor)?);
+        }
+
+        Ok(Self {
+            zkp_cost_chunk_usd: Ratio::from_integer(BigUint::from(10u32).pow(3u32)).inv(),
+            gas_cost_tx,
+            tokens_risk_factors,
+            fee_factors,
+            scale_fee_coefficient,
+            amortized_cost_per_chunk,
+            max_blocks_to_aggregate,
+        })
+    }
+
+    /// Estimates the fee of the operation in USD, using the standard gas cost of the operation.
+    pub fn estimate_fee_usd(
+        &self,
+        fee_type: OutputFeeType,
+        token_id: TokenId,
+        gas_price_wei: &BigUint,
+        wei_price_usd: &Ratio<BigUint>,
+    ) -> Ratio<BigUint> {
+        let gas_tx_amount = self
+            .gas_cost_tx
+            .standard_cost
+            .get(&fee_type)
+            .cloned()
+            .unwrap_or_default();
+        let (zkp_fee, gas_fee) = self.fee_usd(
+            fee_type,
+            gas_tx_amount,
+            BigUint::from(op_chunks(fee_type)),
+            &risk_gas_price_estimate(gas_price_wei.clone()),
+            wei_price_usd,
+        );
+        (zkp_fee + gas_fee) * self.token_risk_factor(token_id)
+    }
+
+    /// Returns the zkp and the gas parts of the operation fee in USD, not adjusted by the token risk factor.
+    fn fee_usd(
+        &self,
+        fee_type: OutputFeeType,
+        gas_tx_amount: BigUint,
+        op_chunks: BigUint,
+        scale_gas_price: &BigUint,
+        wei_price_usd: &Ratio<BigUint>,
+    ) -> (Ratio<BigUint>, Ratio<BigUint>) {
+        let fee_factor = self.fee_factor(fee_type);
+        let zkp_fee = (self.zkp_cost_chunk_usd.clone() * op_chunks) * &fee_factor;
+        let mut gas_fee = (wei_price_usd * gas_tx_amount * scale_gas_price.clone()) * &fee_factor;
+
+        // Increase fee only for L2 operations
+        if is_l2_operation(fee_type) {
+            gas_fee *= self.scale_fee_coefficient.clone();
+        }
+        (zkp_fee, gas_fee)
+    }
+
+    fn fee_factor(&self, fee_type: OutputFeeType) -> Ratio<BigUint> {
+        self.fee_factors
+            .get(&fee_type)
+            .cloned()
+            .unwrap_or_else(|| Ratio::from_integer(1u32.into()))
+    }
+
+    fn token_risk_factor(&self, token_id: TokenId) -> Ratio<BigUint> {
+        self.tokens_risk_factors
+            .get(&token_id)
+            .cloned()
+            .unwrap_or_else(|| Ratio::from_integer(1u32.into()))
+    }
+
+    pub(crate) fn fee_types(&self) -> Vec<OutputFeeType> {
+        self.gas_cost_tx.standard_cost.keys().copied().collect()
+    }
+
+    pub(crate) fn risk_factor_tokens(&self) -> impl Iterator<Item = TokenId> + '_ {
+        self.tokens_risk_factors.keys().copied()
+    }
+}
+
+/// Increases the gas price by a constant coefficient.
+/// Due to the high volatility of gas prices, we are include the risk
+/// in the fee in order not to go into negative territory.
+fn risk_gas_price_estimate(gas_price: BigUint) -> BigUint {
+    gas_price * BigUint::from(130u32) / BigUint::from(100u32)
+}
+
+/// Fees are increased by the scale coefficient only for L2 operations.
+fn is_l2_operation(fee_type: OutputFeeType) -> bool {
+    matches!(
+        fee_type,
+        OutputFeeType::Transfer
+            | OutputFeeType::TransferToNew
+            | OutputFeeType::Swap
+            | OutputFeeType::MintNFT
+            | OutputFeeType::ChangePubKey(_)
+    )
+}
+
+fn op_chunks(fee_type: OutputFeeType) -> usize {
+    match fee_type {
+        OutputFeeType::Withdraw | OutputFeeType::FastWithdraw => WithdrawOp::CHUNKS,
+        OutputFeeType::WithdrawNFT | OutputFeeType::FastWithdrawNFT => WithdrawNFTOp::CHUNKS,
+        OutputFeeType::Transfer => TransferOp::CHUNKS,
+        OutputFeeType::TransferToNew => TransferToNewOp::CHUNKS,
+        OutputFeeType::Swap => SwapOp::CHUNKS,
+        OutputFeeType::ChangePubKey(_) => ChangePubKeyOp::CHUNKS,
+        OutputFeeType::MintNFT => MintNFTOp::CHUNKS,
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum TokenPriceRequestType {
     USDForOneWei,
@@ -192,6 +417,19 @@ pub enum TickerRequest {
     },
 }
 
+impl BalancedRequest for TickerRequest {
+    // Fee requests of the same account are processed in order.
+    fn balancing_key(&self) -> Option<u64> {
+        match self {
+            TickerRequest::GetTxFee { address, .. } => Some(balancing_key(address)),
+            TickerRequest::GetBatchTxFee { transactions, .. } => transactions
+                .first()
+                .map(|(_, address)| balancing_key(address)),
+            TickerRequest::GetTokenPrice { .. } | TickerRequest::IsTokenAllowed { .. } => None,
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum PriceError {
     #[error("Token not found: {0}")]
@@ -219,33 +457,51 @@ impl PriceError {
 struct FeeTicker<API, INFO, WATCHER> {
     api: API,
     info: INFO,
-    requests: Receiver<TickerRequest>,
-    config: TickerConfig,
+    requests: BalancedReceiver<TickerRequest>,
+    config: Arc<TickerConfig>,
+    config_updates: Option<watch::Receiver<Arc<TickerConfig>>>,
     validator: FeeTokenValidator<WATCHER>,
 }
 
 struct FeeTickerBuilder<API, INFO, WATCHER> {
     api: API,
     info: INFO,
-    config: TickerConfig,
+    config_updates: watch::Receiver<Arc<TickerConfig>>,
     validator: FeeTokenValidator<WATCHER>,
 }
 
-impl<API: Clone, INFO: Clone, WATCHER: Clone>
+impl<API: FeeTickerAPI + Clone, INFO: FeeTickerInfo + Clone, WATCHER: TokenWatcher + Clone>
     BuildBalancedItem<TickerRequest, FeeTicker<API, INFO, WATCHER>>
     for FeeTickerBuilder<API, INFO, WATCHER>
 {
     fn build_with_receiver(
         &self,
-        receiver: Receiver<TickerRequest>,
+        receiver: BalancedReceiver<TickerRequest>,
     ) -> FeeTicker<API, INFO, WATCHER> {
-        FeeTicker {
-            api: self.api.clone(),
-            info: self.info.clone(),
-            requests: receiver,
-            config: self.config.clone(),
-            validator: self.validator.clone(),
-        }
+        FeeTicker::new(
+            self.api.clone(),
+            self.info.clone(),
+            receiver,
+            self.config_updates.borrow().as_ref().clone(),
+            self.validator.clone(),
+        )
+        .with_config_updates(self.config_updates.clone())
+    }
+}
+
+/// Resolves the path relative to `$ZKSYNC_HOME`.
+pub fn zksync_home_path(path: &str) -> PathBuf {
+    match std::env::var("ZKSYNC_HOME") {
+        Ok(home) => Path::new(&home).join(path),
+        Err(_) => PathBuf::from(path),
+    }
+}
+
+fn balancing_policy(policy: TickerBalancingPolicy) -> BalancingPolicy {
+    match policy {
+        TickerBalancingPolicy::RoundRobin => BalancingPolicy::RoundRobin,
+        TickerBalancingPolicy::LeastLoaded => BalancingPolicy::LeastLoaded,
+        TickerBalancingPolicy::KeyAffinity => BalancingPolicy::KeyAffinity,
     }
 }
 
@@ -253,22 +509,9 @@ impl<API: Clone, INFO: Clone, WATCHER: Clone>
 pub fn run_ticker_task(
     db_pool: ConnectionPool,
     tricker_requests: Receiver<TickerRequest>,
+    config_updates: watch::Receiver<Arc<TickerConfig>>,
     config: &ZkSyncConfig,
 ) -> JoinHandle<()> {
-    let ticker_config = TickerConfig {
-        zkp_cost_chunk_usd: Ratio::from_integer(BigUint::from(10u32).pow(3u32)).inv(),
-        gas_cost_tx: GasOperationsCost::from_constants(config.ticker.fast_processing_coeff),
-        tokens_risk_factors: HashMap::new(),
-        scale_fee_coefficient: Ratio::new(
-            BigUint::from(config.ticker.scale_fee_percent),
-            BigUint::from(100u32),
-        ),
-        max_blocks_to_aggregate: std::cmp::max(
-            config.chain.state_keeper.max_aggregated_blocks_to_commit,
-            config.chain.state_keeper.max_aggregated_blocks_to_execute,
-        ) as u32,
-    };
-
     let cache = (db_pool.clone(), TokenDBCache::new());
     let watcher = UniswapTokenWatcher::new(config.ticker.uniswap_url.clone());
     let validator = FeeTokenValidator::new(
@@ -279,69 +522,89 @@ pub fn run_ticker_task(
         watcher.clone(),
     );
 
-    let updater = MarketUpdater::new(cache, watcher);
+    let updater = MarketUpdater::new(cache, watcher.clone());
     tokio::spawn(updater.keep_updated(config.ticker.token_market_update_time));
     let client = reqwest::ClientBuilder::new()
         .timeout(CONNECTION_TIMEOUT)
         .connect_timeout(CONNECTION_TIMEOUT)
         .build()
         .expect("Failed to build reqwest::Client");
-    let (price_source, base_url) = config.ticker.price_source();
-    match price_source {
-        TokenPriceSource::CoinMarketCap => {
-            let token_price_api =
-                CoinMarketCapAPI::new(client, base_url.parse().expect("Correct CoinMarketCap url"));
-
-            let ticker_api = TickerApi::new(db_pool.clone(), token_price_api);
-            let token_price_updater = ticker_api.clone();
-            tokio::spawn(token_price_updater.keep_price_updated());
-            let ticker_info = TickerInfo::new(db_pool);
-            let fee_ticker = FeeTicker::new(
-                ticker_api,
-                ticker_info,
-                tricker_requests,
-                ticker_config,
-                validator,
-            );
-
-            tokio::spawn(fee_ticker.run())
-        }
+    let price_sources = config
+        .ticker
+        .token_price_sources
+        .iter()
+        .map(|&source| token_price_api(source, client.clone(), &watcher, config))
+        .collect();
+    let token_price_api =
+        MedianPriceAPI::new(price_sources, config.ticker.max_price_deviation_percent);
+    let ticker_info = TickerInfo::new(db_pool.clone());
+
+    let token_db_cache = TokenDBCache::new();
+    let price_cache = Arc::new(Mutex::new(HashMap::new()));
+    let gas_price_cache = Arc::new(Mutex::new(None));
+    let ticker_api = TickerApi::new(db_pool, token_price_api)
+        .with_token_db_cache(token_db_cache)
+        .with_price_cache(price_cache)
+        .with_gas_price_cache(gas_price_cache);
+
+    let token_price_updater = ticker_api.clone();
+    tokio::spawn(token_price_updater.keep_price_updated());
+
+    let (ticker_balancer, tickers) = Balancer::new(
+        FeeTickerBuilder {
+            api: ticker_api,
+            info: ticker_info,
+            config_updates,
+            validator,
+        },
+        tricker_requests,
+        config.ticker.number_of_ticker_actors,
+        TICKER_CHANNEL_SIZE,
+    );
+    let ticker_balancer = ticker_balancer
+        .with_policy(balancing_policy(config.ticker.balancing_policy))
+        .with_name("ticker");
 
-        TokenPriceSource::CoinGecko => {
-            let token_price_api =
-                CoinGeckoAPI::new(client, base_url.parse().expect("Correct CoinGecko url"))
-                    .expect("failed to init CoinGecko client");
-            let ticker_info = TickerInfo::new(db_pool.clone());
-
-            let token_db_cache = TokenDBCache::new();
-            let price_cache = Arc::new(Mutex::new(HashMap::new()));
-            let gas_price_cache = Arc::new(Mutex::new(None));
-            let ticker_api = TickerApi::new(db_pool, token_price_api)
-                .with_token_db_cache(token_db_cache)
-                .with_price_cache(price_cache)
-                .with_gas_price_cache(gas_price_cache);
-
-            let token_price_updater = ticker_api.clone();
-            tokio::spawn(token_price_updater.keep_price_updated());
-
-            let (ticker_balancer, tickers) = Balancer::new(
-                FeeTickerBuilder {
-                    api: ticker_api,
-                    info: ticker_info,
-                    config: ticker_config,
-                    validator,
-                },
-                tricker_requests,
-                config.ticker.number_of_ticker_actors,
-                TICKER_CHANNEL_SIZE,
-            );
+    for ticker in tickers.into_iter() {
+        tokio::spawn(ticker.run());
+    }
 
-            for ticker in tickers.into_iter() {
-                tokio::spawn(ticker.run());
-            }
+    tokio::spawn(ticker_balancer.run())
+}
 
-            tokio::spawn(ticker_balancer.run())
+/// Creates the API of the token price source, along with the name used in logs and metrics.
+fn token_price_api(
+    source: TokenPriceSource,
+    client: reqwest::Client,
+    watcher: &UniswapTokenWatcher,
+    config: &ZkSyncConfig,
+) -> (&'static str, BoxedPriceAPI) {
+    let url = config.ticker.price_source_url(source);
+    match source {
+        TokenPriceSource::CoinMarketCap => {
+            let url = url.unwrap().parse().expect("Correct CoinMarketCap url");
+            (
+                "coinmarketcap",
+                Arc::new(CoinMarketCapAPI::new(client, url)),
+            )
+        }
+        TokenPriceSource::CoinGecko => {
+            let url = url.unwrap().parse().expect("Correct CoinGecko url");
+            let api = CoinGeckoAPI::new(client, url).expect("failed to init CoinGecko client");
+            ("coingecko", Arc::new(api))
+        }
+        TokenPriceSource::Static => {
+            let path = zksync_home_path(&config.ticker.static_prices_path);
+            let api = StaticPriceAPI::from_file(&path).unwrap_or_else(|err| {
+                panic!(
+                    "Failed to load static prices from {}: {}",
+                    path.display(),
+                    err
+                )
+            });
+            ("static", Arc::new(api))
         }
+        TokenPriceSource::Uniswap => ("uniswap", Arc::new(UniswapTwapAPI::new(watcher.clone()))),
     }
 }
 
@@ -349,7 +612,7 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
     fn new(
         api: API,
         info: INFO,
-        requests: Receiver<TickerRequest>,
+        requests: BalancedReceiver<TickerRequest>,
         config: TickerConfig,
         validator: FeeTokenValidator<WATCHER>,
     ) -> Self {
@@ -357,21 +620,25 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
             api,
             info,
             requests,
-            config,
+            config: Arc::new(config),
+            config_updates: None,
             validator,
         }
     }
 
-    /// Increases the gas price by a constant coefficient.
-    /// Due to the high volatility of gas prices, we are include the risk
-    /// in the fee in order not to go into negative territory.
-    fn risk_gas_price_estimate(gas_price: BigUint) -> BigUint {
-        gas_price * BigUint::from(130u32) / BigUint::from(100u32)
+    /// Makes the ticker follow the fee formula updates.
+    fn with_config_updates(mut self, config_updates: watch::Receiver<Arc<TickerConfig>>) -> Self {
+        self.config = config_updates.borrow().clone();
+        self.config_updates = Some(config_updates);
+        self
     }
 
     async fn run(mut self) {
         while let Some(request) = self.requests.next().await {
             let start = Instant::now();
+            if let Some(config_updates) = &self.config_updates {
+                self.config = config_updates.borrow().clone();
+            }
             match request {
                 TickerRequest::GetTxFee {
                     tx_type,
@@ -442,31 +709,24 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
         token: TokenLike,
         recipient: Address,
     ) -> Result<ResponseFee, anyhow::Error> {
-        let zkp_cost_chunk = self.config.zkp_cost_chunk_usd.clone();
         let token = self.api.get_token(token).await?;
 
         let gas_price_wei = self.api.get_gas_price_wei().await?;
-        let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei.clone());
+        let scale_gas_price = risk_gas_price_estimate(gas_price_wei.clone());
         let wei_price_usd = self.wei_price_usd().await?;
         let token_usd_risk = self.token_usd_risk(&token).await?;
 
         let (fee_type, gas_tx_amount, op_chunks) = self.gas_tx_amount(tx_type, recipient).await;
 
-        let zkp_fee = (zkp_cost_chunk * op_chunks) * &token_usd_risk;
-        let mut normal_gas_fee =
-            (&wei_price_usd * gas_tx_amount.clone() * scale_gas_price.clone()) * &token_usd_risk;
-
-        // Increase fee only for L2 operations
-        if matches!(
+        let (zkp_fee_usd, gas_fee_usd) = self.config.fee_usd(
             fee_type,
-            OutputFeeType::TransferToNew
-                | OutputFeeType::Transfer
-                | OutputFeeType::MintNFT
-                | OutputFeeType::Swap
-                | OutputFeeType::ChangePubKey(_)
-        ) {
-            normal_gas_fee *= self.config.scale_fee_coefficient.clone();
-        }
+            gas_tx_amount.clone(),
+            op_chunks,
+            &scale_gas_price,
+            &wei_price_usd,
+        );
+        let zkp_fee = zkp_fee_usd * &token_usd_risk;
+        let normal_gas_fee = gas_fee_usd * &token_usd_risk;
 
         let normal_fee = Fee::new(
             fee_type,
@@ -488,7 +748,7 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
         let token = self.api.get_token(token).await?;
 
         let gas_price_wei = self.api.get_gas_price_wei().await?;
-        let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei.clone());
+        let scale_gas_price = risk_gas_price_estimate(gas_price_wei.clone());
         let wei_price_usd = self.wei_price_usd().await?;
         let token_usd_risk = self.token_usd_risk(&token).await?;
 
@@ -498,22 +758,16 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
         for (tx_type, recipient) in txs {
             let (output_fee_type, gas_tx_amount, op_chunks) =
                 self.gas_tx_amount(tx_type, recipient).await;
+            let fee_factor = self.config.fee_factor(output_fee_type);
+
+            let mut gas_tx_amount = Ratio::from(gas_tx_amount) * &fee_factor;
             // Increase fee only for L2 operations
-            let gas_tx_amount: Ratio<BigUint> = if matches!(
-                output_fee_type,
-                OutputFeeType::Transfer
-                    | OutputFeeType::TransferToNew
-                    | OutputFeeType::Swap
-                    | OutputFeeType::MintNFT
-                    | OutputFeeType::ChangePubKey(_)
-            ) {
-                self.config.scale_fee_coefficient.clone() * gas_tx_amount
-            } else {
-                gas_tx_amount.into()
-            };
+            if is_l2_operation(output_fee_type) {
+                gas_tx_amount *= self.config.scale_fee_coefficient.clone();
+            }
 
             total_normal_gas_tx_amount += gas_tx_amount;
-            total_op_chunks += op_chunks;
+            total_op_chunks += Ratio::from(op_chunks) * fee_factor;
         }
 
         let total_zkp_fee = (zkp_cost_chunk * total_op_chunks) * token_usd_risk.clone();
@@ -534,12 +788,7 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
     }
 
     async fn token_usd_risk(&mut self, token: &Token) -> anyhow::Result<Ratio<BigUint>> {
-        let token_risk_factor = self
-            .config
-            .tokens_risk_factors
-            .get(&token.id)
-            .cloned()
-            .unwrap_or_else(|| Ratio::from_integer(1u32.into()));
+        let token_risk_factor = self.config.token_risk_factor(token.id);
 
         let token_price_usd = self
             .api
@@ -563,24 +812,23 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
         tx_type: TxFeeTypes,
         recipient: Address,
     ) -> (OutputFeeType, BigUint, BigUint) {
-        let (fee_type, op_chunks) = match tx_type {
-            TxFeeTypes::Withdraw => (OutputFeeType::Withdraw, WithdrawOp::CHUNKS),
-            TxFeeTypes::FastWithdraw => (OutputFeeType::FastWithdraw, WithdrawOp::CHUNKS),
-            TxFeeTypes::WithdrawNFT => (OutputFeeType::WithdrawNFT, WithdrawNFTOp::CHUNKS),
-            TxFeeTypes::FastWithdrawNFT => (OutputFeeType::FastWithdrawNFT, WithdrawNFTOp::CHUNKS),
+        let fee_type = match tx_type {
+            TxFeeTypes::Withdraw => OutputFeeType::Withdraw,
+            TxFeeTypes::FastWithdraw => OutputFeeType::FastWithdraw,
+            TxFeeTypes::WithdrawNFT => OutputFeeType::WithdrawNFT,
+            TxFeeTypes::FastWithdrawNFT => OutputFeeType::FastWithdrawNFT,
             TxFeeTypes::Transfer => {
                 if self.is_account_new(recipient).await {
-                    (OutputFeeType::TransferToNew, TransferToNewOp::CHUNKS)
+                    OutputFeeType::TransferToNew
                 } else {
-                    (OutputFeeType::Transfer, TransferOp::CHUNKS)
+                    OutputFeeType::Transfer
                 }
             }
-            TxFeeTypes::Swap => (OutputFeeType::Swap, SwapOp::CHUNKS),
-            TxFeeTypes::ChangePubKey(arg) => {
-                (OutputFeeType::ChangePubKey(arg), ChangePubKeyOp::CHUNKS)
-            }
-            TxFeeTypes::MintNFT => (OutputFeeType::MintNFT, MintNFTOp::CHUNKS),
+            TxFeeTypes::Swap => OutputFeeType::Swap,
+            TxFeeTypes::ChangePubKey(arg) => OutputFeeType::ChangePubKey(arg),
+            TxFeeTypes::MintNFT => OutputFeeType::MintNFT,
         };
+        let op_chunks = op_chunks(fee_type);
 
         let gas_tx_amount = if matches!(
             fee_type,
@@ -608,7 +856,7 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
             if chunk_size > chunks {
                 0
             } else {
-                chunks * AMORTIZED_COST_PER_CHUNK as usize
+                chunks * self.config.amortized_cost_per_chunk as usize
             }
         });
 
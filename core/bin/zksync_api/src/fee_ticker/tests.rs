@@ -5,6 +5,7 @@ use bigdecimal::BigDecimal;
 use chrono::Utc;
 use futures::future::{AbortHandle, Abortable};
 use futures::{channel::mpsc, executor::block_on};
+use std::collections::VecDeque;
 use std::str::FromStr;
 use std::thread::sleep;
 use tokio::time::Duration;
@@ -14,6 +15,9 @@ use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal, UnsignedRatioSeri
 use crate::fee_ticker::{
     ticker_api::{
         coingecko::{CoinGeckoTokenInfo, CoinGeckoTokenList},
+        median::{BoxedPriceAPI, MedianPriceAPI},
+        static_prices::StaticPriceAPI,
+        uniswap_twap::{add_sample, time_weighted_average},
         TokenPriceAPI,
     },
     validator::{cache::TokenInMemoryCache, FeeTokenValidator},
@@ -113,7 +117,9 @@ fn get_test_ticker_config() -> TickerConfig {
                 t.risk_factor.map(|risk| (id, risk))
             })
             .collect(),
+        fee_factors: HashMap::new(),
         scale_fee_coefficient: Ratio::new(BigUint::from(150u32), BigUint::from(100u32)),
+        amortized_cost_per_chunk: AMORTIZED_COST_PER_CHUNK,
         max_blocks_to_aggregate: 5,
     }
 }
@@ -218,6 +224,31 @@ impl TokenPriceAPI for ErrorTickerApi {
     }
 }
 
+struct FixedPriceApi(&'static str);
+
+#[async_trait::async_trait]
+impl TokenPriceAPI for FixedPriceApi {
+    async fn get_price(&self, _token: &Token) -> Result<TokenPrice, PriceError> {
+        Ok(TokenPrice {
+            usd_price: usd_price(self.0),
+            last_updated: Utc::now(),
+        })
+    }
+}
+
+struct FailingTickerApi;
+
+#[async_trait::async_trait]
+impl TokenPriceAPI for FailingTickerApi {
+    async fn get_price(&self, _token: &Token) -> Result<TokenPrice, PriceError> {
+        Err(PriceError::api_error("Service unavailable"))
+    }
+}
+
+fn usd_price(price: &str) -> Ratio<BigUint> {
+    UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(price).unwrap()
+}
+
 fn run_server(token_address: Address) -> (String, AbortHandle) {
     let mut url = None;
     let mut server = None;
@@ -274,7 +305,7 @@ fn test_ticker_formula() {
     let mut ticker = FeeTicker::new(
         MockApiProvider,
         MockTickerInfo::default(),
-        mpsc::channel(1).1,
+        mpsc::channel(1).1.into(),
         config,
         validator,
     );
@@ -533,7 +564,7 @@ fn test_zero_price_token_fee() {
     let mut ticker = FeeTicker::new(
         MockApiProvider,
         MockTickerInfo::default(),
-        mpsc::channel(1).1,
+        mpsc::channel(1).1.into(),
         config,
         validator,
     );
@@ -555,6 +586,275 @@ fn test_zero_price_token_fee() {
     .unwrap_err();
 }
 
+fn get_formula_ticker_config(formula: &FeeFormulaConfig) -> anyhow::Result<TickerConfig> {
+    TickerConfig::new(
+        TEST_FAST_WITHDRAW_COEFF,
+        Ratio::new(BigUint::from(150u32), BigUint::from(100u32)),
+        5,
+        formula,
+    )
+}
+
+#[test]
+fn test_fee_formula_overrides() {
+    let default_config = get_formula_ticker_config(&FeeFormulaConfig::default()).unwrap();
+    assert_eq!(
+        default_config.gas_cost_tx.standard_cost,
+        GasOperationsCost::from_constants(TEST_FAST_WITHDRAW_COEFF).standard_cost
+    );
+
+    let formula: FeeFormulaConfig = serde_json::from_str(
+        r#"{
+            "operations": [
+                { "feeType": "Transfer", "feeFactor": 0.5 },
+                { "feeType": "Withdraw", "gasCost": 100000 },
+                { "feeType": { "ChangePubKey": "ECDSA" }, "feeFactor": 0 }
+            ],
+            "tokens": [{ "tokenId": 1, "riskFactor": 2.5 }]
+        }"#,
+    )
+    .unwrap();
+    let config = get_formula_ticker_config(&formula).unwrap();
+    assert_eq!(
+        config.gas_cost_tx.standard_cost[&OutputFeeType::Withdraw],
+        BigUint::from(100_000u32)
+    );
+
+    let gas_price_wei = BigUint::from(10u32).pow(10u32);
+    let wei_price_usd = Ratio::new(BigUint::from(3000u32), BigUint::from(10u32).pow(18u32));
+    let fee_usd = |config: &TickerConfig, fee_type: OutputFeeType, token_id: TokenId| {
+        config.estimate_fee_usd(fee_type, token_id, &gas_price_wei, &wei_price_usd)
+    };
+    let ecdsa_change_pubkey = OutputFeeType::ChangePubKey(
+        ChangePubKeyFeeTypeArg::ContractsV4Version(ChangePubKeyType::ECDSA),
+    );
+
+    assert_eq!(
+        fee_usd(&config, OutputFeeType::Transfer, TokenId(0)) * BigUint::from(2u32),
+        fee_usd(&default_config, OutputFeeType::Transfer, TokenId(0))
+    );
+    assert!(fee_usd(&config, ecdsa_change_pubkey, TokenId(0)).is_zero());
+    assert_eq!(
+        fee_usd(&config, OutputFeeType::Swap, TokenId(1)),
+        fee_usd(&default_config, OutputFeeType::Swap, TokenId(0))
+            * Ratio::new(BigUint::from(5u32), BigUint::from(2u32))
+    );
+
+    // Amortized cost of chunks is included into the cost of every operation.
+    let formula = FeeFormulaConfig {
+        amortized_cost_per_chunk: Some(AMORTIZED_COST_PER_CHUNK + 100),
+        ..Default::default()
+    };
+    let config = get_formula_ticker_config(&formula).unwrap();
+    assert_eq!(
+        config.gas_cost_tx.standard_cost[&OutputFeeType::Swap],
+        &default_config.gas_cost_tx.standard_cost[&OutputFeeType::Swap]
+            + BigUint::from(100 * SwapOp::CHUNKS)
+    );
+}
+
+#[test]
+fn test_invalid_fee_formula() {
+    let invalid_formulas = [
+        r#"{ "operations": [{ "feeType": "FastWithdraw", "gasCost": 100000 }] }"#,
+        r#"{ "operations": [{ "feeType": "Transfer", "feeFactor": -1 }] }"#,
+        r#"{ "tokens": [{ "tokenId": 1, "riskFactor": 0 }] }"#,
+    ];
+
+    for formula in &invalid_formulas {
+        let formula: FeeFormulaConfig = serde_json::from_str(formula).unwrap();
+        get_formula_ticker_config(&formula).unwrap_err();
+    }
+}
+
+#[test]
+fn test_ticker_fee_factors() {
+    let validator = FeeTokenValidator::new(
+        TokenInMemoryCache::new(),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+        FakeTokenWatcher,
+    );
+
+    let formula: FeeFormulaConfig = serde_json::from_str(
+        r#"{ "operations": [{ "feeType": { "ChangePubKey": "ECDSA" }, "feeFactor": 0 }] }"#,
+    )
+    .unwrap();
+    let mut ticker = FeeTicker::new(
+        MockApiProvider,
+        MockTickerInfo::default(),
+        mpsc::channel(1).1.into(),
+        get_formula_ticker_config(&formula).unwrap(),
+        validator,
+    );
+
+    let tx_type = TxFeeTypes::ChangePubKey(ChangePubKeyFeeTypeArg::ContractsV4Version(
+        ChangePubKeyType::ECDSA,
+    ));
+    let fee =
+        block_on(ticker.get_fee_from_ticker_in_wei(tx_type, TokenId(0).into(), Address::default()))
+            .unwrap();
+    assert!(fee.normal_fee.total_fee.is_zero());
+
+    let batch_fee = block_on(ticker.get_batch_from_ticker_in_wei(
+        TokenId(0).into(),
+        vec![
+            (tx_type, Address::default()),
+            (TxFeeTypes::Transfer, Address::default()),
+        ],
+    ))
+    .unwrap();
+    let transfer_fee = block_on(ticker.get_fee_from_ticker_in_wei(
+        TxFeeTypes::Transfer,
+        TokenId(0).into(),
+        Address::default(),
+    ))
+    .unwrap();
+    assert_eq!(
+        batch_fee.normal_fee.total_fee,
+        transfer_fee.normal_fee.total_fee
+    );
+}
+
+#[test]
+fn test_fee_formula_dry_run() {
+    let old = get_formula_ticker_config(&FeeFormulaConfig::default()).unwrap();
+    let formula: FeeFormulaConfig = serde_json::from_str(
+        r#"{
+            "operations": [{ "feeType": "Transfer", "feeFactor": 2 }],
+            "tokens": [{ "tokenId": 1, "riskFactor": 1.5 }]
+        }"#,
+    )
+    .unwrap();
+    let new = get_formula_ticker_config(&formula).unwrap();
+
+    let samples = fee_formula::sample_operations(&old, &new);
+    assert_eq!(samples.len(), old.fee_types().len() * 2);
+
+    let comparison = fee_formula::compare_fees(
+        &old,
+        &new,
+        &samples,
+        &BigUint::from(10u32).pow(10u32),
+        &Ratio::from_integer(BigUint::from(3000u32)),
+    );
+    for fee in comparison {
+        let expected_factor = match (fee.fee_type, fee.token_id) {
+            (OutputFeeType::Transfer, TokenId(0)) => Ratio::from_integer(BigUint::from(2u32)),
+            (OutputFeeType::Transfer, _) => Ratio::from_integer(BigUint::from(3u32)),
+            (_, TokenId(0)) => Ratio::from_integer(BigUint::from(1u32)),
+            _ => Ratio::new(BigUint::from(3u32), BigUint::from(2u32)),
+        };
+        assert_eq!(fee.new_fee_usd, fee.old_fee_usd * expected_factor);
+    }
+}
+
+#[tokio::test]
+async fn test_median_price_api() {
+    let token = Token::new(TokenId(1), Address::default(), "DAI", 18, TokenKind::ERC20);
+    let get_price = |sources: Vec<BoxedPriceAPI>| {
+        let sources = sources.into_iter().map(|source| ("test", source)).collect();
+        let api = MedianPriceAPI::new(sources, 20);
+        let token = token.clone();
+        async move { api.get_price(&token).await }
+    };
+
+    // The outlier is rejected, the median of the remaining prices is used.
+    let price = get_price(vec![
+        Arc::new(FixedPriceApi("1.0")),
+        Arc::new(FixedPriceApi("1.1")),
+        Arc::new(FixedPriceApi("100")),
+        Arc::new(FixedPriceApi("0.95")),
+    ])
+    .await
+    .unwrap();
+    assert_eq!(price.usd_price, usd_price("1.0"));
+
+    // Failed sources are ignored.
+    let price = get_price(vec![
+        Arc::new(FixedPriceApi("1.0")),
+        Arc::new(FailingTickerApi),
+        Arc::new(FixedPriceApi("1.2")),
+    ])
+    .await
+    .unwrap();
+    assert_eq!(price.usd_price, usd_price("1.1"));
+
+    // Prices are too far from each other to choose the right one.
+    let error = get_price(vec![
+        Arc::new(FixedPriceApi("1.0")),
+        Arc::new(FixedPriceApi("2.0")),
+    ])
+    .await
+    .unwrap_err();
+    assert!(matches!(error, PriceError::ApiError(_)));
+
+    // Two prices are compared with each other rather than with their average.
+    let error = get_price(vec![
+        Arc::new(FixedPriceApi("1.0")),
+        Arc::new(FixedPriceApi("1.3")),
+    ])
+    .await
+    .unwrap_err();
+    assert!(matches!(error, PriceError::ApiError(_)));
+
+    // The token is unlisted only if none of the sources has the price.
+    let error = get_price(vec![Arc::new(ErrorTickerApi), Arc::new(ErrorTickerApi)])
+        .await
+        .unwrap_err();
+    assert!(matches!(error, PriceError::TokenNotFound(_)));
+    let error = get_price(vec![Arc::new(ErrorTickerApi), Arc::new(FailingTickerApi)])
+        .await
+        .unwrap_err();
+    assert!(matches!(error, PriceError::ApiError(_)));
+}
+
+#[tokio::test]
+async fn test_static_price_api() {
+    let api = StaticPriceAPI::new(
+        vec![("DAI".to_string(), usd_price("1.01"))]
+            .into_iter()
+            .collect(),
+    );
+
+    let dai = Token::new(TokenId(1), Address::default(), "DAI", 18, TokenKind::ERC20);
+    assert_eq!(
+        api.get_price(&dai).await.unwrap().usd_price,
+        usd_price("1.01")
+    );
+
+    let unknown = Token::new(TokenId(2), Address::default(), "UNK", 18, TokenKind::ERC20);
+    let error = api.get_price(&unknown).await.unwrap_err();
+    assert!(matches!(error, PriceError::TokenNotFound(_)));
+}
+
+#[test]
+fn test_uniswap_twap() {
+    let start = Utc::now();
+    let seconds = chrono::Duration::seconds;
+    let window = seconds(100);
+    let mut samples = VecDeque::new();
+
+    add_sample(&mut samples, start, usd_price("1"), window);
+    assert_eq!(time_weighted_average(&samples, start), usd_price("1"));
+
+    // The price was 1 for 30 seconds and then 2 for 10 seconds.
+    add_sample(&mut samples, start + seconds(30), usd_price("2"), window);
+    assert_eq!(
+        time_weighted_average(&samples, start + seconds(40)),
+        usd_price("1.25")
+    );
+
+    // The first sample is out of the window now.
+    add_sample(&mut samples, start + seconds(120), usd_price("4"), window);
+    assert_eq!(samples.len(), 2);
+    assert_eq!(
+        time_weighted_average(&samples, start + seconds(150)),
+        usd_price("2.5")
+    );
+}
+
 #[actix_rt::test]
 #[ignore]
 // It's ignore because we can't initialize coingecko in current way with block
@@ -607,7 +907,7 @@ async fn test_error_coingecko_api() {
     let mut ticker = FeeTicker::new(
         ticker_api,
         MockTickerInfo::default(),
-        mpsc::channel(1).1,
+        mpsc::channel(1).1.into(),
         config,
         validator,
     );
@@ -659,7 +959,7 @@ async fn test_error_api() {
     let mut ticker = FeeTicker::new(
         ticker_api,
         MockTickerInfo::default(),
-        mpsc::channel(1).1,
+        mpsc::channel(1).1.into(),
         config,
         validator,
     );
@@ -0,0 +1,186 @@
+// Built-in deps
+use std::sync::Arc;
+// External deps
+use async_trait::async_trait;
+use futures::future::join_all;
+use num::{rational::Ratio, BigUint};
+// Workspace deps
+use super::TokenPriceAPI;
+use crate::fee_ticker::PriceError;
+use zksync_types::{Token, TokenPrice};
+use zksync_utils::ratio_to_big_decimal;
+
+pub type BoxedPriceAPI = Arc<dyn TokenPriceAPI + Send + Sync>;
+
+/// Minimum number of prices to tell the outliers by their deviation from the median.
+/// The median of two prices is their average, so both of them deviate from it equally.
+const MIN_PRICES_TO_REJECT_OUTLIERS: usize = 3;
+
+/// Queries several price sources at once and returns the median of their prices.
+///
+/// Prices deviating from the median by more than the allowed deviation are rejected as outliers,
+/// so a single source returning a wrong price can't affect the fees. If there are only two prices,
+/// the wrong one can't be told apart, so they're required to be within the allowed deviation
+/// from each other instead.
+#[derive(Clone)]
+pub struct MedianPriceAPI {
+    sources: Vec<(&'static str, BoxedPriceAPI)>,
+    max_deviation: Ratio<BigUint>,
+}
+
+impl MedianPriceAPI {
+    /// Creates the API with the named sources, `max_deviation_percent` is the allowed deviation from the median.
+    pub fn new(sources: Vec<(&'static str, BoxedPriceAPI)>, max_deviation_percent: u32) -> Self {
+        Self {
+            sources,
+            max_deviation: Ratio::new(max_deviation_percent.into(), 100u32.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for MedianPriceAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let responses = join_all(self.sources.iter().map(|(_, api)| api.get_price(token))).await;
+
+        let mut prices = Vec::new();
+        let mut error = None;
+        for ((source, _), response) in self.sources.iter().zip(responses) {
+            match response {
+                Ok(price) => prices.push((*source, price)),
+                Err(err) => {
+                    vlog::debug!(
+                        "Price source {} failed to get the price of {}: {}",
+                        source,
+                        token.symbol,
+                        err
+                    );
+                    metrics::increment_counter!("ticker.price_source.errors", "source" => *source);
+                    // The token is considered unlisted only if all the sources haven't found it.
+                    if error.is_none() || !matches!(err, PriceError::TokenNotFound(_)) {
+                        error = Some(err);
+                    }
+                }
+            }
+        }
+
+        if prices.is_empty() {
+            return Err(error.unwrap_or_else(|| PriceError::api_error("No price sources")));
+        }
+
+        if prices.len() < MIN_PRICES_TO_REJECT_OUTLIERS {
+            return self.agreed_price(token, prices);
+        }
+
+        let usd_prices: Vec<_> = prices
+            .iter()
+            .map(|(_, price)| price.usd_price.clone())
+            .collect();
+        let median_price = median(&usd_prices);
+
+        let mut accepted = Vec::new();
+        for (source, price) in prices {
+            if is_within_deviation(&price.usd_price, &median_price, &self.max_deviation) {
+                accepted.push(price);
+            } else {
+                vlog::warn!(
+                    "Price of {} from {} deviates from the median: {} vs {}",
+                    token.symbol,
+                    source,
+                    ratio_to_big_decimal(&price.usd_price, 6),
+                    ratio_to_big_decimal(&median_price, 6)
+                );
+                metrics::increment_counter!("ticker.price_source.outliers", "source" => source);
+            }
+        }
+
+        if accepted.is_empty() {
+            return Err(PriceError::api_error(format!(
+                "Price sources disagree on the price of {}",
+                token.symbol
+            )));
+        }
+
+        Ok(median_token_price(&accepted))
+    }
+}
+
+impl MedianPriceAPI {
+    /// Returns the median of the prices if they're within the allowed deviation from each other.
+    /// Used when there are too few prices to reject the outliers.
+    fn agreed_price(
+        &self,
+        token: &Token,
+        prices: Vec<(&'static str, TokenPrice)>,
+    ) -> Result<TokenPrice, PriceError> {
+        let usd_prices = prices.iter().map(|(_, price)| &price.usd_price);
+        let min = usd_prices
+            .clone()
+            .min()
+            .expect("There is at least one price");
+        let max = usd_prices.max().unwrap();
+
+        if !is_within_deviation(max, min, &self.max_deviation) {
+            vlog::warn!(
+                "Price sources disagree on the price of {}: {:?}",
+                token.symbol,
+                prices
+                    .iter()
+                    .map(|(source, price)| format!(
+                        "{}: {}",
+                        source,
+                        ratio_to_big_decimal(&price.usd_price, 6)
+                    ))
+                    .collect::<Vec<_>>()
+            );
+            metrics::increment_counter!("ticker.price_source.disagreements");
+            return Err(PriceError::api_error(format!(
+                "Price sources disagree on the price of {}",
+                token.symbol
+            )));
+        }
+
+        let prices: Vec<_> = prices.into_iter().map(|(_, price)| price).collect();
+        Ok(median_token_price(&prices))
+    }
+}
+
+/// Median of the non-empty set of token prices, updated at the time of the oldest of them.
+fn median_token_price(prices: &[TokenPrice]) -> TokenPrice {
+    let usd_prices: Vec<_> = prices.iter().map(|price| price.usd_price.clone()).collect();
+    let last_updated = prices
+        .iter()
+        .map(|price| price.last_updated)
+        .min()
+        .expect("There is at least one price");
+    TokenPrice {
+        usd_price: median(&usd_prices),
+        last_updated,
+    }
+}
+
+/// Median of the non-empty set of prices. For even number of prices, the average of the middle ones is used.
+pub(crate) fn median(prices: &[Ratio<BigUint>]) -> Ratio<BigUint> {
+    let mut prices = prices.to_vec();
+    prices.sort();
+
+    let middle = prices.len() / 2;
+    if prices.len() % 2 == 0 {
+        (&prices[middle - 1] + &prices[middle]) / BigUint::from(2u32)
+    } else {
+        prices[middle].clone()
+    }
+}
+
+fn is_within_deviation(
+    price: &Ratio<BigUint>,
+    median: &Ratio<BigUint>,
+    max_deviation: &Ratio<BigUint>,
+) -> bool {
+    let difference = if price > median {
+        price - median
+    } else {
+        median - price
+    };
+    difference <= median * max_deviation
+}
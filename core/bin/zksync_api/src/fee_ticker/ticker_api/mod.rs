@@ -14,6 +14,9 @@ use zksync_types::{Token, TokenId, TokenLike, TokenPrice};
 
 pub mod coingecko;
 pub mod coinmarkercap;
+pub mod median;
+pub mod static_prices;
+pub mod uniswap_twap;
 
 const API_PRICE_EXPIRATION_TIME_SECS: i64 = 30 * 60;
 const UPDATE_PRICE_INTERVAL_SECS: u64 = 10 * 60;
@@ -0,0 +1,52 @@
+// Built-in deps
+use std::collections::HashMap;
+use std::path::Path;
+// External deps
+use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use num::{rational::Ratio, BigUint};
+// Workspace deps
+use super::TokenPriceAPI;
+use crate::fee_ticker::PriceError;
+use zksync_types::{Token, TokenPrice};
+use zksync_utils::big_decimal_to_ratio;
+
+/// Token prices that never change, intended for development networks.
+#[derive(Debug, Clone)]
+pub struct StaticPriceAPI {
+    /// USD prices by the token symbol.
+    prices: HashMap<String, Ratio<BigUint>>,
+}
+
+impl StaticPriceAPI {
+    pub fn new(prices: HashMap<String, Ratio<BigUint>>) -> Self {
+        Self { prices }
+    }
+
+    /// Loads the prices from the JSON file mapping token symbols to their USD prices, e.g. `{ "ETH": "3000.5" }`.
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let prices: HashMap<String, BigDecimal> = serde_json::from_str(&content)?;
+
+        let prices = prices
+            .into_iter()
+            .map(|(symbol, price)| Ok((symbol, big_decimal_to_ratio(&price)?)))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self::new(prices))
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for StaticPriceAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let usd_price = self.prices.get(&token.symbol).cloned().ok_or_else(|| {
+            PriceError::token_not_found(format!("Token '{}' has no static price", token.symbol))
+        })?;
+
+        Ok(TokenPrice {
+            usd_price,
+            last_updated: Utc::now(),
+        })
+    }
+}
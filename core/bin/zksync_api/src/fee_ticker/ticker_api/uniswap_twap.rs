@@ -0,0 +1,106 @@
+// Built-in deps
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+// External deps
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use num::{rational::Ratio, BigUint};
+use tokio::sync::Mutex;
+// Workspace deps
+use super::TokenPriceAPI;
+use crate::fee_ticker::{validator::watcher::UniswapTokenWatcher, PriceError};
+use zksync_types::{Address, Token, TokenPrice};
+use zksync_utils::big_decimal_to_ratio;
+
+/// Period over which the Uniswap prices are averaged.
+const TWAP_WINDOW_SECS: i64 = 60 * 60;
+
+type PriceSamples = VecDeque<(DateTime<Utc>, Ratio<BigUint>)>;
+
+/// Time-weighted average of the Uniswap spot prices.
+///
+/// Every request samples the spot price, so the samples are taken as often as the ticker updates the prices.
+/// Averaging makes the price resistant to the short-term manipulations of the pool.
+#[derive(Clone)]
+pub struct UniswapTwapAPI {
+    watcher: UniswapTokenWatcher,
+    window: Duration,
+    samples: Arc<Mutex<HashMap<Address, PriceSamples>>>,
+}
+
+impl UniswapTwapAPI {
+    pub fn new(watcher: UniswapTokenWatcher) -> Self {
+        Self {
+            watcher,
+            window: Duration::seconds(TWAP_WINDOW_SECS),
+            samples: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for UniswapTwapAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let spot_price = self
+            .watcher
+            .get_token_price_usd(token.address)
+            .await
+            .map_err(PriceError::api_error)?
+            .ok_or_else(|| {
+                PriceError::token_not_found(format!(
+                    "Token '{}, {:?}' is not traded on Uniswap",
+                    token.symbol, token.address
+                ))
+            })?;
+        let spot_price = big_decimal_to_ratio(&spot_price).map_err(PriceError::api_error)?;
+
+        let now = Utc::now();
+        let mut samples = self.samples.lock().await;
+        let token_samples = samples.entry(token.address).or_default();
+        add_sample(token_samples, now, spot_price, self.window);
+
+        Ok(TokenPrice {
+            usd_price: time_weighted_average(token_samples, now),
+            last_updated: now,
+        })
+    }
+}
+
+/// Adds the price sample and removes the ones that are out of the averaging window.
+pub(crate) fn add_sample(
+    samples: &mut PriceSamples,
+    time: DateTime<Utc>,
+    price: Ratio<BigUint>,
+    window: Duration,
+) {
+    samples.push_back((time, price));
+    while samples
+        .front()
+        .map_or(false, |(sample_time, _)| time - *sample_time > window)
+    {
+        samples.pop_front();
+    }
+}
+
+/// Averages the prices weighted by the time each of them was the latest one.
+pub(crate) fn time_weighted_average(samples: &PriceSamples, now: DateTime<Utc>) -> Ratio<BigUint> {
+    let mut weighted_sum = Ratio::from_integer(BigUint::from(0u32));
+    let mut total_weight = BigUint::from(0u32);
+
+    for (index, (time, price)) in samples.iter().enumerate() {
+        let next_time = samples.get(index + 1).map_or(now, |(time, _)| *time);
+        let weight = BigUint::from((next_time - *time).num_milliseconds().max(0) as u64);
+
+        weighted_sum += price * &weight;
+        total_weight += weight;
+    }
+
+    if total_weight == BigUint::from(0u32) {
+        // All the samples were taken at the same moment.
+        return samples
+            .back()
+            .map(|(_, price)| price.clone())
+            .unwrap_or_else(|| Ratio::from_integer(BigUint::from(0u32)));
+    }
+    weighted_sum / total_weight
+}
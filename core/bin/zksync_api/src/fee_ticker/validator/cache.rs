@@ -3,7 +3,10 @@ use std::{collections::HashMap, sync::Arc};
 use tokio::sync::Mutex;
 
 use zksync_storage::ConnectionPool;
-use zksync_types::{tokens::TokenMarketVolume, Token, TokenId, TokenLike};
+use zksync_types::{
+    tokens::{TokenMarketVolume, TokenSettings},
+    Token, TokenId, TokenLike,
+};
 
 use crate::utils::token_db_cache::TokenDBCache;
 
@@ -23,6 +26,7 @@ pub(crate) struct TokenInDBCache {
 pub(crate) struct TokenInMemoryCache {
     tokens: Arc<Mutex<HashMap<TokenLike, Token>>>,
     market: Arc<Mutex<HashMap<TokenId, TokenMarketVolume>>>,
+    settings: Arc<Mutex<HashMap<TokenId, TokenSettings>>>,
 }
 
 impl TokenInDBCache {
@@ -50,6 +54,13 @@ impl TokenInMemoryCache {
             ..self
         }
     }
+
+    pub fn with_settings(self, settings: HashMap<TokenId, TokenSettings>) -> Self {
+        Self {
+            settings: Arc::new(Mutex::new(settings)),
+            ..self
+        }
+    }
 }
 
 impl From<TokenInMemoryCache> for TokenCacheWrapper {
@@ -93,6 +104,19 @@ impl TokenCacheWrapper {
         }
     }
 
+    pub async fn get_token_settings(
+        &self,
+        token_id: TokenId,
+    ) -> anyhow::Result<Option<TokenSettings>> {
+        match self {
+            Self::DB(cache) => {
+                TokenDBCache::get_token_settings(&mut cache.pool.access_storage().await?, token_id)
+                    .await
+            }
+            Self::Memory(cache) => Ok(cache.settings.lock().await.get(&token_id).cloned()),
+        }
+    }
+
     pub async fn update_token_market_volume(
         &mut self,
         token_id: TokenId,
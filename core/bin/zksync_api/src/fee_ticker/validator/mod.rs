@@ -159,15 +159,16 @@ impl<W: TokenWatcher> FeeTokenValidator<W> {
             }
         }
 
-        let volume = match self.get_token_market_volume(&token).await? {
-            Some(volume) => volume,
-            None => self.get_remote_token_market(&token).await?,
+        // Allowlisted, disallowed and deprecated tokens aren't checked by the market volume.
+        let fee_acceptance = self
+            .tokens_cache
+            .get_token_settings(token.id)
+            .await?
+            .and_then(|settings| settings.fee_acceptance());
+        let allowed = match fee_acceptance {
+            Some(allowed) => allowed,
+            None => self.check_market_volume(&token).await?,
         };
-
-        if Utc::now() - volume.last_updated > self.available_time {
-            vlog::warn!("Token market amount for {} is not relevant", &token.symbol)
-        }
-        let allowed = ratio_to_big_decimal(&volume.market_volume, 2) >= self.liquidity_volume;
         self.tokens.insert(
             token.address,
             AcceptanceData {
@@ -178,6 +179,19 @@ impl<W: TokenWatcher> FeeTokenValidator<W> {
         metrics::histogram!("ticker.validator.check_token", start.elapsed());
         Ok(allowed)
     }
+
+    async fn check_market_volume(&mut self, token: &Token) -> anyhow::Result<bool> {
+        let volume = match self.get_token_market_volume(token).await? {
+            Some(volume) => volume,
+            None => self.get_remote_token_market(token).await?,
+        };
+
+        if Utc::now() - volume.last_updated > self.available_time {
+            vlog::warn!("Token market amount for {} is not relevant", &token.symbol)
+        }
+        Ok(ratio_to_big_decimal(&volume.market_volume, 2) >= self.liquidity_volume)
+    }
+
     // I think, it's redundant method and we could remove watcher from validator and store it only in updater
     async fn get_remote_token_market(
         &mut self,
@@ -209,7 +223,10 @@ mod tests {
     use std::str::FromStr;
     use std::sync::Arc;
     use tokio::sync::Mutex;
-    use zksync_types::{TokenId, TokenKind};
+    use zksync_types::{
+        tokens::{TokenFeePolicy, TokenSettings},
+        TokenId, TokenKind,
+    };
 
     #[derive(Clone)]
     struct InMemoryTokenWatcher {
@@ -343,4 +360,85 @@ mod tests {
         assert!(validator.tokens.get(&dai_token_address).unwrap().allowed);
         assert!(!validator.tokens.get(&phnx_token_address).unwrap().allowed);
     }
+
+    fn token_settings(
+        token: &Token,
+        deprecated: bool,
+        fee_policy: TokenFeePolicy,
+    ) -> TokenSettings {
+        TokenSettings {
+            token_id: token.id,
+            version: 1,
+            symbol: token.symbol.clone(),
+            decimals: token.decimals,
+            name: None,
+            deprecated,
+            fee_policy,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Checks that the fee policy set by the admin overrides the market volume check.
+    #[tokio::test]
+    async fn check_tokens_with_fee_policy() {
+        let dai_token = Token::new(
+            TokenId(1),
+            Address::from_low_u64_be(1),
+            "DAI",
+            18,
+            TokenKind::ERC20,
+        );
+        let phnx_token = Token::new(
+            TokenId(2),
+            Address::from_low_u64_be(2),
+            "PHNX",
+            18,
+            TokenKind::ERC20,
+        );
+        let old_token = Token::new(
+            TokenId(3),
+            Address::from_low_u64_be(3),
+            "OLD",
+            18,
+            TokenKind::ERC20,
+        );
+
+        let mut tokens = HashMap::new();
+        let mut amounts = HashMap::new();
+        let mut settings = HashMap::new();
+        for (token, amount, deprecated, fee_policy) in &[
+            // Token with the high market volume, but disallowed to pay fees in.
+            (&dai_token, 200, false, TokenFeePolicy::Disallowed),
+            // Token with the low market volume from the allowlist.
+            (&phnx_token, 10, false, TokenFeePolicy::Allowed),
+            // Deprecated tokens are not accepted even if they're in the allowlist.
+            (&old_token, 200, true, TokenFeePolicy::Allowed),
+        ] {
+            tokens.insert(TokenLike::Address(token.address), (*token).clone());
+            amounts.insert(token.address, BigDecimal::from(*amount));
+            settings.insert(token.id, token_settings(token, *deprecated, *fee_policy));
+        }
+
+        let cache = TokenInMemoryCache::new()
+            .with_tokens(tokens)
+            .with_settings(settings);
+        let watcher = InMemoryTokenWatcher {
+            amounts: Arc::new(Mutex::new(amounts)),
+        };
+        let mut validator = FeeTokenValidator::new(
+            cache,
+            chrono::Duration::seconds(100),
+            BigDecimal::from(100),
+            HashSet::new(),
+            watcher,
+        );
+
+        for (token, expected) in &[(dai_token, false), (phnx_token, true), (old_token, false)] {
+            let allowed = validator
+                .token_allowed(TokenLike::Address(token.address))
+                .await
+                .unwrap();
+            assert_eq!(allowed, *expected, "unexpected result for {}", token.symbol);
+        }
+    }
 }
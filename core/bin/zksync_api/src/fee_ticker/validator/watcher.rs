@@ -70,6 +70,48 @@ impl UniswapTokenWatcher {
         };
         Ok(volume)
     }
+
+    /// Returns the spot price of the token in USD, or `None` if the token is not traded on Uniswap.
+    /// The zero address stands for ETH.
+    pub async fn get_token_price_usd(
+        &self,
+        address: Address,
+    ) -> anyhow::Result<Option<BigDecimal>> {
+        let start = Instant::now();
+
+        let query = format!(
+            "{{token(id: \"{:#x}\"){{derivedETH}} bundle(id: \"1\"){{ethPrice}}}}",
+            address
+        );
+        let response: GraphqlPriceResponse = self
+            .client
+            .post(&self.addr)
+            .json(&serde_json::json!({
+                "query": query,
+            }))
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|err| anyhow::format_err!("Uniswap API request failed: {}", err))?
+            .json()
+            .await?;
+
+        metrics::histogram!("ticker.uniswap_watcher.get_token_price", start.elapsed());
+
+        let eth_price: BigDecimal = match response.data.bundle {
+            Some(bundle) => bundle.eth_price.parse()?,
+            None => anyhow::bail!("Uniswap API returned no ETH price"),
+        };
+        if address == Address::zero() {
+            return Ok(Some(eth_price));
+        }
+
+        match response.data.token {
+            Some(token) => Ok(Some(token.derived_eth.parse::<BigDecimal>()? * eth_price)),
+            None => Ok(None),
+        }
+    }
+
     async fn update_historical_amount(&mut self, address: Address, amount: BigDecimal) {
         let mut cache = self.cache.lock().await;
         cache.insert(address, amount);
@@ -97,6 +139,31 @@ pub struct TokenResponse {
     pub untracked_volume_usd: String,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GraphqlPriceResponse {
+    pub data: GraphqlTokenPriceResponse,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GraphqlTokenPriceResponse {
+    pub token: Option<TokenPriceResponse>,
+    pub bundle: Option<BundleResponse>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TokenPriceResponse {
+    /// Price of the token in ETH.
+    #[serde(rename = "derivedETH")]
+    pub derived_eth: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BundleResponse {
+    /// Price of ETH in USD.
+    #[serde(rename = "ethPrice")]
+    pub eth_price: String,
+}
+
 #[async_trait::async_trait]
 impl TokenWatcher for UniswapTokenWatcher {
     async fn get_token_market_volume(&mut self, token: &Token) -> anyhow::Result<BigDecimal> {
@@ -1,6 +1,9 @@
 #![recursion_limit = "256"]
 
-use crate::{api_server::start_api_server, fee_ticker::run_ticker_task};
+use crate::{
+    api_server::start_api_server,
+    fee_ticker::{fee_formula::FeeFormulaUpdater, run_ticker_task},
+};
 use futures::channel::mpsc;
 use zksync_config::ZkSyncConfig;
 use zksync_eth_client::EthereumGateway;
@@ -24,12 +27,26 @@ pub fn run_api(
     let channel_size = 32768;
     let (ticker_request_sender, ticker_request_receiver) = mpsc::channel(channel_size);
 
-    let ticker_task = run_ticker_task(connection_pool.clone(), ticker_request_receiver, config);
+    let (fee_formula, fee_formula_updates) =
+        FeeFormulaUpdater::new(config).expect("Failed to load the fee formula");
+    tokio::spawn(
+        fee_formula
+            .clone()
+            .keep_updated(config.ticker.fee_formula_update_time),
+    );
+
+    let ticker_task = run_ticker_task(
+        connection_pool.clone(),
+        ticker_request_receiver,
+        fee_formula_updates,
+        config,
+    );
 
     start_api_server(
         connection_pool,
         panic_notify,
         ticker_request_sender,
+        fee_formula,
         eth_gateway,
         config,
     );
@@ -46,6 +46,7 @@ async fn main() -> anyhow::Result<()> {
             vlog::warn!("Stop signal received, shutting down");
         }
     };
+    vlog::shutdown_exporter().await;
 
     Ok(())
 }
@@ -3,6 +3,19 @@
 //! Main routine of this module operates a multithreaded event loop,
 //! which is used to spawn concurrent tasks to efficiently check the
 //! transactions signatures.
+//!
+//! Requests are distributed between a fixed number of workers by the balancer.
+//! Requests of the same sender always go to the same worker, which verifies them
+//! one at a time, so they are answered in the order they were received.
+//!
+//! Ethereum signatures are checked within the event loop, since some of them
+//! (EIP-1271 ones) require a call to the Ethereum node. CPU-heavy zkSync signatures
+//! are verified on a blocking thread, at most one request per worker. Every signature
+//! of a batch is verified separately.
+//!
+//! Queues of the workers are bounded: once they are full, the routine stops reading
+//! new requests from the channel, and the API servers are waiting for the free space
+//! in the channel.
 
 // Built-in uses
 use std::collections::HashSet;
@@ -13,14 +26,18 @@ use futures::{
     channel::{mpsc, oneshot},
     StreamExt,
 };
-use tokio::runtime::{Builder, Handle};
+use tokio::runtime::Builder;
 // Workspace uses
+use zksync_balancer::{
+    balancing_key, BalancedReceiver, BalancedRequest, Balancer, BalancingPolicy, BuildBalancedItem,
+};
 use zksync_types::{
     tx::{EthBatchSignData, EthSignData, TxEthSignature},
     Address, Order, SignedZkSyncTx, Token, ZkSyncTx,
 };
 // Local uses
 use crate::{eth_checker::EthereumChecker, tx_error::TxAddError};
+use zksync_config::ZkSyncConfig;
 use zksync_eth_client::EthereumGateway;
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
@@ -51,8 +68,14 @@ impl VerifiedTx {
         eth_checker: &EthereumChecker,
     ) -> Result<Self, TxAddError> {
         verify_eth_signature(&request_data, eth_checker).await?;
-        let mut tx_variant = request_data.get_tx_variant();
-        verify_tx_correctness(&mut tx_variant)?;
+        let mut tx_variant = request_data.into_tx_variant();
+        let start = Instant::now();
+        let tx_variant = tokio::task::spawn_blocking(move || {
+            verify_tx_correctness(&mut tx_variant).map(|_| tx_variant)
+        })
+        .await
+        .expect("zkSync signature verification has panicked")?;
+        metrics::histogram!("signature_checker.verify_tx_correctness", start.elapsed());
 
         Ok(Self(tx_variant))
     }
@@ -117,18 +140,13 @@ async fn verify_eth_signature(
             {
                 verify_eth_signature_single_tx(tx, account, token, eth_checker).await?;
             }
+            // Orders of the swaps included into the batch.
+            for order in &request.orders {
+                verify_eth_signature_order(order, eth_checker).await?;
+            }
         }
         RequestData::Order(request) => {
-            let signature_correct = verify_ethereum_signature(
-                &request.sign_data.signature,
-                &request.sign_data.message,
-                request.sender,
-                eth_checker,
-            )
-            .await;
-            if !signature_correct {
-                return Err(TxAddError::IncorrectEthSignature);
-            }
+            verify_eth_signature_order(request, eth_checker).await?;
         }
         RequestData::Toggle2FA(request) => {
             let signature_correct = verify_ethereum_signature(
@@ -147,6 +165,23 @@ async fn verify_eth_signature(
     Ok(())
 }
 
+async fn verify_eth_signature_order(
+    request: &OrderRequest,
+    eth_checker: &EthereumChecker,
+) -> Result<(), TxAddError> {
+    let signature_correct = verify_ethereum_signature(
+        &request.sign_data.signature,
+        &request.sign_data.message,
+        request.sender,
+        eth_checker,
+    )
+    .await;
+    if !signature_correct {
+        return Err(TxAddError::IncorrectEthSignature);
+    }
+    Ok(())
+}
+
 /// Given a single Ethereum signature and a message, checks that it
 /// was signed by an expected address.
 async fn verify_ethereum_signature(
@@ -302,7 +337,7 @@ fn verify_tx_correctness(tx: &mut TxVariant) -> Result<(), TxAddError> {
             }
         }
         TxVariant::Batch(batch, _) => {
-            if batch.iter_mut().any(|tx| !tx.tx.check_correctness()) {
+            if !batch.iter_mut().all(|tx| tx.tx.check_correctness()) {
                 return Err(TxAddError::IncorrectTx);
             }
         }
@@ -334,6 +369,8 @@ pub struct BatchRequest {
     pub batch_sign_data: Option<EthBatchSignData>,
     pub senders: Vec<Address>,
     pub tokens: Vec<Token>,
+    /// Orders of the swaps from the batch which Ethereum signatures have to be verified.
+    pub orders: Vec<OrderRequest>,
 }
 
 #[derive(Debug)]
@@ -376,6 +413,81 @@ impl RequestData {
             RequestData::Toggle2FA(_) => TxVariant::Toggle2FA,
         }
     }
+
+    /// Same as `get_tx_variant`, but doesn't clone the transactions.
+    pub fn into_tx_variant(self) -> TxVariant {
+        match self {
+            RequestData::Tx(request) => TxVariant::Tx(request.tx),
+            RequestData::Batch(request) => TxVariant::Batch(request.txs, request.batch_sign_data),
+            RequestData::Order(request) => TxVariant::Order(request.order),
+            RequestData::Toggle2FA(_) => TxVariant::Toggle2FA,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            RequestData::Tx(_) => "tx",
+            RequestData::Batch(_) => "batch",
+            RequestData::Order(_) => "order",
+            RequestData::Toggle2FA(_) => "toggle_2fa",
+        }
+    }
+}
+
+impl BalancedRequest for VerifySignatureRequest {
+    // Requests of the same sender are verified in the order they were received.
+    fn balancing_key(&self) -> Option<u64> {
+        match &self.data {
+            RequestData::Tx(request) => Some(balancing_key(&request.sender)),
+            RequestData::Batch(request) => request.senders.first().map(balancing_key),
+            RequestData::Order(request) => Some(balancing_key(&request.sender)),
+            RequestData::Toggle2FA(request) => Some(balancing_key(&request.sender)),
+        }
+    }
+}
+
+/// Worker verifying the requests sent to it by the balancer one at a time.
+struct SignatureChecker {
+    eth_checker: EthereumChecker,
+    requests: BalancedReceiver<VerifySignatureRequest>,
+}
+
+struct SignatureCheckerBuilder {
+    eth_checker: EthereumChecker,
+}
+
+impl BuildBalancedItem<VerifySignatureRequest, SignatureChecker> for SignatureCheckerBuilder {
+    fn build_with_receiver(
+        &self,
+        receiver: BalancedReceiver<VerifySignatureRequest>,
+    ) -> SignatureChecker {
+        SignatureChecker {
+            eth_checker: self.eth_checker.clone(),
+            requests: receiver,
+        }
+    }
+}
+
+impl SignatureChecker {
+    /// Receives the requests through the channel and verifies signatures,
+    /// notifying the request sender about the check result.
+    async fn run(mut self) {
+        while let Some(VerifySignatureRequest { data, response }) = self.requests.next().await {
+            let start = Instant::now();
+            let kind = data.kind();
+            let resp = VerifiedTx::verify(data, &self.eth_checker).await;
+            metrics::histogram!("signature_checker.verify", start.elapsed(), "type" => kind);
+            if let Err(err) = &resp {
+                metrics::increment_counter!(
+                    "signature_checker.rejected",
+                    "type" => kind,
+                    "error" => err.as_str()
+                );
+            }
+
+            response.send(resp).unwrap_or_default();
+        }
+    }
 }
 
 /// Main routine of the concurrent signature checker.
@@ -384,26 +496,13 @@ pub fn start_sign_checker_detached(
     client: EthereumGateway,
     input: mpsc::Receiver<VerifySignatureRequest>,
     panic_notify: mpsc::Sender<bool>,
+    config: &ZkSyncConfig,
 ) {
     let eth_checker = EthereumChecker::new(client);
-
-    /// Main signature check requests handler.
-    /// Basically it receives the requests through the channel and verifies signatures,
-    /// notifying the request sender about the check result.
-    async fn checker_routine(
-        handle: Handle,
-        mut input: mpsc::Receiver<VerifySignatureRequest>,
-        eth_checker: EthereumChecker,
-    ) {
-        while let Some(VerifySignatureRequest { data, response }) = input.next().await {
-            let eth_checker = eth_checker.clone();
-            handle.spawn(async move {
-                let resp = VerifiedTx::verify(data, &eth_checker).await;
-
-                response.send(resp).unwrap_or_default();
-            });
-        }
-    }
+    let workers = config.api.common.signature_checker_threads;
+    // Queues of all the workers together hold at most the configured number of pending requests.
+    let channel_capacity =
+        (config.api.common.signature_checker_max_pending_requests / workers as usize).max(1);
 
     std::thread::Builder::new()
         .name("Signature checker thread".to_string())
@@ -414,8 +513,87 @@ pub fn start_sign_checker_detached(
                 .enable_all()
                 .build()
                 .expect("failed to build runtime for signature processor");
-            let handle = runtime.handle().clone();
-            runtime.block_on(checker_routine(handle, input, eth_checker));
+            runtime.block_on(async move {
+                let (balancer, checkers) = Balancer::new(
+                    SignatureCheckerBuilder { eth_checker },
+                    input,
+                    workers,
+                    channel_capacity,
+                );
+                let balancer = balancer
+                    .with_policy(BalancingPolicy::KeyAffinity)
+                    .with_name("signature_checker");
+
+                for checker in checkers.into_iter() {
+                    tokio::spawn(checker.run());
+                }
+                balancer.run().await;
+            });
         })
         .expect("failed to start signature checker thread");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigUint;
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{
+        helpers::{closest_packable_fee_amount, closest_packable_token_amount},
+        AccountId, TokenId, Transfer,
+    };
+
+    fn signed_transfer(account: &ZkSyncAccount, amount: u64) -> Transfer {
+        account
+            .sign_transfer(
+                TokenId(0),
+                "ETH",
+                closest_packable_token_amount(&BigUint::from(amount)),
+                closest_packable_fee_amount(&BigUint::from(10u64)),
+                &Address::repeat_byte(0x11),
+                None,
+                true,
+                Default::default(),
+            )
+            .0
+    }
+
+    fn verify_batch(txs: Vec<Transfer>) -> Result<(), TxAddError> {
+        let batch = txs
+            .into_iter()
+            .map(|tx| SignedZkSyncTx::from(ZkSyncTx::from(tx)))
+            .collect();
+        verify_tx_correctness(&mut TxVariant::Batch(batch, None))
+    }
+
+    /// Checks that a single incorrect signature makes the batch rejected.
+    #[test]
+    fn batch_verification() {
+        let account = ZkSyncAccount::rand();
+        account.set_account_id(Some(AccountId(1)));
+
+        let txs: Vec<_> = (1..=8)
+            .map(|i| signed_transfer(&account, i * 100))
+            .collect();
+        assert!(verify_batch(txs.clone()).is_ok());
+
+        // Signature of the transaction with the changed amount is not valid anymore.
+        let mut corrupted = txs;
+        let tx = corrupted[3].clone();
+        corrupted[3] = Transfer::new(
+            tx.account_id,
+            tx.from,
+            tx.to,
+            tx.token,
+            closest_packable_token_amount(&(tx.amount + BigUint::from(1000u64))),
+            tx.fee,
+            tx.nonce,
+            tx.time_range.unwrap_or_default(),
+            Some(tx.signature),
+        );
+        assert!(matches!(
+            verify_batch(corrupted),
+            Err(TxAddError::IncorrectTx)
+        ));
+    }
+}
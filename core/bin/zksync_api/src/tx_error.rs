@@ -46,6 +46,28 @@ pub enum TxAddError {
     EthSignaturesLimitExceeded,
 }
 
+impl TxAddError {
+    /// Name of the error used as a metrics label.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NonceMismatch => "nonce_mismatch",
+            Self::IncorrectTx => "incorrect_tx",
+            Self::TxFeeTooLow => "tx_fee_too_low",
+            Self::TxBatchFeeTooLow => "tx_batch_fee_too_low",
+            Self::EIP1271SignatureVerificationFail => "eip1271_signature_verification_fail",
+            Self::MissingEthSignature => "missing_eth_signature",
+            Self::IncorrectEthSignature => "incorrect_eth_signature",
+            Self::ChangePkNotAuthorized => "change_pk_not_authorized",
+            Self::Other => "other",
+            Self::DbError => "db_error",
+            Self::EmptyBatch => "empty_batch",
+            Self::BatchTooBig => "batch_too_big",
+            Self::BatchWithdrawalsOverload => "batch_withdrawals_overload",
+            Self::EthSignaturesLimitExceeded => "eth_signatures_limit_exceeded",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Error)]
 pub enum Toggle2FAError {
     #[error("Internal error")]
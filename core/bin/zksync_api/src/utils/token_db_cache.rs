@@ -1,15 +1,23 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use tokio::sync::RwLock;
 
 use zksync_storage::StorageProcessor;
-use zksync_types::tokens::TokenMarketVolume;
+use zksync_types::tokens::{TokenMarketVolume, TokenSettings};
 use zksync_types::{Token, TokenId, TokenLike, NFT};
 
+/// Token symbol and decimals may be changed via the admin API, so the cached tokens are reloaded
+/// from the database once in a while.
+const TOKEN_CACHE_TTL: Duration = Duration::from_secs(60);
+
 #[derive(Debug, Clone, Default)]
 pub struct TokenDBCache {
-    // TODO: handle stale entries, edge case when we rename token after adding it (ZKS-97)
-    cache: Arc<RwLock<HashMap<TokenLike, Token>>>,
+    /// Tokens with the time they were loaded at.
+    cache: Arc<RwLock<HashMap<TokenLike, (Token, Instant)>>>,
     nft_tokens: Arc<RwLock<HashMap<TokenId, NFT>>>,
 }
 
@@ -31,9 +39,11 @@ impl TokenDBCache {
         storage: &mut StorageProcessor<'_>,
         token_query: TokenLike,
     ) -> anyhow::Result<Option<Token>> {
-        // Just return token from cache.
-        if let Some(token) = self.cache.read().await.get(&token_query) {
-            return Ok(Some(token.clone()));
+        // Just return token from cache, unless it's stale.
+        if let Some((token, loaded_at)) = self.cache.read().await.get(&token_query) {
+            if loaded_at.elapsed() < TOKEN_CACHE_TTL {
+                return Ok(Some(token.clone()));
+            }
         }
         // Tries to fetch token from the underlying database.
         let token = {
@@ -42,9 +52,15 @@ impl TokenDBCache {
                 .get_token(token_query.clone())
                 .await?
         };
-        // Stores received token into the local cache.
-        if let Some(token) = &token {
-            self.cache.write().await.insert(token_query, token.clone());
+        // Stores received token into the local cache, the stale entry is removed if the token was renamed.
+        let mut cache = self.cache.write().await;
+        match &token {
+            Some(token) => {
+                cache.insert(token_query, (token.clone(), Instant::now()));
+            }
+            None => {
+                cache.remove(&token_query);
+            }
         }
 
         Ok(token)
@@ -102,6 +118,14 @@ impl TokenDBCache {
         Ok(volume)
     }
 
+    pub async fn get_token_settings(
+        storage: &mut StorageProcessor<'_>,
+        token: TokenId,
+    ) -> anyhow::Result<Option<TokenSettings>> {
+        let settings = storage.tokens_schema().get_token_settings(token).await?;
+        Ok(settings)
+    }
+
     pub async fn update_token_market_volume(
         storage: &mut StorageProcessor<'_>,
         token: TokenId,
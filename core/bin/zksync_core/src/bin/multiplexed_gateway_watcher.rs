@@ -14,6 +14,7 @@ async fn main() {
         config.gateway_watcher.request_timeout(),
         Some(config.gateway_watcher.request_per_task_limit()),
         Some(config.gateway_watcher.task_limit()),
+        config.gateway_watcher.health_thresholds(),
     )
     .run()
     .await;
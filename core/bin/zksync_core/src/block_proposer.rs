@@ -71,6 +71,18 @@ impl BlockProposer {
         let block_timestamp = self.get_pending_block_timestamp().await;
         let proposed_block = self.propose_new_block(block_timestamp).await;
 
+        if proposed_block.is_empty() {
+            metrics::increment_counter!("block_proposer.empty_miniblocks");
+        }
+        metrics::histogram!(
+            "block_proposer.miniblock_priority_ops",
+            proposed_block.priority_ops.len() as f64
+        );
+        metrics::histogram!(
+            "block_proposer.miniblock_txs",
+            proposed_block.txs.len() as f64
+        );
+
         self.current_priority_op_number += proposed_block.priority_ops.len() as u64;
         self.statekeeper_requests
             .send(StateKeeperRequest::ExecuteMiniBlock(proposed_block))
@@ -1,14 +1,18 @@
 // Built-in uses
 use std::time::{Duration, Instant};
 // External uses
-use futures::channel::mpsc::{Receiver, Sender};
+use futures::channel::{
+    mpsc::{Receiver, Sender},
+    oneshot,
+};
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use tokio::{task::JoinHandle, time};
+use vlog::Instrument;
 // Workspace uses
-use crate::mempool::MempoolBlocksRequest;
+use crate::{leader_lease::CORE_LEASE_NAME, mempool::MempoolBlocksRequest};
 use zksync_config::ZkSyncConfig;
-use zksync_storage::ConnectionPool;
+use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::{
     block::{Block, BlockMetadata, ExecutedOperations, PendingBlock},
     AccountUpdates, BlockNumber,
@@ -20,6 +24,8 @@ mod aggregated_committer;
 pub enum CommitRequest {
     PendingBlock((PendingBlock, AppliedUpdatesRequest)),
     Block((BlockCommitRequest, AppliedUpdatesRequest)),
+    /// Notifies the sender once all the previous requests are stored in the database.
+    Flush(oneshot::Sender<()>),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -33,6 +39,8 @@ pub struct BlockCommitRequest {
 pub struct AppliedUpdatesRequest {
     pub account_updates: AccountUpdates,
     pub first_update_order_id: usize,
+    /// Span of the state keeper operation which produced the updates, the span of storing them follows from it.
+    pub span: vlog::Span,
 }
 
 pub struct ExecutedOpsNotify {
@@ -42,20 +50,46 @@ pub struct ExecutedOpsNotify {
 
 const PROOF_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
+/// Ensures that this instance still holds the leader lease, so a stale leader never overwrites the blocks
+/// stored by the new one. The lease stays locked until the end of the transaction.
+async fn check_leader_lease(transaction: &mut StorageProcessor<'_>, lease_holder: Option<&str>) {
+    if let Some(holder) = lease_holder {
+        let held = transaction
+            .leader_lease_schema()
+            .lock_lease(CORE_LEASE_NAME, holder)
+            .await
+            .expect("Unable to check the leader lease");
+        if !held {
+            panic!(
+                "Leader lease is not held by {} anymore, blocks can't be committed",
+                holder
+            );
+        }
+    }
+}
+
 async fn handle_new_commit_task(
     mut rx_for_ops: Receiver<CommitRequest>,
     mut mempool_req_sender: Sender<MempoolBlocksRequest>,
     pool: ConnectionPool,
+    lease_holder: Option<String>,
 ) {
     while let Some(request) = rx_for_ops.next().await {
         match request {
             CommitRequest::Block((block_commit_request, applied_updates_req)) => {
+                let span = vlog::info_span!(
+                    "committer.commit_block",
+                    block_number = *block_commit_request.block.block_number
+                );
+                span.follows_from(&applied_updates_req.span);
                 commit_block(
                     block_commit_request,
                     applied_updates_req,
                     &pool,
                     &mut mempool_req_sender,
+                    lease_holder.as_deref(),
                 )
+                .instrument(span)
                 .await;
             }
             CommitRequest::PendingBlock((pending_block, applied_updates_req)) => {
@@ -67,7 +101,22 @@ async fn handle_new_commit_task(
                         .into_iter()
                         .map(|tx| ExecutedOperations::Tx(Box::new(tx))),
                 );
-                save_pending_block(pending_block, applied_updates_req, &pool).await;
+                let span = vlog::info_span!(
+                    "committer.save_pending_block",
+                    block_number = *pending_block.number
+                );
+                span.follows_from(&applied_updates_req.span);
+                save_pending_block(
+                    pending_block,
+                    applied_updates_req,
+                    &pool,
+                    lease_holder.as_deref(),
+                )
+                .instrument(span)
+                .await;
+            }
+            CommitRequest::Flush(sender) => {
+                sender.send(()).unwrap_or_default();
             }
         }
     }
@@ -77,6 +126,7 @@ async fn save_pending_block(
     pending_block: PendingBlock,
     applied_updates_request: AppliedUpdatesRequest,
     pool: &ConnectionPool,
+    lease_holder: Option<&str>,
 ) {
     let start = Instant::now();
     let mut storage = pool
@@ -88,6 +138,7 @@ async fn save_pending_block(
         .start_transaction()
         .await
         .expect("Failed initializing a DB transaction");
+    check_leader_lease(&mut transaction, lease_holder).await;
 
     let block_number = pending_block.number;
 
@@ -124,6 +175,7 @@ async fn commit_block(
     applied_updates_request: AppliedUpdatesRequest,
     pool: &ConnectionPool,
     mempool_req_sender: &mut Sender<MempoolBlocksRequest>,
+    lease_holder: Option<&str>,
 ) {
     let start = Instant::now();
     let BlockCommitRequest {
@@ -141,6 +193,7 @@ async fn commit_block(
         .start_transaction()
         .await
         .expect("Failed initializing a DB transaction");
+    check_leader_lease(&mut transaction, lease_holder).await;
 
     // This is needed to keep track of how many priority ops are in each block
     // and trigger grafana alerts if there are suspiciously few
@@ -214,17 +267,21 @@ async fn poll_for_new_proofs_task(pool: ConnectionPool, config: ZkSyncConfig) {
     }
 }
 
+/// Starts the committer. If `lease_holder` is provided, blocks are stored only while
+/// this instance holds the leader lease.
 #[must_use]
 pub fn run_committer(
     rx_for_ops: Receiver<CommitRequest>,
     mempool_req_sender: Sender<MempoolBlocksRequest>,
     pool: ConnectionPool,
+    lease_holder: Option<String>,
     config: &ZkSyncConfig,
 ) -> JoinHandle<()> {
     tokio::spawn(handle_new_commit_task(
         rx_for_ops,
         mempool_req_sender,
         pool.clone(),
+        lease_holder,
     ));
     tokio::spawn(poll_for_new_proofs_task(pool, config.clone()))
 }
@@ -13,7 +13,7 @@ use web3::{
 use zksync_contracts::{erc20_contract, governance_contract, zksync_contract};
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
 use zksync_types::{
-    Address, NewTokenEvent, Nonce, PriorityOp, RegisterNFTFactoryEvent, H160, U256,
+    Address, NewTokenEvent, Nonce, PriorityOp, RegisterNFTFactoryEvent, H160, H256, U256,
 };
 
 struct ContractTopics {
@@ -59,6 +59,7 @@ pub trait EthClient {
         to: BlockNumber,
     ) -> anyhow::Result<Vec<NewTokenEvent>>;
     async fn block_number(&self) -> anyhow::Result<u64>;
+    async fn block_hash(&self, number: u64) -> anyhow::Result<Option<H256>>;
     async fn get_auth_fact(&self, address: Address, nonce: Nonce) -> anyhow::Result<Vec<u8>>;
     async fn get_auth_fact_reset_time(&self, address: Address, nonce: Nonce)
         -> anyhow::Result<u64>;
@@ -176,6 +177,10 @@ impl EthClient for EthHttpClient {
         Ok(self.client.block_number().await?.as_u64())
     }
 
+    async fn block_hash(&self, number: u64) -> anyhow::Result<Option<H256>> {
+        self.client.block_hash(number).await
+    }
+
     async fn get_auth_fact(&self, address: Address, nonce: Nonce) -> anyhow::Result<Vec<u8>> {
         self.client
             .call_main_contract_function(
@@ -1,8 +1,8 @@
 // Built-in deps
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 // External uses
 // Workspace deps
-use zksync_types::{NewTokenEvent, PriorityOp, RegisterNFTFactoryEvent, SerialId};
+use zksync_types::{NewTokenEvent, PriorityOp, RegisterNFTFactoryEvent, SerialId, H256};
 // Local deps
 use super::received_ops::ReceivedPriorityOp;
 
@@ -32,6 +32,8 @@ pub struct ETHState {
     new_tokens: Vec<NewTokenEvent>,
     /// List of events denoting registered factories for NFT withdrawing
     register_nft_factory_events: Vec<RegisterNFTFactoryEvent>,
+    /// Hashes of the latest processed blocks, used to detect reorgs.
+    block_hashes: BTreeMap<u64, H256>,
 }
 
 impl ETHState {
@@ -41,6 +43,7 @@ impl ETHState {
         priority_queue: HashMap<SerialId, ReceivedPriorityOp>,
         new_tokens: Vec<NewTokenEvent>,
         register_nft_factory_events: Vec<RegisterNFTFactoryEvent>,
+        block_hashes: BTreeMap<u64, H256>,
     ) -> Self {
         Self {
             last_ethereum_block,
@@ -48,9 +51,24 @@ impl ETHState {
             priority_queue,
             new_tokens,
             register_nft_factory_events,
+            block_hashes,
         }
     }
 
+    /// Returns the state as it was before the blocks following `last_ethereum_block` were processed.
+    /// Only the unconfirmed data is rolled back, confirmed events are expected to be final.
+    pub fn rollback(&self, last_ethereum_block: u64) -> Self {
+        let mut state = self.clone();
+        state.last_ethereum_block = last_ethereum_block;
+        state
+            .unconfirmed_queue
+            .retain(|op| op.eth_block <= last_ethereum_block);
+        state
+            .block_hashes
+            .retain(|&block, _| block <= last_ethereum_block);
+        state
+    }
+
     pub fn last_ethereum_block(&self) -> u64 {
         self.last_ethereum_block
     }
@@ -59,6 +77,15 @@ impl ETHState {
         &self.priority_queue
     }
 
+    /// Takes the span of the priority operation, so it ends once the operation is executed.
+    /// Spans aren't a part of the observed state, so the state itself remains unchanged.
+    pub fn take_priority_op_span(&mut self, serial_id: SerialId) -> vlog::Span {
+        self.priority_queue
+            .get_mut(&serial_id)
+            .map(ReceivedPriorityOp::take_span)
+            .unwrap_or_else(vlog::Span::none)
+    }
+
     pub fn unconfirmed_queue(&self) -> &[PriorityOp] {
         &self.unconfirmed_queue
     }
@@ -70,4 +97,8 @@ impl ETHState {
     pub fn new_tokens(&self) -> &[NewTokenEvent] {
         &self.new_tokens
     }
+
+    pub fn block_hashes(&self) -> &BTreeMap<u64, H256> {
+        &self.block_hashes
+    }
 }
@@ -4,9 +4,12 @@
 //!
 //! Poll interval is configured using the `ETH_POLL_INTERVAL` constant.
 //! Number of confirmations is configured using the `CONFIRMATIONS_FOR_ETH_EVENT` environment variable.
+//!
+//! Hashes of the latest processed blocks are tracked to detect reorgs. Once a reorg is detected,
+//! unconfirmed operations from the reorganized blocks are discarded and these blocks are processed again.
 
 // Built-in deps
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::{Duration, Instant};
 
 // External uses
@@ -75,7 +78,8 @@ pub enum EthWatchRequest {
     GetPriorityQueueOps {
         op_start_id: u64,
         max_chunks: usize,
-        resp: oneshot::Sender<Vec<PriorityOp>>,
+        /// Operations are returned with their spans, so the spans of the block execution can follow from them.
+        resp: oneshot::Sender<Vec<(PriorityOp, vlog::Span)>>,
     },
     GetUnconfirmedDeposits {
         address: Address,
@@ -122,6 +126,8 @@ pub struct EthWatch<W: EthClient> {
     eth_state: ETHState,
     /// All ethereum events are accepted after sufficient confirmations to eliminate risk of block reorg.
     number_of_confirmations_for_event: u64,
+    /// Number of the latest blocks which are checked for reorgs.
+    reorg_tracking_depth: u64,
     mode: WatcherMode,
 }
 
@@ -132,9 +138,17 @@ impl<W: EthClient> EthWatch<W> {
             eth_state: ETHState::default(),
             mode: WatcherMode::Working,
             number_of_confirmations_for_event,
+            reorg_tracking_depth: number_of_confirmations_for_event,
         }
     }
 
+    /// Sets the number of the latest blocks which are checked for reorgs.
+    /// By default only the blocks with unconfirmed events are checked.
+    pub fn with_reorg_tracking_depth(mut self, reorg_tracking_depth: u64) -> Self {
+        self.reorg_tracking_depth = reorg_tracking_depth;
+        self
+    }
+
     /// Atomically replaces the stored Ethereum state.
     fn set_new_state(&mut self, new_state: ETHState) {
         self.eth_state = new_state;
@@ -219,6 +233,7 @@ impl<W: EthClient> EthWatch<W> {
             priority_queue,
             new_tokens,
             register_nft_factory_events,
+            updated_state.block_hashes().clone(),
         );
         self.set_new_state(new_state);
         Ok(())
@@ -245,6 +260,9 @@ impl<W: EthClient> EthWatch<W> {
         let previous_block_with_accepted_events =
             new_block_with_accepted_events.saturating_sub(unprocessed_blocks_amount);
 
+        // Block hashes are loaded before the events, so if a reorg happens in between,
+        // it will be detected during the next poll.
+        let block_hashes = self.load_block_hashes(current_ethereum_block).await?;
         let unconfirmed_queue = self.get_unconfirmed_ops(current_ethereum_block).await?;
         let priority_queue: HashMap<u64, _> = self
             .client
@@ -295,10 +313,108 @@ impl<W: EthClient> EthWatch<W> {
             priority_queue,
             new_tokens,
             new_register_nft_factory_events,
+            block_hashes,
         );
         Ok(state)
     }
 
+    /// Returns the hashes of the tracked blocks up to `current_ethereum_block`.
+    /// Only the hashes of the blocks not known yet are requested from the Ethereum node.
+    async fn load_block_hashes(
+        &self,
+        current_ethereum_block: u64,
+    ) -> anyhow::Result<BTreeMap<u64, H256>> {
+        let first_tracked_block =
+            current_ethereum_block.saturating_sub(self.reorg_tracking_depth) + 1;
+        let mut block_hashes: BTreeMap<_, _> = self
+            .eth_state
+            .block_hashes()
+            .range(first_tracked_block..)
+            .map(|(&block, &hash)| (block, hash))
+            .collect();
+
+        let first_unknown_block = block_hashes
+            .keys()
+            .next_back()
+            .map(|block| block + 1)
+            .unwrap_or(first_tracked_block);
+        for block in first_unknown_block..=current_ethereum_block {
+            let hash = self
+                .client
+                .block_hash(block)
+                .await?
+                .ok_or_else(|| anyhow::format_err!("Ethereum block {} is not found", block))?;
+            block_hashes.insert(block, hash);
+        }
+        Ok(block_hashes)
+    }
+
+    /// Checks whether the tracked blocks are still in the canonical chain.
+    /// Returns the number of the first block that has been reorganized.
+    async fn find_reorganized_block(&self) -> anyhow::Result<Option<u64>> {
+        let block_hashes = self.eth_state.block_hashes();
+        let (&last_block, last_hash) = match block_hashes.iter().next_back() {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+        // Every block commits to its parent, so it's enough to check the last one.
+        if self.client.block_hash(last_block).await?.as_ref() == Some(last_hash) {
+            return Ok(None);
+        }
+
+        let mut first_reorganized_block = last_block;
+        for (&block, hash) in block_hashes.iter().rev().skip(1) {
+            if self.client.block_hash(block).await?.as_ref() == Some(hash) {
+                break;
+            }
+            first_reorganized_block = block;
+        }
+        Ok(Some(first_reorganized_block))
+    }
+
+    /// Discards the data obtained from the reorganized blocks, so they will be processed again.
+    fn rollback_reorganized_blocks(&mut self, first_reorganized_block: u64) {
+        let last_ethereum_block = self.eth_state.last_ethereum_block();
+        let reorg_depth = last_ethereum_block.saturating_sub(first_reorganized_block) + 1;
+        let discarded_ops = self
+            .eth_state
+            .unconfirmed_queue()
+            .iter()
+            .filter(|op| op.eth_block >= first_reorganized_block)
+            .count();
+
+        metrics::increment_counter!("eth_watcher.reorgs");
+        metrics::histogram!("eth_watcher.reorg_depth", reorg_depth as f64);
+        let last_block_with_accepted_events =
+            last_ethereum_block.saturating_sub(self.number_of_confirmations_for_event);
+        if first_reorganized_block <= last_block_with_accepted_events
+            || reorg_depth >= self.reorg_tracking_depth
+        {
+            // Events from the reorganized blocks may have been already accepted.
+            // This can't be fixed automatically, so it requires the attention of the node administrators.
+            metrics::increment_counter!("eth_watcher.deep_reorgs");
+            vlog::error!(
+                "Ethereum reorg deeper than the number of confirmations: first_reorganized_block={}, \
+                 depth={}, confirmations={}",
+                first_reorganized_block,
+                reorg_depth,
+                self.number_of_confirmations_for_event
+            );
+        } else {
+            vlog::warn!(
+                "Ethereum reorg detected: first_reorganized_block={}, depth={}, discarded_unconfirmed_ops={}",
+                first_reorganized_block,
+                reorg_depth,
+                discarded_ops
+            );
+        }
+
+        let new_state = self
+            .eth_state
+            .rollback(first_reorganized_block.saturating_sub(1));
+        self.set_new_state(new_state);
+    }
+
     fn get_register_factory_event(
         &self,
         last_block_number: Option<u64>,
@@ -329,7 +445,11 @@ impl<W: EthClient> EthWatch<W> {
         new_tokens
     }
 
-    fn get_priority_requests(&self, first_serial_id: u64, max_chunks: usize) -> Vec<PriorityOp> {
+    fn get_priority_requests(
+        &mut self,
+        first_serial_id: u64,
+        max_chunks: usize,
+    ) -> Vec<(PriorityOp, vlog::Span)> {
         let mut result = Vec::new();
 
         let mut used_chunks = 0;
@@ -337,14 +457,41 @@ impl<W: EthClient> EthWatch<W> {
 
         while let Some(op) = self.eth_state.priority_queue().get(&current_priority_op) {
             if used_chunks + op.as_ref().data.chunks() <= max_chunks {
-                result.push(op.as_ref().clone());
-                used_chunks += op.as_ref().data.chunks();
+                let op = op.as_ref().clone();
+                used_chunks += op.data.chunks();
+                result.push((
+                    op,
+                    self.eth_state.take_priority_op_span(current_priority_op),
+                ));
                 current_priority_op += 1;
             } else {
                 break;
             }
         }
 
+        let pending_ops: Vec<_> = self
+            .eth_state
+            .priority_queue()
+            .iter()
+            .filter(|(serial_id, _)| **serial_id >= first_serial_id)
+            .map(|(_, op)| op.as_ref())
+            .collect();
+        metrics::gauge!("eth_watch.priority_ops_pending", pending_ops.len() as f64);
+        // Age of the oldest pending operation in Ethereum blocks.
+        let oldest_pending_op_age = pending_ops
+            .iter()
+            .map(|op| {
+                self.eth_state
+                    .last_ethereum_block()
+                    .saturating_sub(op.eth_block)
+            })
+            .max()
+            .unwrap_or(0);
+        metrics::gauge!(
+            "eth_watch.oldest_priority_op_pending_age",
+            oldest_pending_op_age as f64
+        );
+
         result
     }
 
@@ -484,6 +631,9 @@ impl<W: EthClient> EthWatch<W> {
         let start = Instant::now();
         let last_block_number = self.client.block_number().await?;
 
+        if let Some(first_reorganized_block) = self.find_reorganized_block().await? {
+            self.rollback_reorganized_blocks(first_reorganized_block);
+        }
         if last_block_number > self.eth_state.last_ethereum_block() {
             self.process_new_blocks(last_block_number).await?;
         }
@@ -662,7 +812,8 @@ pub fn start_eth_watch(
     let eth_watch = EthWatch::new(
         eth_client,
         config_options.eth_watch.confirmations_for_eth_event,
-    );
+    )
+    .with_reorg_tracking_depth(config_options.eth_watch.reorg_tracking_depth);
 
     tokio::spawn(eth_watch.run(eth_req_receiver));
 
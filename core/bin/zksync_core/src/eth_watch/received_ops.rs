@@ -26,19 +26,35 @@ pub const PRIORITY_OP_EXPIRATION: Duration =
 pub struct ReceivedPriorityOp {
     op: PriorityOp,
     received_at: Instant,
+    /// Span covering the time from receiving the operation until its execution.
+    /// It's taken once the operation is proposed for a block.
+    span: vlog::Span,
 }
 
 impl ReceivedPriorityOp {
     pub fn is_outdated(&self) -> bool {
         self.received_at.elapsed() >= PRIORITY_OP_EXPIRATION
     }
+
+    /// Takes the span of the operation, leaving a disabled one in its place.
+    pub fn take_span(&mut self) -> vlog::Span {
+        std::mem::replace(&mut self.span, vlog::Span::none())
+    }
 }
 
 impl From<PriorityOp> for ReceivedPriorityOp {
     fn from(op: PriorityOp) -> Self {
+        let span = vlog::info_span!(
+            parent: None,
+            "eth_watch.priority_op",
+            serial_id = op.serial_id,
+            eth_hash = %format!("{:#x}", op.eth_hash),
+            eth_block = op.eth_block
+        );
         Self {
             op,
             received_at: Instant::now(),
+            span,
         }
     }
 }
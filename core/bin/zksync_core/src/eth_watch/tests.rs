@@ -18,6 +18,8 @@ use tokio::sync::RwLock;
 struct FakeEthClientData {
     priority_ops: HashMap<u64, Vec<PriorityOp>>,
     last_block_number: u64,
+    /// Number of reorgs happened for every block.
+    block_reorgs: HashMap<u64, u64>,
 }
 
 impl FakeEthClientData {
@@ -25,9 +27,24 @@ impl FakeEthClientData {
         Self {
             priority_ops: Default::default(),
             last_block_number: 0,
+            block_reorgs: Default::default(),
         }
     }
 
+    fn block_hash(&self, number: u64) -> H256 {
+        let reorgs = self.block_reorgs.get(&number).copied().unwrap_or_default();
+        H256::from_low_u64_be((reorgs << 32) + number)
+    }
+
+    /// Replaces the blocks starting from `first_block` with the new ones containing `ops`.
+    fn reorg(&mut self, first_block: u64, ops: &[PriorityOp]) {
+        for number in first_block..=self.last_block_number {
+            self.priority_ops.remove(&number);
+            *self.block_reorgs.entry(number).or_default() += 1;
+        }
+        self.add_operations(ops);
+    }
+
     fn add_operations(&mut self, ops: &[PriorityOp]) {
         for op in ops {
             self.last_block_number = max(op.eth_block, self.last_block_number);
@@ -55,6 +72,10 @@ impl FakeEthClient {
         self.inner.write().await.add_operations(ops);
     }
 
+    async fn reorg(&mut self, first_block: u64, ops: &[PriorityOp]) {
+        self.inner.write().await.reorg(first_block, ops);
+    }
+
     async fn block_to_number(&self, block: &BlockNumber) -> u64 {
         match block {
             BlockNumber::Latest => self.inner.read().await.last_block_number,
@@ -104,6 +125,10 @@ impl EthClient for FakeEthClient {
         Ok(self.inner.read().await.last_block_number)
     }
 
+    async fn block_hash(&self, number: u64) -> anyhow::Result<Option<H256>> {
+        Ok(Some(self.inner.read().await.block_hash(number)))
+    }
+
     async fn get_auth_fact(
         &self,
         _address: Address,
@@ -429,3 +454,55 @@ async fn test_restore_and_poll_time_lag() {
     priority_queues.get(&0).unwrap();
     priority_queues.get(&1).unwrap();
 }
+
+/// Checks that the unconfirmed operations from the reorganized blocks are discarded.
+#[tokio::test]
+async fn test_reorg_detection() {
+    let deposit = |serial_id: u64, eth_hash: u8, eth_block: u64| PriorityOp {
+        serial_id,
+        data: ZkSyncPriorityOp::Deposit(Deposit {
+            from: Default::default(),
+            token: TokenId(0),
+            amount: Default::default(),
+            to: [2u8; 20].into(),
+        }),
+        deadline_block: 0,
+        eth_hash: [eth_hash; 32].into(),
+        eth_block,
+        eth_block_index: Some(1),
+    };
+
+    let mut client = FakeEthClient::new();
+    client
+        .add_operations(&[deposit(0, 1, 1), deposit(1, 2, 5), deposit(2, 3, 6)])
+        .await;
+
+    let mut watcher = EthWatch::new(client.clone(), 3).with_reorg_tracking_depth(5);
+    watcher.poll_eth_node().await.unwrap();
+    assert_eq!(watcher.eth_state.last_ethereum_block(), 6);
+    assert_eq!(watcher.eth_state.block_hashes().len(), 5);
+    assert_eq!(watcher.eth_state.unconfirmed_queue().len(), 2);
+    assert_eq!(watcher.find_reorganized_block().await.unwrap(), None);
+
+    // The last block is replaced, deposit with serial ID 2 is moved to the new block.
+    client.reorg(6, &[deposit(2, 4, 7)]).await;
+    assert_eq!(watcher.find_reorganized_block().await.unwrap(), Some(6));
+
+    watcher.poll_eth_node().await.unwrap();
+    assert_eq!(watcher.eth_state.last_ethereum_block(), 7);
+    assert_eq!(watcher.find_reorganized_block().await.unwrap(), None);
+    assert!(watcher
+        .find_ongoing_op_by_eth_hash(H256::from_slice(&[3u8; 32]))
+        .is_none());
+    let unconfirmed_queue = watcher.eth_state.unconfirmed_queue();
+    assert_eq!(unconfirmed_queue.len(), 2);
+    assert_eq!(unconfirmed_queue[0].serial_id, 1);
+    assert_eq!(unconfirmed_queue[1].eth_hash, H256::from_slice(&[4u8; 32]));
+    assert_eq!(watcher.eth_state.priority_queue().len(), 1);
+
+    // Rollback discards the unconfirmed operations from the reorganized blocks only.
+    let state = watcher.eth_state.rollback(6);
+    assert_eq!(state.last_ethereum_block(), 6);
+    assert_eq!(state.unconfirmed_queue().len(), 1);
+    assert_eq!(state.block_hashes().keys().next_back(), Some(&6));
+}
@@ -0,0 +1,151 @@
+//! Leader election between several instances of `zksync_core`.
+//!
+//! Only one instance may run the state keeper at a time, since the state keeper holds the pending block
+//! and the state in memory. The active instance holds the lease in the database and renews it periodically.
+//! Other instances wait as a hot standby: they keep the state in sync with the database, so once the lease
+//! is released (or expired because the leader has crashed), the standby instance takes over without
+//! restoring the whole state from scratch.
+
+// Built-in uses
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+// External uses
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+use zksync_storage::ConnectionPool;
+// Local uses
+use crate::state_keeper::ZkSyncStateInitParams;
+
+/// Name of the lease held by the active `zksync_core` instance.
+pub const CORE_LEASE_NAME: &str = "zksync_core";
+
+#[derive(Debug, Clone)]
+pub struct LeaderLease {
+    pool: ConnectionPool,
+    holder: String,
+    duration: Duration,
+}
+
+impl LeaderLease {
+    pub fn new(pool: ConnectionPool, duration: Duration) -> Self {
+        let hostname = std::env::var("HOSTNAME").unwrap_or_else(|_| "localhost".to_string());
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Incorrect system time")
+            .as_millis();
+        let holder = format!("{}-{}-{}", hostname, std::process::id(), started_at);
+
+        Self {
+            pool,
+            holder,
+            duration,
+        }
+    }
+
+    /// Identifier of this instance stored in the lease.
+    pub fn holder(&self) -> &str {
+        &self.holder
+    }
+
+    /// The lease is renewed a few times within its duration, so a single failed attempt doesn't lose it.
+    fn renew_interval(&self) -> Duration {
+        self.duration / 3
+    }
+
+    async fn try_acquire(&self) -> anyhow::Result<bool> {
+        let acquired = self
+            .pool
+            .access_storage()
+            .await?
+            .leader_lease_schema()
+            .acquire_lease(CORE_LEASE_NAME, &self.holder, self.duration)
+            .await?;
+        metrics::gauge!("leader_lease.is_leader", if acquired { 1.0 } else { 0.0 });
+        Ok(acquired)
+    }
+
+    /// Waits until the lease is acquired and returns the state to start the state keeper with.
+    /// Meanwhile, the state is kept in sync with the database.
+    pub async fn wait_for_leadership(&self) -> anyhow::Result<ZkSyncStateInitParams> {
+        let mut storage = self.pool.access_storage().await?;
+        let mut state = ZkSyncStateInitParams::restore_from_db(&mut storage).await?;
+        drop(storage);
+
+        let mut timer = time::interval(self.renew_interval());
+        let mut standby = false;
+        loop {
+            timer.tick().await;
+
+            let acquired = self.try_acquire().await?;
+            if !acquired && !standby {
+                vlog::info!(
+                    "Leader lease is held by another instance, {} is waiting as a hot standby",
+                    self.holder
+                );
+                standby = true;
+            }
+
+            // The leader stores the pending block before releasing the lease, so once the lease is acquired,
+            // the state in the database is final.
+            let mut storage = self.pool.access_storage().await?;
+            state.load_state_diff(&mut storage).await?;
+
+            if acquired {
+                vlog::info!(
+                    "Leader lease acquired by {}, last block number: {}",
+                    self.holder,
+                    *state.last_block_number
+                );
+                return Ok(state);
+            }
+        }
+    }
+
+    /// Renews the lease until the process is stopped. Panics if the lease is taken over by another instance
+    /// or can't be renewed before it expires, since two instances must never run the state keeper simultaneously.
+    pub fn run_renewal_task(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut timer = time::interval(self.renew_interval());
+            // The lease was acquired right before the task is started.
+            let mut last_renewed_at = Instant::now();
+            loop {
+                timer.tick().await;
+
+                // Expiration time is counted from the moment the query is sent, not received.
+                let attempt_started_at = Instant::now();
+                match self.try_acquire().await {
+                    Ok(true) => last_renewed_at = attempt_started_at,
+                    Ok(false) => panic!(
+                        "Leader lease of {} has been taken over by another instance",
+                        self.holder
+                    ),
+                    Err(err) => {
+                        vlog::error!("Failed to renew the leader lease: {}", err);
+                        metrics::increment_counter!("leader_lease.renewal_failed");
+                    }
+                }
+
+                // Stop before the lease expires, since the next attempt may come too late.
+                if last_renewed_at.elapsed() + self.renew_interval() >= self.duration {
+                    panic!(
+                        "Leader lease of {} has not been renewed for {:?}, stopping before it expires",
+                        self.holder,
+                        last_renewed_at.elapsed()
+                    );
+                }
+            }
+        })
+    }
+
+    /// Releases the lease, so a standby instance can take over right away.
+    pub async fn release(&self) -> anyhow::Result<()> {
+        self.pool
+            .access_storage()
+            .await?
+            .leader_lease_schema()
+            .release_lease(CORE_LEASE_NAME, &self.holder)
+            .await?;
+        metrics::gauge!("leader_lease.is_leader", 0.0);
+        vlog::info!("Leader lease released by {}", self.holder);
+        Ok(())
+    }
+}
@@ -7,28 +7,32 @@ use crate::{
     mempool::run_mempool_tasks,
     private_api::start_private_core_api,
     rejected_tx_cleaner::run_rejected_tx_cleaner,
-    state_keeper::{start_state_keeper, ZkSyncStateKeeper},
+    state_keeper::{start_state_keeper, SealPolicy, StateKeeperRequest, ZkSyncStateKeeper},
     token_handler::run_token_handler,
+    withdrawals_tracker::run_withdrawals_tracker,
 };
 use futures::{channel::mpsc, future};
+use num::BigUint;
 use tokio::task::JoinHandle;
 use zksync_config::ZkSyncConfig;
 use zksync_eth_client::EthereumGateway;
 use zksync_gateway_watcher::run_gateway_watcher_if_multiplexed;
 use zksync_storage::ConnectionPool;
-use zksync_types::{tokens::get_genesis_token_list, Token, TokenId, TokenKind};
+use zksync_types::{tokens::get_genesis_token_list, Address, Token, TokenId, TokenKind};
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
 
 pub mod block_proposer;
 pub mod committer;
 pub mod eth_watch;
+pub mod leader_lease;
 pub mod mempool;
 pub mod private_api;
 pub mod register_factory_handler;
 pub mod rejected_tx_cleaner;
 pub mod state_keeper;
 pub mod token_handler;
+pub mod withdrawals_tracker;
 
 /// Waits for any of the tokio tasks to be finished.
 /// Since the main tokio tasks are used as actors which should live as long
@@ -51,12 +55,22 @@ pub async fn wait_for_tasks(task_futures: Vec<JoinHandle<()>>) {
 
 /// Inserts the initial information about zkSync tokens into the database.
 pub async fn genesis_init(config: &ZkSyncConfig) {
+    genesis_init_with_balances(config, &[]).await
+}
+
+/// Same as `genesis_init`, but also creates accounts with the provided balances in the genesis block.
+/// Intended for the test networks, which have no deposits to fund the accounts.
+pub async fn genesis_init_with_balances(
+    config: &ZkSyncConfig,
+    balances: &[(Address, TokenId, BigUint)],
+) {
     let pool = ConnectionPool::new(Some(1));
 
     vlog::info!("Generating genesis block.");
-    ZkSyncStateKeeper::create_genesis_block(
+    ZkSyncStateKeeper::create_genesis_block_with_balances(
         pool.clone(),
         &config.chain.state_keeper.fee_account_addr,
+        balances,
     )
     .await;
     vlog::info!("Adding initial tokens to db");
@@ -93,6 +107,7 @@ pub async fn genesis_init(config: &ZkSyncConfig) {
 /// - mempool, module to organize incoming transactions.
 /// - block proposer, module to create block proposals for state keeper.
 /// - committer, module to store pending and completed blocks into the database.
+/// - withdrawals tracker, module to follow the finalization of withdrawals on L1.
 /// - private Core API server.
 pub async fn run_core(
     connection_pool: ConnectionPool,
@@ -100,6 +115,34 @@ pub async fn run_core(
     eth_gateway: EthereumGateway,
     config: &ZkSyncConfig,
 ) -> anyhow::Result<Vec<JoinHandle<()>>> {
+    let mut storage_processor = connection_pool.access_storage().await?;
+    let state_keeper_init = ZkSyncStateInitParams::restore_from_db(&mut storage_processor).await?;
+    drop(storage_processor);
+
+    let (task_futures, _) = run_core_from_state(
+        connection_pool,
+        panic_notify,
+        eth_gateway,
+        state_keeper_init,
+        None,
+        config,
+    )
+    .await?;
+    Ok(task_futures)
+}
+
+/// Same as `run_core`, but starts the state keeper from the provided state.
+/// If `lease_holder` is provided, blocks are committed only while it holds the leader lease.
+/// Returns the sender of the state keeper requests along with the tasks, so the state keeper
+/// can be stopped gracefully via `StateKeeperRequest::Shutdown`.
+pub async fn run_core_from_state(
+    connection_pool: ConnectionPool,
+    panic_notify: mpsc::Sender<bool>,
+    eth_gateway: EthereumGateway,
+    state_keeper_init: ZkSyncStateInitParams,
+    lease_holder: Option<String>,
+    config: &ZkSyncConfig,
+) -> anyhow::Result<(Vec<JoinHandle<()>>, mpsc::Sender<StateKeeperRequest>)> {
     let (proposed_blocks_sender, proposed_blocks_receiver) =
         mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
     let (state_keeper_req_sender, state_keeper_req_receiver) =
@@ -122,7 +165,6 @@ pub async fn run_core(
     let mut storage_processor = connection_pool.access_storage().await?;
 
     // Start State Keeper.
-    let state_keeper_init = ZkSyncStateInitParams::restore_from_db(&mut storage_processor).await?;
     let pending_block = state_keeper_init
         .get_pending_block(&mut storage_processor)
         .await;
@@ -132,10 +174,11 @@ pub async fn run_core(
         config.chain.state_keeper.fee_account_addr,
         state_keeper_req_receiver,
         proposed_blocks_sender,
-        config.chain.state_keeper.block_chunk_sizes.clone(),
+        config.chain.block_chunk_sizes_to_seal(),
         config.chain.state_keeper.miniblock_iterations as usize,
         config.chain.state_keeper.fast_block_miniblock_iterations as usize,
-    );
+    )
+    .with_seal_policy(SealPolicy::from_config(&config.chain.state_keeper));
     let state_keeper_task = start_state_keeper(state_keeper, pending_block);
 
     // Start committer.
@@ -143,6 +186,7 @@ pub async fn run_core(
         proposed_blocks_receiver,
         mempool_block_request_sender.clone(),
         connection_pool.clone(),
+        lease_holder,
         &config,
     );
 
@@ -175,6 +219,10 @@ pub async fn run_core(
     // Start rejected transactions cleaner task.
     let rejected_tx_cleaner_task = run_rejected_tx_cleaner(&config, connection_pool.clone());
 
+    // Start withdrawals tracker.
+    let withdrawals_tracker_task =
+        run_withdrawals_tracker(eth_gateway.clone(), connection_pool.clone(), &config);
+
     // Start block proposer.
     let proposer_task = run_block_proposer_task(
         &config,
@@ -199,11 +247,12 @@ pub async fn run_core(
         rejected_tx_cleaner_task,
         token_handler_task,
         register_factory_task,
+        withdrawals_tracker_task,
     ];
 
     if let Some(task) = gateway_watcher_task_opt {
         task_futures.push(task);
     }
 
-    Ok(task_futures)
+    Ok((task_futures, state_keeper_req_sender))
 }
@@ -1,11 +1,42 @@
-use futures::{channel::mpsc, executor::block_on, SinkExt, StreamExt};
-use std::cell::RefCell;
+use futures::{
+    channel::{mpsc, oneshot},
+    executor::block_on,
+    SinkExt, StreamExt,
+};
+use std::{cell::RefCell, time::Duration};
 use zksync_config::ZkSyncConfig;
-use zksync_core::{run_core, wait_for_tasks};
+use zksync_core::{
+    leader_lease::LeaderLease,
+    run_core_from_state,
+    state_keeper::{StateKeeperRequest, ZkSyncStateInitParams},
+    wait_for_tasks,
+};
 use zksync_eth_client::EthereumGateway;
 use zksync_prometheus_exporter::run_prometheus_exporter;
 use zksync_storage::ConnectionPool;
 
+/// Time given to the state keeper to store the pending block on shutdown.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Stops the state keeper after the current miniblock and waits for the pending block to be stored.
+async fn stop_state_keeper(mut state_keeper_req_sender: mpsc::Sender<StateKeeperRequest>) {
+    let (sender, receiver) = oneshot::channel();
+    if state_keeper_req_sender
+        .send(StateKeeperRequest::Shutdown(sender))
+        .await
+        .is_err()
+    {
+        vlog::warn!("State keeper is already stopped");
+        return;
+    }
+
+    match tokio::time::timeout(SHUTDOWN_TIMEOUT, receiver).await {
+        Ok(Ok(())) => vlog::info!("Pending block is stored"),
+        Ok(Err(_)) => vlog::warn!("State keeper stopped without storing the pending block"),
+        Err(_) => vlog::warn!("Timeout while waiting for the pending block to be stored"),
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let _sentry_guard = vlog::init();
@@ -28,9 +59,43 @@ async fn main() -> anyhow::Result<()> {
     let (prometheus_task_handle, counter_task_handle) =
         run_prometheus_exporter(connection_pool.clone(), config.api.prometheus.port, true);
 
-    let task_handles = run_core(connection_pool, stop_signal_sender, eth_gateway, &config)
-        .await
-        .expect("Unable to start Core actors");
+    // Wait as a hot standby until this instance becomes the leader.
+    let leader_lease = config
+        .chain
+        .state_keeper
+        .leader_lease_duration()
+        .map(|duration| LeaderLease::new(connection_pool.clone(), duration));
+    let state_keeper_init = if let Some(leader_lease) = &leader_lease {
+        tokio::select! {
+            state = leader_lease.wait_for_leadership() => {
+                state.expect("Unable to acquire the leader lease")
+            },
+            _ = stop_signal_receiver.next() => {
+                vlog::warn!("Stop signal received, shutting down");
+                vlog::shutdown_exporter().await;
+                return Ok(());
+            }
+        }
+    } else {
+        let mut storage = connection_pool.access_storage().await?;
+        ZkSyncStateInitParams::restore_from_db(&mut storage).await?
+    };
+
+    let (mut task_handles, state_keeper_req_sender) = run_core_from_state(
+        connection_pool,
+        stop_signal_sender,
+        eth_gateway,
+        state_keeper_init,
+        leader_lease
+            .as_ref()
+            .map(|leader_lease| leader_lease.holder().to_string()),
+        &config,
+    )
+    .await
+    .expect("Unable to start Core actors");
+    if let Some(leader_lease) = &leader_lease {
+        task_handles.push(leader_lease.clone().run_renewal_task());
+    }
 
     tokio::select! {
         _ = async { wait_for_tasks(task_handles).await } => {
@@ -47,5 +112,13 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
+    stop_state_keeper(state_keeper_req_sender).await;
+    if let Some(leader_lease) = &leader_lease {
+        if let Err(err) = leader_lease.release().await {
+            vlog::warn!("Failed to release the leader lease: {}", err);
+        }
+    }
+    vlog::shutdown_exporter().await;
+
     Ok(())
 }
@@ -1,18 +1,30 @@
 use std::cmp::Ordering;
-use std::collections::{BinaryHeap, VecDeque};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::time::Instant;
 use zksync_types::mempool::{RevertedTxVariant, SignedTxVariant};
 
+/// Transaction waiting in the queue. The data tracked for the transaction is stored along with it,
+/// so it leaves the queue together with the transaction.
+#[derive(Debug, Clone)]
+pub struct QueuedTx {
+    pub tx: SignedTxVariant,
+    /// Time when the transaction was added to the queue.
+    pub received_at: Instant,
+    /// Span of the transaction stay in the queue.
+    pub span: vlog::Span,
+}
+
 #[derive(Debug, Clone)]
 struct MempoolPendingTransaction {
     valid_from: u64,
-    tx: SignedTxVariant,
+    tx: QueuedTx,
 }
 
 impl Eq for MempoolPendingTransaction {}
 
 impl PartialEq for MempoolPendingTransaction {
     fn eq(&self, other: &Self) -> bool {
-        self.tx.hashes() == other.tx.hashes()
+        self.tx.tx.hashes() == other.tx.tx.hashes()
     }
 }
 
@@ -22,7 +34,7 @@ impl Ord for MempoolPendingTransaction {
         other
             .valid_from
             .cmp(&self.valid_from)
-            .then_with(|| self.tx.hashes().cmp(&other.tx.hashes()))
+            .then_with(|| self.tx.tx.hashes().cmp(&other.tx.tx.hashes()))
     }
 }
 
@@ -41,18 +53,43 @@ pub struct MempoolTransactionsQueue {
     /// The queue is only accessible for popping elements.
     reverted_txs: VecDeque<RevertedTxVariant>,
     /// Transactions ready for execution.
-    ready_txs: VecDeque<SignedTxVariant>,
+    ready_txs: VecDeque<QueuedTx>,
     /// Transactions that are not ready yet because of the `valid_from` field.
     pending_txs: BinaryHeap<MempoolPendingTransaction>,
+    /// Amount of transactions in the queue for each transaction type.
+    /// Types are never removed from the map, so their counters can drop to zero.
+    size_by_type: HashMap<String, usize>,
 }
 
 impl MempoolTransactionsQueue {
     pub fn new(reverted_txs: VecDeque<RevertedTxVariant>) -> Self {
-        Self {
-            reverted_txs,
+        let mut queue = Self {
+            reverted_txs: VecDeque::new(),
             ready_txs: VecDeque::new(),
             pending_txs: BinaryHeap::new(),
+            size_by_type: HashMap::new(),
+        };
+        for reverted_tx in &reverted_txs {
+            queue.track_size(reverted_tx.as_ref(), true);
         }
+        queue.reverted_txs = reverted_txs;
+        queue
+    }
+
+    fn track_size(&mut self, tx: &SignedTxVariant, added: bool) {
+        for tx in tx.get_transactions() {
+            let size = self.size_by_type.entry(tx.tx.variance_name()).or_default();
+            if added {
+                *size += 1;
+            } else {
+                *size = size.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Returns the amount of transactions in the queue for each transaction type.
+    pub fn size_by_type(&self) -> &HashMap<String, usize> {
+        &self.size_by_type
     }
 
     /// Returns a reference to the front element of the reverted queue, or `None`
@@ -64,18 +101,26 @@ impl MempoolTransactionsQueue {
     /// Removes the first element from the reverted queue and returns it , or `None`
     /// if the queue is empty.
     pub fn reverted_queue_pop_front(&mut self) -> Option<RevertedTxVariant> {
-        self.reverted_txs.pop_front()
+        let reverted_tx = self.reverted_txs.pop_front()?;
+        self.track_size(reverted_tx.as_ref(), false);
+        Some(reverted_tx)
     }
 
-    pub fn pop_front(&mut self) -> Option<SignedTxVariant> {
-        self.ready_txs.pop_front()
+    pub fn pop_front(&mut self) -> Option<QueuedTx> {
+        let tx = self.ready_txs.pop_front()?;
+        self.track_size(&tx.tx, false);
+        Some(tx)
     }
 
-    pub fn push_front(&mut self, tx: SignedTxVariant) {
+    pub fn push_front(&mut self, tx: QueuedTx) {
+        self.track_size(&tx.tx, true);
         self.ready_txs.push_front(tx);
     }
 
-    pub fn add_tx_variant(&mut self, tx: SignedTxVariant) {
+    /// Adds the transaction to the queue. The span is kept until the transaction is taken
+    /// from the queue.
+    pub fn add_tx_variant(&mut self, tx: SignedTxVariant, span: vlog::Span) {
+        self.track_size(&tx, true);
         self.pending_txs.push(MempoolPendingTransaction {
             valid_from: tx
                 .get_transactions()
@@ -83,7 +128,11 @@ impl MempoolTransactionsQueue {
                 .map(|tx| tx.tx.valid_from())
                 .max()
                 .unwrap_or(0),
-            tx,
+            tx: QueuedTx {
+                tx,
+                received_at: Instant::now(),
+                span,
+            },
         });
     }
 
@@ -103,7 +152,7 @@ impl MempoolTransactionsQueue {
 
             // Now transactions should be sorted by the nonce (transaction natural order)
             // According to our convention in batch `fee transaction` would be the last one, so we would use nonce from it as a key for sort
-            ready_pending_transactions.sort_by_key(|tx| match tx {
+            ready_pending_transactions.sort_by_key(|tx| match &tx.tx {
                 SignedTxVariant::Tx(tx) => tx.tx.nonce(),
                 SignedTxVariant::Batch(batch) => batch
                     .txs
@@ -113,7 +162,7 @@ impl MempoolTransactionsQueue {
                     .nonce(),
             });
 
-            VecDeque::<SignedTxVariant>::from(ready_pending_transactions)
+            VecDeque::<QueuedTx>::from(ready_pending_transactions)
         };
 
         self.ready_txs.append(&mut ready_pending_transactions);
@@ -167,11 +216,7 @@ mod tests {
 
     #[test]
     fn test_mempool_transactions_queue() {
-        let mut transactions_queue = MempoolTransactionsQueue {
-            reverted_txs: VecDeque::new(),
-            ready_txs: VecDeque::new(),
-            pending_txs: BinaryHeap::new(),
-        };
+        let mut transactions_queue = MempoolTransactionsQueue::new(VecDeque::new());
 
         let withdraw0 = get_withdraw();
         let transfer1 = get_transfer_with_timestamps(5, 13);
@@ -179,12 +224,12 @@ mod tests {
 
         // Insert transactions to the mempool transcations queue
         {
-            transactions_queue.add_tx_variant(withdraw0.clone());
+            transactions_queue.add_tx_variant(withdraw0.clone(), vlog::Span::none());
             assert_eq!(transactions_queue.pending_txs.peek().unwrap().valid_from, 0);
 
             // Some "random" order for trancsactions
-            transactions_queue.add_tx_variant(transfer2.clone());
-            transactions_queue.add_tx_variant(transfer1.clone());
+            transactions_queue.add_tx_variant(transfer2.clone(), vlog::Span::none());
+            transactions_queue.add_tx_variant(transfer1.clone(), vlog::Span::none());
         }
 
         // At first we should have only one transaction ready
@@ -192,7 +237,10 @@ mod tests {
             transactions_queue.prepare_new_ready_transactions(3);
 
             assert_eq!(transactions_queue.ready_txs.len(), 1);
-            assert_eq!(transactions_queue.ready_txs[0].hashes(), withdraw0.hashes());
+            assert_eq!(
+                transactions_queue.ready_txs[0].tx.hashes(),
+                withdraw0.hashes()
+            );
         }
 
         // One more transaction is ready
@@ -200,7 +248,10 @@ mod tests {
             transactions_queue.prepare_new_ready_transactions(9);
 
             assert_eq!(transactions_queue.ready_txs.len(), 2);
-            assert_eq!(transactions_queue.ready_txs[1].hashes(), transfer1.hashes());
+            assert_eq!(
+                transactions_queue.ready_txs[1].tx.hashes(),
+                transfer1.hashes()
+            );
         }
 
         // The last one is ready
@@ -208,7 +259,20 @@ mod tests {
             transactions_queue.prepare_new_ready_transactions(10);
 
             assert_eq!(transactions_queue.ready_txs.len(), 3);
-            assert_eq!(transactions_queue.ready_txs[2].hashes(), transfer2.hashes());
+            assert_eq!(
+                transactions_queue.ready_txs[2].tx.hashes(),
+                transfer2.hashes()
+            );
+        }
+
+        // Moving transactions between the internal queues doesn't affect the size
+        assert_eq!(transactions_queue.size_by_type()["Transfer"], 2);
+        assert_eq!(transactions_queue.size_by_type()["Withdraw"], 1);
+
+        {
+            let tx = transactions_queue.pop_front().unwrap();
+            assert_eq!(transactions_queue.size_by_type()["Withdraw"], 0);
+            assert_eq!(tx.tx.hashes(), withdraw0.hashes());
         }
     }
 }
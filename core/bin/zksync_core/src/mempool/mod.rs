@@ -18,10 +18,7 @@
 use std::{collections::HashMap, sync::Arc};
 // External uses
 use futures::{
-    channel::{
-        mpsc::{self, Receiver},
-        oneshot,
-    },
+    channel::{mpsc, oneshot},
     SinkExt, StreamExt,
 };
 
@@ -29,9 +26,12 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
+use vlog::Instrument;
 
 // Workspace uses
-use zksync_balancer::{Balancer, BuildBalancedItem};
+use zksync_balancer::{
+    balancing_key, BalancedReceiver, BalancedRequest, Balancer, BalancingPolicy, BuildBalancedItem,
+};
 use zksync_config::ZkSyncConfig;
 use zksync_storage::ConnectionPool;
 use zksync_types::{
@@ -42,7 +42,7 @@ use zksync_types::{
 };
 
 // Local uses
-use crate::mempool::mempool_transactions_queue::MempoolTransactionsQueue;
+use crate::mempool::mempool_transactions_queue::{MempoolTransactionsQueue, QueuedTx};
 use crate::{eth_watch::EthWatchRequest, wait_for_tasks};
 
 mod mempool_transactions_queue;
@@ -93,6 +93,9 @@ pub enum TxAddError {
 pub struct ProposedBlock {
     pub priority_ops: Vec<PriorityOp>,
     pub txs: Vec<SignedTxVariant>,
+    /// Spans of the proposed operations waiting for execution, the spans of the block execution
+    /// follow from them.
+    pub spans: Vec<vlog::Span>,
 }
 
 impl ProposedBlock {
@@ -117,7 +120,12 @@ pub enum MempoolTransactionRequest {
     /// Add new transaction to mempool, transaction should be previously checked
     /// for correctness (including its Ethereum and ZKSync signatures).
     /// oneshot is used to receive tx add result.
-    NewTx(Box<SignedZkSyncTx>, oneshot::Sender<Result<(), TxAddError>>),
+    /// Span of the request is passed, so the transaction processing is traced as a part of it.
+    NewTx(
+        Box<SignedZkSyncTx>,
+        vlog::Span,
+        oneshot::Sender<Result<(), TxAddError>>,
+    ),
     /// Add a new batch of transactions to the mempool. All transactions in batch must
     /// be either executed successfully, or otherwise fail all together.
     /// Invariants for each individual transaction in the batch are the same as in
@@ -125,10 +133,23 @@ pub enum MempoolTransactionRequest {
     NewTxsBatch(
         Vec<SignedZkSyncTx>,
         Vec<TxEthSignature>,
+        vlog::Span,
         oneshot::Sender<Result<(), TxAddError>>,
     ),
 }
 
+impl BalancedRequest for MempoolTransactionRequest {
+    // Transactions of the same account are added to the mempool in the order they were received.
+    fn balancing_key(&self) -> Option<u64> {
+        match self {
+            MempoolTransactionRequest::NewTx(tx, _, _) => Some(balancing_key(&tx.account())),
+            MempoolTransactionRequest::NewTxsBatch(txs, _, _, _) => {
+                txs.first().map(|tx| balancing_key(&tx.account()))
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum MempoolBlocksRequest {
     /// When block is committed, nonces of the account tree should be updated too.
@@ -214,7 +235,7 @@ impl MempoolState {
         let mut transactions_queue = MempoolTransactionsQueue::new(reverted_txs);
 
         for tx in mempool_txs {
-            transactions_queue.add_tx_variant(tx);
+            transactions_queue.add_tx_variant(tx, vlog::Span::none());
         }
 
         transaction
@@ -239,14 +260,22 @@ impl MempoolState {
     }
 
     fn add_tx(&mut self, tx: SignedZkSyncTx) {
-        self.transactions_queue.add_tx_variant(tx.into());
+        let span = vlog::info_span!(parent: None, "mempool.queued_tx", tx_hash = %tx.hash());
+        span.follows_from(&vlog::Span::current());
+        self.transactions_queue.add_tx_variant(tx.into(), span);
     }
 
     fn add_batch(&mut self, batch: SignedTxsBatch) {
         assert_ne!(batch.batch_id, 0, "Batch ID was not set");
 
+        let span = vlog::info_span!(
+            parent: None,
+            "mempool.queued_batch",
+            batch_id = batch.batch_id
+        );
+        span.follows_from(&vlog::Span::current());
         self.transactions_queue
-            .add_tx_variant(SignedTxVariant::Batch(batch));
+            .add_tx_variant(SignedTxVariant::Batch(batch), span);
     }
 }
 
@@ -338,16 +367,32 @@ impl MempoolBlocksHandler {
                 "Proposing new block with reverted operations, chunks used: {}",
                 self.max_block_size_chunks - chunks_left
             );
+            self.report_queue_metrics().await;
             return reverted_block;
         }
 
         let (chunks_left, priority_ops) = self
             .select_priority_ops(current_unprocessed_priority_op)
             .await;
-        let (_chunks_left, txs) = self
+        let (priority_ops, mut spans): (Vec<_>, Vec<_>) = priority_ops.into_iter().unzip();
+        let (_chunks_left, queued_txs) = self
             .prepare_tx_for_block(chunks_left, block_timestamp)
             .await;
 
+        let mut txs = Vec::with_capacity(queued_txs.len());
+        for queued_tx in queued_txs {
+            // Transactions restored from the database are tracked since the server start.
+            for tx in queued_tx.tx.get_transactions() {
+                metrics::histogram!(
+                    "mempool.tx_inclusion_time",
+                    queued_tx.received_at.elapsed(),
+                    "name" => tx.tx.variance_name()
+                );
+            }
+            spans.push(queued_tx.span);
+            txs.push(queued_tx.tx);
+        }
+
         if !priority_ops.is_empty() {
             vlog::debug!("Proposed priority ops for block: {:?}", priority_ops);
         }
@@ -373,14 +418,27 @@ impl MempoolBlocksHandler {
                 metrics::increment_counter!("mempool.transactions_count", &labels)
             }
         }
-        ProposedBlock { priority_ops, txs }
+        self.report_queue_metrics().await;
+        ProposedBlock {
+            priority_ops,
+            txs,
+            spans,
+        }
+    }
+
+    /// Reports the amount of transactions left in the queue.
+    async fn report_queue_metrics(&self) {
+        let mempool_state = self.mempool_state.read().await;
+        for (name, size) in mempool_state.transactions_queue.size_by_type() {
+            metrics::gauge!("mempool.queue_size", *size as f64, "name" => name.clone());
+        }
     }
 
     /// Returns: chunks left from max amount of chunks, ops selected
     async fn select_priority_ops(
         &self,
         current_unprocessed_priority_op: u64,
-    ) -> (usize, Vec<PriorityOp>) {
+    ) -> (usize, Vec<(PriorityOp, vlog::Span)>) {
         let (sender, receiver) = oneshot::channel();
         self.eth_watch_req
             .clone()
@@ -398,7 +456,7 @@ impl MempoolBlocksHandler {
             self.max_block_size_chunks
                 - priority_ops
                     .iter()
-                    .map(|op| op.data.chunks())
+                    .map(|(op, _)| op.data.chunks())
                     .sum::<usize>(),
             priority_ops,
         )
@@ -408,7 +466,7 @@ impl MempoolBlocksHandler {
         &mut self,
         mut chunks_left: usize,
         block_timestamp: u64,
-    ) -> (usize, Vec<SignedTxVariant>) {
+    ) -> (usize, Vec<QueuedTx>) {
         let mut mempool_state = self.mempool_state.write().await;
 
         mempool_state
@@ -418,7 +476,7 @@ impl MempoolBlocksHandler {
         let mut txs_for_commit = Vec::new();
 
         while let Some(tx) = mempool_state.transactions_queue.pop_front() {
-            let chunks_for_tx = mempool_state.required_chunks(&tx);
+            let chunks_for_tx = mempool_state.required_chunks(&tx.tx);
             if chunks_left >= chunks_for_tx {
                 txs_for_commit.push(tx);
                 chunks_left -= chunks_for_tx;
@@ -438,8 +496,13 @@ impl MempoolBlocksHandler {
             match request {
                 MempoolBlocksRequest::GetBlock(block) => {
                     // Generate proposed block.
+                    let span = vlog::info_span!(
+                        "mempool.propose_block",
+                        last_priority_op_number = block.last_priority_op_number
+                    );
                     let proposed_block = self
                         .propose_new_block(block.last_priority_op_number, block.block_timestamp)
+                        .instrument(span)
                         .await;
 
                     // Send the proposed block to the request initiator.
@@ -516,7 +579,7 @@ impl MempoolBlocksHandler {
 struct MempoolTransactionsHandler {
     db_pool: ConnectionPool,
     mempool_state: Arc<RwLock<MempoolState>>,
-    requests: mpsc::Receiver<MempoolTransactionRequest>,
+    requests: BalancedReceiver<MempoolTransactionRequest>,
     max_block_size_chunks: usize,
 }
 
@@ -531,7 +594,7 @@ impl BuildBalancedItem<MempoolTransactionRequest, MempoolTransactionsHandler>
 {
     fn build_with_receiver(
         &self,
-        receiver: Receiver<MempoolTransactionRequest>,
+        receiver: BalancedReceiver<MempoolTransactionRequest>,
     ) -> MempoolTransactionsHandler {
         MempoolTransactionsHandler {
             db_pool: self.db_pool.clone(),
@@ -617,12 +680,14 @@ impl MempoolTransactionsHandler {
         vlog::info!("Transaction mempool handler is running");
         while let Some(request) = self.requests.next().await {
             match request {
-                MempoolTransactionRequest::NewTx(tx, resp) => {
-                    let tx_add_result = self.add_tx(*tx).await;
+                MempoolTransactionRequest::NewTx(tx, span, resp) => {
+                    let span = vlog::info_span!(parent: &span, "mempool.add_tx");
+                    let tx_add_result = self.add_tx(*tx).instrument(span).await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
-                MempoolTransactionRequest::NewTxsBatch(txs, eth_signatures, resp) => {
-                    let tx_add_result = self.add_batch(txs, eth_signatures).await;
+                MempoolTransactionRequest::NewTxsBatch(txs, eth_signatures, span, resp) => {
+                    let span = vlog::info_span!(parent: &span, "mempool.add_batch");
+                    let tx_add_result = self.add_batch(txs, eth_signatures).instrument(span).await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
             }
@@ -661,6 +726,9 @@ pub fn run_mempool_tasks(
             number_of_mempool_transaction_handlers,
             channel_capacity,
         );
+        let balancer = balancer
+            .with_policy(BalancingPolicy::KeyAffinity)
+            .with_name("mempool");
 
         for item in handlers.into_iter() {
             tasks.push(tokio::spawn(item.run()));
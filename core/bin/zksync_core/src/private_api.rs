@@ -9,13 +9,14 @@
 
 use crate::{eth_watch::EthWatchRequest, mempool::MempoolTransactionRequest};
 use actix_web::error::InternalError;
-use actix_web::{web, App, HttpResponse, HttpServer};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
 use futures::{
     channel::{mpsc, oneshot},
     sink::SinkExt,
 };
 use serde::Deserialize;
 use std::{str::FromStr, thread};
+use vlog::Instrument;
 use zksync_api_types::{
     v02::pagination::{ApiEither, PaginationDirection, PaginationQuery, PendingOpsRequest},
     PriorityOpLookupQuery,
@@ -30,24 +31,42 @@ struct AppState {
     eth_watch_req_sender: mpsc::Sender<EthWatchRequest>,
 }
 
+/// Continues the trace of the API server request, which context is passed in the request headers.
+fn set_remote_parent(span: &vlog::Span, request: &HttpRequest) {
+    let headers = request
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)));
+    vlog::set_remote_parent(span, headers);
+}
+
 /// Adds a new transaction into the mempool.
 /// Returns a JSON representation of `Result<(), TxAddError>`.
 /// Expects transaction to be checked on the API side.
 #[actix_web::post("/new_tx")]
 async fn new_tx(
+    request: HttpRequest,
     data: web::Data<AppState>,
     web::Json(tx): web::Json<SignedZkSyncTx>,
 ) -> actix_web::Result<HttpResponse> {
+    let span = vlog::info_span!("private_api.new_tx", tx_hash = %tx.hash().to_string());
+    set_remote_parent(&span, &request);
+
     let (sender, receiver) = oneshot::channel();
-    let item = MempoolTransactionRequest::NewTx(Box::new(tx), sender);
+    let item = MempoolTransactionRequest::NewTx(Box::new(tx), span.clone(), sender);
     let mut mempool_sender = data.mempool_tx_sender.clone();
-    mempool_sender.send(item).await.map_err(|err| {
-        InternalError::from_response(err, HttpResponse::InternalServerError().finish())
-    })?;
-
-    let response = receiver.await.map_err(|err| {
-        InternalError::from_response(err, HttpResponse::InternalServerError().finish())
-    })?;
+    let response = async move {
+        mempool_sender.send(item).await.map_err(|err| {
+            InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+        })?;
+
+        let response = receiver.await.map_err(|err| {
+            InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+        })?;
+        Ok::<_, actix_web::Error>(response)
+    }
+    .instrument(span)
+    .await?;
 
     Ok(HttpResponse::Ok().json(response))
 }
@@ -57,19 +76,28 @@ async fn new_tx(
 /// Expects transaction to be checked on the API side.
 #[actix_web::post("/new_txs_batch")]
 async fn new_txs_batch(
+    request: HttpRequest,
     data: web::Data<AppState>,
     web::Json((txs, eth_signatures)): web::Json<(Vec<SignedZkSyncTx>, Vec<TxEthSignature>)>,
 ) -> actix_web::Result<HttpResponse> {
+    let span = vlog::info_span!("private_api.new_txs_batch", batch_size = txs.len());
+    set_remote_parent(&span, &request);
+
     let (sender, receiver) = oneshot::channel();
-    let item = MempoolTransactionRequest::NewTxsBatch(txs, eth_signatures, sender);
+    let item = MempoolTransactionRequest::NewTxsBatch(txs, eth_signatures, span.clone(), sender);
     let mut mempool_sender = data.mempool_tx_sender.clone();
-    mempool_sender.send(item).await.map_err(|err| {
-        InternalError::from_response(err, HttpResponse::InternalServerError().finish())
-    })?;
-
-    let response = receiver.await.map_err(|err| {
-        InternalError::from_response(err, HttpResponse::InternalServerError().finish())
-    })?;
+    let response = async move {
+        mempool_sender.send(item).await.map_err(|err| {
+            InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+        })?;
+
+        let response = receiver.await.map_err(|err| {
+            InternalError::from_response(err, HttpResponse::InternalServerError().finish())
+        })?;
+        Ok::<_, actix_web::Error>(response)
+    }
+    .instrument(span)
+    .await?;
 
     Ok(HttpResponse::Ok().json(response))
 }
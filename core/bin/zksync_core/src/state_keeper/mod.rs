@@ -9,6 +9,7 @@ use futures::{
 };
 use itertools::Itertools;
 use tokio::task::JoinHandle;
+use vlog::Instrument;
 // Workspace uses
 use zksync_crypto::{
     ff::{self, PrimeField, PrimeFieldRepr},
@@ -34,6 +35,9 @@ use crate::{
 };
 use zksync_state::error::{OpError, TxBatchError};
 
+pub use self::seal_policy::SealPolicy;
+
+mod seal_policy;
 #[cfg(test)]
 mod tests;
 
@@ -42,6 +46,42 @@ pub enum ExecutedOpId {
     PriorityOp(u64),
 }
 
+/// Rule that caused the pending block to be sealed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SealReason {
+    /// There are no chunks left in the block, or the next operation does not fit into it.
+    BlockFull,
+    /// Adding the next operation would exceed the gas limit of the block.
+    GasLimit,
+    /// The block has reached the maximum number of miniblock iterations.
+    Timeout,
+    /// The block contains fast withdrawals and has reached the reduced number of miniblock iterations.
+    FastProcessing,
+    /// The block was sealed on an explicit request.
+    Requested,
+    /// The block has reached the maximum age.
+    MaxBlockAge,
+    /// Adding the next operation would exceed the maximum number of withdrawals in the block.
+    WithdrawalsLimit,
+    /// Adding the next operation would exceed the gas budget of the block.
+    GasBudget,
+}
+
+impl SealReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BlockFull => "block_full",
+            Self::GasLimit => "gas_limit",
+            Self::Timeout => "timeout",
+            Self::FastProcessing => "fast_processing",
+            Self::Requested => "requested",
+            Self::MaxBlockAge => "max_block_age",
+            Self::WithdrawalsLimit => "withdrawals_limit",
+            Self::GasBudget => "gas_budget",
+        }
+    }
+}
+
 pub enum StateKeeperRequest {
     GetAccount(Address, oneshot::Sender<Option<(AccountId, Account)>>),
     GetPendingBlockTimestamp(oneshot::Sender<u64>),
@@ -49,6 +89,9 @@ pub enum StateKeeperRequest {
     ExecuteMiniBlock(ProposedBlock),
     SealBlock,
     GetCurrentState(oneshot::Sender<ZkSyncStateInitParams>),
+    /// Persists the pending block and stops the state keeper. The sender is notified once
+    /// the pending block is stored in the database.
+    Shutdown(oneshot::Sender<()>),
 }
 
 #[derive(Debug, Clone)]
@@ -61,6 +104,8 @@ struct PendingBlock {
     unprocessed_priority_op_before: u64,
     pending_block_iteration: usize,
     gas_counter: GasCounter,
+    /// Number of the operations in the block which require the withdrawal on L1.
+    withdrawals: usize,
     /// Option denoting if this block should be generated faster than usual.
     fast_processing_required: bool,
     /// Fee should be applied only when sealing the block (because of corresponding logic in the circuit)
@@ -69,6 +114,8 @@ struct PendingBlock {
     stored_account_updates: usize,
     previous_block_root_hash: H256,
     timestamp: u64,
+    /// Time when the first successful operation was added to the block, the block age is measured from it.
+    first_operation_timestamp: Option<u64>,
 }
 
 impl PendingBlock {
@@ -92,13 +139,21 @@ impl PendingBlock {
             unprocessed_priority_op_before,
             pending_block_iteration: 0,
             gas_counter: GasCounter::new(),
+            withdrawals: 0,
             fast_processing_required: false,
             collected_fees: Vec::new(),
             stored_account_updates: 0,
             previous_block_root_hash,
             timestamp,
+            first_operation_timestamp: None,
         }
     }
+
+    fn add_success_operation(&mut self, operation: ExecutedOperations) {
+        self.first_operation_timestamp
+            .get_or_insert_with(system_time_timestamp);
+        self.success_operations.push(operation);
+    }
 }
 
 pub fn system_time_timestamp() -> u64 {
@@ -122,8 +177,7 @@ pub struct ZkSyncStateKeeper {
     tx_for_commitments: mpsc::Sender<CommitRequest>,
 
     available_block_chunk_sizes: Vec<usize>,
-    max_miniblock_iterations: usize,
-    fast_miniblock_iterations: usize,
+    seal_policy: SealPolicy,
 
     // Two fields below are for optimization: we don't want to overwrite all the block contents over and over.
     // With these fields we'll be able save the diff between two pending block states only.
@@ -336,6 +390,7 @@ impl ZkSyncStateInitParams {
             }
             self.unprocessed_priority_op =
                 Self::unprocessed_priority_op_id(storage, block_number).await?;
+            self.nfts = Self::load_nft_tokens(storage, block_number).await?;
             self.last_block_number = block_number;
         }
         Ok(())
@@ -442,8 +497,7 @@ impl ZkSyncStateKeeper {
                 system_time_timestamp(),
             ),
             available_block_chunk_sizes,
-            max_miniblock_iterations,
-            fast_miniblock_iterations,
+            seal_policy: SealPolicy::new(max_miniblock_iterations, fast_miniblock_iterations),
 
             success_txs_pending_len: 0,
             failed_txs_pending_len: 0,
@@ -455,6 +509,12 @@ impl ZkSyncStateKeeper {
         keeper
     }
 
+    /// Replaces the default policy which seals the block only by the miniblock iterations limit.
+    pub fn with_seal_policy(mut self, seal_policy: SealPolicy) -> Self {
+        self.seal_policy = seal_policy;
+        self
+    }
+
     pub async fn initialize(&mut self, pending_block: Option<SendablePendingBlock>) {
         let start = Instant::now();
         if let Some(pending_block) = pending_block {
@@ -466,6 +526,11 @@ impl ZkSyncStateKeeper {
             // `apply_txs_batch` to preserve the original execution order. Otherwise there may
             // be a state corruption, if e.g. `Deposit` will be executed before `TransferToNew`
             // and account IDs will change.
+            //
+            // The limits of the seal policy may have been changed since the operations were executed,
+            // so they are not applied to the restored operations.
+            let seal_policy = self.seal_policy.without_content_limits();
+            let seal_policy = std::mem::replace(&mut self.seal_policy, seal_policy);
             let mut txs_count = 0;
             let mut priority_op_count = 0;
             for operation in pending_block.success_operations {
@@ -482,6 +547,7 @@ impl ZkSyncStateKeeper {
                     }
                 }
             }
+            self.seal_policy = seal_policy;
             self.pending_block.stored_account_updates = self.pending_block.account_updates.len();
 
             vlog::info!(
@@ -492,6 +558,10 @@ impl ZkSyncStateKeeper {
             );
             self.pending_block.failed_txs = pending_block.failed_txs;
             self.pending_block.timestamp = pending_block.timestamp;
+            // The restored operations were added before the restart, when the block timestamp was set.
+            if !self.pending_block.success_operations.is_empty() {
+                self.pending_block.first_operation_timestamp = Some(pending_block.timestamp);
+            }
         } else {
             vlog::info!("There is no pending block to restore");
         }
@@ -500,6 +570,16 @@ impl ZkSyncStateKeeper {
     }
 
     pub async fn create_genesis_block(pool: ConnectionPool, fee_account_address: &Address) {
+        Self::create_genesis_block_with_balances(pool, fee_account_address, &[]).await
+    }
+
+    /// Same as `create_genesis_block`, but also creates accounts with the provided balances in the genesis
+    /// block. It's intended for the test networks that have no deposits to fund the accounts.
+    pub async fn create_genesis_block_with_balances(
+        pool: ConnectionPool,
+        fee_account_address: &Address,
+        balances: &[(Address, TokenId, num::BigUint)],
+    ) {
         let start = Instant::now();
         let mut storage = pool
             .access_storage()
@@ -557,18 +637,45 @@ impl ZkSyncStateKeeper {
         };
         accounts.insert(NFT_STORAGE_ACCOUNT_ID, special_account);
 
+        let mut updates = vec![
+            (AccountId(0), db_create_fee_account),
+            db_create_special_account[0].clone(),
+            (NFT_STORAGE_ACCOUNT_ID, db_set_special_account_balance),
+        ];
+        // Accounts are created in order after the fee account, which can be funded as well.
+        let mut account_ids = HashMap::new();
+        account_ids.insert(*fee_account_address, AccountId(0));
+        for (address, token, amount) in balances {
+            let account_id = match account_ids.get(address) {
+                Some(account_id) => *account_id,
+                None => {
+                    let account_id = AccountId(account_ids.len() as u32);
+                    let (account, db_create_account) =
+                        Account::create_account(account_id, *address);
+                    accounts.insert(account_id, account);
+                    updates.extend(db_create_account);
+                    account_ids.insert(*address, account_id);
+                    account_id
+                }
+            };
+
+            let account = accounts.get_mut(&account_id).unwrap();
+            let old_balance = account.get_balance(*token);
+            account.add_balance(*token, amount);
+            updates.push((
+                account_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: account.nonce,
+                    new_nonce: account.nonce,
+                    balance_update: (*token, old_balance, account.get_balance(*token)),
+                },
+            ));
+        }
+
         transaction
             .chain()
             .state_schema()
-            .commit_state_update(
-                BlockNumber(0),
-                &[
-                    (AccountId(0), db_create_fee_account),
-                    db_create_special_account[0].clone(),
-                    (NFT_STORAGE_ACCOUNT_ID, db_set_special_account_balance),
-                ],
-                0,
-            )
+            .commit_state_update(BlockNumber(0), &updates, 0)
             .await
             .expect("db fail");
         transaction
@@ -616,16 +723,78 @@ impl ZkSyncStateKeeper {
                         .unwrap_or_default();
                 }
                 StateKeeperRequest::ExecuteMiniBlock(proposed_block) => {
-                    self.execute_proposed_block(proposed_block).await;
+                    let span = self.miniblock_span(&proposed_block);
+                    self.execute_proposed_block(proposed_block)
+                        .instrument(span)
+                        .await;
                 }
                 StateKeeperRequest::SealBlock => {
-                    self.seal_pending_block().await;
+                    let span = vlog::info_span!(
+                        "state_keeper.seal_block",
+                        block_number = *self.state.block_number
+                    );
+                    self.seal_pending_block(SealReason::Requested)
+                        .instrument(span)
+                        .await;
                 }
                 StateKeeperRequest::GetCurrentState(sender) => {
                     sender.send(self.get_current_state()).unwrap_or_default();
                 }
+                StateKeeperRequest::Shutdown(sender) => {
+                    self.shutdown(sender).await;
+                    break;
+                }
+            }
+        }
+    }
+
+    async fn shutdown(&mut self, stored_notify: oneshot::Sender<()>) {
+        // Requests are processed one by one, so the current miniblock is already executed.
+        if !self.pending_block.success_operations.is_empty()
+            || !self.pending_block.failed_txs.is_empty()
+        {
+            self.store_pending_block().await;
+        }
+        vlog::info!(
+            "State keeper is stopped, pending block: {}, operations: {}",
+            *self.state.block_number,
+            self.pending_block.success_operations.len()
+        );
+        self.tx_for_commitments
+            .send(CommitRequest::Flush(stored_notify))
+            .await
+            .expect("committer receiver dropped");
+    }
+
+    /// Creates the span of the miniblock execution. Hashes of the included operations are recorded,
+    /// so the trace of a certain transaction or deposit can be found, and the span follows from
+    /// the spans of the operations waiting in the mempool and Ethereum watcher.
+    fn miniblock_span(&self, proposed_block: &ProposedBlock) -> vlog::Span {
+        let span = vlog::info_span!(
+            "state_keeper.execute_miniblock",
+            block_number = *self.state.block_number,
+            tx_hashes = vlog::field::Empty,
+            priority_op_eth_hashes = vlog::field::Empty,
+        );
+        if !span.is_disabled() {
+            let tx_hashes = proposed_block
+                .txs
+                .iter()
+                .flat_map(|tx| tx.hashes())
+                .map(|hash| hash.to_string())
+                .join(",");
+            let eth_hashes = proposed_block
+                .priority_ops
+                .iter()
+                .map(|op| format!("{:#x}", op.eth_hash))
+                .join(",");
+            span.record("tx_hashes", &tx_hashes.as_str());
+            span.record("priority_op_eth_hashes", &eth_hashes.as_str());
+            for operation_span in &proposed_block.spans {
+                span.follows_from(operation_span);
             }
         }
+        span
     }
 
     async fn execute_proposed_block(&mut self, proposed_block: ProposedBlock) {
@@ -649,8 +818,8 @@ impl ZkSyncStateKeeper {
                 Ok(exec_op) => {
                     executed_ops.push(exec_op);
                 }
-                Err(priority_op) => {
-                    self.seal_pending_block().await;
+                Err((priority_op, reason)) => {
+                    self.seal_pending_block(reason).await;
 
                     priority_op_queue.push_front(priority_op);
                 }
@@ -665,11 +834,11 @@ impl ZkSyncStateKeeper {
                         Ok(exec_op) => {
                             executed_ops.push(exec_op);
                         }
-                        Err(_) => {
+                        Err(reason) => {
                             // We could not execute the tx due to either of block size limit
                             // or the withdraw operations limit, so we seal this block and
                             // the last transaction will go to the next block instead.
-                            self.seal_pending_block().await;
+                            self.seal_pending_block(reason).await;
 
                             tx_queue.push_front(variant);
                         }
@@ -680,11 +849,11 @@ impl ZkSyncStateKeeper {
                         Ok(mut ops) => {
                             executed_ops.append(&mut ops);
                         }
-                        Err(_) => {
+                        Err(reason) => {
                             // We could not execute the batch tx due to either of block size limit
                             // or the withdraw operations limit, so we seal this block and
                             // the last transaction will go to the next block instead.
-                            self.seal_pending_block().await;
+                            self.seal_pending_block(reason).await;
 
                             tx_queue.push_front(variant);
                         }
@@ -697,16 +866,11 @@ impl ZkSyncStateKeeper {
             self.pending_block.pending_block_iteration += 1;
         }
 
-        // If pending block contains withdrawals we seal it faster
-        let max_miniblock_iterations = if self.pending_block.fast_processing_required {
-            self.fast_miniblock_iterations
-        } else {
-            self.max_miniblock_iterations
-        };
-        if self.pending_block.chunks_left == 0
-            || self.pending_block.pending_block_iteration > max_miniblock_iterations
-        {
-            self.seal_pending_block().await;
+        let seal_reason = self
+            .seal_policy
+            .check_miniblock(&self.pending_block, self.max_block_chunks());
+        if let Some(reason) = seal_reason {
+            self.seal_pending_block(reason).await;
         } else {
             // We've already incremented the pending block iteration, so this iteration will count towards
             // reaching the block commitment timeout.
@@ -716,6 +880,10 @@ impl ZkSyncStateKeeper {
             }
         }
 
+        metrics::gauge!(
+            "state_keeper.pending_block_chunks_used",
+            self.pending_block_chunks_used() as f64
+        );
         metrics::histogram!("state_keeper.execute_proposed_block", start.elapsed());
     }
 
@@ -723,11 +891,11 @@ impl ZkSyncStateKeeper {
     fn apply_priority_op(
         &mut self,
         priority_op: PriorityOp,
-    ) -> Result<ExecutedOperations, PriorityOp> {
+    ) -> Result<ExecutedOperations, (PriorityOp, SealReason)> {
         let start = Instant::now();
         let chunks_needed = priority_op.data.chunks();
         if self.pending_block.chunks_left < chunks_needed {
-            return Err(priority_op);
+            return Err((priority_op, SealReason::BlockFull));
         }
 
         // Check if adding this transaction to the block won't make the contract operations
@@ -735,6 +903,12 @@ impl ZkSyncStateKeeper {
         let non_executed_op = self
             .state
             .priority_op_to_zksync_op(priority_op.data.clone());
+        if let Err(reason) = self
+            .seal_policy
+            .check_operations(&self.pending_block, std::slice::from_ref(&non_executed_op))
+        {
+            return Err((priority_op, reason));
+        }
         if self
             .pending_block
             .gas_counter
@@ -743,7 +917,7 @@ impl ZkSyncStateKeeper {
         {
             // We've reached the gas limit, seal the block.
             // This transaction will go into the next one.
-            return Err(priority_op);
+            return Err((priority_op, SealReason::GasLimit));
         }
 
         let OpSuccess {
@@ -752,6 +926,9 @@ impl ZkSyncStateKeeper {
             executed_op,
         } = self.state.execute_priority_op(priority_op.data.clone());
 
+        if executed_op.is_processable_onchain_operation() {
+            self.pending_block.withdrawals += 1;
+        }
         self.pending_block.chunks_left -= chunks_needed;
         self.pending_block.account_updates.append(&mut updates);
         if let Some(fee) = fee {
@@ -767,8 +944,7 @@ impl ZkSyncStateKeeper {
             created_at: chrono::Utc::now(),
         }));
         self.pending_block
-            .success_operations
-            .push(exec_result.clone());
+            .add_success_operation(exec_result.clone());
         self.current_unprocessed_priority_op += 1;
 
         metrics::histogram!("state_keeper.apply_priority_op", start.elapsed());
@@ -833,7 +1009,7 @@ impl ZkSyncStateKeeper {
         &mut self,
         txs: &[SignedZkSyncTx],
         batch_id: i64,
-    ) -> Result<Vec<ExecutedOperations>, ()> {
+    ) -> Result<Vec<ExecutedOperations>, SealReason> {
         metrics::gauge!("tx_batch_size", txs.len() as f64);
         let start = Instant::now();
 
@@ -842,7 +1018,7 @@ impl ZkSyncStateKeeper {
         // If we can't add the tx to the block due to the size limit, we return this tx,
         // seal the block and execute it again.
         if self.pending_block.chunks_left < chunks_needed {
-            return Err(());
+            return Err(SealReason::BlockFull);
         }
 
         let ops: Vec<_> = txs
@@ -857,6 +1033,11 @@ impl ZkSyncStateKeeper {
             let fail_reason = "Amount of gas required to process batch is too big".to_string();
             vlog::warn!("Failed to execute batch: {}", fail_reason);
             for tx in txs {
+                metrics::increment_counter!(
+                    "state_keeper.rejected_txs",
+                    "error" => "GasLimit",
+                    "reason" => "BatchTooExpensive"
+                );
                 let failed_tx = ExecutedTx {
                     signed_tx: tx.clone(),
                     success: false,
@@ -877,8 +1058,10 @@ impl ZkSyncStateKeeper {
         // If we can't add the tx to the block due to the gas limit, we return this tx,
         // seal the block and execute it again.
         if !self.pending_block.gas_counter.can_include(&ops) {
-            return Err(());
+            return Err(SealReason::GasLimit);
         }
+        self.seal_policy
+            .check_operations(&self.pending_block, &ops)?;
 
         let all_updates = self.execute_txs_batch(txs, self.pending_block.timestamp);
 
@@ -894,6 +1077,9 @@ impl ZkSyncStateKeeper {
                         .add_op(&executed_op)
                         .expect("We have already checked that we can include this tx");
 
+                    if executed_op.is_processable_onchain_operation() {
+                        self.pending_block.withdrawals += 1;
+                    }
                     self.pending_block.chunks_left -= executed_op.chunks();
                     self.pending_block.account_updates.append(&mut updates);
                     if let Some(fee) = fee {
@@ -912,12 +1098,12 @@ impl ZkSyncStateKeeper {
                         batch_id: Some(batch_id),
                     }));
                     self.pending_block
-                        .success_operations
-                        .push(exec_result.clone());
+                        .add_success_operation(exec_result.clone());
                     executed_operations.push(exec_result);
                 }
                 Err(e) => {
                     vlog::warn!("Failed to execute transaction: {:?}, {}", tx, e);
+                    Self::report_rejected_tx(&e.reason);
                     let failed_tx = ExecutedTx {
                         signed_tx: tx.clone(),
                         success: false,
@@ -938,14 +1124,14 @@ impl ZkSyncStateKeeper {
         Ok(executed_operations)
     }
 
-    fn apply_tx(&mut self, tx: &SignedZkSyncTx) -> Result<ExecutedOperations, ()> {
+    fn apply_tx(&mut self, tx: &SignedZkSyncTx) -> Result<ExecutedOperations, SealReason> {
         let start = Instant::now();
         let chunks_needed = self.state.chunks_for_tx(&tx);
 
         // If we can't add the tx to the block due to the size limit, we return this tx,
         // seal the block and execute it again.
         if self.pending_block.chunks_left < chunks_needed {
-            return Err(());
+            return Err(SealReason::BlockFull);
         }
 
         // Check if adding this transaction to the block won't make the contract operations
@@ -954,15 +1140,14 @@ impl ZkSyncStateKeeper {
         if let Ok(non_executed_op) = non_executed_op {
             // We only care about successful conversions, since if conversion failed,
             // then transaction will fail as well (as it shares the same code base).
-            if !self
-                .pending_block
-                .gas_counter
-                .can_include(&[non_executed_op])
-            {
+            let ops = [non_executed_op];
+            if !self.pending_block.gas_counter.can_include(&ops) {
                 // We've reached the gas limit, seal the block.
                 // This transaction will go into the next one.
-                return Err(());
+                return Err(SealReason::GasLimit);
             }
+            self.seal_policy
+                .check_operations(&self.pending_block, &ops)?;
         }
 
         if let ZkSyncTx::Withdraw(tx) = &tx.tx {
@@ -985,6 +1170,9 @@ impl ZkSyncStateKeeper {
                     .add_op(&executed_op)
                     .expect("We have already checked that we can include this tx");
 
+                if executed_op.is_processable_onchain_operation() {
+                    self.pending_block.withdrawals += 1;
+                }
                 self.pending_block.chunks_left -= chunks_needed;
                 self.pending_block.account_updates.append(&mut updates);
                 if let Some(fee) = fee {
@@ -1003,12 +1191,12 @@ impl ZkSyncStateKeeper {
                     batch_id: None,
                 }));
                 self.pending_block
-                    .success_operations
-                    .push(exec_result.clone());
+                    .add_success_operation(exec_result.clone());
                 exec_result
             }
             Err(e) => {
                 vlog::warn!("Failed to execute transaction: {:?}, {}", tx, e);
+                Self::report_rejected_tx(&e);
                 let failed_tx = ExecutedTx {
                     signed_tx: tx.clone(),
                     success: false,
@@ -1027,9 +1215,31 @@ impl ZkSyncStateKeeper {
         Ok(exec_result)
     }
 
+    fn report_rejected_tx(error: &OpError) {
+        metrics::increment_counter!(
+            "state_keeper.rejected_txs",
+            "error" => error.variance_name(),
+            "reason" => error.reason_name()
+        );
+    }
+
+    fn max_block_chunks(&self) -> usize {
+        *self
+            .available_block_chunk_sizes
+            .iter()
+            .max()
+            .expect("Expected at least one block chunks size")
+    }
+
+    /// Amount of chunks occupied by the operations of the pending block.
+    fn pending_block_chunks_used(&self) -> usize {
+        self.max_block_chunks() - self.pending_block.chunks_left
+    }
+
     /// Finalizes the pending block, transforming it into a full block.
-    async fn seal_pending_block(&mut self) {
+    async fn seal_pending_block(&mut self, reason: SealReason) {
         let start = Instant::now();
+        let chunks_used = self.pending_block_chunks_used();
 
         // Apply fees of pending block
         let fee_updates = self
@@ -1095,17 +1305,33 @@ impl ZkSyncStateKeeper {
         let applied_updates_request = AppliedUpdatesRequest {
             account_updates,
             first_update_order_id,
+            span: vlog::Span::current(),
         };
         pending_block.stored_account_updates = pending_block.account_updates.len();
         *self.state.block_number += 1;
 
         vlog::info!(
-            "Creating full block: {}, operations: {}, chunks_left: {}, miniblock iterations: {}",
+            "Creating full block: {}, operations: {}, chunks_left: {}, block size: {}, miniblock iterations: {}, seal reason: {}",
             *block_commit_request.block.block_number,
             block_commit_request.block.block_transactions.len(),
             pending_block.chunks_left,
-            pending_block.pending_block_iteration
+            block_commit_request.block.block_chunks_size,
+            pending_block.pending_block_iteration,
+            reason.as_str()
+        );
+
+        let block_chunks_size = block_commit_request.block.block_chunks_size;
+        metrics::increment_counter!("state_keeper.sealed_blocks", "reason" => reason.as_str());
+        metrics::histogram!("state_keeper.block_chunks_used", chunks_used as f64);
+        metrics::increment_counter!(
+            "state_keeper.sealed_blocks_by_size",
+            "size" => block_chunks_size.to_string()
+        );
+        metrics::histogram!(
+            "state_keeper.block_fill_ratio",
+            chunks_used as f64 / block_chunks_size as f64
         );
+        metrics::gauge!("state_keeper.pending_block_chunks_used", 0.0);
 
         let commit_request = CommitRequest::Block((block_commit_request, applied_updates_request));
         self.tx_for_commitments
@@ -1152,6 +1378,7 @@ impl ZkSyncStateKeeper {
         let applied_updates_request = AppliedUpdatesRequest {
             account_updates,
             first_update_order_id,
+            span: vlog::Span::current(),
         };
         self.pending_block.stored_account_updates = self.pending_block.account_updates.len();
 
@@ -0,0 +1,178 @@
+//! Rules deciding when the pending block is sealed.
+//!
+//! The block is always sealed when it has no chunks left or the next operation exceeds the
+//! contract gas limit. On top of that, the policy applies the configurable rules, so the latency
+//! and the cost of the blocks can be tuned per deployment:
+//!
+//! - maximum amount of miniblock iterations (reduced one for the blocks with fast withdrawals);
+//! - maximum age of the block;
+//! - minimum fill ratio the block must reach before it's sealed by the miniblock iterations limit;
+//! - maximum number of withdrawals in the block;
+//! - gas budget of the block, lower than the contract gas limit.
+//!
+//! Rules that limit the block contents never apply to an empty block, so every operation
+//! eventually gets into some block.
+
+// Built-in uses
+use std::time::Duration;
+// Workspace uses
+use zksync_config::configs::chain::StateKeeper as StateKeeperConfig;
+use zksync_types::{operations::ZkSyncOp, U256};
+// Local uses
+use super::{system_time_timestamp, PendingBlock, SealReason};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SealPolicy {
+    max_miniblock_iterations: usize,
+    fast_miniblock_iterations: usize,
+    max_block_age: Option<Duration>,
+    min_fill_ratio: Option<f64>,
+    max_withdrawals: Option<usize>,
+    gas_budget: Option<U256>,
+}
+
+impl SealPolicy {
+    /// Creates the policy which seals the block only by the miniblock iterations limit.
+    pub fn new(max_miniblock_iterations: usize, fast_miniblock_iterations: usize) -> Self {
+        Self {
+            max_miniblock_iterations,
+            fast_miniblock_iterations,
+            max_block_age: None,
+            min_fill_ratio: None,
+            max_withdrawals: None,
+            gas_budget: None,
+        }
+    }
+
+    /// Creates the policy from the config, zero values disable the corresponding rules.
+    pub fn from_config(config: &StateKeeperConfig) -> Self {
+        let mut policy = Self::new(
+            config.miniblock_iterations as usize,
+            config.fast_block_miniblock_iterations as usize,
+        );
+        if config.max_block_age > 0 {
+            policy = policy.with_max_block_age(Duration::from_secs(config.max_block_age));
+        }
+        if config.min_block_fill_ratio > 0.0 {
+            policy = policy.with_min_fill_ratio(config.min_block_fill_ratio);
+        }
+        if config.max_withdrawals_per_block > 0 {
+            policy = policy.with_max_withdrawals(config.max_withdrawals_per_block);
+        }
+        if config.block_gas_budget > 0 {
+            policy = policy.with_gas_budget(config.block_gas_budget.into());
+        }
+        policy
+    }
+
+    /// Seals the block once this time has passed since its first operation, regardless of the fill ratio.
+    pub fn with_max_block_age(mut self, max_block_age: Duration) -> Self {
+        self.max_block_age = Some(max_block_age);
+        self
+    }
+
+    /// Keeps the block open after the miniblock iterations limit until the share of the used chunks
+    /// reaches the ratio. Blocks with fast withdrawals are not delayed.
+    pub fn with_min_fill_ratio(mut self, min_fill_ratio: f64) -> Self {
+        self.min_fill_ratio = Some(min_fill_ratio);
+        self
+    }
+
+    pub fn with_max_withdrawals(mut self, max_withdrawals: usize) -> Self {
+        self.max_withdrawals = Some(max_withdrawals);
+        self
+    }
+
+    /// Limits both commit and verify gas of the block.
+    pub fn with_gas_budget(mut self, gas_budget: U256) -> Self {
+        self.gas_budget = Some(gas_budget);
+        self
+    }
+
+    /// Returns the same policy without the rules limiting the block contents.
+    pub(super) fn without_content_limits(&self) -> Self {
+        Self::new(
+            self.max_miniblock_iterations,
+            self.fast_miniblock_iterations,
+        )
+    }
+
+    /// Checks whether the block has to be sealed before the operations are added to it.
+    pub(super) fn check_operations(
+        &self,
+        block: &PendingBlock,
+        ops: &[ZkSyncOp],
+    ) -> Result<(), SealReason> {
+        if block.success_operations.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(max_withdrawals) = self.max_withdrawals {
+            let withdrawals = ops
+                .iter()
+                .filter(|op| op.is_processable_onchain_operation())
+                .count();
+            if withdrawals > 0 && block.withdrawals + withdrawals > max_withdrawals {
+                return Err(SealReason::WithdrawalsLimit);
+            }
+        }
+
+        if let Some(gas_budget) = self.gas_budget {
+            let mut gas_counter = block.gas_counter.clone();
+            // Operations exceeding the gas limit are handled by the state keeper itself.
+            let fits_gas_limit = ops.iter().all(|op| gas_counter.add_op(op).is_ok());
+            if fits_gas_limit
+                && (gas_counter.commit_gas_limit() > gas_budget
+                    || gas_counter.verify_gas_limit() > gas_budget)
+            {
+                return Err(SealReason::GasBudget);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the block has to be sealed after the miniblock has been executed.
+    pub(super) fn check_miniblock(
+        &self,
+        block: &PendingBlock,
+        max_block_chunks: usize,
+    ) -> Option<SealReason> {
+        if block.chunks_left == 0 {
+            return Some(SealReason::BlockFull);
+        }
+        if block.success_operations.is_empty() {
+            return None;
+        }
+
+        if let (Some(max_block_age), Some(first_operation_timestamp)) =
+            (self.max_block_age, block.first_operation_timestamp)
+        {
+            let age = system_time_timestamp().saturating_sub(first_operation_timestamp);
+            if age >= max_block_age.as_secs() {
+                return Some(SealReason::MaxBlockAge);
+            }
+        }
+
+        if block.fast_processing_required {
+            if block.pending_block_iteration > self.fast_miniblock_iterations {
+                return Some(SealReason::FastProcessing);
+            }
+        } else if block.pending_block_iteration > self.max_miniblock_iterations {
+            let fill_ratio =
+                (max_block_chunks - block.chunks_left) as f64 / max_block_chunks as f64;
+            match self.min_fill_ratio {
+                Some(min_fill_ratio) if fill_ratio < min_fill_ratio => {
+                    vlog::debug!(
+                        "Block sealing is delayed: fill ratio {:.2} is below {:.2}",
+                        fill_ratio,
+                        min_fill_ratio
+                    );
+                }
+                _ => return Some(SealReason::Timeout),
+            }
+        }
+
+        None
+    }
+}
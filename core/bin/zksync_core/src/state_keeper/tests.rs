@@ -1,6 +1,9 @@
-use super::{CommitRequest, ZkSyncStateInitParams, ZkSyncStateKeeper};
+use super::{CommitRequest, SealPolicy, SealReason, ZkSyncStateInitParams, ZkSyncStateKeeper};
 use crate::mempool::ProposedBlock;
-use futures::{channel::mpsc, stream::StreamExt};
+use futures::{
+    channel::{mpsc, oneshot},
+    stream::StreamExt,
+};
 use num::BigUint;
 use zksync_crypto::{
     priv_key_from_fs,
@@ -202,6 +205,7 @@ async fn apply_single_transfer(tester: &mut StateKeeperTester) {
     let proposed_block = ProposedBlock {
         txs: vec![SignedTxVariant::Tx(transfer)],
         priority_ops: Vec::new(),
+        spans: Vec::new(),
     };
     tester
         .state_keeper
@@ -221,6 +225,7 @@ async fn apply_batch_with_two_transfers(tester: &mut StateKeeperTester) {
             eth_signatures: Vec::new(),
         })],
         priority_ops: Vec::new(),
+        spans: Vec::new(),
     };
     tester
         .state_keeper
@@ -450,7 +455,10 @@ async fn seal_pending_block() {
     assert!(tester.state_keeper.apply_priority_op(deposit).is_ok());
 
     let old_updates_len = tester.state_keeper.pending_block.account_updates.len();
-    tester.state_keeper.seal_pending_block().await;
+    tester
+        .state_keeper
+        .seal_pending_block(SealReason::Requested)
+        .await;
 
     assert!(tester.state_keeper.pending_block.failed_txs.is_empty());
     assert!(tester
@@ -486,6 +494,36 @@ async fn seal_pending_block() {
     }
 }
 
+/// Checks that the partially filled block is sealed into the smallest block size it fits,
+/// while the pending block is filled up to the largest size.
+#[tokio::test]
+async fn seal_into_smallest_block_size() {
+    let mut tester = StateKeeperTester::new(20, 3, 3);
+    tester.state_keeper.available_block_chunk_sizes = vec![6, 20];
+
+    apply_single_transfer(&mut tester).await;
+    assert!(matches!(
+        tester.response_rx.next().await,
+        Some(CommitRequest::PendingBlock(_))
+    ));
+    tester
+        .state_keeper
+        .seal_pending_block(SealReason::Requested)
+        .await;
+    assert_eq!(tester.state_keeper.pending_block.chunks_left, 20);
+
+    if let Some(CommitRequest::Block((block, _))) = tester.response_rx.next().await {
+        assert_eq!(block.block.block_chunks_size, 6);
+        // The rest of the block is padded with noops.
+        assert_eq!(
+            block.block.get_eth_public_data().len(),
+            6 * zksync_crypto::params::CHUNK_BIT_WIDTH / 8
+        );
+    } else {
+        panic!("Block is not received!");
+    }
+}
+
 /// Checks if block storing is done correctly by storing a block
 /// with 1 priority_op, 1 succeeded tx, 1 failed tx
 #[tokio::test]
@@ -545,6 +583,30 @@ async fn store_pending_block() {
     }
 }
 
+/// Checks that on shutdown the pending block is stored before the committer is asked to flush.
+#[tokio::test]
+async fn shutdown() {
+    let mut tester = StateKeeperTester::new(20, 3, 3);
+    let deposit = create_deposit(TokenId(0), 12u32);
+    assert!(tester.state_keeper.apply_priority_op(deposit).is_ok());
+
+    let (sender, mut receiver) = oneshot::channel();
+    tester.state_keeper.shutdown(sender).await;
+
+    if let Some(CommitRequest::PendingBlock((block, _))) = tester.response_rx.next().await {
+        assert_eq!(block.number, tester.state_keeper.state.block_number);
+        assert_eq!(block.success_operations.len(), 1);
+    } else {
+        panic!("Pending block is not received!");
+    }
+    if let Some(CommitRequest::Flush(sender)) = tester.response_rx.next().await {
+        sender.send(()).unwrap();
+    } else {
+        panic!("Flush request is not received!");
+    }
+    assert_eq!(receiver.try_recv(), Ok(Some(())));
+}
+
 mod execute_proposed_block {
 
     use super::*;
@@ -688,6 +750,7 @@ mod execute_proposed_block {
                 SignedTxVariant::Tx(bad_withdraw),
             ],
             priority_ops: vec![deposit],
+            spans: Vec::new(),
         };
         let pending_block_iteration = tester.state_keeper.pending_block.pending_block_iteration;
         tester
@@ -733,6 +796,7 @@ mod execute_proposed_block {
                 SignedTxVariant::Tx(bad_withdraw),
             ],
             priority_ops: vec![deposit],
+            spans: Vec::new(),
         };
         tester
             .state_keeper
@@ -776,6 +840,7 @@ mod execute_proposed_block {
                 SignedTxVariant::Tx(bad_withdraw),
             ],
             priority_ops: vec![deposit],
+            spans: Vec::new(),
         };
         tester
             .state_keeper
@@ -806,6 +871,7 @@ mod execute_proposed_block {
         let proposed_block = ProposedBlock {
             priority_ops: Vec::new(),
             txs: vec![withdraw.into()],
+            spans: Vec::new(),
         };
 
         tester
@@ -834,6 +900,7 @@ mod execute_proposed_block {
         let proposed_block = ProposedBlock {
             txs: vec![],
             priority_ops: vec![],
+            spans: Vec::new(),
         };
 
         tester
@@ -863,6 +930,7 @@ mod execute_proposed_block {
         let proposed_block = ProposedBlock {
             txs: vec![SignedTxVariant::Tx(bad_withdraw)],
             priority_ops: vec![],
+            spans: Vec::new(),
         };
 
         tester
@@ -892,6 +960,7 @@ mod execute_proposed_block {
         let proposed_block = ProposedBlock {
             txs: vec![SignedTxVariant::Tx(good_withdraw)],
             priority_ops: vec![],
+            spans: Vec::new(),
         };
 
         let pending_block_iteration = tester.state_keeper.pending_block.pending_block_iteration;
@@ -922,6 +991,7 @@ mod execute_proposed_block {
         let proposed_block = ProposedBlock {
             txs: vec![SignedTxVariant::Tx(bad_withdraw)],
             priority_ops: vec![],
+            spans: Vec::new(),
         };
 
         let pending_block_iteration = tester.state_keeper.pending_block.pending_block_iteration;
@@ -944,6 +1014,7 @@ mod execute_proposed_block {
         let proposed_block = ProposedBlock {
             txs: vec![],
             priority_ops: vec![],
+            spans: Vec::new(),
         };
 
         let pending_block_iteration = tester.state_keeper.pending_block.pending_block_iteration;
@@ -989,6 +1060,7 @@ mod execute_proposed_block {
                 SignedTxVariant::Tx(bad_withdraw_1.clone()),
             ],
             priority_ops: vec![],
+            spans: Vec::new(),
         };
 
         let good_withdraw_2 = create_account_and_withdrawal(
@@ -1013,6 +1085,7 @@ mod execute_proposed_block {
                 SignedTxVariant::Tx(bad_withdraw_2.clone()),
             ],
             priority_ops: vec![],
+            spans: Vec::new(),
         };
 
         tester
@@ -1119,6 +1192,7 @@ mod execute_proposed_block {
                 SignedTxVariant::Tx(correct_transfer.clone()),
             ],
             priority_ops: vec![],
+            spans: Vec::new(),
         };
 
         tester
@@ -1196,6 +1270,7 @@ mod execute_proposed_block {
                 eth_signatures: Vec::new(),
             })],
             priority_ops: Vec::new(),
+            spans: Vec::new(),
         };
         tester
             .state_keeper
@@ -1283,6 +1358,7 @@ mod execute_proposed_block {
         let proposed_block = ProposedBlock {
             txs,
             priority_ops: Vec::new(),
+            spans: Vec::new(),
         };
         tester
             .state_keeper
@@ -1339,6 +1415,7 @@ mod execute_proposed_block {
                 eth_signatures: Vec::new(),
             })],
             priority_ops: Vec::new(),
+            spans: Vec::new(),
         };
         // Execute big batch.
         tester
@@ -1357,3 +1434,132 @@ mod execute_proposed_block {
         }
     }
 }
+
+mod seal_policy {
+    use super::*;
+    use std::time::Duration;
+    use zksync_types::gas_counter::GasCounter;
+
+    /// Checks that the block is sealed once it reaches the withdrawals limit.
+    #[tokio::test]
+    async fn withdrawals_limit() {
+        let mut tester = StateKeeperTester::new(100, 100, 100);
+        tester.state_keeper.seal_policy = SealPolicy::new(100, 100).with_max_withdrawals(2);
+
+        let txs = (1..=3)
+            .map(|i| {
+                let withdraw = create_account_and_withdrawal(
+                    &mut tester,
+                    TokenId(0),
+                    AccountId(i),
+                    200u32,
+                    145u32,
+                    Default::default(),
+                );
+                SignedTxVariant::Tx(withdraw)
+            })
+            .collect();
+        let proposed_block = ProposedBlock {
+            txs,
+            priority_ops: Vec::new(),
+            spans: Vec::new(),
+        };
+        tester
+            .state_keeper
+            .execute_proposed_block(proposed_block)
+            .await;
+
+        if let Some(CommitRequest::Block((block, _))) = tester.response_rx.next().await {
+            assert_eq!(block.block.block_transactions.len(), 2);
+        } else {
+            panic!("Sealed block is not received");
+        }
+        assert_eq!(tester.state_keeper.pending_block.withdrawals, 1);
+    }
+
+    /// Checks that the block is sealed once it exceeds the gas budget,
+    /// but the first operation is always included.
+    #[tokio::test]
+    async fn gas_budget() {
+        let mut tester = StateKeeperTester::new(100, 100, 100);
+        let empty_block_gas = GasCounter::new().commit_gas_limit();
+        tester.state_keeper.seal_policy =
+            SealPolicy::new(100, 100).with_gas_budget(empty_block_gas);
+
+        apply_single_transfer(&mut tester).await;
+        assert!(matches!(
+            tester.response_rx.next().await,
+            Some(CommitRequest::PendingBlock(_))
+        ));
+
+        apply_single_transfer(&mut tester).await;
+        if let Some(CommitRequest::Block((block, _))) = tester.response_rx.next().await {
+            assert_eq!(block.block.block_transactions.len(), 1);
+        } else {
+            panic!("Sealed block is not received");
+        }
+    }
+
+    /// Checks that the block which is not filled enough is not sealed by the iterations limit,
+    /// unless it reaches the maximum age.
+    #[tokio::test]
+    async fn min_fill_ratio() {
+        let mut tester = StateKeeperTester::new(20, 0, 0);
+        tester.state_keeper.seal_policy = SealPolicy::new(0, 0).with_min_fill_ratio(0.5);
+
+        apply_single_transfer(&mut tester).await;
+        assert!(matches!(
+            tester.response_rx.next().await,
+            Some(CommitRequest::PendingBlock(_))
+        ));
+        assert_eq!(
+            tester
+                .state_keeper
+                .seal_policy
+                .check_miniblock(&tester.state_keeper.pending_block, 20),
+            None
+        );
+
+        tester.state_keeper.seal_policy = SealPolicy::new(0, 0)
+            .with_min_fill_ratio(0.5)
+            .with_max_block_age(Duration::from_secs(0));
+        assert_eq!(
+            tester
+                .state_keeper
+                .seal_policy
+                .check_miniblock(&tester.state_keeper.pending_block, 20),
+            Some(SealReason::MaxBlockAge)
+        );
+    }
+
+    /// Checks that the block age is measured from its first operation rather than from the block timestamp.
+    #[tokio::test]
+    async fn max_block_age_since_first_operation() {
+        let mut tester = StateKeeperTester::new(20, 0, 0);
+        tester.state_keeper.seal_policy =
+            SealPolicy::new(100, 100).with_max_block_age(Duration::from_secs(60));
+
+        apply_single_transfer(&mut tester).await;
+        assert!(matches!(
+            tester.response_rx.next().await,
+            Some(CommitRequest::PendingBlock(_))
+        ));
+        tester.state_keeper.pending_block.timestamp = 0;
+        assert_eq!(
+            tester
+                .state_keeper
+                .seal_policy
+                .check_miniblock(&tester.state_keeper.pending_block, 20),
+            None
+        );
+
+        tester.state_keeper.pending_block.first_operation_timestamp = Some(0);
+        assert_eq!(
+            tester
+                .state_keeper
+                .seal_policy
+                .check_miniblock(&tester.state_keeper.pending_block, 20),
+            Some(SealReason::MaxBlockAge)
+        );
+    }
+}
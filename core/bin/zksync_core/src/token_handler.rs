@@ -151,14 +151,14 @@ impl TokenHandler {
                             match try_insert_token {
                                 Ok(..) => (),
                                 Err(StoreTokenError::Other(anyhow_err)) => return Err(anyhow_err),
-                                Err(StoreTokenError::TokenAlreadyExistsError(err)) => {
+                                Err(err) => {
                                     vlog::warn!("failed to store token in database: {}", err)
                                 }
                             }
 
                             token
                         }
-                        Err(StoreTokenError::Other(anyhow_err)) => return Err(anyhow_err),
+                        Err(err) => return Err(err.into()),
                     }
                 }
                 None => {
@@ -174,7 +174,7 @@ impl TokenHandler {
                     match try_insert_token {
                         Ok(..) => (),
                         Err(StoreTokenError::Other(anyhow_err)) => return Err(anyhow_err),
-                        Err(StoreTokenError::TokenAlreadyExistsError(err)) => {
+                        Err(err) => {
                             vlog::warn!("failed to store token in database: {}", err)
                         }
                     }
@@ -0,0 +1,369 @@
+//! Withdrawals tracker follows the finalization of the fungible withdrawals on L1.
+//!
+//! Once the block is executed on Ethereum, every fungible withdrawal from it either transfers funds
+//! to the recipient or, if the transfer fails, stores them as the pending balance on the main contract.
+//! The contract emits `Withdrawal` or `WithdrawalPending` event for every such operation in the order
+//! they are processed, so the tracker matches the events from the `executeBlocks` transaction receipt
+//! to the withdrawal operations of the executed blocks.
+//!
+//! Withdrawals stored as the pending balance are checked periodically: once the recipient claims
+//! the funds via `withdrawPendingBalance`, the pending balance on the contract decreases and the
+//! oldest withdrawals which are not covered by it anymore are marked as claimed.
+
+// Built-in deps
+use std::time::Duration;
+// External uses
+use anyhow::format_err;
+use ethabi::Hash;
+use num::BigUint;
+use tokio::{task::JoinHandle, time};
+use web3::contract::Options;
+// Workspace deps
+use zksync_config::ZkSyncConfig;
+use zksync_contracts::zksync_contract;
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_eth_client::EthereumGateway;
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_types::{
+    aggregated_operations::{AggregatedActionType, AggregatedOperation},
+    block::Block,
+    event::withdrawal::{WithdrawalEvent, WithdrawalStatus},
+    tx::TxHash,
+    Address, BlockNumber, ExecutedOperations, TokenId, TokenLike, ZkSyncOp, H256, U256,
+};
+
+struct ContractTopics {
+    withdrawal: Hash,
+    withdrawal_pending: Hash,
+}
+
+impl ContractTopics {
+    fn new(zksync_contract: &ethabi::Contract) -> Self {
+        Self {
+            withdrawal: zksync_contract
+                .event("Withdrawal")
+                .expect("main contract abi error")
+                .signature(),
+            withdrawal_pending: zksync_contract
+                .event("WithdrawalPending")
+                .expect("main contract abi error")
+                .signature(),
+        }
+    }
+}
+
+/// Fungible withdrawal operation from the executed block.
+#[derive(Debug, Clone)]
+struct FungibleWithdrawal {
+    tx_hash: TxHash,
+    address: Address,
+    token_id: TokenId,
+    amount: BigUint,
+}
+
+impl FungibleWithdrawal {
+    /// Returns fungible withdrawals of the block in the order they're processed by the contract.
+    fn from_block(block: &Block) -> Vec<Self> {
+        block
+            .block_transactions
+            .iter()
+            .filter(|exec_op| exec_op.is_successful())
+            .filter_map(|exec_op| {
+                let tx_hash = match exec_op {
+                    ExecutedOperations::Tx(tx) => tx.signed_tx.hash(),
+                    ExecutedOperations::PriorityOp(op) => op.priority_op.tx_hash(),
+                };
+                let (address, token_id, amount) = match exec_op.get_executed_op()? {
+                    ZkSyncOp::Withdraw(op) => (op.tx.to, op.tx.token, op.tx.amount.clone()),
+                    ZkSyncOp::ForcedExit(op) => (
+                        op.tx.target,
+                        op.tx.token,
+                        op.withdraw_amount.clone().unwrap_or_default().0,
+                    ),
+                    ZkSyncOp::FullExit(op) if *op.priority_op.token < MIN_NFT_TOKEN_ID => (
+                        op.priority_op.eth_address,
+                        op.priority_op.token,
+                        op.withdraw_amount.clone().unwrap_or_default().0,
+                    ),
+                    _ => return None,
+                };
+                Some(Self {
+                    tx_hash,
+                    address,
+                    token_id,
+                    amount,
+                })
+            })
+            .collect()
+    }
+}
+
+pub struct WithdrawalsTracker {
+    eth_gateway: EthereumGateway,
+    pool: ConnectionPool,
+    zksync_contract_addr: Address,
+    topics: ContractTopics,
+    /// Withdrawal events of the last fetched `executeBlocks` transaction,
+    /// since several consecutive blocks are usually executed by the same transaction.
+    receipt_cache: Option<(H256, Vec<(TokenId, WithdrawalStatus)>)>,
+}
+
+impl WithdrawalsTracker {
+    pub fn new(
+        eth_gateway: EthereumGateway,
+        pool: ConnectionPool,
+        zksync_contract_addr: Address,
+    ) -> Self {
+        Self {
+            eth_gateway,
+            pool,
+            zksync_contract_addr,
+            topics: ContractTopics::new(&zksync_contract()),
+            receipt_cache: None,
+        }
+    }
+
+    /// Processes the withdrawals of the blocks executed since the last run.
+    async fn track_executed_blocks(&mut self) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage().await?;
+        let last_executed_block = storage
+            .chain()
+            .operations_schema()
+            .get_last_block_by_aggregated_action(AggregatedActionType::ExecuteBlocks, Some(true))
+            .await?;
+
+        let last_tracked_block = match storage.withdrawals_schema().last_tracked_block().await? {
+            Some(block_number) => block_number,
+            None => {
+                // The tracker doesn't go through the history on the first launch.
+                vlog::info!(
+                    "Withdrawals tracker starts from block {}",
+                    *last_executed_block
+                );
+                storage
+                    .withdrawals_schema()
+                    .set_last_tracked_block(last_executed_block)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        for block_number in (*last_tracked_block + 1)..=*last_executed_block {
+            self.track_block(&mut storage, BlockNumber(block_number))
+                .await?;
+        }
+        metrics::gauge!(
+            "withdrawals_tracker.last_tracked_block",
+            *last_executed_block.max(last_tracked_block) as f64
+        );
+        Ok(())
+    }
+
+    async fn track_block(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        block_number: BlockNumber,
+    ) -> anyhow::Result<()> {
+        let eth_tx_hash = storage
+            .ethereum_schema()
+            .aggregated_op_final_hash(block_number)
+            .await?
+            .ok_or_else(|| {
+                format_err!("No executeBlocks transaction for block {}", *block_number)
+            })?;
+        let blocks = match storage
+            .chain()
+            .operations_schema()
+            .get_aggregated_op_that_affects_block(AggregatedActionType::ExecuteBlocks, block_number)
+            .await?
+        {
+            Some((_, AggregatedOperation::ExecuteBlocks(op))) => op.blocks,
+            _ => {
+                return Err(format_err!(
+                    "No executeBlocks operation for block {}",
+                    *block_number
+                ))
+            }
+        };
+
+        // Events of the blocks executed earlier in the same transaction precede the block events.
+        let offset: usize = blocks
+            .iter()
+            .filter(|block| block.block_number < block_number)
+            .map(|block| FungibleWithdrawal::from_block(block).len())
+            .sum();
+        let total: usize = blocks
+            .iter()
+            .map(|block| FungibleWithdrawal::from_block(block).len())
+            .sum();
+        let withdrawals = blocks
+            .iter()
+            .find(|block| block.block_number == block_number)
+            .map(FungibleWithdrawal::from_block)
+            .unwrap_or_default();
+
+        let statuses = self.withdrawal_statuses(eth_tx_hash).await?;
+        // The block is retried on the next poll, so it's never marked as tracked without the withdrawals.
+        if statuses.len() != total {
+            self.receipt_cache = None;
+            metrics::increment_counter!("withdrawals_tracker.events_mismatch");
+            return Err(format_err!(
+                "Withdrawal events of transaction {:#x} don't match the executed blocks: expected {}, got {}",
+                eth_tx_hash,
+                total,
+                statuses.len()
+            ));
+        }
+        let mut events = Vec::with_capacity(withdrawals.len());
+        for (withdrawal, (token_id, status)) in withdrawals.into_iter().zip(&statuses[offset..]) {
+            if withdrawal.token_id != *token_id {
+                self.receipt_cache = None;
+                metrics::increment_counter!("withdrawals_tracker.events_mismatch");
+                return Err(format_err!(
+                    "Token mismatch for withdrawal {} in block {}: expected {}, got {}",
+                    withdrawal.tx_hash.to_string(),
+                    *block_number,
+                    *withdrawal.token_id,
+                    **token_id
+                ));
+            }
+            events.push(WithdrawalEvent {
+                tx_hash: withdrawal.tx_hash,
+                address: withdrawal.address,
+                token_id: withdrawal.token_id,
+                amount: withdrawal.amount,
+                status: *status,
+                eth_tx_hash,
+            });
+        }
+
+        storage
+            .withdrawals_schema()
+            .store_block_withdrawals(block_number, &events)
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the token and the status of every fungible withdrawal processed by the transaction.
+    async fn withdrawal_statuses(
+        &mut self,
+        eth_tx_hash: H256,
+    ) -> anyhow::Result<Vec<(TokenId, WithdrawalStatus)>> {
+        if let Some((hash, statuses)) = &self.receipt_cache {
+            if *hash == eth_tx_hash {
+                return Ok(statuses.clone());
+            }
+        }
+
+        let receipt = self
+            .eth_gateway
+            .tx_receipt(eth_tx_hash)
+            .await?
+            .ok_or_else(|| format_err!("No receipt for transaction {:#x}", eth_tx_hash))?;
+        let statuses: Vec<_> = receipt
+            .logs
+            .into_iter()
+            .filter(|log| log.address == self.zksync_contract_addr && log.topics.len() == 2)
+            .filter_map(|log| {
+                let status = if log.topics[0] == self.topics.withdrawal {
+                    WithdrawalStatus::Executed
+                } else if log.topics[0] == self.topics.withdrawal_pending {
+                    WithdrawalStatus::PendingBalance
+                } else {
+                    return None;
+                };
+                let token_id = U256::from_big_endian(log.topics[1].as_bytes()).as_u32();
+                Some((TokenId(token_id), status))
+            })
+            .collect();
+
+        self.receipt_cache = Some((eth_tx_hash, statuses.clone()));
+        Ok(statuses)
+    }
+
+    /// Marks the pending withdrawals which funds have been claimed by the recipients.
+    async fn track_pending_balances(&mut self) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage().await?;
+        let pending_balances = storage.withdrawals_schema().load_pending_balances().await?;
+        metrics::gauge!(
+            "withdrawals_tracker.pending_balances",
+            pending_balances.len() as f64
+        );
+
+        for (address, token_id) in pending_balances {
+            let token = storage
+                .tokens_schema()
+                .get_token(TokenLike::Id(token_id))
+                .await?
+                .ok_or_else(|| format_err!("Unknown token {}", *token_id))?;
+            let balance: U256 = self
+                .eth_gateway
+                .call_main_contract_function(
+                    "getPendingBalance",
+                    (address, token.address),
+                    None,
+                    Options::default(),
+                    None,
+                )
+                .await
+                .map_err(|e| format_err!("Failed to query contract getPendingBalance: {}", e))?;
+            let mut balance = BigUint::from(balance.as_u128());
+
+            // Funds may be claimed partially, so the latest withdrawals are considered
+            // to be pending as long as the remaining balance covers them.
+            let mut claimed = Vec::new();
+            for withdrawal in storage
+                .withdrawals_schema()
+                .load_pending_withdrawals(address, token_id)
+                .await?
+            {
+                let amount = withdrawal.amount();
+                if amount <= balance {
+                    balance -= amount;
+                } else {
+                    balance = BigUint::from(0u32);
+                    claimed.push(withdrawal);
+                }
+            }
+
+            if !claimed.is_empty() {
+                vlog::info!(
+                    "{} withdrawals of token {} to {:#x} have been claimed",
+                    claimed.len(),
+                    *token_id,
+                    address
+                );
+                storage
+                    .withdrawals_schema()
+                    .mark_withdrawals_claimed(claimed)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn run(mut self, poll_interval: Duration) {
+        let mut timer = time::interval(poll_interval);
+        loop {
+            timer.tick().await;
+
+            if let Err(err) = self.track_executed_blocks().await {
+                vlog::error!("Failed to track executed withdrawals: {}", err);
+                continue;
+            }
+            if let Err(err) = self.track_pending_balances().await {
+                vlog::error!("Failed to track pending balances: {}", err);
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn run_withdrawals_tracker(
+    eth_gateway: EthereumGateway,
+    pool: ConnectionPool,
+    config: &ZkSyncConfig,
+) -> JoinHandle<()> {
+    let tracker = WithdrawalsTracker::new(eth_gateway, pool, config.contracts.contract_addr);
+    tokio::spawn(tracker.run(config.eth_watch.withdrawals_tracker_poll_interval()))
+}
@@ -4,11 +4,12 @@
 //! every transaction is executed successfully and confirmed.
 
 // Built-in deps
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
 // External uses
 use anyhow::format_err;
 use tokio::{task::JoinHandle, time};
+use vlog::Instrument;
 use web3::{
     contract::Options,
     types::{TransactionReceipt, H256, U256},
@@ -121,6 +122,9 @@ struct ETHSender<DB: DatabaseInterface> {
     gas_adjuster: GasAdjuster<DB>,
     /// Settings for the `ETHSender`.
     options: ETHSenderConfig,
+    /// Spans covering the whole processing of the ongoing operations, keyed by operation ID.
+    /// Spans of the processing steps follow from them.
+    operation_spans: HashMap<i64, vlog::Span>,
 }
 
 impl<DB: DatabaseInterface> ETHSender<DB> {
@@ -179,6 +183,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             tx_queue,
             gas_adjuster,
             options,
+            operation_spans: HashMap::new(),
         }
     }
 
@@ -259,7 +264,13 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         };
 
         while let Some(tx) = self.tx_queue.pop_front() {
-            if let Err(e) = self.initialize_operation(tx.clone(), current_block).await {
+            let span = vlog::info_span!("eth_sender.initialize_operation");
+            self.follow_operation_span(&span, Some(&tx.operation));
+            if let Err(e) = self
+                .initialize_operation(tx.clone(), current_block)
+                .instrument(span)
+                .await
+            {
                 Self::process_error(e).await;
                 // Return the unperformed operation to the queue, since failing the
                 // operation initialization means that it was not stored in the database.
@@ -287,8 +298,11 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 // network issue which won't appear the next time, so we report the situation to the
                 // log and consider the operation pending (meaning that we won't process it on this
                 // step, but will try to do so on the next one).
+                let span = vlog::info_span!("eth_sender.commitment_step");
+                self.follow_operation_span(&span, current_op.op.as_ref());
                 let commitment = match self
                     .perform_commitment_step(&mut current_op, current_block)
+                    .instrument(span)
                     .await
                 {
                     Ok(commitment) => commitment,
@@ -302,6 +316,9 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     OperationCommitment::Committed => {
                         // Free a slot for the next tx in the queue.
                         self.tx_queue.report_commitment();
+                        if let Some((id, _)) = &current_op.op {
+                            self.operation_spans.remove(id);
+                        }
                     }
                     OperationCommitment::Pending => {
                         // Poll this operation on the next iteration.
@@ -321,6 +338,22 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         current_block
     }
 
+    /// Links the span of the operation processing step to the span of the whole operation processing.
+    /// The latter is created on the first step and ends once the operation is committed.
+    fn follow_operation_span(
+        &mut self,
+        span: &vlog::Span,
+        operation: Option<&(i64, AggregatedOperation)>,
+    ) {
+        if let Some(operation) = operation {
+            let operation_span = self
+                .operation_spans
+                .entry(operation.0)
+                .or_insert_with(|| operation_span(operation));
+            span.follows_from(&*operation_span);
+        }
+    }
+
     async fn process_error(err: anyhow::Error) {
         vlog::warn!("Error while trying to complete uncommitted op: {}", err);
         if err.to_string().contains(RATE_LIMIT_HTTP_CODE) {
@@ -794,6 +827,20 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     }
 }
 
+/// Creates the span of the operation processing, so the Ethereum transactions can be
+/// correlated with the zkSync blocks they include.
+fn operation_span((id, op): &(i64, AggregatedOperation)) -> vlog::Span {
+    let (first_block, last_block) = op.get_block_range();
+    vlog::info_span!(
+        parent: None,
+        "eth_sender.operation",
+        id,
+        action = %op.get_action_type().to_string(),
+        first_block = *first_block,
+        last_block = *last_block
+    )
+}
+
 #[must_use]
 pub fn run_eth_sender(
     pool: ConnectionPool,
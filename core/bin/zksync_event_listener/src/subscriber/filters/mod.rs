@@ -8,11 +8,15 @@ use zksync_types::event::ZkSyncEvent;
 use serde::de::{MapAccess, Visitor};
 use serde::{Deserialize, Deserializer};
 // Local uses
-use self::{account::AccountFilter, block::BlockFilter, transaction::TransactionFilter};
+use self::{
+    account::AccountFilter, block::BlockFilter, transaction::TransactionFilter,
+    withdrawal::WithdrawalFilter,
+};
 
 mod account;
 mod block;
 mod transaction;
+mod withdrawal;
 
 #[cfg(test)]
 mod tests;
@@ -22,6 +26,7 @@ pub enum EventFilter {
     Account(AccountFilter),
     Block(BlockFilter),
     Transaction(TransactionFilter),
+    Withdrawal(WithdrawalFilter),
 }
 
 impl EventFilter {
@@ -30,6 +35,7 @@ impl EventFilter {
             EventFilter::Account(account_filter) => account_filter.matches(event),
             EventFilter::Block(block_filter) => block_filter.matches(event),
             EventFilter::Transaction(tx_filter) => tx_filter.matches(event),
+            EventFilter::Withdrawal(withdrawal_filter) => withdrawal_filter.matches(event),
         }
     }
 }
@@ -69,6 +75,9 @@ impl<'de> Visitor<'de> for EventFiltersVisitor {
                 EventType::Transaction => {
                     EventFilter::Transaction(access.next_value::<TransactionFilter>()?)
                 }
+                EventType::Withdrawal => {
+                    EventFilter::Withdrawal(access.next_value::<WithdrawalFilter>()?)
+                }
             };
 
             map.insert(key, value);
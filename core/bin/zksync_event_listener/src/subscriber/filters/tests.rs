@@ -103,6 +103,12 @@ fn test_filters_deserialize() {
             },
             "transaction": {}
         }"#,
+        r#"{
+            "withdrawal": {
+                "status": "pending_balance",
+                "tokens": [0, 1]
+            }
+        }"#,
     ];
     for (i, input) in VALID.iter().enumerate() {
         let result = serde_json::from_str::<SubscriberFilters>(input);
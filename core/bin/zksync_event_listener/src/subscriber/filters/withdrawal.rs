@@ -0,0 +1,83 @@
+// Built-in uses
+use std::collections::HashSet;
+// External uses
+use serde::Deserialize;
+// Workspace uses
+use zksync_types::{
+    event::{withdrawal::*, EventData, ZkSyncEvent},
+    Address, TokenId,
+};
+// Local uses
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WithdrawalFilter {
+    pub addresses: Option<HashSet<Address>>,
+    pub tokens: Option<HashSet<TokenId>>,
+    pub status: Option<WithdrawalStatus>,
+}
+
+impl WithdrawalFilter {
+    pub fn matches(&self, event: &ZkSyncEvent) -> bool {
+        let withdrawal_event = match &event.data {
+            EventData::Withdrawal(withdrawal_event) => withdrawal_event,
+            _ => return false,
+        };
+        if let Some(status) = &self.status {
+            if withdrawal_event.status != *status {
+                return false;
+            }
+        }
+        if let Some(token_ids) = &self.tokens {
+            if !token_ids.contains(&withdrawal_event.token_id) {
+                return false;
+            }
+        }
+        if let Some(addresses) = &self.addresses {
+            if !addresses.contains(&withdrawal_event.address) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::event::test_data::get_withdrawal_event;
+
+    #[test]
+    fn test_withdrawal_filter() {
+        let address = Address::from_low_u64_be(1);
+        let other_address = Address::from_low_u64_be(2);
+
+        // Match all events.
+        let mut withdrawal_filter = WithdrawalFilter {
+            addresses: None,
+            tokens: None,
+            status: None,
+        };
+        let event = get_withdrawal_event(address, TokenId(0), WithdrawalStatus::Executed);
+        assert!(withdrawal_filter.matches(&event));
+
+        // Only match by address.
+        withdrawal_filter.addresses = Some([address].iter().copied().collect());
+        assert!(withdrawal_filter.matches(&event));
+        let event = get_withdrawal_event(other_address, TokenId(0), WithdrawalStatus::Executed);
+        assert!(!withdrawal_filter.matches(&event));
+
+        // Add token filter.
+        withdrawal_filter.tokens = Some([TokenId(10)].iter().copied().collect());
+        let event = get_withdrawal_event(address, TokenId(0), WithdrawalStatus::Executed);
+        assert!(!withdrawal_filter.matches(&event));
+        let event = get_withdrawal_event(address, TokenId(10), WithdrawalStatus::PendingBalance);
+        assert!(withdrawal_filter.matches(&event));
+
+        // Finally, add a status filter.
+        withdrawal_filter.status = Some(WithdrawalStatus::PendingBalance);
+        assert!(withdrawal_filter.matches(&event));
+        let event = get_withdrawal_event(address, TokenId(10), WithdrawalStatus::Claimed);
+        assert!(!withdrawal_filter.matches(&event));
+    }
+}
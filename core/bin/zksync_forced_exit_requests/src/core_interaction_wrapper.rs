@@ -3,14 +3,15 @@ use num::Zero;
 use zksync_config::ZkSyncConfig;
 use zksync_storage::{chain::operations_ext::records::TxReceiptResponse, ConnectionPool};
 use zksync_types::{
-    forced_exit_requests::{ForcedExitRequest, ForcedExitRequestId},
+    forced_exit_requests::{ForcedExitRequest, ForcedExitRequestId, ForcedExitRequestState},
     tx::TxHash,
-    AccountId, Nonce,
+    AccountId, Address, Nonce,
 };
 
 use zksync_api::{
     api_server::forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
     core_api_client::CoreApiClient,
+    tx_error::TxAddError,
 };
 use zksync_types::SignedZkSyncTx;
 
@@ -41,6 +42,23 @@ pub trait CoreInteractionWrapper {
         deleting_threshold: chrono::Duration,
     ) -> anyhow::Result<()>;
     async fn check_forced_exit_request(&self, request: &ForcedExitRequest) -> anyhow::Result<bool>;
+    async fn update_state(
+        &self,
+        id: ForcedExitRequestId,
+        state: ForcedExitRequestState,
+        tx_hashes: Vec<TxHash>,
+    ) -> anyhow::Result<()>;
+    async fn set_payer(&self, id: ForcedExitRequestId, payer: Address) -> anyhow::Result<()>;
+    async fn get_requests_by_state(
+        &self,
+        state: ForcedExitRequestState,
+    ) -> anyhow::Result<Vec<ForcedExitRequest>>;
+    async fn send_refund(&self, tx: SignedZkSyncTx) -> anyhow::Result<Result<(), TxAddError>>;
+    async fn set_refund(
+        &self,
+        id: ForcedExitRequestId,
+        refund: Option<(TxHash, Nonce)>,
+    ) -> anyhow::Result<()>;
 }
 
 #[derive(Clone)]
@@ -198,4 +216,64 @@ impl CoreInteractionWrapper for MempoolCoreInteractionWrapper {
             Ok(false)
         }
     }
+
+    async fn update_state(
+        &self,
+        id: ForcedExitRequestId,
+        state: ForcedExitRequestState,
+        tx_hashes: Vec<TxHash>,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        storage
+            .forced_exit_requests_schema()
+            .update_state(id, state, &tx_hashes)
+            .await?;
+
+        let labels = vec![("state", state.as_str())];
+        metrics::increment_counter!("forced_exit_requests.state_changes", &labels);
+        vlog::info!("ForcedExit request with id {} is {}", id, state.as_str());
+
+        Ok(())
+    }
+
+    async fn set_payer(&self, id: ForcedExitRequestId, payer: Address) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        storage
+            .forced_exit_requests_schema()
+            .set_payer(id, payer)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_requests_by_state(
+        &self,
+        state: ForcedExitRequestState,
+    ) -> anyhow::Result<Vec<ForcedExitRequest>> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let requests = storage
+            .forced_exit_requests_schema()
+            .get_requests_by_state(state)
+            .await?;
+
+        Ok(requests)
+    }
+
+    async fn send_refund(&self, tx: SignedZkSyncTx) -> anyhow::Result<Result<(), TxAddError>> {
+        self.core_api_client.send_tx(tx).await
+    }
+
+    async fn set_refund(
+        &self,
+        id: ForcedExitRequestId,
+        refund: Option<(TxHash, Nonce)>,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        storage
+            .forced_exit_requests_schema()
+            .set_refund(id, refund)
+            .await?;
+
+        Ok(())
+    }
 }
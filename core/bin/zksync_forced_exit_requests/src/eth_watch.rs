@@ -11,7 +11,7 @@ use tokio::time;
 use web3::{
     contract::Contract,
     transports::Http,
-    types::{BlockNumber, FilterBuilder, Log},
+    types::{BlockNumber, FilterBuilder, Log, TransactionId},
     Web3,
 };
 use zksync_config::ZkSyncConfig;
@@ -96,6 +96,23 @@ impl EthHttpClient {
         )
         .await
     }
+
+    async fn load_payers(
+        &self,
+        mut events: Vec<FundsReceivedEvent>,
+    ) -> anyhow::Result<Vec<FundsReceivedEvent>> {
+        for event in &mut events {
+            if let Some(tx_hash) = event.tx_hash {
+                let tx = self
+                    .web3
+                    .eth()
+                    .transaction(TransactionId::Hash(tx_hash))
+                    .await?;
+                event.payer = tx.and_then(|tx| tx.from);
+            }
+        }
+        Ok(events)
+    }
 }
 
 #[async_trait::async_trait]
@@ -110,6 +127,12 @@ impl EthClient for EthHttpClient {
             .get_events(from, to, vec![self.topics.funds_received])
             .await;
 
+        // Payer is not a part of the event, so it is taken from the transaction with the payment
+        let result = match result {
+            Ok(events) => self.load_payers(events).await,
+            Err(err) => Err(err),
+        };
+
         metrics::histogram!(
             "forced_exit_requests.get_funds_received_events",
             start.elapsed()
@@ -326,12 +349,18 @@ where
 
         for e in events {
             self.forced_exit_sender
-                .process_request(e.amount, lower_bound_block_time(e.block_number, last_block))
+                .process_request(
+                    e.amount,
+                    lower_bound_block_time(e.block_number, last_block),
+                    e.payer,
+                )
                 .await;
         }
 
         self.last_viewed_block = last_confirmed_block;
 
+        self.forced_exit_sender.process_pending_requests().await;
+
         if Utc::now().sub(self.db_cleanup_interval) > self.last_db_cleanup_time {
             if let Err(err) = self.delete_expired().await {
                 // If an error during deletion occures we should be notified, however
@@ -481,7 +510,10 @@ mod test {
     use num::{BigUint, FromPrimitive};
     use std::{str::FromStr, sync::Mutex};
     use zksync_config::ZkSyncConfig;
-    use zksync_types::{forced_exit_requests::ForcedExitRequest, Address, TokenId};
+    use zksync_types::{
+        forced_exit_requests::{ForcedExitRequest, ForcedExitRequestState},
+        Address, TokenId,
+    };
 
     use super::*;
     use crate::test::{add_request, MockCoreInteractionWrapper};
@@ -526,13 +558,20 @@ mod test {
 
     #[async_trait::async_trait]
     impl ForcedExitSender for DummyForcedExitSender {
-        async fn process_request(&self, amount: BigUint, submission_time: DateTime<Utc>) {
+        async fn process_request(
+            &self,
+            amount: BigUint,
+            submission_time: DateTime<Utc>,
+            _payer: Option<Address>,
+        ) {
             let mut write_lock = self
                 .processed_requests
                 .lock()
                 .expect("Failed to get write lock for processed_requests");
             (*write_lock).push((amount, submission_time));
         }
+
+        async fn process_pending_requests(&self) {}
     }
 
     type TestForcedExitContractWatcher =
@@ -576,6 +615,10 @@ mod test {
             created_at: Utc::now().sub(week).sub(three_days),
             fulfilled_at: None,
             fulfilled_by: None,
+            state: ForcedExitRequestState::Created,
+            payer: None,
+            refunded_by: None,
+            refund_nonce: None,
         };
 
         add_request(
@@ -641,6 +684,10 @@ mod test {
             created_at: Utc::now().sub(chrono::Duration::milliseconds(1)),
             fulfilled_at: None,
             fulfilled_by: None,
+            state: ForcedExitRequestState::Created,
+            payer: None,
+            refunded_by: None,
+            refund_nonce: None,
         }]);
 
         watcher
@@ -666,6 +713,10 @@ mod test {
             created_at: Utc::now().sub(chrono::Duration::weeks(1)),
             fulfilled_at: None,
             fulfilled_by: None,
+            state: ForcedExitRequestState::Created,
+            payer: None,
+            refunded_by: None,
+            refund_nonce: None,
         }]);
 
         watcher
@@ -690,16 +741,22 @@ mod test {
                 // Should be processed
                 amount: BigUint::from_str("1000000001").unwrap(),
                 block_number: TEST_FIRST_CURRENT_BLOCK - 2 * wait_confirmations,
+                tx_hash: None,
+                payer: None,
             },
             FundsReceivedEvent {
                 amount: BigUint::from_str("1000000002").unwrap(),
                 // Should be processed
                 block_number: TEST_FIRST_CURRENT_BLOCK - wait_confirmations - 1,
+                tx_hash: None,
+                payer: None,
             },
             FundsReceivedEvent {
                 amount: BigUint::from_str("1000000003").unwrap(),
                 // Should not be processed
                 block_number: TEST_FIRST_CURRENT_BLOCK - 1,
+                tx_hash: None,
+                payer: None,
             },
         ];
 
@@ -7,12 +7,15 @@ use tokio::time;
 use zksync_config::ZkSyncConfig;
 
 use zksync_types::{
-    forced_exit_requests::ForcedExitRequest, tx::TimeRange, tx::TxHash, AccountId, Address, Nonce,
-    TokenId, ZkSyncTx,
+    forced_exit_requests::{ForcedExitRequest, ForcedExitRequestState},
+    helpers::closest_greater_or_eq_packable_token_amount,
+    tx::TimeRange,
+    tx::TxHash,
+    AccountId, Address, Nonce, TokenId, ZkSyncTx,
 };
 
-use zksync_types::ForcedExit;
 use zksync_types::SignedZkSyncTx;
+use zksync_types::{ForcedExit, Transfer};
 
 use crate::{core_interaction_wrapper::CoreInteractionWrapper, utils};
 
@@ -24,7 +27,16 @@ const PROCESSING_ATTEMPTS: u32 = 3;
 
 #[async_trait::async_trait]
 pub trait ForcedExitSender {
-    async fn process_request(&self, amount: BigUint, submission_time: DateTime<Utc>);
+    async fn process_request(
+        &self,
+        amount: BigUint,
+        submission_time: DateTime<Utc>,
+        payer: Option<Address>,
+    );
+    /// Moves the already processed requests further through their lifecycle:
+    /// retries the requests which were paid but not fulfilled, finalizes the
+    /// committed requests and refunds the expired ones.
+    async fn process_pending_requests(&self);
 }
 
 pub struct MempoolForcedExitSender<T: CoreInteractionWrapper> {
@@ -36,14 +48,19 @@ pub struct MempoolForcedExitSender<T: CoreInteractionWrapper> {
 
 #[async_trait::async_trait]
 impl<T: CoreInteractionWrapper + Sync + Send> ForcedExitSender for MempoolForcedExitSender<T> {
-    async fn process_request(&self, amount: BigUint, submission_time: DateTime<Utc>) {
+    async fn process_request(
+        &self,
+        amount: BigUint,
+        submission_time: DateTime<Utc>,
+        payer: Option<Address>,
+    ) {
         let mut attempts: u32 = 0;
         // Typically this should not run any longer than 1 iteration
         // In case something bad happens we do not want the server crush because
         // of the forced_exit_requests component
         loop {
             let processing_attempt = self
-                .try_process_request(amount.clone(), submission_time)
+                .try_process_request(amount.clone(), submission_time, payer)
                 .await;
 
             if processing_attempt.is_ok() {
@@ -58,6 +75,24 @@ impl<T: CoreInteractionWrapper + Sync + Send> ForcedExitSender for MempoolForced
             }
         }
     }
+
+    async fn process_pending_requests(&self) {
+        if let Err(err) = self.retry_unfulfilled_requests().await {
+            vlog::warn!("Failed to retry the paid forced exit requests: {}", err);
+        }
+        if let Err(err) = self.confirm_submitted_requests().await {
+            vlog::warn!(
+                "Failed to confirm the submitted forced exit requests: {}",
+                err
+            );
+        }
+        if let Err(err) = self.finalize_committed_requests().await {
+            vlog::warn!("Failed to finalize the forced exit requests: {}", err);
+        }
+        if let Err(err) = self.refund_expired_requests().await {
+            vlog::warn!("Failed to refund the expired forced exit requests: {}", err);
+        }
+    }
 }
 
 impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
@@ -102,6 +137,28 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
         }
     }
 
+    pub fn build_refund(&self, nonce: Nonce, payer: Address, amount: &BigUint) -> SignedZkSyncTx {
+        // Refund is made in ETH, since it was the currency of the payment
+        let tx = Transfer::new_signed(
+            self.forced_exit_sender_account_id,
+            self.config.forced_exit_requests.sender_account_address,
+            payer,
+            TokenId(0),
+            // The refund must not be less than the payment, so the amount is rounded up
+            closest_greater_or_eq_packable_token_amount(amount),
+            BigUint::from(0u32),
+            nonce,
+            TimeRange::default(),
+            &self.sender_private_key,
+        )
+        .expect("Failed to create signed Transfer transaction");
+
+        SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(tx)),
+            eth_sign_data: None,
+        }
+    }
+
     pub async fn build_transactions(
         &self,
         // storage: &mut StorageProcessor<'_>,
@@ -123,14 +180,9 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
         Ok(transactions)
     }
 
-    // Returns the id the request if it should be fulfilled,
-    // error otherwise
-    pub fn check_request(
-        &self,
-        amount: BigUint,
-        submission_time: DateTime<Utc>,
-        request: Option<ForcedExitRequest>,
-    ) -> bool {
+    // Returns true if the payment is made for the request that was not processed yet.
+    // Note that the expired requests are accepted too, so that the payment could be refunded
+    pub fn check_request(&self, amount: BigUint, request: Option<ForcedExitRequest>) -> bool {
         let request = match request {
             Some(r) => r,
             None => {
@@ -139,12 +191,12 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
             }
         };
 
-        if request.fulfilled_at.is_some() {
-            // We should not re-process requests that were fulfilled before
+        if !request.state.is_awaiting_processing() {
+            // We should not re-process requests that were processed before
             return false;
         }
 
-        request.valid_until > submission_time && request.price_in_wei == amount
+        request.price_in_wei == amount
     }
 
     // Awaits until the request is complete
@@ -155,12 +207,15 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
         let hashes = request.fulfilled_by.clone();
 
         if let Some(hashes) = hashes {
-            for hash in hashes.into_iter() {
+            for hash in hashes.iter().copied() {
                 self.wait_until_comitted(hash).await?;
                 self.core_interaction_wrapper
                     .set_fulfilled_at(request.id)
                     .await?;
             }
+            self.core_interaction_wrapper
+                .update_state(request.id, ForcedExitRequestState::Committed, hashes)
+                .await?;
         }
         Ok(())
     }
@@ -184,6 +239,9 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
                 self.core_interaction_wrapper
                     .set_fulfilled_by(request.id, None)
                     .await?;
+                self.core_interaction_wrapper
+                    .update_state(request.id, ForcedExitRequestState::Paid, vec![])
+                    .await?;
             }
         }
 
@@ -200,9 +258,13 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
 
         loop {
             if time_passed >= timeout_millis {
-                // If a transaction takes more than 2 minutes to commit we consider the server
-                // broken and panic
-                panic!("Comitting ForcedExit transaction failed!");
+                // The transaction may still be committed later, the request will be
+                // checked again during the next poll
+                anyhow::bail!(
+                    "Transaction {} was not committed in {} ms",
+                    tx_hash,
+                    timeout_millis
+                );
             }
 
             let receipt = self.core_interaction_wrapper.get_receipt(tx_hash).await?;
@@ -220,10 +282,239 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
         }
     }
 
+    pub async fn finalize_committed_requests(&self) -> anyhow::Result<()> {
+        let requests = self
+            .core_interaction_wrapper
+            .get_requests_by_state(ForcedExitRequestState::Committed)
+            .await?;
+
+        for request in requests.into_iter() {
+            let hashes = request.fulfilled_by.unwrap_or_default();
+            // The transactions are sent in a batch, so they are verified in the same block
+            let verified = match hashes.first() {
+                Some(hash) => self
+                    .core_interaction_wrapper
+                    .get_receipt(*hash)
+                    .await?
+                    .map(|receipt| receipt.verified)
+                    .unwrap_or(false),
+                None => false,
+            };
+
+            if verified {
+                self.core_interaction_wrapper
+                    .update_state(request.id, ForcedExitRequestState::Finalized, hashes)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn refund_expired_requests(&self) -> anyhow::Result<()> {
+        let requests = self
+            .core_interaction_wrapper
+            .get_requests_by_state(ForcedExitRequestState::Expired)
+            .await?;
+
+        for request in requests.into_iter() {
+            // A failed refund must not prevent the other ones from being processed,
+            // it will be retried during the next poll
+            if let Err(err) = self.refund_request(&request).await {
+                vlog::error!(
+                    "Refund for the forced exit request with id {} has failed: {}",
+                    request.id,
+                    err
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn refund_request(&self, request: &ForcedExitRequest) -> anyhow::Result<()> {
+        let payer = match request.payer {
+            Some(payer) => payer,
+            None => {
+                vlog::error!(
+                    "Forced exit request with id {} can not be refunded: the payer is unknown",
+                    request.id
+                );
+                return Ok(());
+            }
+        };
+
+        // The refund could have been sent before: it must not be sent again
+        // unless it is known that the previous one will never be executed
+        if let (Some(refund_hash), Some(refund_nonce)) = (request.refunded_by, request.refund_nonce)
+        {
+            // The nonce must be loaded before the receipt: otherwise the refund could be
+            // committed in between and would be considered as never executed
+            let sender_nonce = self.get_sender_nonce().await?;
+            match self
+                .core_interaction_wrapper
+                .get_receipt(refund_hash)
+                .await?
+            {
+                Some(receipt) if receipt.success => {
+                    self.core_interaction_wrapper
+                        .update_state(
+                            request.id,
+                            ForcedExitRequestState::Refunded,
+                            vec![refund_hash],
+                        )
+                        .await?;
+                    return Ok(());
+                }
+                Some(receipt) => {
+                    vlog::error!(
+                        "Refund for the forced exit request with id {} has failed: {:?}. Sending it again",
+                        request.id,
+                        receipt.fail_reason
+                    );
+                }
+                None => {
+                    if sender_nonce <= refund_nonce {
+                        // The refund is not executed yet, but it still can be
+                        return Ok(());
+                    }
+                    // The nonce is used by another transaction, so the refund will never be executed
+                    vlog::warn!(
+                        "Refund for the forced exit request with id {} was not executed. Sending it again",
+                        request.id
+                    );
+                }
+            }
+            self.core_interaction_wrapper
+                .set_refund(request.id, None)
+                .await?;
+        }
+
+        let nonce = self.get_sender_nonce().await?;
+        let tx = self.build_refund(nonce, payer, &request.price_in_wei);
+        let refund_hash = tx.hash();
+
+        // The refund is saved before sending, so that after a failure it's possible
+        // to find out whether it was executed
+        self.core_interaction_wrapper
+            .set_refund(request.id, Some((refund_hash, nonce)))
+            .await?;
+
+        if let Err(err) = self.core_interaction_wrapper.send_refund(tx).await? {
+            // The transaction was rejected by the mempool, so it's safe to send it again
+            self.core_interaction_wrapper
+                .set_refund(request.id, None)
+                .await?;
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
+    async fn get_sender_nonce(&self) -> anyhow::Result<Nonce> {
+        let nonce = self
+            .core_interaction_wrapper
+            .get_nonce(self.forced_exit_sender_account_id)
+            .await?
+            .expect("Forced Exit sender account does not have nonce");
+        Ok(nonce)
+    }
+
+    /// Moves the submitted requests which transactions are committed to the `Committed` state.
+    /// Requests which transactions have failed are returned to the `Paid` state to be retried.
+    pub async fn confirm_submitted_requests(&self) -> anyhow::Result<()> {
+        let requests = self
+            .core_interaction_wrapper
+            .get_requests_by_state(ForcedExitRequestState::Submitted)
+            .await?;
+
+        for request in requests.into_iter() {
+            let hashes = request.fulfilled_by.unwrap_or_default();
+            // The transactions are sent in a batch, so it's enough to check the first one
+            let receipt = match hashes.first() {
+                Some(hash) => self.core_interaction_wrapper.get_receipt(*hash).await?,
+                None => None,
+            };
+
+            match receipt {
+                Some(receipt) if receipt.success => {
+                    self.core_interaction_wrapper
+                        .set_fulfilled_at(request.id)
+                        .await?;
+                    self.core_interaction_wrapper
+                        .update_state(request.id, ForcedExitRequestState::Committed, hashes)
+                        .await?;
+                }
+                Some(_) => {
+                    vlog::error!(
+                        "ForcedExit transactions for the request with id {} have failed",
+                        request.id
+                    );
+                    self.core_interaction_wrapper
+                        .set_fulfilled_by(request.id, None)
+                        .await?;
+                    self.core_interaction_wrapper
+                        .update_state(request.id, ForcedExitRequestState::Paid, vec![])
+                        .await?;
+                }
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retries the requests which were paid, but the transactions for them were not sent,
+    /// e.g. because of a failure during the processing or a restart.
+    pub async fn retry_unfulfilled_requests(&self) -> anyhow::Result<()> {
+        let mut requests = self
+            .core_interaction_wrapper
+            .get_requests_by_state(ForcedExitRequestState::Paid)
+            .await?;
+        requests.extend(
+            self.core_interaction_wrapper
+                .get_requests_by_state(ForcedExitRequestState::Scheduled)
+                .await?,
+        );
+
+        for request in requests.into_iter() {
+            if request.state == ForcedExitRequestState::Scheduled {
+                // The batch could have been accepted by the mempool right before the failure
+                let hashes = request.fulfilled_by.clone().unwrap_or_default();
+                let sent = match hashes.first() {
+                    Some(hash) => self
+                        .core_interaction_wrapper
+                        .get_receipt(*hash)
+                        .await?
+                        .is_some(),
+                    None => false,
+                };
+                if sent {
+                    self.core_interaction_wrapper
+                        .update_state(request.id, ForcedExitRequestState::Submitted, hashes)
+                        .await?;
+                    continue;
+                }
+            }
+
+            let id = request.id;
+            if let Err(err) = self.fulfill_request(request).await {
+                vlog::warn!(
+                    "Failed to fulfill the forced exit request with id {}: {}",
+                    id,
+                    err
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     pub async fn try_process_request(
         &self,
         amount: BigUint,
         submission_time: DateTime<Utc>,
+        payer: Option<Address>,
     ) -> anyhow::Result<()> {
         let (id, amount) = utils::extract_id_from_amount(
             amount,
@@ -232,7 +523,7 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
 
         let fe_request = self.core_interaction_wrapper.get_request_by_id(id).await?;
 
-        let fe_request = if self.check_request(amount, submission_time, fe_request.clone()) {
+        let fe_request = if self.check_request(amount, fe_request.clone()) {
             // The self.check_request already checked that the fe_request is Some(_)
             fe_request.unwrap()
         } else {
@@ -240,6 +531,30 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
             return Ok(());
         };
 
+        if fe_request.state == ForcedExitRequestState::Created {
+            if let Some(payer) = payer {
+                self.core_interaction_wrapper.set_payer(id, payer).await?;
+            }
+            self.core_interaction_wrapper
+                .update_state(id, ForcedExitRequestState::Paid, vec![])
+                .await?;
+        }
+
+        if fe_request.valid_until <= submission_time {
+            // The payment came too late, it will be refunded
+            self.core_interaction_wrapper
+                .update_state(id, ForcedExitRequestState::Expired, vec![])
+                .await?;
+            return Ok(());
+        }
+
+        self.fulfill_request(fe_request).await
+    }
+
+    /// Sends the `ForcedExit` transactions for the paid request and waits until they are committed.
+    /// If the request is not `Committed` after an error, it is retried during the next poll.
+    async fn fulfill_request(&self, fe_request: ForcedExitRequest) -> anyhow::Result<()> {
+        let id = fe_request.id;
         let txs = self.build_transactions(fe_request.clone()).await?;
 
         // Right before sending the transactions we must check if the request is possible at all
@@ -248,18 +563,47 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
             .check_forced_exit_request(&fe_request)
             .await?;
         if !is_request_possible {
-            // If not possible at all, return without sending any transactions
+            // If not possible at all, refund the payment without sending any transactions
+            self.core_interaction_wrapper
+                .update_state(id, ForcedExitRequestState::Expired, vec![])
+                .await?;
             return Ok(());
         }
-        let hashes = self
+
+        let hashes: Vec<TxHash> = txs.iter().map(|tx| tx.hash()).collect();
+        // The hashes are saved before sending, so that after a failure it's possible
+        // to find out whether the transactions were sent
+        self.core_interaction_wrapper
+            .set_fulfilled_by(id, Some(hashes.clone()))
+            .await?;
+        self.core_interaction_wrapper
+            .update_state(id, ForcedExitRequestState::Scheduled, hashes.clone())
+            .await?;
+        let sent = self
             .core_interaction_wrapper
             .send_and_save_txs_batch(&fe_request, txs)
+            .await;
+        if let Err(err) = sent {
+            // Transactions were not accepted, so the request can be processed again
+            self.core_interaction_wrapper
+                .set_fulfilled_by(id, None)
+                .await?;
+            self.core_interaction_wrapper
+                .update_state(id, ForcedExitRequestState::Paid, vec![])
+                .await?;
+            return Err(err);
+        }
+        self.core_interaction_wrapper
+            .update_state(id, ForcedExitRequestState::Submitted, hashes.clone())
             .await?;
 
         // We wait only for the first transaction to complete since the transactions
         // are sent in a batch
         self.wait_until_comitted(hashes[0]).await?;
         self.core_interaction_wrapper.set_fulfilled_at(id).await?;
+        self.core_interaction_wrapper
+            .update_state(id, ForcedExitRequestState::Committed, hashes)
+            .await?;
 
         Ok(())
     }
@@ -272,6 +616,7 @@ mod test {
     };
 
     use zksync_config::ForcedExitRequestsConfig;
+    use zksync_types::helpers::is_token_amount_packable;
 
     use super::*;
     use crate::test::{add_request, MockCoreInteractionWrapper};
@@ -321,12 +666,16 @@ mod test {
                 created_at: Utc::now(),
                 fulfilled_by: None,
                 fulfilled_at: None,
+                state: ForcedExitRequestState::Created,
+                payer: None,
+                refunded_by: None,
+                refund_nonce: None,
             },
         );
 
         // Not the right amount, because not enough zeroes
         forced_exit_sender
-            .process_request(BigUint::from_str("1000000012").unwrap(), Utc::now())
+            .process_request(BigUint::from_str("1000000012").unwrap(), Utc::now(), None)
             .await;
         assert_eq!(
             forced_exit_sender
@@ -340,7 +689,7 @@ mod test {
 
         // Not the right amount, because id is not correct
         forced_exit_sender
-            .process_request(BigUint::from_str("10000000001").unwrap(), Utc::now())
+            .process_request(BigUint::from_str("10000000001").unwrap(), Utc::now(), None)
             .await;
         assert_eq!(
             forced_exit_sender
@@ -357,6 +706,7 @@ mod test {
             .process_request(
                 BigUint::from_str("10000000001").unwrap(),
                 Utc::now().add(day.mul(3)),
+                None,
             )
             .await;
 
@@ -372,7 +722,7 @@ mod test {
 
         // The transaction is correct
         forced_exit_sender
-            .process_request(BigUint::from_str("10000000012").unwrap(), Utc::now())
+            .process_request(BigUint::from_str("10000000012").unwrap(), Utc::now(), None)
             .await;
 
         assert_eq!(
@@ -385,4 +735,138 @@ mod test {
             1
         );
     }
+
+    #[tokio::test]
+    async fn test_forced_exit_sender_refund() {
+        let day = chrono::Duration::days(1);
+
+        let config = ZkSyncConfig::from_env();
+        let forced_exit_requests = ForcedExitRequestsConfig {
+            digits_in_id: 10,
+            ..config.forced_exit_requests
+        };
+        let config = ZkSyncConfig {
+            forced_exit_requests,
+            ..config
+        };
+
+        let forced_exit_sender = get_test_forced_exit_sender(Some(config));
+        let core_interaction_wrapper = &forced_exit_sender.core_interaction_wrapper;
+        let payer = Address::random();
+
+        add_request(
+            &core_interaction_wrapper.requests,
+            ForcedExitRequest {
+                id: 12,
+                target: Address::random(),
+                tokens: vec![TokenId(1)],
+                price_in_wei: BigUint::from_str("10000000000").unwrap(),
+                valid_until: Utc::now().add(day),
+                created_at: Utc::now(),
+                fulfilled_by: None,
+                fulfilled_at: None,
+                state: ForcedExitRequestState::Created,
+                payer: None,
+                refunded_by: None,
+                refund_nonce: None,
+            },
+        );
+
+        // The payment came after the request has expired
+        forced_exit_sender
+            .process_request(
+                BigUint::from_str("10000000012").unwrap(),
+                Utc::now().add(day.mul(3)),
+                Some(payer),
+            )
+            .await;
+
+        assert!(core_interaction_wrapper.sent_txs.lock().unwrap().is_empty());
+        assert_eq!(
+            *core_interaction_wrapper.state_changes.lock().unwrap(),
+            vec![
+                (12, ForcedExitRequestState::Paid),
+                (12, ForcedExitRequestState::Expired)
+            ]
+        );
+
+        // The first poll sends the refund, the second one confirms it
+        forced_exit_sender.process_pending_requests().await;
+        forced_exit_sender.process_pending_requests().await;
+
+        // The refund must not be sent twice
+        let sent_txs = core_interaction_wrapper.sent_txs.lock().unwrap().clone();
+        assert_eq!(sent_txs.len(), 1);
+        match &sent_txs[0].tx {
+            ZkSyncTx::Transfer(transfer) => {
+                assert_eq!(transfer.to, payer);
+                assert_eq!(transfer.token, TokenId(0));
+                assert_eq!(transfer.amount, BigUint::from_str("10000000000").unwrap());
+            }
+            tx => panic!("Unexpected refund transaction: {:?}", tx),
+        }
+
+        let request = core_interaction_wrapper
+            .get_request_by_id(12)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(request.state, ForcedExitRequestState::Refunded);
+        assert_eq!(request.refunded_by, Some(sent_txs[0].hash()));
+        assert_eq!(request.refund_nonce, Some(Nonce(0)));
+    }
+
+    #[tokio::test]
+    async fn test_forced_exit_sender_refund_is_rounded_up() {
+        let forced_exit_sender = get_test_forced_exit_sender(None);
+
+        // The amount can't be packed, so it has to be rounded
+        let price = BigUint::from_str("123456789012345678").unwrap();
+        let refund = forced_exit_sender.build_refund(Nonce(0), Address::random(), &price);
+
+        match &refund.tx {
+            ZkSyncTx::Transfer(transfer) => {
+                assert!(transfer.amount >= price);
+                assert!(is_token_amount_packable(&transfer.amount));
+            }
+            tx => panic!("Unexpected refund transaction: {:?}", tx),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_forced_exit_sender_retries_paid_requests() {
+        let day = chrono::Duration::days(1);
+
+        let forced_exit_sender = get_test_forced_exit_sender(None);
+        let core_interaction_wrapper = &forced_exit_sender.core_interaction_wrapper;
+
+        // The request was paid, but the processing was interrupted before the transactions were sent
+        add_request(
+            &core_interaction_wrapper.requests,
+            ForcedExitRequest {
+                id: 12,
+                target: Address::random(),
+                tokens: vec![TokenId(1)],
+                price_in_wei: BigUint::from_str("10000000000").unwrap(),
+                valid_until: Utc::now().add(day),
+                created_at: Utc::now(),
+                fulfilled_by: None,
+                fulfilled_at: None,
+                state: ForcedExitRequestState::Paid,
+                payer: Some(Address::random()),
+                refunded_by: None,
+                refund_nonce: None,
+            },
+        );
+
+        forced_exit_sender.process_pending_requests().await;
+
+        assert_eq!(core_interaction_wrapper.sent_txs.lock().unwrap().len(), 1);
+        let request = core_interaction_wrapper
+            .get_request_by_id(12)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(request.state, ForcedExitRequestState::Committed);
+    }
 }
@@ -4,11 +4,13 @@ use chrono::Utc;
 use zksync_storage::chain::operations_ext::records::TxReceiptResponse;
 use zksync_types::Nonce;
 use zksync_types::{
-    forced_exit_requests::{ForcedExitRequest, ForcedExitRequestId},
+    forced_exit_requests::{ForcedExitRequest, ForcedExitRequestId, ForcedExitRequestState},
     tx::TxHash,
-    AccountId, SignedZkSyncTx,
+    AccountId, Address, SignedZkSyncTx,
 };
 
+use zksync_api::tx_error::TxAddError;
+
 use super::core_interaction_wrapper::CoreInteractionWrapper;
 
 pub struct MockCoreInteractionWrapper {
@@ -18,6 +20,7 @@ pub struct MockCoreInteractionWrapper {
     pub sent_txs: Mutex<Vec<SignedZkSyncTx>>,
     // It is easier when keeping track of the deleted txs
     pub deleted_requests: Mutex<Vec<ForcedExitRequest>>,
+    pub state_changes: Mutex<Vec<(ForcedExitRequestId, ForcedExitRequestState)>>,
 }
 
 impl Default for MockCoreInteractionWrapper {
@@ -36,6 +39,7 @@ impl Default for MockCoreInteractionWrapper {
             }),
             sent_txs: Mutex::new(vec![]),
             deleted_requests: Mutex::new(vec![]),
+            state_changes: Mutex::new(vec![]),
         }
     }
 }
@@ -168,6 +172,65 @@ impl CoreInteractionWrapper for MockCoreInteractionWrapper {
         // For tests it is better to just return true all the time
         Ok(true)
     }
+
+    async fn update_state(
+        &self,
+        id: ForcedExitRequestId,
+        state: ForcedExitRequestState,
+        _tx_hashes: Vec<TxHash>,
+    ) -> anyhow::Result<()> {
+        let index = self.get_request_index_by_id(id)?;
+        let mut requests = self.lock_requests();
+
+        requests[index].state = state;
+        self.state_changes.lock().unwrap().push((id, state));
+
+        Ok(())
+    }
+
+    async fn set_payer(&self, id: ForcedExitRequestId, payer: Address) -> anyhow::Result<()> {
+        let index = self.get_request_index_by_id(id)?;
+        let mut requests = self.lock_requests();
+
+        requests[index].payer = Some(payer);
+
+        Ok(())
+    }
+
+    async fn get_requests_by_state(
+        &self,
+        state: ForcedExitRequestState,
+    ) -> anyhow::Result<Vec<ForcedExitRequest>> {
+        let requests = self.lock_requests();
+
+        let requests = requests
+            .iter()
+            .filter(|r| r.state == state)
+            .cloned()
+            .collect();
+
+        Ok(requests)
+    }
+
+    async fn send_refund(&self, tx: SignedZkSyncTx) -> anyhow::Result<Result<(), TxAddError>> {
+        self.lock_sent_txs().push(tx);
+
+        Ok(Ok(()))
+    }
+
+    async fn set_refund(
+        &self,
+        id: ForcedExitRequestId,
+        refund: Option<(TxHash, Nonce)>,
+    ) -> anyhow::Result<()> {
+        let index = self.get_request_index_by_id(id)?;
+        let mut requests = self.lock_requests();
+
+        requests[index].refunded_by = refund.map(|(hash, _)| hash);
+        requests[index].refund_nonce = refund.map(|(_, nonce)| nonce);
+
+        Ok(())
+    }
 }
 
 pub fn add_request(requests: &Mutex<Vec<ForcedExitRequest>>, new_request: ForcedExitRequest) {
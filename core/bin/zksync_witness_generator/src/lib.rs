@@ -308,6 +308,8 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
         if let Some(witness) = witness_for_next_single_block {
             let prover_data: ProverData =
                 serde_json::from_value(witness).expect("incorrect single block witness");
+            // Blocks may be sealed into different sizes, so the size is taken from the block itself:
+            // the witness is padded with noops up to the size of the block.
             let block_size = prover_data.operations.len();
             let job_data =
                 serde_json::to_value(JobRequestData::BlockProof(prover_data, block_size))
@@ -356,6 +358,7 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
                     .load_proof(&mut connection, block.block_number)
                     .await?
                     .expect("Single proof should exist");
+                // Aggregated blocks may be of different sizes, the prover picks the verification key per block.
                 let block_size = block.block_chunks_size;
                 data.push((proof, block_size));
             }
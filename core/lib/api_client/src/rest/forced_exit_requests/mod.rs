@@ -6,7 +6,10 @@
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
-use zksync_types::{forced_exit_requests::ForcedExitRequest, Address, TokenId};
+use zksync_types::{
+    forced_exit_requests::{ForcedExitRequest, ForcedExitRequestEvent, ForcedExitRequestId},
+    Address, TokenId,
+};
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
 use num::BigUint;
@@ -61,4 +64,26 @@ impl Client {
             .send()
             .await
     }
+
+    pub async fn get_forced_exit_request(
+        &self,
+        id: ForcedExitRequestId,
+    ) -> ClientResult<ForcedExitRequest> {
+        self.get_with_scope(FORCED_EXIT_REQUESTS_SCOPE, format!("requests/{}", id))
+            .send()
+            .await
+    }
+
+    /// Returns the history of the request state changes, from the oldest to the newest one.
+    pub async fn get_forced_exit_request_events(
+        &self,
+        id: ForcedExitRequestId,
+    ) -> ClientResult<Vec<ForcedExitRequestEvent>> {
+        self.get_with_scope(
+            FORCED_EXIT_REQUESTS_SCOPE,
+            format!("requests/{}/events", id),
+        )
+        .send()
+        .await
+    }
 }
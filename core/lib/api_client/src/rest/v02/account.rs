@@ -4,7 +4,7 @@ use zksync_api_types::v02::{
     pagination::{ApiEither, PaginationQuery},
     Response,
 };
-use zksync_types::{tx::TxHash, SerialId};
+use zksync_types::{tx::TxHash, SerialId, TokenId};
 
 impl Client {
     pub async fn account_info(
@@ -56,4 +56,32 @@ impl Client {
         .send()
         .await
     }
+
+    pub async fn account_nfts(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TokenId>>,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/nfts", account_id_or_address),
+        )
+        .query(pagination_query)
+        .send()
+        .await
+    }
+
+    pub async fn account_minted_nfts(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TokenId>>,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/nfts/minted", account_id_or_address),
+        )
+        .query(pagination_query)
+        .send()
+        .await
+    }
 }
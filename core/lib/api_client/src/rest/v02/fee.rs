@@ -4,7 +4,7 @@ use zksync_api_types::v02::{
     fee::{ApiTxFeeTypes, BatchFeeRequest, TxFeeRequest, TxInBatchFeeRequest},
     Response,
 };
-use zksync_types::{Address, TokenLike};
+use zksync_types::{Address, Nonce, TokenLike};
 
 impl Client {
     pub async fn get_txs_fee(
@@ -18,6 +18,29 @@ impl Client {
                 tx_type,
                 address,
                 token_like,
+                sender: None,
+                nonce: None,
+            })
+            .send()
+            .await
+    }
+
+    /// Requests the fee along with the quote for the transaction with the given sender and nonce.
+    pub async fn get_txs_fee_quote(
+        &self,
+        tx_type: ApiTxFeeTypes,
+        address: Address,
+        token_like: TokenLike,
+        sender: Address,
+        nonce: Nonce,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "fee")
+            .body(&TxFeeRequest {
+                tx_type,
+                address,
+                token_like,
+                sender: Some(sender),
+                nonce: Some(nonce),
             })
             .send()
             .await
@@ -3,7 +3,7 @@ use zksync_api_types::v02::{
     pagination::{ApiEither, PaginationQuery},
     Response,
 };
-use zksync_types::{TokenId, TokenLike};
+use zksync_types::{tx::TxHash, TokenId, TokenLike};
 
 impl Client {
     pub async fn token_pagination(
@@ -42,4 +42,18 @@ impl Client {
             .send()
             .await
     }
+
+    pub async fn nft_txs(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TxHash>>,
+        id: TokenId,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("tokens/nft/{}/transactions", id),
+        )
+        .query(pagination_query)
+        .send()
+        .await
+    }
 }
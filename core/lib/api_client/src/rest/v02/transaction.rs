@@ -3,7 +3,10 @@ use zksync_api_types::{
     v02::{transaction::IncomingTxBatch, Response},
     TxWithSignature,
 };
-use zksync_types::tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, ZkSyncTx};
+use zksync_types::{
+    tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, ZkSyncTx},
+    SerialId,
+};
 
 impl Client {
     pub async fn submit_tx(
@@ -37,6 +40,15 @@ impl Client {
         .await
     }
 
+    pub async fn priority_op_receipt(&self, serial_id: SerialId) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/priorityOps/{}", serial_id),
+        )
+        .send()
+        .await
+    }
+
     pub async fn tx_data(&self, tx_hash: TxHash) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
@@ -46,6 +58,15 @@ impl Client {
         .await
     }
 
+    pub async fn withdrawal_status(&self, tx_hash: TxHash) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/{}/withdrawal", tx_hash.to_string()),
+        )
+        .send()
+        .await
+    }
+
     pub async fn get_batch(&self, batch_hash: TxHash) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
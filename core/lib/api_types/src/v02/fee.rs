@@ -1,6 +1,8 @@
 use num::BigUint;
 use serde::{Deserialize, Serialize};
-use zksync_types::{tokens::ChangePubKeyFeeTypeArg, Address, BatchFee, Fee, TokenLike, TxFeeTypes};
+use zksync_types::{
+    tokens::ChangePubKeyFeeTypeArg, Address, BatchFee, Fee, FeeQuote, Nonce, TokenLike, TxFeeTypes,
+};
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -12,6 +14,9 @@ pub struct ApiFee {
     pub zkp_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Signed quote of the fee, issued only for the single transactions with the known sender and nonce.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote: Option<FeeQuote>,
 }
 
 impl From<Fee> for ApiFee {
@@ -20,6 +25,7 @@ impl From<Fee> for ApiFee {
             gas_fee: fee.gas_fee,
             zkp_fee: fee.zkp_fee,
             total_fee: fee.total_fee,
+            quote: fee.quote,
         }
     }
 }
@@ -30,6 +36,7 @@ impl From<BatchFee> for ApiFee {
             gas_fee: fee.gas_fee,
             zkp_fee: fee.zkp_fee,
             total_fee: fee.total_fee,
+            quote: None,
         }
     }
 }
@@ -71,12 +78,33 @@ impl From<ApiTxFeeTypes> for TxFeeTypes {
     }
 }
 
+impl From<TxFeeTypes> for ApiTxFeeTypes {
+    fn from(fee_type: TxFeeTypes) -> ApiTxFeeTypes {
+        match fee_type {
+            TxFeeTypes::Withdraw => ApiTxFeeTypes::Withdraw,
+            TxFeeTypes::FastWithdraw => ApiTxFeeTypes::FastWithdraw,
+            TxFeeTypes::Transfer => ApiTxFeeTypes::Transfer,
+            TxFeeTypes::ChangePubKey(cpk_arg) => ApiTxFeeTypes::ChangePubKey(cpk_arg),
+            TxFeeTypes::MintNFT => ApiTxFeeTypes::MintNFT,
+            TxFeeTypes::WithdrawNFT => ApiTxFeeTypes::WithdrawNFT,
+            TxFeeTypes::FastWithdrawNFT => ApiTxFeeTypes::FastWithdrawNFT,
+            TxFeeTypes::Swap => ApiTxFeeTypes::Swap,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TxFeeRequest {
     pub tx_type: ApiTxFeeTypes,
     pub address: Address,
     pub token_like: TokenLike,
+    /// Sender of the transaction. The fee is quoted only if the sender and the nonce are provided.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<Address>,
+    /// Nonce of the transaction the fee is quoted for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<Nonce>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
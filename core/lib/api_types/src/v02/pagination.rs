@@ -2,7 +2,7 @@ use either::Either;
 use serde::{Deserialize, Serialize, Serializer};
 use std::str::FromStr;
 use thiserror::Error;
-use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, SerialId};
+use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, SerialId, TokenId};
 
 pub const MAX_LIMIT: u32 = 100;
 
@@ -137,3 +137,21 @@ pub struct AccountTxsRequest {
     pub address: Address,
     pub tx_hash: ApiEither<TxHash>,
 }
+
+#[derive(Debug, Serialize)]
+pub struct AccountNFTsRequest {
+    pub account_id: AccountId,
+    pub token_id: ApiEither<TokenId>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MintedNFTsRequest {
+    pub creator_id: AccountId,
+    pub token_id: ApiEither<TokenId>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NFTTxsRequest {
+    pub token_id: TokenId,
+    pub tx_hash: ApiEither<TxHash>,
+}
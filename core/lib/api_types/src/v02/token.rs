@@ -1,6 +1,6 @@
 use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
-use zksync_types::{AccountId, Address, Token, TokenId, H256};
+use zksync_types::{AccountId, Address, Token, TokenId, TokenKind, H256};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -9,6 +9,7 @@ pub struct ApiToken {
     pub address: Address,
     pub symbol: String,
     pub decimals: u8,
+    pub kind: TokenKind,
     pub enabled_for_fees: bool,
 }
 
@@ -29,6 +30,7 @@ impl ApiToken {
             address: token.address,
             symbol: token.symbol,
             decimals: token.decimals,
+            kind: token.kind,
             enabled_for_fees: eligibility,
         }
     }
@@ -4,15 +4,27 @@ use chrono::{DateTime, Utc};
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 use zksync_types::{
+    event::withdrawal::WithdrawalStatus,
     tx::{
         ChangePubKey, Close, EthBatchSignatures, ForcedExit, MintNFT, Swap, Transfer,
-        TxEthSignature, TxHash, Withdraw, WithdrawNFT,
+        TxEthSignature, TxEthSignatureVariant, TxHash, Withdraw, WithdrawNFT,
     },
-    AccountId, Address, BlockNumber, EthBlockId, SerialId, TokenId, ZkSyncOp, ZkSyncPriorityOp,
-    H256,
+    AccountId, Address, BlockNumber, EthBlockId, FeeQuote, SerialId, TokenId, ZkSyncOp,
+    ZkSyncPriorityOp, ZkSyncTx, H256,
 };
 use zksync_utils::{BigUintSerdeAsRadix10Str, ZeroPrefixHexSerde};
 
+/// Transaction submitted along with the fee quote received from the `fee` endpoint.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingTx {
+    pub tx: ZkSyncTx,
+    #[serde(default)]
+    pub signature: TxEthSignatureVariant,
+    #[serde(default)]
+    pub fee_quote: Option<FeeQuote>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct IncomingTxBatch {
@@ -71,6 +83,22 @@ pub enum Receipt {
     L2(L2Receipt),
 }
 
+/// Status of the withdrawal finalization on L1.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalInfo {
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub tx_hash: TxHash,
+    pub rollup_block: BlockNumber,
+    pub address: Address,
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    pub status: WithdrawalStatus,
+    /// Hash of the `executeBlocks` transaction.
+    pub eth_tx_hash: H256,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
@@ -1,20 +1,106 @@
-use std::time::Instant;
+//! Library for distributing requests received from a single channel between several workers.
+//!
+//! Every worker receives requests through its own channel, wrapped into [`BalancedReceiver`].
+//! The worker for each request is chosen according to the [`BalancingPolicy`]:
+//!
+//! - `RoundRobin` sends an equal number of requests to each worker;
+//! - `LeastLoaded` sends the request to the worker with the shortest queue;
+//! - `KeyAffinity` sends all the requests with the same key (e.g. the same account) to the same worker,
+//!   so they are processed in the order they were received. Requests without a key are sent to the
+//!   least loaded worker.
+//!
+//! If a worker stops (i.e. drops its receiver), it is excluded from balancing and its requests are sent
+//! to the remaining workers. The balancer stops once there are no workers left.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Instant,
+};
 
 use futures::{
     channel::mpsc::{self, Receiver, Sender},
-    SinkExt, StreamExt,
+    future, Stream, StreamExt,
 };
 
-pub struct Balancer<R> {
-    channels: Vec<Sender<R>>,
-    requests: Receiver<R>,
+/// Strategy of choosing the worker for the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalancingPolicy {
+    RoundRobin,
+    LeastLoaded,
+    KeyAffinity,
+}
+
+/// Request that can be distributed by the balancer.
+pub trait BalancedRequest {
+    /// Key used by the `KeyAffinity` policy. Requests with the same key are always processed by the same worker.
+    fn balancing_key(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Helper to derive the balancing key from any hashable value, e.g. an account address.
+pub fn balancing_key<K: Hash>(key: &K) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
 }
 
 pub trait BuildBalancedItem<R, S> {
-    fn build_with_receiver(&self, receiver: Receiver<R>) -> S;
+    fn build_with_receiver(&self, receiver: BalancedReceiver<R>) -> S;
+}
+
+/// Receiving end of the worker channel. Keeps track of the worker queue depth for the balancer.
+#[derive(Debug)]
+pub struct BalancedReceiver<R> {
+    receiver: Receiver<R>,
+    queue_depth: Option<Arc<AtomicUsize>>,
 }
 
-impl<R> Balancer<R> {
+impl<R> Stream for BalancedReceiver<R> {
+    type Item = R;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<R>> {
+        let item = futures::ready!(self.receiver.poll_next_unpin(cx));
+        if let (Some(_), Some(queue_depth)) = (&item, &self.queue_depth) {
+            queue_depth.fetch_sub(1, Ordering::SeqCst);
+        }
+        Poll::Ready(item)
+    }
+}
+
+/// Allows to run the worker without the balancer, e.g. in tests.
+impl<R> From<Receiver<R>> for BalancedReceiver<R> {
+    fn from(receiver: Receiver<R>) -> Self {
+        Self {
+            receiver,
+            queue_depth: None,
+        }
+    }
+}
+
+struct Worker<R> {
+    sender: Sender<R>,
+    queue_depth: Arc<AtomicUsize>,
+    alive: bool,
+}
+
+pub struct Balancer<R> {
+    workers: Vec<Worker<R>>,
+    requests: Receiver<R>,
+    policy: BalancingPolicy,
+    name: &'static str,
+    next_worker: usize,
+}
+
+impl<R: BalancedRequest> Balancer<R> {
+    /// Creates a round-robin balancer and the workers built by `balanced_item`.
     pub fn new<T, S>(
         balanced_item: T,
         requests: Receiver<R>,
@@ -25,49 +111,185 @@ impl<R> Balancer<R> {
         T: BuildBalancedItem<R, S> + Sync + Send + 'static,
     {
         let mut balanced_items = vec![];
-        let mut channels = vec![];
+        let mut workers = vec![];
 
         for _ in 0..number_of_items {
             let (request_sender, request_receiver) = mpsc::channel(channel_capacity);
-            channels.push(request_sender);
-            balanced_items.push(balanced_item.build_with_receiver(request_receiver));
+            let queue_depth = Arc::new(AtomicUsize::new(0));
+            workers.push(Worker {
+                sender: request_sender,
+                queue_depth: queue_depth.clone(),
+                alive: true,
+            });
+            balanced_items.push(balanced_item.build_with_receiver(BalancedReceiver {
+                receiver: request_receiver,
+                queue_depth: Some(queue_depth),
+            }));
         }
 
-        (Self { channels, requests }, balanced_items)
+        let balancer = Self {
+            workers,
+            requests,
+            policy: BalancingPolicy::RoundRobin,
+            name: "default",
+            next_worker: 0,
+        };
+        (balancer, balanced_items)
+    }
+
+    pub fn with_policy(mut self, policy: BalancingPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the name used to label the balancer metrics.
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
     }
 
     pub async fn run(mut self) {
-        // It's an obvious way of balancing. Send an equal number of requests to each ticker
-        let mut channel_indexes = (0..self.channels.len()).into_iter().cycle();
-        // It's the easiest way how to cycle over channels, because cycle required clone trait.
+        metrics::gauge!(
+            "balancer.alive_workers",
+            self.workers.len() as f64,
+            "balancer" => self.name
+        );
         while let Some(request) = self.requests.next().await {
-            let channel_index = channel_indexes
-                .next()
-                .expect("Exactly one channel should exists");
             let start = Instant::now();
-            self.channels[channel_index]
-                .send(request)
-                .await
-                .unwrap_or_default();
-            metrics::histogram!("ticker.dispatcher.request", start.elapsed());
+            if !self.dispatch(request).await {
+                vlog::error!("Balancer {}: all the workers have stopped", self.name);
+                return;
+            }
+            metrics::histogram!("balancer.dispatch", start.elapsed(), "balancer" => self.name);
         }
     }
+
+    /// Sends the request to one of the workers. Returns `false` if there are no workers left.
+    async fn dispatch(&mut self, mut request: R) -> bool {
+        loop {
+            let index = match self.choose_worker(&request) {
+                Some(index) => index,
+                None => return false,
+            };
+
+            match self.send_to_worker(index, request).await {
+                Ok(()) => return true,
+                Err(returned) => {
+                    self.mark_stopped(index);
+                    request = returned;
+                }
+            }
+        }
+    }
+
+    fn choose_worker(&mut self, request: &R) -> Option<usize> {
+        let alive: Vec<usize> = (0..self.workers.len())
+            .filter(|&index| self.workers[index].alive)
+            .collect();
+        if alive.is_empty() {
+            return None;
+        }
+
+        let index = match self.policy {
+            BalancingPolicy::RoundRobin => {
+                let index = alive
+                    .iter()
+                    .copied()
+                    .find(|&index| index >= self.next_worker)
+                    .unwrap_or(alive[0]);
+                self.next_worker = index + 1;
+                index
+            }
+            BalancingPolicy::LeastLoaded => self.least_loaded(&alive),
+            BalancingPolicy::KeyAffinity => match request.balancing_key() {
+                Some(key) => {
+                    let index = (key % self.workers.len() as u64) as usize;
+                    if self.workers[index].alive {
+                        index
+                    } else {
+                        // Keys of the stopped worker are spread between the remaining ones,
+                        // keys of the other workers stay where they were.
+                        alive[(key % alive.len() as u64) as usize]
+                    }
+                }
+                None => self.least_loaded(&alive),
+            },
+        };
+        Some(index)
+    }
+
+    fn least_loaded(&self, alive: &[usize]) -> usize {
+        alive
+            .iter()
+            .copied()
+            .min_by_key(|&index| self.workers[index].queue_depth.load(Ordering::SeqCst))
+            .expect("There is at least one alive worker")
+    }
+
+    /// Sends the request to the worker, or returns it back if the worker has stopped.
+    async fn send_to_worker(&mut self, index: usize, request: R) -> Result<(), R> {
+        let worker = &mut self.workers[index];
+        if future::poll_fn(|cx| worker.sender.poll_ready(cx))
+            .await
+            .is_err()
+        {
+            return Err(request);
+        }
+
+        // Counted before sending, so the worker never sees the counter below zero.
+        let queue_depth = worker.queue_depth.fetch_add(1, Ordering::SeqCst) + 1;
+        if let Err(err) = worker.sender.try_send(request) {
+            worker.queue_depth.fetch_sub(1, Ordering::SeqCst);
+            return Err(err.into_inner());
+        }
+
+        metrics::gauge!(
+            "balancer.queue_depth",
+            queue_depth as f64,
+            "balancer" => self.name,
+            "worker" => index.to_string()
+        );
+        Ok(())
+    }
+
+    fn mark_stopped(&mut self, index: usize) {
+        self.workers[index].alive = false;
+        let alive_workers = self.workers.iter().filter(|worker| worker.alive).count();
+
+        vlog::warn!(
+            "Balancer {}: worker {} has stopped, {} workers left",
+            self.name,
+            index,
+            alive_workers
+        );
+        metrics::gauge!(
+            "balancer.alive_workers",
+            alive_workers as f64,
+            "balancer" => self.name
+        );
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Balancer, BuildBalancedItem};
+    use crate::{BalancedReceiver, BalancedRequest, Balancer, BalancingPolicy, BuildBalancedItem};
     use futures::channel::mpsc;
-    use futures::channel::mpsc::Receiver;
     use futures::{SinkExt, StreamExt};
 
+    impl BalancedRequest for i32 {
+        fn balancing_key(&self) -> Option<u64> {
+            // Requests are grouped by the last digit.
+            Some((self % 10) as u64)
+        }
+    }
+
     struct SomeBalancedItemBuilder;
     struct SomeBalancedItem {
-        receiver: Receiver<i32>,
+        receiver: BalancedReceiver<i32>,
     }
 
     impl BuildBalancedItem<i32, SomeBalancedItem> for SomeBalancedItemBuilder {
-        fn build_with_receiver(&self, receiver: Receiver<i32>) -> SomeBalancedItem {
+        fn build_with_receiver(&self, receiver: BalancedReceiver<i32>) -> SomeBalancedItem {
             SomeBalancedItem { receiver }
         }
     }
@@ -88,4 +310,67 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn least_loaded() {
+        let (_request_sender, request_receiver) = mpsc::channel(2);
+
+        let (balancer, mut items) = Balancer::new(SomeBalancedItemBuilder, request_receiver, 2, 8);
+        let mut balancer = balancer.with_policy(BalancingPolicy::LeastLoaded);
+
+        // Nobody processes the requests yet, so they are distributed evenly.
+        for i in 0..3 {
+            assert!(balancer.dispatch(i).await);
+        }
+        assert_eq!(items[1].receiver.next().await, Some(1));
+
+        // Now the second worker has a shorter queue.
+        assert!(balancer.dispatch(3).await);
+        assert_eq!(items[1].receiver.next().await, Some(3));
+        assert_eq!(items[0].receiver.next().await, Some(0));
+        assert_eq!(items[0].receiver.next().await, Some(2));
+    }
+
+    #[tokio::test]
+    async fn key_affinity() {
+        let (mut request_sender, request_receiver) = mpsc::channel(2);
+
+        let (balancer, mut items) = Balancer::new(SomeBalancedItemBuilder, request_receiver, 3, 32);
+        tokio::spawn(balancer.with_policy(BalancingPolicy::KeyAffinity).run());
+
+        for i in 0..30 {
+            request_sender.send(i).await.unwrap();
+        }
+
+        // Requests with the same key go to the same worker in the original order.
+        for (worker, item) in items.iter_mut().enumerate() {
+            let expected: Vec<i32> = (0..30).filter(|i| (i % 10) % 3 == worker as i32).collect();
+            let mut received = vec![];
+            for _ in 0..expected.len() {
+                received.push(item.receiver.next().await.unwrap());
+            }
+            assert_eq!(received, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn stopped_worker() {
+        let (_request_sender, request_receiver) = mpsc::channel(2);
+
+        let (balancer, mut items) = Balancer::new(SomeBalancedItemBuilder, request_receiver, 2, 2);
+        let mut balancer = balancer.with_policy(BalancingPolicy::KeyAffinity);
+
+        // The worker responsible for the even keys has stopped.
+        let mut alive = items.pop().unwrap();
+        drop(items);
+
+        for i in 0..10 {
+            assert!(balancer.dispatch(i).await);
+            assert_eq!(alive.receiver.next().await, Some(i));
+        }
+
+        // Nobody is left to process the requests.
+        drop(alive);
+        assert!(!balancer.dispatch(10).await);
+    }
 }
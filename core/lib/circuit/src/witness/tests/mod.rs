@@ -5,6 +5,7 @@
 //!   are placed in the corresponding modules.
 //! - Low-level tests for circuit generation algorithm are placed in the `noop` module.
 //! - Generic tests for the combinations of different operations are placed in this module.
+//! - Property-based tests for the random sequences of operations are placed in the `state_fuzzing` module.
 
 // External deps
 use num::BigUint;
@@ -42,6 +43,7 @@ mod forced_exit;
 mod full_exit;
 mod mint_nft;
 mod noop;
+mod state_fuzzing;
 mod swap;
 pub(crate) mod test_utils;
 mod transfer;
@@ -0,0 +1,540 @@
+//! Property-based tests for the state transitions.
+//!
+//! Random sequences of operations are executed through `ZkSyncState` the same way the state keeper does it,
+//! and the invariants of the state are checked after every operation. Every successfully executed operation
+//! is also applied to the circuit account tree through `WitnessBuilder`, so the state and the witness generation
+//! code are checked to agree on the root hash.
+//!
+//! Tests are placed here rather than in `zksync_state`, since `zksync_circuit` depends on it.
+//! Amount of cases can be changed with the `PROPTEST_CASES` environment variable.
+
+// External deps
+use num::{BigUint, Zero};
+use proptest::{collection::vec, prelude::*};
+use std::collections::HashMap;
+// Workspace deps
+use zksync_crypto::{
+    circuit::CircuitAccountTree,
+    params::{MIN_NFT_TOKEN_ID, NFT_STORAGE_ACCOUNT_ID, NFT_TOKEN_ID},
+};
+use zksync_state::state::{CollectedFee, OpSuccess, ZkSyncState};
+use zksync_test_account::ZkSyncAccount;
+use zksync_types::{
+    AccountId, Address, BlockNumber, Deposit, FullExit, Nonce, TokenId, ZkSyncOp, ZkSyncPriorityOp,
+    ZkSyncTx, H256,
+};
+// Local deps
+use crate::witness::{
+    tests::test_utils::{WitnessTestAccount, ZkSyncStateGenerator, FEE_ACCOUNT_ID},
+    utils::WitnessBuilder,
+};
+
+/// Fungible tokens used in the operations.
+const TOKENS: [TokenId; 2] = [TokenId(0), TokenId(1)];
+/// Accounts that have the signing keys, their IDs start from 1.
+const SIGNERS: usize = 4;
+/// Addresses that don't have accounts at the beginning, they can only receive funds.
+const NEW_ADDRESSES: usize = 3;
+/// Initial balance of every signer in every token.
+const INITIAL_BALANCE: u64 = 1000;
+/// Amounts are generated up to this value, so some operations fail because of the insufficient balance.
+/// All the amounts and fees below it are packable, so the state and the circuit see the same values.
+const MAX_AMOUNT: u64 = 1500;
+const MAX_FEE: u64 = 50;
+
+/// Operation to be executed, accounts and tokens are referenced by their indices.
+#[derive(Debug, Clone)]
+enum FuzzOp {
+    Deposit {
+        to: usize,
+        token: usize,
+        amount: u64,
+    },
+    Transfer {
+        from: usize,
+        to: usize,
+        token: usize,
+        amount: u64,
+        fee: u64,
+    },
+    Withdraw {
+        from: usize,
+        token: usize,
+        amount: u64,
+        fee: u64,
+    },
+    Swap {
+        submitter: usize,
+        accounts: (usize, usize),
+        token: usize,
+        amounts: (u64, u64),
+        fee: u64,
+    },
+    MintNFT {
+        creator: usize,
+        recipient: usize,
+        fee_token: usize,
+        fee: u64,
+    },
+    FullExit {
+        account: usize,
+        token: usize,
+        /// If not set, the request is sent from a different address and should not withdraw anything.
+        own_address: bool,
+    },
+}
+
+fn fuzz_op() -> impl Strategy<Value = FuzzOp> {
+    let signer = 0..SIGNERS;
+    let address = 0..SIGNERS + NEW_ADDRESSES;
+    let token = 0..TOKENS.len();
+    let amount = 0..MAX_AMOUNT;
+    let fee = 0..MAX_FEE;
+
+    prop_oneof![
+        (address.clone(), token.clone(), amount.clone())
+            .prop_map(|(to, token, amount)| FuzzOp::Deposit { to, token, amount }),
+        (
+            signer.clone(),
+            address.clone(),
+            token.clone(),
+            amount.clone(),
+            fee.clone()
+        )
+            .prop_map(|(from, to, token, amount, fee)| FuzzOp::Transfer {
+                from,
+                to,
+                token,
+                amount,
+                fee
+            }),
+        (signer.clone(), token.clone(), amount.clone(), fee.clone()).prop_map(
+            |(from, token, amount, fee)| FuzzOp::Withdraw {
+                from,
+                token,
+                amount,
+                fee
+            }
+        ),
+        (
+            signer.clone(),
+            (signer.clone(), signer.clone()),
+            token.clone(),
+            (amount.clone(), amount),
+            fee.clone()
+        )
+            .prop_map(|(submitter, accounts, token, amounts, fee)| FuzzOp::Swap {
+                submitter,
+                accounts,
+                token,
+                amounts,
+                fee
+            }),
+        (signer.clone(), address, token.clone(), fee).prop_map(
+            |(creator, recipient, fee_token, fee)| FuzzOp::MintNFT {
+                creator,
+                recipient,
+                fee_token,
+                fee
+            }
+        ),
+        (signer, token, any::<bool>()).prop_map(|(account, token, own_address)| {
+            FuzzOp::FullExit {
+                account,
+                token,
+                own_address,
+            }
+        }),
+    ]
+}
+
+/// State under test along with the witness builder that follows it.
+struct Harness<'a> {
+    state: ZkSyncState,
+    witness: WitnessBuilder<'a>,
+    signers: Vec<ZkSyncAccount>,
+    new_addresses: Vec<Address>,
+    /// Expected amount of every fungible token in the accounts and the fees to be collected.
+    supply: HashMap<TokenId, BigUint>,
+    /// Fees collected by the executed operations, they're added to the fee account at the end of the block.
+    fees: Vec<CollectedFee>,
+}
+
+impl<'a> Harness<'a> {
+    fn new(
+        state: ZkSyncState,
+        account_tree: &'a mut CircuitAccountTree,
+        signers: Vec<ZkSyncAccount>,
+    ) -> Self {
+        let witness = WitnessBuilder::new(account_tree, FEE_ACCOUNT_ID, BlockNumber(1), 0);
+        let new_addresses = (0..NEW_ADDRESSES)
+            .map(|i| Address::from_low_u64_be(0x1000 + i as u64))
+            .collect();
+        let mut harness = Self {
+            state,
+            witness,
+            signers,
+            new_addresses,
+            supply: HashMap::new(),
+            fees: Vec::new(),
+        };
+        harness.supply = TOKENS
+            .iter()
+            .map(|&token| (token, harness.total_balance(token)))
+            .collect();
+        harness
+    }
+
+    fn address(&self, index: usize) -> Address {
+        if index < self.signers.len() {
+            self.signers[index].address
+        } else {
+            self.new_addresses[index - self.signers.len()]
+        }
+    }
+
+    fn signer_id(&self, index: usize) -> AccountId {
+        self.signers[index].get_account_id().unwrap()
+    }
+
+    fn nonce(&self, index: usize) -> Nonce {
+        self.state.get_account(self.signer_id(index)).unwrap().nonce
+    }
+
+    fn total_balance(&self, token: TokenId) -> BigUint {
+        let balances: BigUint = self
+            .state
+            .get_accounts()
+            .iter()
+            .map(|(_, account)| account.get_balance(token))
+            .sum();
+        let fees: BigUint = self
+            .fees
+            .iter()
+            .filter(|fee| fee.token == token)
+            .map(|fee| fee.amount.clone())
+            .sum();
+        balances + fees
+    }
+
+    /// Executes the operation and checks the invariants.
+    fn execute(&mut self, op: &FuzzOp) {
+        let state_before = self.state.clone();
+        let (result, initiator) = match op.clone() {
+            FuzzOp::Deposit { to, token, amount } => {
+                let deposit = Deposit {
+                    from: Address::repeat_byte(0x11),
+                    token: TOKENS[token],
+                    amount: amount.into(),
+                    to: self.address(to),
+                };
+                let op = ZkSyncPriorityOp::Deposit(deposit);
+                (Ok(self.state.execute_priority_op(op)), None)
+            }
+            FuzzOp::Transfer {
+                from,
+                to,
+                token,
+                amount,
+                fee,
+            } => {
+                let tx = self.signers[from]
+                    .sign_transfer(
+                        TOKENS[token],
+                        "",
+                        amount.into(),
+                        fee.into(),
+                        &self.address(to),
+                        Some(self.nonce(from)),
+                        false,
+                        Default::default(),
+                    )
+                    .0;
+                (self.state.execute_tx(tx.into()), Some(from))
+            }
+            FuzzOp::Withdraw {
+                from,
+                token,
+                amount,
+                fee,
+            } => {
+                let tx = self.signers[from]
+                    .sign_withdraw(
+                        TOKENS[token],
+                        "",
+                        amount.into(),
+                        fee.into(),
+                        &self.address(from),
+                        Some(self.nonce(from)),
+                        false,
+                        Default::default(),
+                    )
+                    .0;
+                (self.state.execute_tx(tx.into()), Some(from))
+            }
+            FuzzOp::Swap {
+                submitter,
+                accounts,
+                token,
+                amounts,
+                fee,
+            } => {
+                let tokens = (TOKENS[token], TOKENS[(token + 1) % TOKENS.len()]);
+                let orders = (
+                    self.signers[accounts.0].sign_order(
+                        tokens.0,
+                        tokens.1,
+                        amounts.0.into(),
+                        amounts.1.into(),
+                        amounts.0.into(),
+                        &self.address(accounts.0),
+                        Some(self.nonce(accounts.0)),
+                        false,
+                        Default::default(),
+                    ),
+                    self.signers[accounts.1].sign_order(
+                        tokens.1,
+                        tokens.0,
+                        amounts.1.into(),
+                        amounts.0.into(),
+                        amounts.1.into(),
+                        &self.address(accounts.1),
+                        Some(self.nonce(accounts.1)),
+                        false,
+                        Default::default(),
+                    ),
+                );
+                let tx = self.signers[submitter]
+                    .sign_swap(
+                        orders,
+                        (amounts.0.into(), amounts.1.into()),
+                        Some(self.nonce(submitter)),
+                        false,
+                        tokens.0,
+                        "",
+                        fee.into(),
+                    )
+                    .0;
+                (self.state.execute_tx(tx.into()), Some(submitter))
+            }
+            FuzzOp::MintNFT {
+                creator,
+                recipient,
+                fee_token,
+                fee,
+            } => {
+                let tx = self.signers[creator]
+                    .sign_mint_nft(
+                        TOKENS[fee_token],
+                        "",
+                        H256::random(),
+                        fee.into(),
+                        &self.address(recipient),
+                        Some(self.nonce(creator)),
+                        false,
+                    )
+                    .0;
+                (self.state.execute_tx(ZkSyncTx::from(tx)), Some(creator))
+            }
+            FuzzOp::FullExit {
+                account,
+                token,
+                own_address,
+            } => {
+                let eth_address = if own_address {
+                    self.address(account)
+                } else {
+                    Address::repeat_byte(0x22)
+                };
+                let full_exit = FullExit {
+                    account_id: self.signer_id(account),
+                    eth_address,
+                    token: TOKENS[token],
+                    is_legacy: false,
+                };
+                let op = ZkSyncPriorityOp::FullExit(full_exit);
+                (Ok(self.state.execute_priority_op(op)), None)
+            }
+        };
+
+        match result {
+            Ok(success) => {
+                let initiator = initiator.map(|index| self.signer_id(index));
+                self.check_success(&state_before, success, initiator);
+            }
+            Err(_) => assert_eq!(
+                self.state.root_hash(),
+                state_before.root_hash(),
+                "failed operation {:?} has changed the state",
+                op
+            ),
+        }
+        self.check_nfts();
+    }
+
+    fn check_success(
+        &mut self,
+        state_before: &ZkSyncState,
+        success: OpSuccess,
+        initiator: Option<AccountId>,
+    ) {
+        let OpSuccess {
+            fee,
+            updates,
+            executed_op,
+        } = success;
+
+        // Returned updates should describe the state change exactly.
+        let mut updated_state = state_before.clone();
+        updated_state.apply_account_updates(updates);
+        assert_eq!(
+            updated_state.root_hash(),
+            self.state.root_hash(),
+            "updates of {:?} don't match the state change",
+            executed_op
+        );
+
+        // Nonces never decrease, and every transaction increments the nonce of its initiator.
+        for (id, account_before) in state_before.get_accounts() {
+            let id = AccountId(id);
+            let nonce = self.state.get_account(id).unwrap().nonce;
+            if Some(id) == initiator {
+                assert_eq!(*nonce, *account_before.nonce + 1, "{:?}", executed_op);
+            } else {
+                assert!(nonce >= account_before.nonce, "{:?}", executed_op);
+            }
+        }
+
+        // Funds are only created by deposits and removed by withdrawals,
+        // fees stay in the block until they're collected.
+        match &executed_op {
+            ZkSyncOp::Deposit(op) => {
+                *self.supply.get_mut(&op.priority_op.token).unwrap() += &op.priority_op.amount;
+            }
+            ZkSyncOp::Withdraw(op) => {
+                *self.supply.get_mut(&op.tx.token).unwrap() -= &op.tx.amount;
+            }
+            ZkSyncOp::FullExit(op) => {
+                if let Some(amount) = &op.withdraw_amount {
+                    *self.supply.get_mut(&op.priority_op.token).unwrap() -= &amount.0;
+                }
+            }
+            _ => {}
+        }
+        if let Some(fee) = &fee {
+            self.fees.push(fee.clone());
+        }
+        self.check_supply();
+
+        // Circuit should reach the same state and collect the same fee.
+        let witness_fee = self
+            .witness
+            .add_executed_op(&executed_op)
+            .expect("failed to build the witness");
+        assert_eq!(
+            fee.map(|fee| (fee.token, fee.amount)),
+            witness_fee.map(|fee| (fee.token, fee.amount)),
+            "fee of {:?} mismatch",
+            executed_op
+        );
+        assert_eq!(
+            self.state.root_hash(),
+            self.witness.account_tree.root_hash(),
+            "root hash mismatch after {:?}",
+            executed_op
+        );
+    }
+
+    fn check_supply(&self) {
+        for (token, supply) in &self.supply {
+            assert_eq!(&self.total_balance(*token), supply, "token {}", token);
+        }
+    }
+
+    /// Every minted NFT belongs to exactly one account.
+    fn check_nfts(&self) {
+        let accounts = self.state.get_accounts();
+        for token in self.state.nfts.keys() {
+            let owners: Vec<_> = accounts
+                .iter()
+                .filter(|(_, account)| !account.get_balance(*token).is_zero())
+                .map(|(_, account)| account.get_balance(*token))
+                .collect();
+            assert_eq!(owners, vec![BigUint::from(1u32)], "NFT {}", token);
+        }
+        let storage = self.state.get_account(NFT_STORAGE_ACCOUNT_ID).unwrap();
+        assert_eq!(
+            storage.get_balance(NFT_TOKEN_ID),
+            BigUint::from(MIN_NFT_TOKEN_ID as usize + self.state.nfts.len())
+        );
+    }
+
+    /// Collects the fees as in the end of the block and checks the final state.
+    fn finish(mut self) {
+        let fee_balances_before: Vec<_> = {
+            let fee_account = self.state.get_account(FEE_ACCOUNT_ID).unwrap();
+            TOKENS
+                .iter()
+                .map(|&token| fee_account.get_balance(token))
+                .collect()
+        };
+
+        let fees = std::mem::take(&mut self.fees);
+        self.state.collect_fee(&fees, FEE_ACCOUNT_ID);
+        self.witness.collect_fees(&fees);
+
+        let fee_account = self.state.get_account(FEE_ACCOUNT_ID).unwrap();
+        for (token, balance_before) in TOKENS.iter().zip(fee_balances_before) {
+            let collected: BigUint = fees
+                .iter()
+                .filter(|fee| fee.token == *token)
+                .map(|fee| fee.amount.clone())
+                .sum();
+            assert_eq!(fee_account.get_balance(*token), balance_before + collected);
+        }
+        self.check_supply();
+
+        assert_eq!(
+            self.state.root_hash(),
+            self.witness.root_after_fees.unwrap(),
+            "root hash mismatch after collecting fees"
+        );
+    }
+}
+
+fn signer_account(id: AccountId) -> WitnessTestAccount {
+    let mut account = WitnessTestAccount::new(id, INITIAL_BALANCE);
+    for token in &TOKENS[1..] {
+        account
+            .account
+            .add_balance(*token, &BigUint::from(INITIAL_BALANCE));
+    }
+    account
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn state_transitions(ops in vec(fuzz_op(), 1..24)) {
+        let mut accounts: Vec<_> = (1..=SIGNERS)
+            .map(|id| signer_account(AccountId(id as u32)))
+            .collect();
+        accounts.push(WitnessTestAccount::new_with_token(
+            NFT_STORAGE_ACCOUNT_ID,
+            NFT_TOKEN_ID,
+            MIN_NFT_TOKEN_ID as u64,
+        ));
+        let signers = accounts[..SIGNERS]
+            .iter()
+            .map(|account| account.zksync_account.clone())
+            .collect();
+
+        let (state, mut account_tree) = ZkSyncStateGenerator::generate(&accounts);
+        let mut harness = Harness::new(state, &mut account_tree, signers);
+        for op in &ops {
+            harness.execute(op);
+        }
+        harness.finish();
+    }
+}
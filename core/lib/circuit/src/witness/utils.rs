@@ -125,6 +125,198 @@ impl<'a> WitnessBuilder<'a> {
         self.offset_commitment.extend(offset_commitment.into_iter());
     }
 
+    /// Applies the operation executed by the state keeper to the tree and adds its witness.
+    /// Returns the fee that should be collected for the operation.
+    pub fn add_executed_op(
+        &mut self,
+        op: &ZkSyncOp,
+    ) -> Result<Option<CollectedFee>, anyhow::Error> {
+        let fee = match op {
+            ZkSyncOp::Deposit(deposit) => {
+                let deposit_witness = DepositWitness::apply_tx(&mut self.account_tree, deposit);
+
+                let deposit_operations = deposit_witness.calculate_operations(());
+                self.add_operation_with_pubdata(
+                    deposit_operations,
+                    deposit_witness.get_pubdata(),
+                    deposit_witness.get_offset_commitment_data(),
+                );
+                None
+            }
+            ZkSyncOp::Transfer(transfer) => {
+                let transfer_witness = TransferWitness::apply_tx(&mut self.account_tree, transfer);
+
+                let input = SigDataInput::from_transfer_op(transfer)?;
+                let transfer_operations = transfer_witness.calculate_operations(input);
+
+                self.add_operation_with_pubdata(
+                    transfer_operations,
+                    transfer_witness.get_pubdata(),
+                    transfer_witness.get_offset_commitment_data(),
+                );
+                Some(CollectedFee {
+                    token: transfer.tx.token,
+                    amount: transfer.tx.fee.clone(),
+                })
+            }
+            ZkSyncOp::TransferToNew(transfer_to_new) => {
+                let transfer_to_new_witness =
+                    TransferToNewWitness::apply_tx(&mut self.account_tree, transfer_to_new);
+
+                let input = SigDataInput::from_transfer_to_new_op(transfer_to_new)?;
+                let transfer_to_new_operations =
+                    transfer_to_new_witness.calculate_operations(input);
+
+                self.add_operation_with_pubdata(
+                    transfer_to_new_operations,
+                    transfer_to_new_witness.get_pubdata(),
+                    transfer_to_new_witness.get_offset_commitment_data(),
+                );
+                Some(CollectedFee {
+                    token: transfer_to_new.tx.token,
+                    amount: transfer_to_new.tx.fee.clone(),
+                })
+            }
+            ZkSyncOp::Withdraw(withdraw) => {
+                let withdraw_witness = WithdrawWitness::apply_tx(&mut self.account_tree, withdraw);
+
+                let input = SigDataInput::from_withdraw_op(withdraw)?;
+                let withdraw_operations = withdraw_witness.calculate_operations(input);
+
+                self.add_operation_with_pubdata(
+                    withdraw_operations,
+                    withdraw_witness.get_pubdata(),
+                    withdraw_witness.get_offset_commitment_data(),
+                );
+                Some(CollectedFee {
+                    token: withdraw.tx.token,
+                    amount: withdraw.tx.fee.clone(),
+                })
+            }
+            ZkSyncOp::Close(close) => {
+                let close_account_witness =
+                    CloseAccountWitness::apply_tx(&mut self.account_tree, close);
+
+                let input = SigDataInput::from_close_op(close)?;
+                let close_account_operations = close_account_witness.calculate_operations(input);
+
+                self.add_operation_with_pubdata(
+                    close_account_operations,
+                    close_account_witness.get_pubdata(),
+                    close_account_witness.get_offset_commitment_data(),
+                );
+                None
+            }
+            ZkSyncOp::FullExit(full_exit_op) => {
+                let success = full_exit_op.withdraw_amount.is_some();
+
+                let full_exit_witness = FullExitWitness::apply_tx(
+                    &mut self.account_tree,
+                    &((**full_exit_op).clone(), success),
+                );
+
+                let full_exit_operations = full_exit_witness.calculate_operations(());
+
+                self.add_operation_with_pubdata(
+                    full_exit_operations,
+                    full_exit_witness.get_pubdata(),
+                    full_exit_witness.get_offset_commitment_data(),
+                );
+                None
+            }
+            ZkSyncOp::ChangePubKeyOffchain(change_pkhash_op) => {
+                let change_pkhash_witness =
+                    ChangePubkeyOffChainWitness::apply_tx(&mut self.account_tree, change_pkhash_op);
+
+                let input = SigDataInput::from_change_pubkey_op(change_pkhash_op)?;
+                let change_pkhash_operations = change_pkhash_witness.calculate_operations(input);
+
+                self.add_operation_with_pubdata(
+                    change_pkhash_operations,
+                    change_pkhash_witness.get_pubdata(),
+                    change_pkhash_witness.get_offset_commitment_data(),
+                );
+                Some(CollectedFee {
+                    token: change_pkhash_op.tx.fee_token,
+                    amount: change_pkhash_op.tx.fee.clone(),
+                })
+            }
+            ZkSyncOp::ForcedExit(forced_exit) => {
+                let forced_exit_witness =
+                    ForcedExitWitness::apply_tx(&mut self.account_tree, forced_exit);
+
+                let input = SigDataInput::from_forced_exit_op(forced_exit)?;
+                let forced_exit_operations = forced_exit_witness.calculate_operations(input);
+
+                self.add_operation_with_pubdata(
+                    forced_exit_operations,
+                    forced_exit_witness.get_pubdata(),
+                    forced_exit_witness.get_offset_commitment_data(),
+                );
+                Some(CollectedFee {
+                    token: forced_exit.tx.token,
+                    amount: forced_exit.tx.fee.clone(),
+                })
+            }
+            ZkSyncOp::Swap(swap) => {
+                let swap_witness = SwapWitness::apply_tx(&mut self.account_tree, swap);
+
+                let input = (
+                    SigDataInput::from_order(&swap.tx.orders.0)?,
+                    SigDataInput::from_order(&swap.tx.orders.1)?,
+                    SigDataInput::from_swap_op(swap)?,
+                );
+
+                let swap_operations = swap_witness.calculate_operations(input);
+
+                self.add_operation_with_pubdata(
+                    swap_operations,
+                    swap_witness.get_pubdata(),
+                    swap_witness.get_offset_commitment_data(),
+                );
+                Some(CollectedFee {
+                    token: swap.tx.fee_token,
+                    amount: swap.tx.fee.clone(),
+                })
+            }
+            ZkSyncOp::Noop(_) => None, // Noops are added by `extend_pubdata_with_noops`
+            ZkSyncOp::MintNFTOp(mint_nft) => {
+                let mint_nft_witness = MintNFTWitness::apply_tx(&mut self.account_tree, mint_nft);
+
+                let input = SigDataInput::from_mint_nft_op(mint_nft)?;
+                let mint_nft_operations = mint_nft_witness.calculate_operations(input);
+
+                self.add_operation_with_pubdata(
+                    mint_nft_operations,
+                    mint_nft_witness.get_pubdata(),
+                    mint_nft_witness.get_offset_commitment_data(),
+                );
+                Some(CollectedFee {
+                    token: mint_nft.tx.fee_token,
+                    amount: mint_nft.tx.fee.clone(),
+                })
+            }
+            ZkSyncOp::WithdrawNFT(withdraw_nft) => {
+                let withdraw_nft_witness =
+                    WithdrawNFTWitness::apply_tx(&mut self.account_tree, withdraw_nft);
+
+                let input = SigDataInput::from_withdraw_nft_op(withdraw_nft)?;
+                let withdraw_nft_operations = withdraw_nft_witness.calculate_operations(input);
+
+                self.add_operation_with_pubdata(
+                    withdraw_nft_operations,
+                    withdraw_nft_witness.get_pubdata(),
+                    withdraw_nft_witness.get_offset_commitment_data(),
+                );
+                Some(CollectedFee {
+                    token: withdraw_nft.tx.fee_token,
+                    amount: withdraw_nft.tx.fee.clone(),
+                })
+            }
+        };
+        Ok(fee)
+    }
+
     /// Add noops if pubdata isn't of right size
     pub fn extend_pubdata_with_noops(&mut self, block_size_chunks: usize) {
         let chunks_used = self.operations.len();
@@ -760,180 +952,13 @@ pub fn build_block_witness<'a>(
         .iter()
         .filter_map(|tx| tx.get_executed_op().cloned());
 
-    let mut operations = vec![];
-    let mut pub_data = vec![];
-    let mut offset_commitment = vec![];
     let mut fees = vec![];
     for op in ops {
-        match op {
-            ZkSyncOp::Deposit(deposit) => {
-                let deposit_witness =
-                    DepositWitness::apply_tx(&mut witness_accum.account_tree, &deposit);
-
-                let deposit_operations = deposit_witness.calculate_operations(());
-                operations.extend(deposit_operations);
-                pub_data.extend(deposit_witness.get_pubdata());
-                offset_commitment.extend(deposit_witness.get_offset_commitment_data())
-            }
-            ZkSyncOp::Transfer(transfer) => {
-                let transfer_witness =
-                    TransferWitness::apply_tx(&mut witness_accum.account_tree, &transfer);
-
-                let input = SigDataInput::from_transfer_op(&transfer)?;
-                let transfer_operations = transfer_witness.calculate_operations(input);
-
-                operations.extend(transfer_operations);
-                fees.push(CollectedFee {
-                    token: transfer.tx.token,
-                    amount: transfer.tx.fee,
-                });
-                pub_data.extend(transfer_witness.get_pubdata());
-                offset_commitment.extend(transfer_witness.get_offset_commitment_data())
-            }
-            ZkSyncOp::TransferToNew(transfer_to_new) => {
-                let transfer_to_new_witness = TransferToNewWitness::apply_tx(
-                    &mut witness_accum.account_tree,
-                    &transfer_to_new,
-                );
-
-                let input = SigDataInput::from_transfer_to_new_op(&transfer_to_new)?;
-                let transfer_to_new_operations =
-                    transfer_to_new_witness.calculate_operations(input);
-
-                operations.extend(transfer_to_new_operations);
-                fees.push(CollectedFee {
-                    token: transfer_to_new.tx.token,
-                    amount: transfer_to_new.tx.fee,
-                });
-                pub_data.extend(transfer_to_new_witness.get_pubdata());
-                offset_commitment.extend(transfer_to_new_witness.get_offset_commitment_data())
-            }
-            ZkSyncOp::Withdraw(withdraw) => {
-                let withdraw_witness =
-                    WithdrawWitness::apply_tx(&mut witness_accum.account_tree, &withdraw);
-
-                let input = SigDataInput::from_withdraw_op(&withdraw)?;
-                let withdraw_operations = withdraw_witness.calculate_operations(input);
-
-                operations.extend(withdraw_operations);
-                fees.push(CollectedFee {
-                    token: withdraw.tx.token,
-                    amount: withdraw.tx.fee,
-                });
-                pub_data.extend(withdraw_witness.get_pubdata());
-                offset_commitment.extend(withdraw_witness.get_offset_commitment_data())
-            }
-            ZkSyncOp::Close(close) => {
-                let close_account_witness =
-                    CloseAccountWitness::apply_tx(&mut witness_accum.account_tree, &close);
-
-                let input = SigDataInput::from_close_op(&close)?;
-                let close_account_operations = close_account_witness.calculate_operations(input);
-
-                operations.extend(close_account_operations);
-                pub_data.extend(close_account_witness.get_pubdata());
-                offset_commitment.extend(close_account_witness.get_offset_commitment_data())
-            }
-            ZkSyncOp::FullExit(full_exit_op) => {
-                let success = full_exit_op.withdraw_amount.is_some();
-
-                let full_exit_witness = FullExitWitness::apply_tx(
-                    &mut witness_accum.account_tree,
-                    &(*full_exit_op, success),
-                );
-
-                let full_exit_operations = full_exit_witness.calculate_operations(());
-
-                operations.extend(full_exit_operations);
-                pub_data.extend(full_exit_witness.get_pubdata());
-                offset_commitment.extend(full_exit_witness.get_offset_commitment_data())
-            }
-            ZkSyncOp::ChangePubKeyOffchain(change_pkhash_op) => {
-                let change_pkhash_witness = ChangePubkeyOffChainWitness::apply_tx(
-                    &mut witness_accum.account_tree,
-                    &change_pkhash_op,
-                );
-
-                let input = SigDataInput::from_change_pubkey_op(&change_pkhash_op)?;
-                let change_pkhash_operations = change_pkhash_witness.calculate_operations(input);
-
-                operations.extend(change_pkhash_operations);
-                fees.push(CollectedFee {
-                    token: change_pkhash_op.tx.fee_token,
-                    amount: change_pkhash_op.tx.fee,
-                });
-                pub_data.extend(change_pkhash_witness.get_pubdata());
-                offset_commitment.extend(change_pkhash_witness.get_offset_commitment_data())
-            }
-            ZkSyncOp::ForcedExit(forced_exit) => {
-                let forced_exit_witness =
-                    ForcedExitWitness::apply_tx(&mut witness_accum.account_tree, &forced_exit);
-
-                let input = SigDataInput::from_forced_exit_op(&forced_exit)?;
-                let forced_exit_operations = forced_exit_witness.calculate_operations(input);
-
-                operations.extend(forced_exit_operations);
-                fees.push(CollectedFee {
-                    token: forced_exit.tx.token,
-                    amount: forced_exit.tx.fee,
-                });
-                pub_data.extend(forced_exit_witness.get_pubdata());
-                offset_commitment.extend(forced_exit_witness.get_offset_commitment_data())
-            }
-            ZkSyncOp::Swap(swap) => {
-                let swap_witness = SwapWitness::apply_tx(&mut witness_accum.account_tree, &swap);
-
-                let input = (
-                    SigDataInput::from_order(&swap.tx.orders.0)?,
-                    SigDataInput::from_order(&swap.tx.orders.1)?,
-                    SigDataInput::from_swap_op(&swap)?,
-                );
-
-                let swap_operations = swap_witness.calculate_operations(input);
-
-                operations.extend(swap_operations);
-                fees.push(CollectedFee {
-                    token: swap.tx.fee_token,
-                    amount: swap.tx.fee,
-                });
-                pub_data.extend(swap_witness.get_pubdata());
-                offset_commitment.extend(swap_witness.get_offset_commitment_data())
-            }
-            ZkSyncOp::Noop(_) => {} // Noops are handled below
-            ZkSyncOp::MintNFTOp(mint_nft) => {
-                let mint_nft_witness =
-                    MintNFTWitness::apply_tx(&mut witness_accum.account_tree, &mint_nft);
-
-                let input = SigDataInput::from_mint_nft_op(&mint_nft)?;
-                let mint_nft_operations = mint_nft_witness.calculate_operations(input);
-
-                operations.extend(mint_nft_operations);
-                fees.push(CollectedFee {
-                    token: mint_nft.tx.fee_token,
-                    amount: mint_nft.tx.fee,
-                });
-                pub_data.extend(mint_nft_witness.get_pubdata());
-                offset_commitment.extend(mint_nft_witness.get_offset_commitment_data())
-            }
-            ZkSyncOp::WithdrawNFT(withdraw_nft) => {
-                let withdraw_nft_witness =
-                    WithdrawNFTWitness::apply_tx(&mut witness_accum.account_tree, &withdraw_nft);
-
-                let input = SigDataInput::from_withdraw_nft_op(&withdraw_nft)?;
-                let withdraw_nft_operations = withdraw_nft_witness.calculate_operations(input);
-
-                operations.extend(withdraw_nft_operations);
-                fees.push(CollectedFee {
-                    token: withdraw_nft.tx.fee_token,
-                    amount: withdraw_nft.tx.fee,
-                });
-                pub_data.extend(withdraw_nft_witness.get_pubdata());
-                offset_commitment.extend(withdraw_nft_witness.get_offset_commitment_data())
-            }
+        if let Some(fee) = witness_accum.add_executed_op(&op)? {
+            fees.push(fee);
         }
     }
 
-    witness_accum.add_operation_with_pubdata(operations, pub_data, offset_commitment);
     witness_accum.extend_pubdata_with_noops(block_size);
     assert_eq!(witness_accum.pubdata.len(), CHUNK_BIT_WIDTH * block_size);
     assert_eq!(witness_accum.operations.len(), block_size);
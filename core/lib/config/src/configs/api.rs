@@ -26,6 +26,8 @@ pub struct ApiConfig {
     pub prover: ProverApi,
     /// Configuration options for the Prometheus exporter.
     pub prometheus: Prometheus,
+    /// Configuration options for the rate limiting of the public API servers.
+    pub rate_limit: RateLimit,
 }
 
 impl ApiConfig {
@@ -39,6 +41,7 @@ impl ApiConfig {
             private: envy_load!("private", "API_PRIVATE_"),
             prover: envy_load!("prover", "API_PROVER_"),
             prometheus: envy_load!("prometheus", "API_PROMETHEUS_"),
+            rate_limit: envy_load!("rate_limit", "API_RATE_LIMIT_"),
         }
     }
 }
@@ -57,6 +60,21 @@ pub struct Common {
 
     pub max_number_of_transactions_per_batch: u64,
     pub max_number_of_authors_per_batch: u64,
+
+    /// Number of workers used to verify zkSync signatures of the incoming transactions.
+    /// Requests of the same sender are always verified by the same worker.
+    pub signature_checker_threads: u8,
+    /// Maximum number of signature verification requests waiting in the queues of the workers.
+    /// Requests above this limit are waiting in the channel.
+    pub signature_checker_max_pending_requests: usize,
+
+    /// Secret used to sign the fee quotes.
+    pub fee_quote_secret: String,
+    /// Time during which the quoted fee is honored. Type of value is seconds.
+    pub fee_quote_validity_secs: u64,
+    /// Maximum increase of the required fee (in percent of the quoted fee)
+    /// for which the quoted fee is still accepted.
+    pub fee_quote_max_fee_increase_percent: u32,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -163,6 +181,60 @@ pub struct Prometheus {
     pub port: u16,
 }
 
+/// Configuration of the token-bucket rate limiter used by the public API servers.
+///
+/// Every request consumes the amount of tokens equal to the cost of the called method
+/// both from the bucket of the client IP address and (if the request concerns a certain account)
+/// from the bucket of the zkSync account.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RateLimit {
+    /// Whether requests should be rate limited at all.
+    pub enabled: bool,
+    /// Maximum amount of tokens in the bucket of a single IP address.
+    pub ip_bucket_capacity: u32,
+    /// Amount of tokens restored per second in the bucket of a single IP address.
+    pub ip_refill_per_second: u32,
+    /// Maximum amount of tokens in the bucket of a single zkSync account.
+    pub account_bucket_capacity: u32,
+    /// Amount of tokens restored per second in the bucket of a single zkSync account.
+    pub account_refill_per_second: u32,
+    /// Cost of methods that are not listed in `method_costs`.
+    pub default_method_cost: u32,
+    /// Costs of the particular methods in the `method=cost` format, e.g. `get_tx_fee=5`.
+    /// For the REST API the method is the matched route pattern, e.g. `/api/v0.2/fee`.
+    pub method_costs: Vec<String>,
+    /// Maximum amount of buckets kept in memory. When the limit is exceeded,
+    /// the least recently used buckets are removed.
+    pub max_tracked_keys: usize,
+    /// Addresses or subnets (e.g. `10.0.0.0/8`) of the proxies allowed to set the
+    /// `X-Forwarded-For` / `X-Real-IP` headers. Headers of the other clients are ignored.
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+}
+
+impl RateLimit {
+    /// Parses `method_costs` into `(method, cost)` pairs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the entries has incorrect format.
+    pub fn method_costs(&self) -> Vec<(String, u32)> {
+        self.method_costs
+            .iter()
+            .map(|entry| {
+                let (method, cost) = entry
+                    .split_once('=')
+                    .unwrap_or_else(|| panic!("Incorrect method cost entry: {}", entry));
+                let cost = cost
+                    .trim()
+                    .parse()
+                    .unwrap_or_else(|err| panic!("Incorrect method cost {}: {}", entry, err));
+                (method.trim().to_string(), cost)
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,6 +250,11 @@ mod tests {
                 max_number_of_transactions_per_batch: 200,
                 max_number_of_authors_per_batch: 10,
                 fee_free_accounts: vec![AccountId(4078), AccountId(387)],
+                signature_checker_threads: 8,
+                signature_checker_max_pending_requests: 1024,
+                fee_quote_secret: "sample".into(),
+                fee_quote_validity_secs: 120,
+                fee_quote_max_fee_increase_percent: 20,
             },
             admin: AdminApi {
                 port: 8080,
@@ -209,6 +286,17 @@ mod tests {
                 secret_auth: "sample".into(),
             },
             prometheus: Prometheus { port: 3312 },
+            rate_limit: RateLimit {
+                enabled: true,
+                ip_bucket_capacity: 100,
+                ip_refill_per_second: 20,
+                account_bucket_capacity: 50,
+                account_refill_per_second: 10,
+                default_method_cost: 1,
+                method_costs: vec!["get_tx_fee=5".into(), "eth_getLogs=10".into()],
+                max_tracked_keys: 100000,
+                trusted_proxies: vec!["10.0.0.0/8".into(), "127.0.0.1".into()],
+            },
         }
     }
 
@@ -221,6 +309,11 @@ API_COMMON_FEE_FREE_ACCOUNTS=4078,387
 API_COMMON_ENFORCE_PUBKEY_CHANGE_FEE=true
 API_COMMON_MAX_NUMBER_OF_TRANSACTIONS_PER_BATCH=200
 API_COMMON_MAX_NUMBER_OF_AUTHORS_PER_BATCH=10
+API_COMMON_SIGNATURE_CHECKER_THREADS=8
+API_COMMON_SIGNATURE_CHECKER_MAX_PENDING_REQUESTS=1024
+API_COMMON_FEE_QUOTE_SECRET="sample"
+API_COMMON_FEE_QUOTE_VALIDITY_SECS=120
+API_COMMON_FEE_QUOTE_MAX_FEE_INCREASE_PERCENT=20
 API_ADMIN_PORT="8080"
 API_ADMIN_URL="http://127.0.0.1:8080"
 API_ADMIN_SECRET_AUTH="sample"
@@ -239,6 +332,15 @@ API_PROVER_PORT="8088"
 API_PROVER_URL="http://127.0.0.1:8088"
 API_PROVER_SECRET_AUTH="sample"
 API_PROMETHEUS_PORT="3312"
+API_RATE_LIMIT_ENABLED="true"
+API_RATE_LIMIT_IP_BUCKET_CAPACITY="100"
+API_RATE_LIMIT_IP_REFILL_PER_SECOND="20"
+API_RATE_LIMIT_ACCOUNT_BUCKET_CAPACITY="50"
+API_RATE_LIMIT_ACCOUNT_REFILL_PER_SECOND="10"
+API_RATE_LIMIT_DEFAULT_METHOD_COST="1"
+API_RATE_LIMIT_METHOD_COSTS="get_tx_fee=5,eth_getLogs=10"
+API_RATE_LIMIT_MAX_TRACKED_KEYS="100000"
+API_RATE_LIMIT_TRUSTED_PROXIES="10.0.0.0/8,127.0.0.1"
         "#;
         set_env(config);
 
@@ -276,5 +378,12 @@ API_PROMETHEUS_PORT="3312"
             config.web3.bind_addr(),
             SocketAddr::new(bind_broadcast_addr, config.web3.port)
         );
+        assert_eq!(
+            config.rate_limit.method_costs(),
+            vec![
+                ("get_tx_fee".to_string(), 5),
+                ("eth_getLogs".to_string(), 10)
+            ]
+        );
     }
 }
@@ -26,6 +26,47 @@ impl ChainConfig {
             state_keeper: envy_load!("state_keeper", "CHAIN_STATE_KEEPER_"),
         }
     }
+
+    /// Block sizes the state keeper may seal the block into. By default these are the configured sizes.
+    /// With `seal_into_supported_sizes` enabled, all the sizes supported by the circuit which don't exceed
+    /// the largest configured one are added, so that partially filled blocks don't waste the proof and
+    /// calldata costs.
+    ///
+    /// Panics if any of the resulting sizes is not supported by the circuit: such blocks could never be proven.
+    pub fn block_chunk_sizes_to_seal(&self) -> Vec<usize> {
+        let supported_sizes = &self.circuit.supported_block_chunks_sizes;
+        if let Some(size) = self
+            .state_keeper
+            .block_chunk_sizes
+            .iter()
+            .find(|size| !supported_sizes.contains(size))
+        {
+            panic!(
+                "Block size {} is not in the supported block sizes {:?}",
+                size, supported_sizes
+            );
+        }
+        if !self.state_keeper.seal_into_supported_sizes {
+            return self.state_keeper.block_chunk_sizes.clone();
+        }
+
+        let max_block_size = self
+            .state_keeper
+            .block_chunk_sizes
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or_default();
+        let mut sizes: Vec<_> = supported_sizes
+            .iter()
+            .copied()
+            .filter(|size| *size <= max_block_size)
+            .chain(self.state_keeper.block_chunk_sizes.iter().copied())
+            .collect();
+        sizes.sort_unstable();
+        sizes.dedup();
+        sizes
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -74,12 +115,28 @@ pub struct StateKeeper {
     /// otherwise block will never be proven. This list can contain not all the values though: e.g. for local
     /// development usually a couple of smallest block sizes is enough.
     pub block_chunk_sizes: Vec<usize>,
+    /// Whether blocks may also be sealed into the sizes supported by the circuit which are not in `block_chunk_sizes`
+    /// but don't exceed the largest of them. The block is still filled up to the largest configured size.
+    #[serde(default)]
+    pub seal_into_supported_sizes: bool,
     /// Time between two miniblocks created by mempool / block_proposer.
     pub miniblock_iteration_interval: u64,
     /// Maximum amount of miniblock iterations before sealing the block.
     pub miniblock_iterations: u64,
     /// Maximum amount of miniblock iterations in case of block containing a fast withdrawal request.
     pub fast_block_miniblock_iterations: u64,
+    /// Maximum age of the block in seconds, the block is sealed regardless of the other rules. 0 disables the rule.
+    pub max_block_age: u64,
+    /// Minimum share of the block chunks that must be used before the block is sealed by the
+    /// miniblock iterations limit. 0 disables the rule.
+    pub min_block_fill_ratio: f64,
+    /// Maximum number of withdrawals in the block. 0 disables the rule.
+    pub max_withdrawals_per_block: usize,
+    /// Maximum commit and verify gas of the block, must be below the contract gas limit. 0 disables the rule.
+    pub block_gas_budget: u64,
+    /// Duration of the leader lease in seconds. Only the instance holding the lease runs the state keeper,
+    /// other ones wait as a hot standby. 0 disables the leader election.
+    pub leader_lease_duration: u64,
     pub fee_account_addr: Address,
     pub aggregated_proof_sizes: Vec<usize>,
     pub max_aggregated_blocks_to_commit: usize,
@@ -96,6 +153,15 @@ impl StateKeeper {
         Duration::from_millis(self.miniblock_iteration_interval)
     }
 
+    /// Converts `self.leader_lease_duration` into `Duration`, `None` if the leader election is disabled.
+    pub fn leader_lease_duration(&self) -> Option<Duration> {
+        if self.leader_lease_duration > 0 {
+            Some(Duration::from_secs(self.leader_lease_duration))
+        } else {
+            None
+        }
+    }
+
     pub fn block_commit_deadline(&self) -> Duration {
         Duration::from_secs(self.block_commit_deadline)
     }
@@ -130,9 +196,15 @@ mod tests {
             },
             state_keeper: StateKeeper {
                 block_chunk_sizes: vec![6, 30],
+                seal_into_supported_sizes: false,
                 miniblock_iteration_interval: 200,
                 miniblock_iterations: 10,
                 fast_block_miniblock_iterations: 5,
+                max_block_age: 60,
+                min_block_fill_ratio: 0.5,
+                max_withdrawals_per_block: 20,
+                block_gas_budget: 3_000_000,
+                leader_lease_duration: 30,
                 fee_account_addr: addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7"),
                 aggregated_proof_sizes: vec![1, 5],
                 max_aggregated_blocks_to_commit: 3,
@@ -158,9 +230,15 @@ CHAIN_CIRCUIT_BALANCE_TREE_DEPTH="11"
 CHAIN_ETH_MAX_NUMBER_OF_WITHDRAWALS_PER_BLOCK="10"
 CHAIN_ETH_NETWORK="localhost"
 CHAIN_STATE_KEEPER_BLOCK_CHUNK_SIZES="6,30"
+CHAIN_STATE_KEEPER_SEAL_INTO_SUPPORTED_SIZES="false"
 CHAIN_STATE_KEEPER_MINIBLOCK_ITERATION_INTERVAL="200"
 CHAIN_STATE_KEEPER_MINIBLOCK_ITERATIONS="10"
 CHAIN_STATE_KEEPER_FAST_BLOCK_MINIBLOCK_ITERATIONS="5"
+CHAIN_STATE_KEEPER_MAX_BLOCK_AGE="60"
+CHAIN_STATE_KEEPER_MIN_BLOCK_FILL_RATIO="0.5"
+CHAIN_STATE_KEEPER_MAX_WITHDRAWALS_PER_BLOCK="20"
+CHAIN_STATE_KEEPER_BLOCK_GAS_BUDGET="3000000"
+CHAIN_STATE_KEEPER_LEADER_LEASE_DURATION="30"
 CHAIN_STATE_KEEPER_FEE_ACCOUNT_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
 CHAIN_STATE_KEEPER_AGGREGATED_PROOF_SIZES="1,5"
 CHAIN_STATE_KEEPER_MAX_AGGREGATED_BLOCKS_TO_COMMIT="3"
@@ -185,5 +263,16 @@ CHAIN_STATE_KEEPER_MAX_AGGREGATED_TX_GAS="4000000"
             config.state_keeper.miniblock_iteration_interval(),
             Duration::from_millis(config.state_keeper.miniblock_iteration_interval)
         );
+        assert_eq!(config.block_chunk_sizes_to_seal(), vec![6, 30]);
+        assert_eq!(
+            config.state_keeper.leader_lease_duration(),
+            Some(Duration::from_secs(30))
+        );
+
+        let mut config = config;
+        config.state_keeper.block_chunk_sizes = vec![150];
+        assert_eq!(config.block_chunk_sizes_to_seal(), vec![150]);
+        config.state_keeper.seal_into_supported_sizes = true;
+        assert_eq!(config.block_chunk_sizes_to_seal(), vec![6, 30, 74, 150]);
     }
 }
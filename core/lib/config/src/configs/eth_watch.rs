@@ -14,6 +14,12 @@ pub struct ETHWatchConfig {
     /// How often we want to poll the Ethereum node.
     /// Value in milliseconds.
     pub eth_node_poll_interval: u64,
+    /// Number of the latest blocks checked for reorgs. Should not be less than `confirmations_for_eth_event`,
+    /// so reorgs affecting the already accepted events can be detected.
+    pub reorg_tracking_depth: u64,
+    /// How often the withdrawals tracker checks executed blocks and pending balances.
+    /// Value in seconds.
+    pub withdrawals_tracker_poll_interval: u64,
 }
 
 impl ETHWatchConfig {
@@ -25,6 +31,11 @@ impl ETHWatchConfig {
     pub fn poll_interval(&self) -> Duration {
         Duration::from_millis(self.eth_node_poll_interval)
     }
+
+    /// Converts `self.withdrawals_tracker_poll_interval` into `Duration`.
+    pub fn withdrawals_tracker_poll_interval(&self) -> Duration {
+        Duration::from_secs(self.withdrawals_tracker_poll_interval)
+    }
 }
 
 #[cfg(test)]
@@ -36,6 +47,8 @@ mod tests {
         ETHWatchConfig {
             confirmations_for_eth_event: 0,
             eth_node_poll_interval: 300,
+            reorg_tracking_depth: 16,
+            withdrawals_tracker_poll_interval: 60,
         }
     }
 
@@ -44,6 +57,8 @@ mod tests {
         let config = r#"
 ETH_WATCH_CONFIRMATIONS_FOR_ETH_EVENT="0"
 ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
+ETH_WATCH_REORG_TRACKING_DEPTH="16"
+ETH_WATCH_WITHDRAWALS_TRACKER_POLL_INTERVAL="60"
         "#;
         set_env(config);
 
@@ -60,5 +75,9 @@ ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
             config.poll_interval(),
             Duration::from_millis(config.eth_node_poll_interval)
         );
+        assert_eq!(
+            config.withdrawals_tracker_poll_interval(),
+            Duration::from_secs(config.withdrawals_tracker_poll_interval)
+        );
     }
 }
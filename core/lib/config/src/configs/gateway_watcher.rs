@@ -20,6 +20,23 @@ pub struct GatewayWatcherConfig {
     pub gateway_check_request_per_task_limit: usize,
     /// How many tasks are allowed to simultaneously make requests.
     pub gateway_check_task_limit: usize,
+    /// How many blocks a gateway may lag behind the longest chain before it's marked unhealthy.
+    pub gateway_max_block_lag: u64,
+    /// How many checks in a row may report a diverging block hash before the gateway is marked unhealthy.
+    pub gateway_max_hash_mismatches: u32,
+    /// Max share of failed requests within the error rate window, in percents.
+    pub gateway_max_error_rate_percent: u32,
+    /// Number of the latest checks used to calculate the error rate of a gateway.
+    pub gateway_error_rate_window: usize,
+}
+
+/// Thresholds after which the gateway is considered unhealthy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GatewayHealthThresholds {
+    pub max_block_lag: u64,
+    pub max_hash_mismatches: u32,
+    pub max_error_rate_percent: u32,
+    pub error_rate_window: usize,
 }
 
 impl GatewayWatcherConfig {
@@ -49,6 +66,16 @@ impl GatewayWatcherConfig {
     pub fn request_per_task_limit(&self) -> usize {
         self.gateway_check_request_per_task_limit
     }
+
+    /// Thresholds used to mark gateways unhealthy.
+    pub fn health_thresholds(&self) -> GatewayHealthThresholds {
+        GatewayHealthThresholds {
+            max_block_lag: self.gateway_max_block_lag,
+            max_hash_mismatches: self.gateway_max_hash_mismatches,
+            max_error_rate_percent: self.gateway_max_error_rate_percent,
+            error_rate_window: self.gateway_error_rate_window,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -63,6 +90,10 @@ mod tests {
             gateway_check_request_per_task_limit: 10,
             gateway_check_task_limit: 1,
             gateway_check_request_timeout: 1000,
+            gateway_max_block_lag: 3,
+            gateway_max_hash_mismatches: 2,
+            gateway_max_error_rate_percent: 50,
+            gateway_error_rate_window: 10,
         }
     }
 
@@ -74,6 +105,10 @@ GATEWAY_WATCHER_GATEWAY_CHECK_RETRY_DELAY="500"
 GATEWAY_WATCHER_GATEWAY_CHECK_REQUEST_PER_TASK_LIMIT="10"
 GATEWAY_WATCHER_GATEWAY_CHECK_TASK_LIMIT="1"
 GATEWAY_WATCHER_GATEWAY_CHECK_REQUEST_TIMEOUT="1000"
+GATEWAY_WATCHER_GATEWAY_MAX_BLOCK_LAG="3"
+GATEWAY_WATCHER_GATEWAY_MAX_HASH_MISMATCHES="2"
+GATEWAY_WATCHER_GATEWAY_MAX_ERROR_RATE_PERCENT="50"
+GATEWAY_WATCHER_GATEWAY_ERROR_RATE_WINDOW="10"
         "#;
         set_env(config);
 
@@ -9,13 +9,35 @@ use crate::envy_load;
 pub enum TokenPriceSource {
     CoinGecko,
     CoinMarketCap,
+    /// Prices loaded from the file, intended for development networks.
+    Static,
+    /// Time-weighted average of the Uniswap prices.
+    Uniswap,
+}
+
+/// Policy of distributing the fee requests between the ticker actors.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum TickerBalancingPolicy {
+    RoundRobin,
+    LeastLoaded,
+    /// Requests of the same account are always processed by the same actor in order.
+    KeyAffinity,
 }
 
 /// Configuration for the fee ticker.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct TickerConfig {
-    /// Indicator of the API to be used for getting token prices.
-    pub token_price_source: TokenPriceSource,
+    /// APIs to be used for getting token prices. If there are several APIs,
+    /// the median of their prices is used.
+    #[serde(default)]
+    pub token_price_sources: Vec<TokenPriceSource>,
+    /// Deprecated single API for getting token prices, used if `token_price_sources` is not set.
+    #[serde(default)]
+    pub token_price_source: Option<TokenPriceSource>,
+    /// Prices deviating from the median by more than this percentage are rejected.
+    pub max_price_deviation_percent: u32,
+    /// Path to the JSON file with the token prices for the static price source. Relative to `$ZKSYNC_HOME`.
+    pub static_prices_path: String,
     /// URL of CoinMarketCap API. Can be set to the mock server for local development.
     pub coinmarketcap_base_url: String,
     /// URL of CoinGecko API. Can be set to the mock server for local development.
@@ -36,20 +58,41 @@ pub struct TickerConfig {
     pub token_market_update_time: u64,
     /// Number of tickers for load balancing.
     pub number_of_ticker_actors: u8,
+    /// Policy of distributing the requests between the tickers.
+    pub balancing_policy: TickerBalancingPolicy,
+    /// Path to the JSON file with the fee formula overrides. Relative to `$ZKSYNC_HOME`.
+    pub fee_formula_path: String,
+    /// Interval of reloading the fee formula file in seconds.
+    pub fee_formula_update_time: u64,
 }
 
 impl TickerConfig {
     pub fn from_env() -> Self {
-        envy_load!("fee_ticker", "FEE_TICKER_")
+        let config: Self = envy_load!("fee_ticker", "FEE_TICKER_");
+        config.with_fallback_price_source()
     }
 
-    /// Returns the token price source type and the corresponding API URL.
-    pub fn price_source(&self) -> (TokenPriceSource, &str) {
-        let url = match self.token_price_source {
-            TokenPriceSource::CoinGecko => self.coingecko_base_url.as_ref(),
-            TokenPriceSource::CoinMarketCap => self.coinmarketcap_base_url.as_ref(),
-        };
-        (self.token_price_source, url)
+    /// Falls back to the deprecated `token_price_source` if `token_price_sources` is not set.
+    /// Panics if there are no price sources at all.
+    fn with_fallback_price_source(mut self) -> Self {
+        if self.token_price_sources.is_empty() {
+            self.token_price_sources.extend(self.token_price_source);
+        }
+        assert!(
+            !self.token_price_sources.is_empty(),
+            "Cannot load config <fee_ticker>: no token price sources are set"
+        );
+        self
+    }
+
+    /// Returns the API URL of the token price source, if the source uses one.
+    pub fn price_source_url(&self, source: TokenPriceSource) -> Option<&str> {
+        match source {
+            TokenPriceSource::CoinGecko => Some(self.coingecko_base_url.as_ref()),
+            TokenPriceSource::CoinMarketCap => Some(self.coinmarketcap_base_url.as_ref()),
+            TokenPriceSource::Uniswap => Some(self.uniswap_url.as_ref()),
+            TokenPriceSource::Static => None,
+        }
     }
 }
 
@@ -60,7 +103,10 @@ mod tests {
 
     fn expected_config() -> TickerConfig {
         TickerConfig {
-            token_price_source: TokenPriceSource::CoinGecko,
+            token_price_sources: vec![TokenPriceSource::CoinGecko, TokenPriceSource::Uniswap],
+            token_price_source: None,
+            max_price_deviation_percent: 20,
+            static_prices_path: "etc/fee_ticker/static_prices.json".into(),
             coinmarketcap_base_url: "http://127.0.0.1:9876".into(),
             coingecko_base_url: "http://127.0.0.1:9876".into(),
             scale_fee_percent: 100,
@@ -71,13 +117,18 @@ mod tests {
             unconditionally_valid_tokens: vec![addr("0000000000000000000000000000000000000000")],
             token_market_update_time: 120,
             number_of_ticker_actors: 4,
+            balancing_policy: TickerBalancingPolicy::KeyAffinity,
+            fee_formula_path: "etc/fee_ticker/fee_formula.json".into(),
+            fee_formula_update_time: 60,
         }
     }
 
     #[test]
     fn from_env() {
         let config = r#"
-FEE_TICKER_TOKEN_PRICE_SOURCE="CoinGecko"
+FEE_TICKER_TOKEN_PRICE_SOURCES="CoinGecko,Uniswap"
+FEE_TICKER_MAX_PRICE_DEVIATION_PERCENT=20
+FEE_TICKER_STATIC_PRICES_PATH="etc/fee_ticker/static_prices.json"
 FEE_TICKER_COINMARKETCAP_BASE_URL="http://127.0.0.1:9876"
 FEE_TICKER_COINGECKO_BASE_URL="http://127.0.0.1:9876"
 FEE_TICKER_FAST_PROCESSING_COEFF="10"
@@ -87,6 +138,9 @@ FEE_TICKER_TOKEN_MARKET_UPDATE_TIME=120
 FEE_TICKER_UNCONDITIONALLY_VALID_TOKENS="0x0000000000000000000000000000000000000000"
 FEE_TICKER_LIQUIDITY_VOLUME=100
 FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
+FEE_TICKER_BALANCING_POLICY="KeyAffinity"
+FEE_TICKER_FEE_FORMULA_PATH="etc/fee_ticker/fee_formula.json"
+FEE_TICKER_FEE_FORMULA_UPDATE_TIME=60
 FEE_TICKER_SUBSIDIZED_TOKENS_LIMITS=156
 FEE_TICKER_SCALE_FEE_PERCENT=100
         "#;
@@ -107,16 +161,36 @@ FEE_TICKER_SCALE_FEE_PERCENT=100
         config.coingecko_base_url = COINGECKO_URL.into();
         config.coinmarketcap_base_url = COINMARKETCAP_URL.into();
 
-        config.token_price_source = TokenPriceSource::CoinGecko;
         assert_eq!(
-            config.price_source(),
-            (TokenPriceSource::CoinGecko, COINGECKO_URL)
+            config.price_source_url(TokenPriceSource::CoinGecko),
+            Some(COINGECKO_URL)
+        );
+        assert_eq!(
+            config.price_source_url(TokenPriceSource::CoinMarketCap),
+            Some(COINMARKETCAP_URL)
         );
+        assert_eq!(config.price_source_url(TokenPriceSource::Static), None);
+    }
+
+    /// Checks that the deprecated single price source is used if the list is not set.
+    #[test]
+    fn fallback_price_source() {
+        let mut config = expected_config();
+        config.token_price_sources = Vec::new();
+        config.token_price_source = Some(TokenPriceSource::CoinMarketCap);
 
-        config.token_price_source = TokenPriceSource::CoinMarketCap;
+        let config = config.with_fallback_price_source();
         assert_eq!(
-            config.price_source(),
-            (TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL)
+            config.token_price_sources,
+            vec![TokenPriceSource::CoinMarketCap]
         );
     }
+
+    #[test]
+    #[should_panic(expected = "no token price sources")]
+    fn no_price_sources() {
+        let mut config = expected_config();
+        config.token_price_sources = Vec::new();
+        config.with_fallback_price_source();
+    }
 }
@@ -423,6 +423,19 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(logs)
     }
 
+    /// Returns the hash of the block with the given number, or `None` if there is no such block yet.
+    pub async fn block_hash(&self, number: u64) -> anyhow::Result<Option<H256>> {
+        let start = Instant::now();
+        let block = self
+            .inner
+            .web3
+            .eth()
+            .block(BlockId::Number(BlockNumber::Number(number.into())))
+            .await?;
+        metrics::histogram!("eth_client.direct.block_hash", start.elapsed());
+        Ok(block.and_then(|block| block.hash))
+    }
+
     pub fn contract(&self) -> &ethabi::Contract {
         &self.inner.contract
     }
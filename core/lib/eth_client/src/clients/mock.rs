@@ -6,6 +6,7 @@ use std::collections::{HashMap, HashSet};
 use tokio::sync::RwLock;
 use web3::contract::tokens::{Detokenize, Tokenize};
 use web3::contract::Options;
+use web3::ethabi::Token;
 use web3::transports::Http;
 use web3::types::{BlockId, Filter, Log, Transaction, U64};
 
@@ -22,6 +23,11 @@ struct MockEthereumInner {
     gas_price: U256,
     tx_statuses: Arc<RwLock<HashMap<H256, ExecutedTxStatus>>>,
     sent_txs: Arc<RwLock<HashSet<Vec<u8>>>>,
+    /// If set, every sent transaction is considered successfully executed with this amount of confirmations.
+    auto_confirmations: Option<u64>,
+    /// Responses to the contract calls, keyed by the contract address (`None` for the zkSync contract)
+    /// and the function name.
+    contract_call_responses: Arc<RwLock<HashMap<(Option<Address>, String), Vec<Token>>>>,
 }
 
 /// Mock Ethereum client is capable of recording all the incoming requests for the further analysis.
@@ -37,11 +43,25 @@ impl Default for MockEthereumInner {
             gas_price: 100.into(),
             tx_statuses: Default::default(),
             sent_txs: Default::default(),
+            auto_confirmations: None,
+            contract_call_responses: Default::default(),
         }
     }
 }
 
 impl MockEthereum {
+    /// Creates a mock client which considers every sent transaction successfully executed
+    /// with the provided amount of confirmations, so the `ETHSender` can run without
+    /// manual interaction with the mock.
+    pub fn with_auto_confirmations(confirmations: u64) -> Self {
+        Self {
+            inner: Arc::new(MockEthereumInner {
+                auto_confirmations: Some(confirmations),
+                ..Default::default()
+            }),
+        }
+    }
+
     /// A fake `sha256` hasher, which calculates an `std::hash` instead.
     /// This is done for simplicity and it's also much faster.
     pub fn fake_sha256(data: &[u8]) -> H256 {
@@ -65,6 +85,53 @@ impl MockEthereum {
         );
     }
 
+    /// Sets the response returned for the calls of the `func` function of the contract deployed at `contract`.
+    pub async fn set_contract_call_response<R: Tokenize>(
+        &self,
+        contract: Address,
+        func: &str,
+        response: R,
+    ) {
+        self.inner
+            .contract_call_responses
+            .write()
+            .await
+            .insert((Some(contract), func.to_owned()), response.into_tokens());
+    }
+
+    /// Same as `set_contract_call_response`, but for the zkSync contract.
+    pub async fn set_main_contract_call_response<R: Tokenize>(&self, func: &str, response: R) {
+        self.inner
+            .contract_call_responses
+            .write()
+            .await
+            .insert((None, func.to_owned()), response.into_tokens());
+    }
+
+    async fn contract_call_response<R: Detokenize>(
+        &self,
+        contract: Option<Address>,
+        func: &str,
+    ) -> anyhow::Result<R> {
+        let tokens = self
+            .inner
+            .contract_call_responses
+            .read()
+            .await
+            .get(&(contract, func.to_owned()))
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::format_err!(
+                    "No response is set for the call of `{}` of the contract {:?}",
+                    func,
+                    contract
+                )
+            })?;
+        R::from_tokens(tokens).map_err(|err| {
+            anyhow::format_err!("Failed to decode the response of `{}`: {}", func, err)
+        })
+    }
+
     /// Adds an response for the sent transaction for `ETHSender` to receive.
     pub async fn add_execution(&mut self, hash: &H256, status: &ExecutedTxStatus) {
         self.inner
@@ -103,7 +170,20 @@ impl MockEthereum {
         hash: H256,
         _current_block: Option<u64>,
     ) -> anyhow::Result<Option<ExecutedTxStatus>> {
-        Ok(self.inner.tx_statuses.read().await.get(&hash).cloned())
+        if let Some(status) = self.inner.tx_statuses.read().await.get(&hash) {
+            return Ok(Some(status.clone()));
+        }
+
+        match self.inner.auto_confirmations {
+            Some(confirmations) if self.inner.sent_txs.read().await.contains(hash.as_bytes()) => {
+                Ok(Some(ExecutedTxStatus {
+                    confirmations,
+                    success: true,
+                    receipt: None,
+                }))
+            }
+            _ => Ok(None),
+        }
     }
 
     pub async fn block_number(&self) -> anyhow::Result<U64> {
@@ -219,7 +299,7 @@ impl MockEthereum {
 
     pub async fn call_main_contract_function<R, A, P, B>(
         &self,
-        _func: &str,
+        func: &str,
         _params: P,
         _from: A,
         _options: Options,
@@ -231,22 +311,31 @@ impl MockEthereum {
         B: Into<Option<BlockId>>,
         P: Tokenize,
     {
-        todo!()
+        self.contract_call_response(None, func).await
     }
 
     pub async fn logs(&self, _filter: Filter) -> anyhow::Result<Vec<Log>> {
-        todo!()
+        // Mock doesn't have any contracts, so there are no events.
+        Ok(Vec::new())
+    }
+
+    pub async fn block_hash(&self, number: u64) -> anyhow::Result<Option<H256>> {
+        // Mock chain is never reorganized, so the hash may be derived from the block number.
+        if number > self.inner.block_number {
+            return Ok(None);
+        }
+        Ok(Some(H256::from_low_u64_be(number)))
     }
 
     #[allow(clippy::too_many_arguments)]
     pub async fn call_contract_function<R, A, B, P>(
         &self,
-        _func: &str,
+        func: &str,
         _params: P,
         _from: A,
         _options: Options,
         _block: B,
-        _token_address: Address,
+        token_address: Address,
         _erc20_abi: ethabi::Contract,
     ) -> Result<R, anyhow::Error>
     where
@@ -255,7 +344,7 @@ impl MockEthereum {
         B: Into<Option<BlockId>>,
         P: Tokenize,
     {
-        todo!()
+        self.contract_call_response(Some(token_address), func).await
     }
 
     pub fn create_contract(
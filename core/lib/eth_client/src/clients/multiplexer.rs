@@ -1,6 +1,7 @@
 use ethabi::Contract;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use web3::{
     contract::tokens::{Detokenize, Tokenize},
     contract::Options,
@@ -17,6 +18,17 @@ use crate::ETHDirectClient;
 struct MultiplexerEthereumClientInner {
     clients: Vec<(String, ETHDirectClient<PrivateKeySigner>)>,
     preferred: AtomicUsize,
+    /// Reasons why the clients are considered unhealthy, by client name.
+    unhealthy: RwLock<HashMap<String, String>>,
+}
+
+/// Health of the client as decided by the gateway watcher.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientHealth {
+    pub name: String,
+    pub preferred: bool,
+    /// Reason why the client is unhealthy, `None` if the client is healthy.
+    pub unhealthy_reason: Option<String>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -61,9 +73,41 @@ impl MultiplexerEthereumClient {
         }
     }
 
-    pub fn clients(&self) -> impl Iterator<Item = (&str, &ETHDirectClient<PrivateKeySigner>)> {
+    /// Marks the client unhealthy if the reason is provided and healthy otherwise.
+    /// Returns `true` if the client health has changed.
+    pub fn set_client_health(&self, name: &str, unhealthy_reason: Option<String>) -> bool {
+        let mut unhealthy = self.inner.unhealthy.write().unwrap();
+        match unhealthy_reason {
+            Some(reason) => unhealthy.insert(name.to_owned(), reason).is_none(),
+            None => unhealthy.remove(name).is_some(),
+        }
+    }
+
+    pub fn is_client_healthy(&self, name: &str) -> bool {
+        !self.inner.unhealthy.read().unwrap().contains_key(name)
+    }
+
+    pub fn clients_health(&self) -> Vec<ClientHealth> {
         let preferred = self.inner.preferred.load(Ordering::Relaxed);
+        let unhealthy = self.inner.unhealthy.read().unwrap();
         self.inner
+            .clients
+            .iter()
+            .enumerate()
+            .map(|(idx, (name, _))| ClientHealth {
+                name: name.clone(),
+                preferred: idx == preferred,
+                unhealthy_reason: unhealthy.get(name).cloned(),
+            })
+            .collect()
+    }
+
+    /// Returns clients in the order they should be used: the preferred client goes first,
+    /// unhealthy clients go last and are used only if all the healthy ones fail.
+    pub fn clients(&self) -> impl Iterator<Item = (&str, &ETHDirectClient<PrivateKeySigner>)> {
+        let preferred = self.inner.preferred.load(Ordering::Relaxed);
+        let mut clients: Vec<_> = self
+            .inner
             .clients
             .get(preferred)
             .into_iter()
@@ -76,6 +120,11 @@ impl MultiplexerEthereumClient {
                     .iter(),
             )
             .map(|(name, client)| (name.as_str(), client))
+            .collect();
+
+        let unhealthy = self.inner.unhealthy.read().unwrap();
+        clients.sort_by_key(|(name, _)| unhealthy.contains_key(*name));
+        clients.into_iter()
     }
 
     pub fn create_contract(
@@ -215,6 +264,10 @@ impl MultiplexerEthereumClient {
         multiple_call!(self, logs(filter));
     }
 
+    pub async fn block_hash(&self, number: u64) -> anyhow::Result<Option<H256>> {
+        multiple_call!(self, block_hash(number));
+    }
+
     pub fn encode_tx_data<P: Tokenize + Clone>(&self, func: &str, params: P) -> Vec<u8> {
         let (_, client) = self
             .clients()
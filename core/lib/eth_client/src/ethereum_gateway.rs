@@ -10,7 +10,7 @@ use zksync_eth_signer::PrivateKeySigner;
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
 use crate::clients::mock::MockEthereum;
-use crate::clients::multiplexer::MultiplexerEthereumClient;
+use crate::clients::multiplexer::{ClientHealth, MultiplexerEthereumClient};
 use crate::ETHDirectClient;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -241,6 +241,11 @@ impl EthereumGateway {
         delegate_call!(self.logs(filter))
     }
 
+    /// Returns the hash of the block with the given number, or `None` if there is no such block yet.
+    pub async fn block_hash(&self, number: u64) -> anyhow::Result<Option<H256>> {
+        delegate_call!(self.block_hash(number))
+    }
+
     pub fn encode_tx_data<P: Tokenize + Clone>(&self, func: &str, params: P) -> Vec<u8> {
         match self {
             EthereumGateway::Multiplexed(c) => c.encode_tx_data(func, params),
@@ -265,6 +270,14 @@ impl EthereumGateway {
         matches!(self, EthereumGateway::Multiplexed(_))
     }
 
+    /// Returns the health of the multiplexed clients, empty for the other gateways.
+    pub fn clients_health(&self) -> Vec<ClientHealth> {
+        match self {
+            EthereumGateway::Multiplexed(c) => c.clients_health(),
+            _ => Vec::new(),
+        }
+    }
+
     pub fn get_mut_mock(&mut self) -> Option<&mut MockEthereum> {
         match self {
             EthereumGateway::Mock(ref mut m) => Some(m),
@@ -1,5 +1,5 @@
 pub mod clients;
 pub mod ethereum_gateway;
 pub use clients::http_client::ETHDirectClient;
-pub use clients::multiplexer::MultiplexerEthereumClient;
+pub use clients::multiplexer::{ClientHealth, MultiplexerEthereumClient};
 pub use ethereum_gateway::{EthereumGateway, SignedCallResult};
@@ -1,13 +1,14 @@
 use futures::{future::ready, stream, StreamExt};
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::{task::JoinHandle, time};
 use tokio_stream::wrappers::IntervalStream;
 use web3::types::{Block, BlockId, BlockNumber, H256, U64};
 
-use zksync_config::ZkSyncConfig;
+use zksync_config::{configs::gateway_watcher::GatewayHealthThresholds, ZkSyncConfig};
 use zksync_eth_client::{EthereumGateway, MultiplexerEthereumClient};
 use zksync_utils::retry_opt;
 
@@ -25,20 +26,83 @@ pub struct MultiplexedGatewayWatcher {
     req_per_task_limit: Option<usize>,
     /// How many tasks are allowed to simultaneously make requests.
     task_limit: Option<usize>,
+    /// Thresholds after which the gateway is marked unhealthy.
+    thresholds: GatewayHealthThresholds,
+    /// Results of the previous checks, by gateway name.
+    stats: Mutex<HashMap<String, GatewayStats>>,
 }
 
-const MAX_BLOCK_NUMBER_DIFFERENCE: u64 = 1;
-
 #[derive(Error, Debug, PartialEq)]
 enum BlockVerificationError {
     #[error("Hash verification failed: {0:?} != {1:?}")]
     IncorrectHash(H256, H256),
-    #[error("Difference between block numbers is greater than 1: {0:?} > {1:?}")]
+    #[error("Difference between block numbers is too large: {0:?} > {1:?}")]
     LargeNumDiff(U64, U64),
     #[error("Invalid block: {0:?}")]
     InvalidBlock(Box<Block<H256>>),
 }
 
+/// Results of the latest checks of a single gateway.
+#[derive(Debug, Default)]
+struct GatewayStats {
+    /// Results of the latest checks, `true` stands for a failed one.
+    failures: VecDeque<bool>,
+    /// Number of the checks in a row which reported a diverging block hash.
+    hash_mismatches: u32,
+    /// Set if the gateway lagged behind the longest chain during the last successful check.
+    lag: Option<String>,
+}
+
+impl GatewayStats {
+    /// Records the result of the check, `None` stands for the failed request.
+    /// Returns the reason why the gateway is unhealthy, if any.
+    fn record(
+        &mut self,
+        check: Option<&Result<(), BlockVerificationError>>,
+        thresholds: &GatewayHealthThresholds,
+    ) -> Option<String> {
+        let failed = matches!(
+            check,
+            None | Some(Err(BlockVerificationError::InvalidBlock(_)))
+        );
+        self.failures.push_back(failed);
+        while self.failures.len() > thresholds.error_rate_window {
+            self.failures.pop_front();
+        }
+
+        match check {
+            Some(Err(BlockVerificationError::IncorrectHash(..))) => self.hash_mismatches += 1,
+            Some(Err(BlockVerificationError::InvalidBlock(_))) | None => {}
+            Some(_) => self.hash_mismatches = 0,
+        }
+        match check {
+            Some(Err(err @ BlockVerificationError::LargeNumDiff(..))) => {
+                self.lag = Some(err.to_string())
+            }
+            Some(Ok(())) | Some(Err(BlockVerificationError::IncorrectHash(..))) => self.lag = None,
+            _ => {}
+        }
+
+        // Rate is calculated over the full window, so a single failure of the new gateway
+        // doesn't make it unhealthy.
+        let errors = self.failures.iter().filter(|failed| **failed).count();
+        let error_rate = errors * 100 / thresholds.error_rate_window.max(1);
+        if error_rate > thresholds.max_error_rate_percent as usize {
+            Some(format!(
+                "Error rate {}% exceeds {}%",
+                error_rate, thresholds.max_error_rate_percent
+            ))
+        } else if self.hash_mismatches > thresholds.max_hash_mismatches {
+            Some(format!(
+                "Block hash diverged in {} checks in a row",
+                self.hash_mismatches
+            ))
+        } else {
+            self.lag.clone()
+        }
+    }
+}
+
 impl MultiplexedGatewayWatcher {
     /// Instantiates `MultiplexedGatewayWatcher` for provided multiplexed ethereum gateway.
     ///
@@ -52,6 +116,7 @@ impl MultiplexedGatewayWatcher {
         req_timeout: Duration,
         req_per_task_limit: Option<usize>,
         task_limit: Option<usize>,
+        thresholds: GatewayHealthThresholds,
     ) -> Self {
         Self {
             client: match gateway {
@@ -65,6 +130,8 @@ impl MultiplexedGatewayWatcher {
             req_timeout,
             req_per_task_limit,
             task_limit,
+            thresholds,
+            stats: Default::default(),
         }
     }
 
@@ -78,10 +145,11 @@ impl MultiplexedGatewayWatcher {
     }
 
     /// Checks if either blocks are equal by hash and number or `block_to_check` is a valid parent of
-    /// `latest_block`.
+    /// `latest_block`. Blocks which are more than `max_block_lag` blocks behind are rejected.
     fn verify_blocks(
         latest_block: &Block<H256>,
         block_to_check: &Block<H256>,
+        max_block_lag: u64,
     ) -> Result<(), BlockVerificationError> {
         macro_rules! block_opt {
             ($block: expr, $opt: ident) => {
@@ -101,7 +169,7 @@ impl MultiplexedGatewayWatcher {
             block_opt!(block_to_check, number),
         );
 
-        if last_num - num > U64::from(MAX_BLOCK_NUMBER_DIFFERENCE) {
+        if last_num - num > U64::from(max_block_lag) {
             Err(BlockVerificationError::LargeNumDiff(last_num, num))
         } else if last_num == num && last_hash != hash {
             Err(BlockVerificationError::IncorrectHash(last_hash, hash))
@@ -115,8 +183,8 @@ impl MultiplexedGatewayWatcher {
         }
     }
 
-    /// Checks multiplexed client gateways and prioritizes one with longest chain,
-    /// most frequent hash and lowest latency.
+    /// Checks multiplexed client gateways, marks the diverging, lagging and failing ones unhealthy
+    /// and prioritizes the healthy one with longest chain, most frequent hash and lowest latency.
     async fn check_client_gateways(&self) {
         // Fetch latest block for each client.
         // Each request will resolve to (client key, client latest block) pair.
@@ -140,24 +208,31 @@ impl MultiplexedGatewayWatcher {
                         let req_time = start.elapsed();
                         metrics::histogram!("eth_client.multiplexed.block", req_time, &[("address", key.to_owned())]);
 
-                        Some((key, block, req_time))
+                        (key, Some((block, req_time)))
                     } else {
                         vlog::error!(
                             "Failed to get latest block from Ethereum Gateway `{}` within specified timeout",
                             key
                         );
-                        None
+                        (key, None)
                     }
                 })
                 .collect();
 
         // Execute all requests concurrently.
         // Max amount of concurrent tasks is limited by `req_per_task_limit`.
-        let client_latest_blocks: Vec<_> = stream::iter(latest_block_reqs.into_iter())
+        let client_responses: Vec<_> = stream::iter(latest_block_reqs.into_iter())
             .buffer_unordered(self.req_per_task_limit.unwrap_or(usize::MAX))
-            .filter_map(ready)
             .collect()
             .await;
+        let client_latest_blocks: Vec<_> = client_responses
+            .iter()
+            .filter_map(|(key, response)| {
+                response
+                    .as_ref()
+                    .map(|(block, req_time)| (*key, block, *req_time))
+            })
+            .collect();
 
         // Latest hash distribution across all clients.
         let hash_counts =
@@ -170,31 +245,86 @@ impl MultiplexedGatewayWatcher {
                     map
                 });
 
-        // Preferred client must have longest chain with most frequent hash and lowest latency.
-        let preferred_client =
-            client_latest_blocks
-                .iter()
-                .max_by(|(_, block1, lat1), (_, block2, lat2)| {
-                    match block1.number.cmp(&block2.number) {
-                        Ordering::Equal => match hash_counts
-                            .get(&block1.hash)
-                            .cmp(&hash_counts.get(&block2.hash))
-                        {
-                            Ordering::Equal => lat2.cmp(&lat1),
-                            other => other,
-                        },
-                        other => other,
-                    }
-                });
+        // Blocks are verified against the chain of the client which would be preferred.
+        let latest_block = client_latest_blocks
+            .iter()
+            .max_by(|client1, client2| Self::compare_clients(&hash_counts, client1, client2))
+            .map(|(_, block, _)| *block);
+
+        for (key, response) in &client_responses {
+            let check = match (response, latest_block) {
+                (Some((block, _)), Some(latest_block)) => Some(Self::verify_blocks(
+                    latest_block,
+                    block,
+                    self.thresholds.max_block_lag,
+                )),
+                _ => None,
+            };
+            if let Some(Err(err)) = &check {
+                vlog::error!("Ethereum Gateway `{}` - check failed: {}", key, err);
+            }
+            self.update_client_health(key, check.as_ref());
+        }
+
+        let preferred_client = client_latest_blocks
+            .iter()
+            .filter(|(key, _, _)| self.client.is_client_healthy(key))
+            .max_by(|client1, client2| Self::compare_clients(&hash_counts, client1, client2));
 
-        if let Some((preferred_client_key, latest_block, _)) = preferred_client {
+        if let Some((preferred_client_key, _, _)) = preferred_client {
             if self.client.prioritize_client(preferred_client_key) {
                 vlog::info!("Prioritized Ethereum Gateway: `{}`", preferred_client_key);
             }
-            for (key, block, _) in &client_latest_blocks {
-                if let Err(err) = Self::verify_blocks(latest_block, block) {
-                    vlog::error!("Ethereum Gateway `{}` - check failed: {}", key, err);
-                }
+        }
+    }
+
+    /// Preferred client must have longest chain with most frequent hash and lowest latency.
+    fn compare_clients(
+        hash_counts: &HashMap<&Option<H256>, usize>,
+        (_, block1, lat1): &(&str, &Block<H256>, Duration),
+        (_, block2, lat2): &(&str, &Block<H256>, Duration),
+    ) -> Ordering {
+        match block1.number.cmp(&block2.number) {
+            Ordering::Equal => match hash_counts
+                .get(&block1.hash)
+                .cmp(&hash_counts.get(&block2.hash))
+            {
+                Ordering::Equal => lat2.cmp(lat1),
+                other => other,
+            },
+            other => other,
+        }
+    }
+
+    /// Records the result of the check and updates the client health accordingly.
+    fn update_client_health(&self, key: &str, check: Option<&Result<(), BlockVerificationError>>) {
+        let unhealthy_reason = self
+            .stats
+            .lock()
+            .unwrap()
+            .entry(key.to_owned())
+            .or_default()
+            .record(check, &self.thresholds);
+
+        let healthy = unhealthy_reason.is_none();
+        metrics::gauge!(
+            "eth_client.multiplexed.healthy",
+            if healthy { 1.0 } else { 0.0 },
+            "address" => key.to_owned()
+        );
+
+        if let Some(reason) = &unhealthy_reason {
+            vlog::warn!("Ethereum Gateway `{}` is unhealthy: {}", key, reason);
+        }
+        if self.client.set_client_health(key, unhealthy_reason) {
+            if healthy {
+                vlog::info!("Ethereum Gateway `{}` is healthy again", key);
+            } else {
+                vlog::error!("Ethereum Gateway `{}` is de-prioritized", key);
+                metrics::increment_counter!(
+                    "eth_client.multiplexed.became_unhealthy",
+                    "address" => key.to_owned()
+                );
             }
         }
     }
@@ -217,6 +347,7 @@ pub fn run_multiplexed_gateway_watcher(
         config.gateway_watcher.request_timeout(),
         Some(config.gateway_watcher.request_per_task_limit()),
         Some(config.gateway_watcher.task_limit()),
+        config.gateway_watcher.health_thresholds(),
     );
 
     tokio::spawn(gateway_watcher.run())
@@ -251,12 +382,15 @@ mod tests {
         b1.number = Some(U64::from(1u64));
         b2.number = Some(U64::from(1u64));
 
-        assert_eq!(MultiplexedGatewayWatcher::verify_blocks(&b1, &b2), Ok(()));
+        assert_eq!(
+            MultiplexedGatewayWatcher::verify_blocks(&b1, &b2, 1),
+            Ok(())
+        );
 
         b2.hash = Some(h2);
 
         assert_eq!(
-            MultiplexedGatewayWatcher::verify_blocks(&b1, &b2),
+            MultiplexedGatewayWatcher::verify_blocks(&b1, &b2, 1),
             Err(BlockVerificationError::IncorrectHash(h1, h2))
         );
     }
@@ -274,12 +408,15 @@ mod tests {
         b1.number = Some(U64::from(1u64));
         b2.number = Some(U64::from(0u64));
 
-        assert_eq!(MultiplexedGatewayWatcher::verify_blocks(&b1, &b2), Ok(()));
+        assert_eq!(
+            MultiplexedGatewayWatcher::verify_blocks(&b1, &b2, 1),
+            Ok(())
+        );
 
         b2.hash = Some(h1);
 
         assert_eq!(
-            MultiplexedGatewayWatcher::verify_blocks(&b1, &b2),
+            MultiplexedGatewayWatcher::verify_blocks(&b1, &b2, 1),
             Err(BlockVerificationError::IncorrectHash(h2, h1))
         );
     }
@@ -297,11 +434,71 @@ mod tests {
         b2.number = Some(U64::from(0u64));
 
         assert_eq!(
-            MultiplexedGatewayWatcher::verify_blocks(&b1, &b2),
+            MultiplexedGatewayWatcher::verify_blocks(&b1, &b2, 1),
             Err(BlockVerificationError::LargeNumDiff(
                 U64::from(2u64),
                 U64::from(0u64)
             ))
         );
     }
+
+    #[test]
+    fn test_block_lag_threshold() {
+        let mut b1 = Block::default();
+        let mut b2 = Block::default();
+
+        b1.hash = Some(H256::random());
+        b2.hash = Some(H256::random());
+        b1.number = Some(U64::from(5u64));
+        b2.number = Some(U64::from(2u64));
+
+        assert_eq!(
+            MultiplexedGatewayWatcher::verify_blocks(&b1, &b2, 3),
+            Ok(())
+        );
+        assert_eq!(
+            MultiplexedGatewayWatcher::verify_blocks(&b1, &b2, 2),
+            Err(BlockVerificationError::LargeNumDiff(
+                U64::from(5u64),
+                U64::from(2u64)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_gateway_health() {
+        let thresholds = GatewayHealthThresholds {
+            max_block_lag: 1,
+            max_hash_mismatches: 1,
+            max_error_rate_percent: 50,
+            error_rate_window: 4,
+        };
+        let mismatch = Err(BlockVerificationError::IncorrectHash(
+            H256::zero(),
+            H256::repeat_byte(1),
+        ));
+        let lag = Err(BlockVerificationError::LargeNumDiff(
+            U64::from(3u64),
+            U64::from(1u64),
+        ));
+        let mut stats = GatewayStats::default();
+
+        // Single hash mismatch is tolerated, e.g. the gateway may not have received the block yet.
+        assert!(stats.record(Some(&mismatch), &thresholds).is_none());
+        assert!(stats.record(Some(&mismatch), &thresholds).is_some());
+        assert!(stats.record(Some(&Ok(())), &thresholds).is_none());
+
+        // Lagging gateway is unhealthy until it catches up, failed requests don't change that.
+        assert!(stats.record(Some(&lag), &thresholds).is_some());
+        assert!(stats.record(None, &thresholds).is_some());
+        assert!(stats.record(Some(&Ok(())), &thresholds).is_none());
+
+        // Error rate is calculated over the window.
+        let mut stats = GatewayStats::default();
+        assert!(stats.record(None, &thresholds).is_none());
+        assert!(stats.record(None, &thresholds).is_none());
+        assert!(stats.record(None, &thresholds).is_some());
+        assert!(stats.record(Some(&Ok(())), &thresholds).is_some());
+        assert!(stats.record(Some(&Ok(())), &thresholds).is_none());
+    }
 }
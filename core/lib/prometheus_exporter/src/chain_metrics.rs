@@ -0,0 +1,177 @@
+//! Chain-level metrics computed from the storage.
+//!
+//! Metrics are refreshed periodically by a single task using one connection at a time, so they don't compete
+//! with the server for the database. The queries scanning large tables (balances, transactions and accounts)
+//! are refreshed less often than the cheap ones.
+
+// Built-in uses
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+// External uses
+use tokio::time;
+// Workspace uses
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_types::aggregated_operations::AggregatedActionType::{self, *};
+
+/// Interval between updates of the cheap metrics.
+const QUERY_INTERVAL: Duration = Duration::from_secs(60);
+/// Interval between updates of the metrics requiring heavy queries.
+const HEAVY_QUERY_INTERVAL: Duration = Duration::from_secs(10 * 60);
+/// Period for which accounts creation and collected fees are reported.
+const REPORT_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub(crate) async fn run_chain_metrics(connection_pool: ConnectionPool) {
+    let mut timer = time::interval(QUERY_INTERVAL);
+    let mut last_heavy_update: Option<Instant> = None;
+
+    loop {
+        timer.tick().await;
+
+        let mut storage = match connection_pool.access_storage().await {
+            Ok(storage) => storage,
+            Err(err) => {
+                vlog::warn!("Unable to access storage to update chain metrics: {}", err);
+                continue;
+            }
+        };
+
+        if let Err(err) = update_operations_metrics(&mut storage).await {
+            vlog::warn!("Unable to update aggregated operations metrics: {}", err);
+        }
+        if let Err(err) = update_blocks_metrics(&mut storage).await {
+            vlog::warn!("Unable to update blocks metrics: {}", err);
+        }
+
+        let heavy_update_required = last_heavy_update
+            .map(|updated_at| updated_at.elapsed() >= HEAVY_QUERY_INTERVAL)
+            .unwrap_or(true);
+        if heavy_update_required {
+            let start = Instant::now();
+            if let Err(err) = update_tokens_metrics(&mut storage).await {
+                vlog::warn!("Unable to update tokens metrics: {}", err);
+            }
+            if let Err(err) = update_period_metrics(&mut storage).await {
+                vlog::warn!("Unable to update accounts and fees metrics: {}", err);
+            }
+            metrics::histogram!("chain_metrics.heavy_update", start.elapsed());
+            last_heavy_update = Some(Instant::now());
+        }
+    }
+}
+
+async fn update_operations_metrics(storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+    let mut transaction = storage.start_transaction().await?;
+    let mut block_schema = transaction.chain().block_schema();
+
+    for &action in &[CommitBlocks, ExecuteBlocks] {
+        for &is_confirmed in &[false, true] {
+            let result = block_schema
+                .count_aggregated_operations(action, is_confirmed)
+                .await?;
+            metrics::gauge!(
+                "count_operations",
+                result as f64,
+                "action" => action.to_string(),
+                "confirmed" => is_confirmed.to_string()
+            );
+        }
+    }
+
+    transaction.commit().await?;
+    Ok(())
+}
+
+/// Reports the last block on each stage, the lag between the stages and operations in the last block.
+async fn update_blocks_metrics(storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+    let last_sealed = storage
+        .chain()
+        .block_schema()
+        .get_last_saved_block()
+        .await?;
+    let last_committed = last_confirmed_block(storage, CommitBlocks).await?;
+    let last_proven = last_confirmed_block(storage, PublishProofBlocksOnchain).await?;
+    let last_executed = last_confirmed_block(storage, ExecuteBlocks).await?;
+
+    for &(stage, block_number) in &[
+        ("sealed", *last_sealed),
+        ("committed", last_committed),
+        ("proven", last_proven),
+        ("executed", last_executed),
+    ] {
+        metrics::gauge!("chain.last_block", block_number as f64, "stage" => stage);
+    }
+    // Lag of each stage behind the previous one.
+    for &(stage, lag) in &[
+        ("committed", last_sealed.saturating_sub(last_committed)),
+        ("proven", last_committed.saturating_sub(last_proven)),
+        ("executed", last_proven.saturating_sub(last_executed)),
+    ] {
+        metrics::gauge!("chain.blocks_lag", lag as f64, "stage" => stage);
+    }
+
+    let operations = storage
+        .chain()
+        .stats_schema()
+        .count_block_operations_by_type(last_sealed)
+        .await?;
+    for operations_count in operations {
+        metrics::gauge!(
+            "chain.last_block_operations",
+            operations_count.count as f64,
+            "type" => operations_count.op_type
+        );
+    }
+
+    Ok(())
+}
+
+async fn last_confirmed_block(
+    storage: &mut StorageProcessor<'_>,
+    action: AggregatedActionType,
+) -> anyhow::Result<u32> {
+    let block_number = storage
+        .chain()
+        .operations_schema()
+        .get_last_block_by_aggregated_action(action, Some(true))
+        .await?;
+    Ok(*block_number)
+}
+
+async fn update_tokens_metrics(storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+    let total_value_locked = storage.chain().stats_schema().total_value_locked().await?;
+    for token in total_value_locked {
+        metrics::gauge!("chain.total_value_locked", token.amount, "token" => token.symbol);
+    }
+    Ok(())
+}
+
+/// Reports the number of created accounts and collected fees for the last `REPORT_PERIOD`.
+async fn update_period_metrics(storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+    let period_start = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Incorrect system time")
+        .as_secs()
+        .saturating_sub(REPORT_PERIOD.as_secs());
+    let first_block = storage
+        .chain()
+        .stats_schema()
+        .first_block_since(period_start)
+        .await?;
+
+    let (created_accounts, collected_fees) = match first_block {
+        Some(first_block) => {
+            let mut stats_schema = storage.chain().stats_schema();
+            (
+                stats_schema.count_created_accounts(first_block).await?,
+                stats_schema.collected_fees(first_block).await?,
+            )
+        }
+        // No blocks were sealed during the period.
+        None => (0, Vec::new()),
+    };
+
+    metrics::gauge!("chain.created_accounts_per_day", created_accounts as f64);
+    for fee in collected_fees {
+        metrics::gauge!("chain.collected_fees_per_day", fee.amount, "token" => fee.symbol);
+    }
+    Ok(())
+}
@@ -1,14 +1,15 @@
 //! This module handles metric export to the Prometheus server
 
 use metrics_exporter_prometheus::PrometheusBuilder;
-use std::time::Duration;
 use tokio::task::JoinHandle;
-use tokio::time::sleep;
 use zksync_storage::ConnectionPool;
-use zksync_types::aggregated_operations::AggregatedActionType::*;
 
-const QUERY_INTERVAL: Duration = Duration::from_secs(60);
+use crate::chain_metrics::run_chain_metrics;
 
+mod chain_metrics;
+
+/// Runs the Prometheus exporter server. If `is_operation_counter_needed` is set, also runs the task
+/// reporting the chain-level metrics computed from the storage, it should be done by a single server only.
 pub fn run_prometheus_exporter(
     connection_pool: ConnectionPool,
     port: u16,
@@ -31,42 +32,7 @@ pub fn run_prometheus_exporter(
     });
 
     let operation_counter_handle = if is_operation_counter_needed {
-        Some(tokio::spawn(async move {
-            let mut storage = connection_pool
-                .access_storage()
-                .await
-                .expect("unable to access storage");
-
-            loop {
-                let mut transaction = storage
-                    .start_transaction()
-                    .await
-                    .expect("unable to start db transaction");
-                let mut block_schema = transaction.chain().block_schema();
-
-                for &action in &[CommitBlocks, ExecuteBlocks] {
-                    for &is_confirmed in &[false, true] {
-                        let result = block_schema
-                            .count_aggregated_operations(action, is_confirmed)
-                            .await
-                            .expect("");
-                        metrics::gauge!(
-                            "count_operations",
-                            result as f64,
-                            "action" => action.to_string(),
-                            "confirmed" => is_confirmed.to_string()
-                        );
-                    }
-                }
-
-                transaction
-                    .commit()
-                    .await
-                    .expect("unable to commit db transaction");
-
-                sleep(QUERY_INTERVAL).await;
-            }
-        }))
+        Some(tokio::spawn(run_chain_metrics(connection_pool)))
     } else {
         None
     };
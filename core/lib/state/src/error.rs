@@ -23,6 +23,39 @@ pub enum OpError {
     TimestampError,
 }
 
+impl OpError {
+    /// Returns the name of the error variant, e.g. `TransferOpError`.
+    pub fn variance_name(&self) -> &'static str {
+        match self {
+            Self::TransferOpError(_) => "TransferOpError",
+            Self::WithdrawOpError(_) => "WithdrawOpError",
+            Self::WithdrawNFTOpError(_) => "WithdrawNFTOpError",
+            Self::CloseOpError(_) => "CloseOpError",
+            Self::ChangePubKeyOpError(_) => "ChangePubKeyOpError",
+            Self::ForcedExitOpError(_) => "ForcedExitOpError",
+            Self::SwapOpError(_) => "SwapOpError",
+            Self::MintNFTOpError(_) => "MintNFTOpError",
+            Self::TimestampError => "TimestampError",
+        }
+    }
+
+    /// Returns the name of the underlying handler error variant without its payload,
+    /// e.g. `InsufficientBalance`. Suitable to be used as a metric label.
+    pub fn reason_name(&self) -> &'static str {
+        match self {
+            Self::TransferOpError(err) => err.name(),
+            Self::WithdrawOpError(err) => err.name(),
+            Self::WithdrawNFTOpError(err) => err.name(),
+            Self::CloseOpError(err) => err.name(),
+            Self::ChangePubKeyOpError(err) => err.name(),
+            Self::ForcedExitOpError(err) => err.name(),
+            Self::SwapOpError(err) => err.name(),
+            Self::MintNFTOpError(err) => err.name(),
+            Self::TimestampError => self.variance_name(),
+        }
+    }
+}
+
 #[derive(Debug, Error, PartialEq)]
 #[error(
     "Batch execution failed, since tx #{failed_tx_index} of batch failed with a reason: {reason}"
@@ -31,3 +64,19 @@ pub struct TxBatchError {
     pub failed_tx_index: usize,
     pub reason: OpError,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_names() {
+        let error = OpError::from(CloseOpError::AccountNotEmpty(2));
+        assert_eq!(error.variance_name(), "CloseOpError");
+        assert_eq!(error.reason_name(), "AccountNotEmpty");
+
+        let error = OpError::TimestampError;
+        assert_eq!(error.variance_name(), "TimestampError");
+        assert_eq!(error.reason_name(), "TimestampError");
+    }
+}
@@ -22,6 +22,23 @@ pub enum ChangePubKeyOpError {
     InsufficientBalance,
 }
 
+impl ChangePubKeyOpError {
+    /// Returns the name of the error variant without its payload.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InvalidFeeTokenId => "InvalidFeeTokenId",
+            Self::AccountNotFound => "AccountNotFound",
+            Self::InvalidAccountAddress => "InvalidAccountAddress",
+            Self::InvalidAuthData => "InvalidAuthData",
+            Self::InvalidZksyncSignature => "InvalidZksyncSignature",
+            Self::InvalidAccountId => "InvalidAccountId",
+            Self::AccountIdTooBig => "AccountIdTooBig",
+            Self::NonceMismatch => "NonceMismatch",
+            Self::InsufficientBalance => "InsufficientBalance",
+        }
+    }
+}
+
 #[derive(Clone, Debug, Error, PartialEq)]
 pub enum CloseOpError {
     #[error("Close operations are disabled")]
@@ -34,6 +51,18 @@ pub enum CloseOpError {
     NonceMismatch,
 }
 
+impl CloseOpError {
+    /// Returns the name of the error variant without its payload.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CloseOperationsDisabled => "CloseOperationsDisabled",
+            Self::InvalidAccountId => "InvalidAccountId",
+            Self::AccountNotEmpty(_) => "AccountNotEmpty",
+            Self::NonceMismatch => "NonceMismatch",
+        }
+    }
+}
+
 #[derive(Clone, Debug, Error, PartialEq)]
 pub enum DepositOpError {
     #[error("Deposit token is out of range, this should be enforced by contract")]
@@ -66,6 +95,25 @@ pub enum ForcedExitOpError {
     TargetAccountBalanceMismatch,
 }
 
+impl ForcedExitOpError {
+    /// Returns the name of the error variant without its payload.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InitiatorAccountNotFound => "InitiatorAccountNotFound",
+            Self::IncorrectInitiatorAccount => "IncorrectInitiatorAccount",
+            Self::TargetAccountNotFound => "TargetAccountNotFound",
+            Self::InvalidSignature => "InvalidSignature",
+            Self::InvalidFeeTokenId => "InvalidFeeTokenId",
+            Self::InvalidTokenId => "InvalidTokenId",
+            Self::TargetAccountNotLocked => "TargetAccountNotLocked",
+            Self::InvalidTargetAccount => "InvalidTargetAccount",
+            Self::NonceMismatch => "NonceMismatch",
+            Self::InitiatorInsufficientBalance => "InitiatorInsufficientBalance",
+            Self::TargetAccountBalanceMismatch => "TargetAccountBalanceMismatch",
+        }
+    }
+}
+
 #[derive(Clone, Debug, Error, PartialEq)]
 pub enum TransferOpError {
     #[error("FeeToken id is not supported")]
@@ -94,6 +142,26 @@ pub enum TransferOpError {
     CannotTransferToSelf,
 }
 
+impl TransferOpError {
+    /// Returns the name of the error variant without its payload.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InvalidFeeTokenId => "InvalidFeeTokenId",
+            Self::InvalidTokenId => "InvalidTokenId",
+            Self::TargetAccountZero => "TargetAccountZero",
+            Self::FromAccountNotFound => "FromAccountNotFound",
+            Self::FromAccountLocked => "FromAccountLocked",
+            Self::TransferAccountIncorrect => "TransferAccountIncorrect",
+            Self::InvalidSignature => "InvalidSignature",
+            Self::SourceAccountIncorrect => "SourceAccountIncorrect",
+            Self::TargetAccountIncorrect => "TargetAccountIncorrect",
+            Self::NonceMismatch => "NonceMismatch",
+            Self::InsufficientBalance => "InsufficientBalance",
+            Self::CannotTransferToSelf => "CannotTransferToSelf",
+        }
+    }
+}
+
 #[derive(Clone, Debug, Error, PartialEq)]
 pub enum MintNFTOpError {
     #[error("Token id is not supported")]
@@ -118,6 +186,24 @@ pub enum MintNFTOpError {
     TokenIsAlreadyInAccount,
 }
 
+impl MintNFTOpError {
+    /// Returns the name of the error variant without its payload.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InvalidTokenId => "InvalidTokenId",
+            Self::CreatorAccountIsLocked => "CreatorAccountIsLocked",
+            Self::CreatorAccountNotFound => "CreatorAccountNotFound",
+            Self::CreatorAccountLocked => "CreatorAccountLocked",
+            Self::InvalidSignature => "InvalidSignature",
+            Self::RecipientAccountIncorrect => "RecipientAccountIncorrect",
+            Self::RecipientAccountNotFound => "RecipientAccountNotFound",
+            Self::NonceMismatch => "NonceMismatch",
+            Self::InsufficientBalance => "InsufficientBalance",
+            Self::TokenIsAlreadyInAccount => "TokenIsAlreadyInAccount",
+        }
+    }
+}
+
 #[derive(Clone, Debug, Error, PartialEq)]
 pub enum WithdrawNFTOpError {
     #[error("FeeToken id is not supported")]
@@ -144,6 +230,25 @@ pub enum WithdrawNFTOpError {
     NFTNotFound,
 }
 
+impl WithdrawNFTOpError {
+    /// Returns the name of the error variant without its payload.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InvalidFeeTokenId => "InvalidFeeTokenId",
+            Self::InvalidTokenId => "InvalidTokenId",
+            Self::FromAccountNotFound => "FromAccountNotFound",
+            Self::FromAccountLocked => "FromAccountLocked",
+            Self::InvalidSignature => "InvalidSignature",
+            Self::FromAccountIncorrect => "FromAccountIncorrect",
+            Self::CreatorAccountIncorrect => "CreatorAccountIncorrect",
+            Self::NonceMismatch => "NonceMismatch",
+            Self::InsufficientBalance => "InsufficientBalance",
+            Self::InsufficientNFTBalance => "InsufficientNFTBalance",
+            Self::NFTNotFound => "NFTNotFound",
+        }
+    }
+}
+
 #[derive(Clone, Debug, Error, PartialEq)]
 pub enum WithdrawOpError {
     #[error("FeeToken id is not supported")]
@@ -163,6 +268,23 @@ pub enum WithdrawOpError {
     #[error("Not enough balance")]
     InsufficientBalance,
 }
+
+impl WithdrawOpError {
+    /// Returns the name of the error variant without its payload.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InvalidFeeTokenId => "InvalidFeeTokenId",
+            Self::InvalidTokenId => "InvalidTokenId",
+            Self::FromAccountNotFound => "FromAccountNotFound",
+            Self::FromAccountLocked => "FromAccountLocked",
+            Self::InvalidSignature => "InvalidSignature",
+            Self::FromAccountIncorrect => "FromAccountIncorrect",
+            Self::NonceMismatch => "NonceMismatch",
+            Self::InsufficientBalance => "InsufficientBalance",
+        }
+    }
+}
+
 #[derive(Clone, Debug, Error, PartialEq)]
 pub enum SwapOpError {
     #[error("Token id is not supported")]
@@ -202,3 +324,29 @@ pub enum SwapOpError {
     #[error("Self-swap is not allowed")]
     SelfSwap,
 }
+
+impl SwapOpError {
+    /// Returns the name of the error variant without its payload.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InvalidTokenId => "InvalidTokenId",
+            Self::AccountZero => "AccountZero",
+            Self::AccountIncorrect => "AccountIncorrect",
+            Self::SubmitterAccountIncorrect => "SubmitterAccountIncorrect",
+            Self::SubmitterAccountNotFound => "SubmitterAccountNotFound",
+            Self::AccountNotFound => "AccountNotFound",
+            Self::AccountLocked => "AccountLocked",
+            Self::SwapInvalidSignature => "SwapInvalidSignature",
+            Self::OrderInvalidSignature => "OrderInvalidSignature",
+            Self::SourceAccountIncorrect => "SourceAccountIncorrect",
+            Self::RecipientAccountNotFound => "RecipientAccountNotFound",
+            Self::NonceMismatch => "NonceMismatch",
+            Self::InsufficientBalance => "InsufficientBalance",
+            Self::BuySellNotMatched => "BuySellNotMatched",
+            Self::SwapSameToken => "SwapSameToken",
+            Self::AmountsNotMatched => "AmountsNotMatched",
+            Self::AmountsNotCompatible => "AmountsNotCompatible",
+            Self::SelfSwap => "SelfSwap",
+        }
+    }
+}
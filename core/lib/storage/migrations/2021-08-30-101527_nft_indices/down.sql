@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS nft_creator_account_id_idx;
+DROP INDEX IF EXISTS executed_transactions_token_idx;
+DROP INDEX IF EXISTS executed_transactions_swap_first_token_sell_idx;
+DROP INDEX IF EXISTS executed_transactions_swap_second_token_sell_idx;
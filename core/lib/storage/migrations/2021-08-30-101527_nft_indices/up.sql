@@ -0,0 +1,14 @@
+-- Index for listing NFTs minted by the creator.
+CREATE INDEX IF NOT EXISTS nft_creator_account_id_idx
+    ON "nft" (creator_account_id, token_id);
+
+-- Indices for the NFT transfer history.
+CREATE INDEX IF NOT EXISTS executed_transactions_token_idx
+    ON "executed_transactions" USING hash ((tx->'token'))
+    WHERE tx->'type' IN ('"Transfer"', '"WithdrawNFT"');
+CREATE INDEX IF NOT EXISTS executed_transactions_swap_first_token_sell_idx
+    ON "executed_transactions" USING hash ((tx->'orders'->0->'tokenSell'))
+    WHERE tx->'type' = '"Swap"';
+CREATE INDEX IF NOT EXISTS executed_transactions_swap_second_token_sell_idx
+    ON "executed_transactions" USING hash ((tx->'orders'->1->'tokenSell'))
+    WHERE tx->'type' = '"Swap"';
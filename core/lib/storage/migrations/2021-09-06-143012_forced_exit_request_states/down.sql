@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS forced_exit_request_events;
+
+ALTER TABLE forced_exit_requests DROP COLUMN IF EXISTS state;
+ALTER TABLE forced_exit_requests DROP COLUMN IF EXISTS payer;
+ALTER TABLE forced_exit_requests DROP COLUMN IF EXISTS refunded_by;
+ALTER TABLE forced_exit_requests DROP COLUMN IF EXISTS refund_nonce;
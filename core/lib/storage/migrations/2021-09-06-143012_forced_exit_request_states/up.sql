@@ -0,0 +1,20 @@
+ALTER TABLE forced_exit_requests ADD COLUMN state TEXT NOT NULL DEFAULT 'created';
+ALTER TABLE forced_exit_requests ADD COLUMN payer TEXT; -- address the payment was sent from
+ALTER TABLE forced_exit_requests ADD COLUMN refunded_by TEXT; -- hash of the refund transfer
+ALTER TABLE forced_exit_requests ADD COLUMN refund_nonce BIGINT; -- nonce of the refund transfer
+
+UPDATE forced_exit_requests SET state = CASE
+    WHEN fulfilled_at IS NOT NULL THEN 'committed'
+    WHEN fulfilled_by IS NOT NULL THEN 'submitted'
+    ELSE 'created'
+END;
+
+CREATE TABLE forced_exit_request_events (
+    id BIGSERIAL PRIMARY KEY,
+    request_id BIGINT NOT NULL REFERENCES forced_exit_requests(id) ON DELETE CASCADE,
+    state TEXT NOT NULL,
+    tx_hashes TEXT, -- comma-separated list of the hashes of the transactions related to the state change
+    created_at TIMESTAMP with time zone NOT NULL
+);
+
+CREATE INDEX forced_exit_request_events_request_id_idx ON forced_exit_request_events (request_id);
@@ -0,0 +1,6 @@
+-- Lease which allows only one instance of the component (e.g. `zksync_core`) to be active at a time.
+CREATE TABLE leader_lease (
+    name TEXT PRIMARY KEY,
+    holder TEXT NOT NULL,
+    expires_at TIMESTAMP with time zone NOT NULL
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS token_settings;
+DROP TYPE IF EXISTS token_fee_policy;
@@ -0,0 +1,15 @@
+CREATE TYPE token_fee_policy AS ENUM ('MarketVolume', 'Allowed', 'Disallowed');
+
+-- Every change of the token metadata or listing settings made by the admin creates a new version.
+-- The latest version is the current one, tokens without versions have the default settings.
+CREATE TABLE token_settings (
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON DELETE CASCADE,
+    version BIGINT NOT NULL,
+    symbol TEXT NOT NULL,
+    decimals SMALLINT NOT NULL,
+    name TEXT,
+    deprecated BOOLEAN NOT NULL,
+    fee_policy token_fee_policy NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (token_id, version)
+);
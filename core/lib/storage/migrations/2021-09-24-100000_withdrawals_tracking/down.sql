@@ -0,0 +1,16 @@
+DROP TABLE IF EXISTS withdrawals_tracker_state;
+DROP TABLE IF EXISTS withdrawals;
+DROP TYPE IF EXISTS withdrawal_status;
+
+-- There is no easy way to remove a value from enum, so the type has to be recreated.
+DELETE FROM events WHERE event_type::text = 'Withdrawal';
+
+CREATE TYPE event_type_old AS ENUM ('Account', 'Block', 'Transaction');
+
+ALTER TABLE events
+    ALTER COLUMN event_type TYPE event_type_old
+        USING (event_type::text::event_type_old);
+
+DROP TYPE event_type;
+
+ALTER TYPE event_type_old RENAME TO event_type;
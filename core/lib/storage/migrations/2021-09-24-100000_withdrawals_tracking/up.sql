@@ -0,0 +1,25 @@
+ALTER TYPE event_type ADD VALUE 'Withdrawal';
+
+CREATE TYPE withdrawal_status AS ENUM ('Executed', 'PendingBalance', 'Claimed');
+
+-- Fungible withdrawals from the blocks executed on L1.
+CREATE TABLE withdrawals (
+    tx_hash bytea PRIMARY KEY,
+    block_number BIGINT NOT NULL,
+    address bytea NOT NULL,
+    token_id INTEGER NOT NULL,
+    amount NUMERIC NOT NULL,
+    status withdrawal_status NOT NULL,
+    eth_tx_hash bytea NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE INDEX withdrawals_pending_balance_index ON withdrawals (address, token_id)
+    WHERE status = 'PendingBalance';
+
+-- The last executed block, which withdrawals have been processed by the tracker.
+CREATE TABLE withdrawals_tracker_state (
+    id BOOL PRIMARY KEY DEFAULT true,
+    last_block BIGINT NOT NULL,
+    CONSTRAINT single_withdrawals_tracker_state CHECK (id)
+);
@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS executed_priority_operations_full_exit_token_idx;
+DROP INDEX IF EXISTS executed_transactions_mint_nft_block_number_idx;
+
+DROP INDEX IF EXISTS executed_transactions_token_idx;
+CREATE INDEX IF NOT EXISTS executed_transactions_token_idx
+    ON "executed_transactions" USING hash ((tx->'token'))
+    WHERE tx->'type' IN ('"Transfer"', '"WithdrawNFT"');
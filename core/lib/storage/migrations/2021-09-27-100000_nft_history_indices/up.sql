@@ -0,0 +1,15 @@
+-- NFT transfer history includes `ForcedExit` transactions as well.
+DROP INDEX IF EXISTS executed_transactions_token_idx;
+CREATE INDEX IF NOT EXISTS executed_transactions_token_idx
+    ON "executed_transactions" USING hash ((tx->'token'))
+    WHERE tx->'type' IN ('"Transfer"', '"WithdrawNFT"', '"ForcedExit"');
+
+-- Index for finding the `MintNFT` transaction of the NFT by the block it was minted in.
+CREATE INDEX IF NOT EXISTS executed_transactions_mint_nft_block_number_idx
+    ON "executed_transactions" (block_number)
+    WHERE tx->'type' = '"MintNFT"';
+
+-- Index for the `FullExit` operations of the NFT.
+CREATE INDEX IF NOT EXISTS executed_priority_operations_full_exit_token_idx
+    ON "executed_priority_operations" USING hash ((operation->'priority_op'->'token'))
+    WHERE operation->'type' = '"FullExit"';
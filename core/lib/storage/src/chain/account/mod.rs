@@ -4,8 +4,9 @@ use std::time::Instant;
 use num::{BigUint, Zero};
 use sqlx::{types::BigDecimal, Acquire};
 // Workspace imports
+use zksync_api_types::v02::pagination::{PaginationDirection, PaginationQuery};
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_STORAGE_ACCOUNT_ID, NFT_TOKEN_ID};
-use zksync_types::{Account, AccountId, AccountUpdates, Address, BlockNumber, TokenId};
+use zksync_types::{Account, AccountId, AccountUpdates, Address, BlockNumber, TokenId, NFT};
 // Local imports
 use self::records::*;
 use crate::chain::block::BlockSchema;
@@ -473,6 +474,120 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(balance)
     }
 
+    /// Returns the amount of NFTs owned by the account according to the finalized state.
+    pub async fn get_account_nfts_count(&mut self, account_id: AccountId) -> QueryResult<u32> {
+        let start = Instant::now();
+
+        let count = sqlx::query!(
+            r#"
+                SELECT COUNT(*) FROM balances
+                WHERE account_id = $1 AND coin_id >= $2 AND coin_id < $3 AND balance = 1
+            "#,
+            i64::from(account_id.0),
+            MIN_NFT_TOKEN_ID as i32,
+            NFT_TOKEN_ID.0 as i32
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count
+        .unwrap_or(0) as u32;
+
+        metrics::histogram!("sql.chain.account.get_account_nfts_count", start.elapsed());
+        Ok(count)
+    }
+
+    /// Returns the greatest id of NFT owned by the account, if any.
+    pub async fn get_account_last_nft_id(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<Option<TokenId>> {
+        let start = Instant::now();
+
+        let record = sqlx::query!(
+            r#"
+                SELECT MAX(coin_id) as "token_id" FROM balances
+                WHERE account_id = $1 AND coin_id >= $2 AND coin_id < $3 AND balance = 1
+            "#,
+            i64::from(account_id.0),
+            MIN_NFT_TOKEN_ID as i32,
+            NFT_TOKEN_ID.0 as i32
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.account.get_account_last_nft_id", start.elapsed());
+        Ok(record.token_id.map(|id| TokenId(id as u32)))
+    }
+
+    /// Loads NFTs owned by the account for the given pagination query.
+    /// NFTs are sorted by their token ids.
+    pub async fn load_account_nfts_page(
+        &mut self,
+        account_id: AccountId,
+        query: &PaginationQuery<TokenId>,
+    ) -> QueryResult<Vec<NFT>> {
+        let start = Instant::now();
+
+        let nfts = match query.direction {
+            PaginationDirection::Newer => {
+                sqlx::query_as!(
+                    StorageNFT,
+                    r#"
+                    SELECT
+                        nft.token_id as "token_id!", creator_account_id as "creator_account_id!",
+                        creator_address as "creator_address!", serial_id as "serial_id!",
+                        nft.address as "address!", content_hash as "content_hash!",
+                        tokens.symbol as "symbol!"
+                    FROM balances
+                    INNER JOIN nft
+                    ON nft.token_id = balances.coin_id
+                    INNER JOIN tokens
+                    ON tokens.id = nft.token_id
+                    WHERE balances.account_id = $1 AND balances.balance = 1
+                        AND balances.coin_id >= $2
+                    ORDER BY balances.coin_id ASC
+                    LIMIT $3
+                    "#,
+                    i64::from(account_id.0),
+                    std::cmp::max(query.from.0, MIN_NFT_TOKEN_ID) as i32,
+                    i64::from(query.limit)
+                )
+                .fetch_all(self.0.conn())
+                .await?
+            }
+            PaginationDirection::Older => {
+                sqlx::query_as!(
+                    StorageNFT,
+                    r#"
+                    SELECT
+                        nft.token_id as "token_id!", creator_account_id as "creator_account_id!",
+                        creator_address as "creator_address!", serial_id as "serial_id!",
+                        nft.address as "address!", content_hash as "content_hash!",
+                        tokens.symbol as "symbol!"
+                    FROM balances
+                    INNER JOIN nft
+                    ON nft.token_id = balances.coin_id
+                    INNER JOIN tokens
+                    ON tokens.id = nft.token_id
+                    WHERE balances.account_id = $1 AND balances.balance = 1
+                        AND balances.coin_id <= $2 AND balances.coin_id >= $3
+                    ORDER BY balances.coin_id DESC
+                    LIMIT $4
+                    "#,
+                    i64::from(account_id.0),
+                    query.from.0 as i32,
+                    MIN_NFT_TOKEN_ID as i32,
+                    i64::from(query.limit)
+                )
+                .fetch_all(self.0.conn())
+                .await?
+            }
+        };
+
+        metrics::histogram!("sql.chain.account.load_account_nfts_page", start.elapsed());
+        Ok(nfts.into_iter().map(NFT::from).collect())
+    }
+
     pub async fn get_nft_owner(&mut self, token_id: TokenId) -> QueryResult<Option<AccountId>> {
         let start = Instant::now();
 
@@ -7,7 +7,7 @@ use chrono::{DateTime, Utc};
 // Workspace imports
 use zksync_api_types::{
     v02::{
-        pagination::{AccountTxsRequest, PaginationDirection, PaginationQuery},
+        pagination::{AccountTxsRequest, NFTTxsRequest, PaginationDirection, PaginationQuery},
         transaction::{
             ApiTxBatch, BatchStatus, Receipt, Transaction, TxData, TxHashSerializeWrapper,
             TxInBlockStatus,
@@ -1360,6 +1360,347 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok((tx_count + priority_op_count) as u32)
     }
 
+    /// Loads successful transactions that move the NFT, i.e. `MintNFT`, `Transfer`, `WithdrawNFT`,
+    /// `ForcedExit`, `Swap` where it is the sold token, and `FullExit` priority operations which
+    /// withdrew it. `MintNFT` is found by the creator nonce stored along with the minted NFT.
+    ///
+    /// Returns `None` if the transaction from the query doesn't exist.
+    pub async fn get_nft_transactions(
+        &mut self,
+        query: &PaginationQuery<NFTTxsRequest>,
+    ) -> QueryResult<Option<Vec<Transaction>>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let tx_hash = match query.from.tx_hash.inner {
+            Either::Left(tx_hash) => tx_hash,
+            Either::Right(_) => {
+                if let Some(tx_hash) = transaction
+                    .chain()
+                    .operations_ext_schema()
+                    .get_nft_last_tx_hash(query.from.token_id)
+                    .await?
+                {
+                    tx_hash
+                } else {
+                    return Ok(Some(Vec::new()));
+                }
+            }
+        };
+        let created_at_and_block = transaction
+            .chain()
+            .operations_ext_schema()
+            .get_tx_created_at_and_block_number(tx_hash)
+            .await?;
+        let token_id_value = serde_json::to_value(query.from.token_id).unwrap();
+
+        let txs = if let Some((time_from, _)) = created_at_and_block {
+            let raw_txs: Vec<TransactionItem> = match query.direction {
+                PaginationDirection::Newer => {
+                    sqlx::query_as!(
+                        TransactionItem,
+                        r#"
+                            WITH transactions AS (
+                                SELECT
+                                    tx_hash,
+                                    tx as op,
+                                    block_number,
+                                    created_at,
+                                    success,
+                                    fail_reason,
+                                    Null::bytea as eth_hash,
+                                    Null::bigint as priority_op_serialid,
+                                    block_index
+                                FROM executed_transactions
+                                WHERE success = true AND (
+                                    (
+                                        tx->'type' IN ('"Transfer"', '"WithdrawNFT"', '"ForcedExit"')
+                                        AND tx->'token' = $1
+                                    )
+                                    OR (
+                                        tx->'type' = '"Swap"'
+                                        AND (
+                                            tx->'orders'->0->'tokenSell' = $1
+                                            OR
+                                            tx->'orders'->1->'tokenSell' = $1
+                                        )
+                                    )
+                                    OR (
+                                        tx->'type' = '"MintNFT"'
+                                        AND (block_number, tx->'creatorId', tx->'nonce') IN (
+                                            SELECT block_number, to_jsonb(creator_account_id), to_jsonb(nonce)
+                                            FROM mint_nft_updates
+                                            WHERE token_id = $2
+                                        )
+                                    )
+                                )
+                                    AND created_at >= $3
+                            ), priority_ops AS (
+                                SELECT
+                                    tx_hash,
+                                    operation as op,
+                                    block_number,
+                                    created_at,
+                                    true as success,
+                                    Null as fail_reason,
+                                    eth_hash,
+                                    priority_op_serialid,
+                                    block_index
+                                FROM executed_priority_operations
+                                WHERE operation->'type' = '"FullExit"'
+                                    AND operation->'priority_op'->'token' = $1
+                                    AND operation->>'withdraw_amount' <> '0'
+                                    AND created_at >= $3
+                            ), everything AS (
+                                SELECT * FROM transactions
+                                UNION ALL
+                                SELECT * FROM priority_ops
+                            )
+                            SELECT
+                                tx_hash as "tx_hash!",
+                                block_number as "block_number!",
+                                op as "op!",
+                                created_at as "created_at!",
+                                success as "success!",
+                                fail_reason as "fail_reason?",
+                                eth_hash as "eth_hash?",
+                                priority_op_serialid as "priority_op_serialid?"
+                            FROM everything
+                            ORDER BY created_at ASC, block_index ASC
+                            LIMIT $4
+                        "#,
+                        token_id_value,
+                        *query.from.token_id as i32,
+                        time_from,
+                        i64::from(query.limit),
+                    )
+                    .fetch_all(transaction.conn())
+                    .await?
+                }
+                PaginationDirection::Older => {
+                    sqlx::query_as!(
+                        TransactionItem,
+                        r#"
+                            WITH transactions AS (
+                                SELECT
+                                    tx_hash,
+                                    tx as op,
+                                    block_number,
+                                    created_at,
+                                    success,
+                                    fail_reason,
+                                    Null::bytea as eth_hash,
+                                    Null::bigint as priority_op_serialid,
+                                    block_index
+                                FROM executed_transactions
+                                WHERE success = true AND (
+                                    (
+                                        tx->'type' IN ('"Transfer"', '"WithdrawNFT"', '"ForcedExit"')
+                                        AND tx->'token' = $1
+                                    )
+                                    OR (
+                                        tx->'type' = '"Swap"'
+                                        AND (
+                                            tx->'orders'->0->'tokenSell' = $1
+                                            OR
+                                            tx->'orders'->1->'tokenSell' = $1
+                                        )
+                                    )
+                                    OR (
+                                        tx->'type' = '"MintNFT"'
+                                        AND (block_number, tx->'creatorId', tx->'nonce') IN (
+                                            SELECT block_number, to_jsonb(creator_account_id), to_jsonb(nonce)
+                                            FROM mint_nft_updates
+                                            WHERE token_id = $2
+                                        )
+                                    )
+                                )
+                                    AND created_at <= $3
+                            ), priority_ops AS (
+                                SELECT
+                                    tx_hash,
+                                    operation as op,
+                                    block_number,
+                                    created_at,
+                                    true as success,
+                                    Null as fail_reason,
+                                    eth_hash,
+                                    priority_op_serialid,
+                                    block_index
+                                FROM executed_priority_operations
+                                WHERE operation->'type' = '"FullExit"'
+                                    AND operation->'priority_op'->'token' = $1
+                                    AND operation->>'withdraw_amount' <> '0'
+                                    AND created_at <= $3
+                            ), everything AS (
+                                SELECT * FROM transactions
+                                UNION ALL
+                                SELECT * FROM priority_ops
+                            )
+                            SELECT
+                                tx_hash as "tx_hash!",
+                                block_number as "block_number!",
+                                op as "op!",
+                                created_at as "created_at!",
+                                success as "success!",
+                                fail_reason as "fail_reason?",
+                                eth_hash as "eth_hash?",
+                                priority_op_serialid as "priority_op_serialid?"
+                            FROM everything
+                            ORDER BY created_at DESC, block_index DESC
+                            LIMIT $4
+                        "#,
+                        token_id_value,
+                        *query.from.token_id as i32,
+                        time_from,
+                        i64::from(query.limit),
+                    )
+                    .fetch_all(transaction.conn())
+                    .await?
+                }
+            };
+            let last_finalized = transaction
+                .chain()
+                .block_schema()
+                .get_last_verified_confirmed_block()
+                .await?;
+            let txs: Vec<Transaction> = raw_txs
+                .into_iter()
+                .map(|tx| {
+                    let is_finalized = tx.block_number as u32 <= *last_finalized;
+                    TransactionItem::transaction_from_item(tx, is_finalized)
+                })
+                .collect();
+            Some(txs)
+        } else {
+            None
+        };
+        transaction.commit().await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_nft_transactions",
+            start.elapsed()
+        );
+        Ok(txs)
+    }
+
+    pub async fn get_nft_last_tx_hash(&mut self, token_id: TokenId) -> QueryResult<Option<TxHash>> {
+        let start = Instant::now();
+        let token_id_value = serde_json::to_value(token_id).unwrap();
+        let record = sqlx::query!(
+            r#"
+                WITH transactions AS (
+                    SELECT tx_hash, created_at, block_index
+                    FROM executed_transactions
+                    WHERE success = true AND (
+                        (
+                            tx->'type' IN ('"Transfer"', '"WithdrawNFT"', '"ForcedExit"')
+                            AND tx->'token' = $1
+                        )
+                        OR (
+                            tx->'type' = '"Swap"'
+                            AND (
+                                tx->'orders'->0->'tokenSell' = $1
+                                OR
+                                tx->'orders'->1->'tokenSell' = $1
+                            )
+                        )
+                        OR (
+                            tx->'type' = '"MintNFT"'
+                            AND (block_number, tx->'creatorId', tx->'nonce') IN (
+                                SELECT block_number, to_jsonb(creator_account_id), to_jsonb(nonce)
+                                FROM mint_nft_updates
+                                WHERE token_id = $2
+                            )
+                        )
+                    )
+                ), priority_ops AS (
+                    SELECT tx_hash, created_at, block_index
+                    FROM executed_priority_operations
+                    WHERE operation->'type' = '"FullExit"'
+                        AND operation->'priority_op'->'token' = $1
+                        AND operation->>'withdraw_amount' <> '0'
+                ), everything AS (
+                    SELECT * FROM transactions
+                    UNION ALL
+                    SELECT * FROM priority_ops
+                )
+                SELECT
+                    tx_hash as "tx_hash!"
+                FROM everything
+                ORDER BY created_at DESC, block_index DESC
+                LIMIT 1
+            "#,
+            token_id_value,
+            *token_id as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_nft_last_tx_hash",
+            start.elapsed()
+        );
+        Ok(record.map(|record| TxHash::from_slice(&record.tx_hash).unwrap()))
+    }
+
+    pub async fn get_nft_transactions_count(&mut self, token_id: TokenId) -> QueryResult<u32> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let token_id_value = serde_json::to_value(token_id).unwrap();
+        let tx_count = sqlx::query!(
+            r#"
+                SELECT COUNT(*) as "count!" FROM executed_transactions
+                WHERE success = true AND (
+                    (
+                        tx->'type' IN ('"Transfer"', '"WithdrawNFT"', '"ForcedExit"')
+                        AND tx->'token' = $1
+                    )
+                    OR (
+                        tx->'type' = '"Swap"'
+                        AND (
+                            tx->'orders'->0->'tokenSell' = $1
+                            OR
+                            tx->'orders'->1->'tokenSell' = $1
+                        )
+                    )
+                    OR (
+                        tx->'type' = '"MintNFT"'
+                        AND (block_number, tx->'creatorId', tx->'nonce') IN (
+                            SELECT block_number, to_jsonb(creator_account_id), to_jsonb(nonce)
+                            FROM mint_nft_updates
+                            WHERE token_id = $2
+                        )
+                    )
+                )
+            "#,
+            token_id_value,
+            *token_id as i32
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .count;
+
+        let priority_op_count = sqlx::query!(
+            r#"
+                SELECT COUNT(*) as "count!" FROM executed_priority_operations
+                WHERE operation->'type' = '"FullExit"'
+                    AND operation->'priority_op'->'token' = $1
+                    AND operation->>'withdraw_amount' <> '0'
+            "#,
+            token_id_value
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .count;
+        transaction.commit().await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_nft_transactions_count",
+            start.elapsed()
+        );
+        Ok((tx_count + priority_op_count) as u32)
+    }
+
     /// Returns `created_at` and `block_number` fields for transaction with given hash.
     pub async fn get_tx_created_at_and_block_number(
         &mut self,
@@ -7,6 +7,21 @@ use zksync_types::BlockNumber;
 // Local imports
 use crate::{QueryResult, StorageProcessor};
 
+/// Amount of the token, converted to the token units (i.e. divided by `10^decimals`).
+/// Intended for metrics, so the precision of `f64` is enough.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenAmount {
+    pub symbol: String,
+    pub amount: f64,
+}
+
+/// Number of the operations of a certain type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationsCount {
+    pub op_type: String,
+    pub count: i64,
+}
+
 /// Auxiliary schema encapsulating the stats counting logic for the storage tables.
 #[derive(Debug)]
 pub struct StatsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
@@ -47,4 +62,117 @@ impl<'a, 'c> StatsSchema<'a, 'c> {
         metrics::histogram!("sql.chain.stats.count_total_transactions", start.elapsed());
         Ok((count_tx + prior_ops) as u32)
     }
+
+    /// Returns the number of the first block sealed at or after the given timestamp.
+    pub async fn first_block_since(&mut self, timestamp: u64) -> QueryResult<Option<BlockNumber>> {
+        let start = Instant::now();
+        let block_number = sqlx::query!(
+            "SELECT MIN(number) FROM blocks WHERE timestamp >= $1",
+            timestamp as i64
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .min
+        .map(|number| BlockNumber(number as u32));
+
+        metrics::histogram!("sql.chain.stats.first_block_since", start.elapsed());
+        Ok(block_number)
+    }
+
+    /// Returns the total balance of every fungible token on all the accounts.
+    pub async fn total_value_locked(&mut self) -> QueryResult<Vec<TokenAmount>> {
+        let start = Instant::now();
+        let amounts = sqlx::query_as!(
+            TokenAmount,
+            r#"
+            SELECT tokens.symbol, (SUM(balances.balance) / power(10, tokens.decimals))::float8 AS "amount!"
+            FROM balances
+            INNER JOIN tokens ON tokens.id = balances.coin_id
+            WHERE tokens.kind != 'NFT'::token_kind
+            GROUP BY tokens.id
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.total_value_locked", start.elapsed());
+        Ok(amounts)
+    }
+
+    /// Returns the amount of accounts created in the sealed blocks starting from `from_block`.
+    pub async fn count_created_accounts(&mut self, from_block: BlockNumber) -> QueryResult<u32> {
+        let start = Instant::now();
+        let count = sqlx::query!(
+            r#"
+            SELECT COUNT(*) FROM account_creates
+            WHERE is_create = true AND block_number >= $1
+                AND block_number <= (SELECT COALESCE(MAX(number), 0) FROM blocks)
+            "#,
+            i64::from(*from_block)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count
+        .unwrap_or(0);
+
+        metrics::histogram!("sql.chain.stats.count_created_accounts", start.elapsed());
+        Ok(count as u32)
+    }
+
+    /// Returns the amount of successfully executed operations of each type in the block.
+    pub async fn count_block_operations_by_type(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Vec<OperationsCount>> {
+        let start = Instant::now();
+        let counts = sqlx::query_as!(
+            OperationsCount,
+            r#"
+            SELECT tx->>'type' AS "op_type!", COUNT(*) AS "count!"
+            FROM executed_transactions
+            WHERE block_number = $1 AND success = true
+            GROUP BY tx->>'type'
+            UNION ALL
+            SELECT operation->>'type' AS "op_type!", COUNT(*) AS "count!"
+            FROM executed_priority_operations
+            WHERE block_number = $1
+            GROUP BY operation->>'type'
+            "#,
+            i64::from(*block_number)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.stats.count_block_operations_by_type",
+            start.elapsed()
+        );
+        Ok(counts)
+    }
+
+    /// Returns the fees collected in each token in the sealed blocks starting from `from_block`.
+    pub async fn collected_fees(
+        &mut self,
+        from_block: BlockNumber,
+    ) -> QueryResult<Vec<TokenAmount>> {
+        let start = Instant::now();
+        // Depending on the transaction type, the fee token is stored either in the `feeToken` field
+        // or in the `token` one.
+        let fees = sqlx::query_as!(
+            TokenAmount,
+            r#"
+            SELECT tokens.symbol, (SUM((tx->>'fee')::numeric) / power(10, tokens.decimals))::float8 AS "amount!"
+            FROM executed_transactions
+            INNER JOIN tokens ON tokens.id = COALESCE(tx->>'feeToken', tx->>'token')::integer
+            WHERE block_number >= $1 AND success = true AND tx->>'fee' IS NOT NULL
+            GROUP BY tokens.id
+            "#,
+            i64::from(*from_block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.collected_fees", start.elapsed());
+        Ok(fees)
+    }
 }
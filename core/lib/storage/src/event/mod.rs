@@ -10,6 +10,7 @@ use zksync_types::event::{
     },
     block::{BlockEvent, BlockStatus},
     transaction::{TransactionEvent, TransactionStatus},
+    withdrawal::WithdrawalEvent,
     EventId,
 };
 use zksync_types::{block::ExecutedOperations, priority_ops::ZkSyncPriorityOp};
@@ -351,4 +352,25 @@ impl<'a, 'c> EventSchema<'a, 'c> {
         metrics::histogram!("sql.event.store_queued_transaction_event", start.elapsed());
         Ok(())
     }
+
+    /// Stores events for the withdrawals from the given block.
+    pub async fn store_withdrawal_events(
+        &mut self,
+        block_number: BlockNumber,
+        withdrawals: &[WithdrawalEvent],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let events: Vec<_> = withdrawals
+            .iter()
+            .map(|withdrawal| {
+                serde_json::to_value(withdrawal).expect("couldn't serialize withdrawal event")
+            })
+            .collect();
+
+        self.store_event_data(block_number, EventType::Withdrawal, &events)
+            .await?;
+
+        metrics::histogram!("sql.event.store_withdrawal_events", start.elapsed());
+        Ok(())
+    }
 }
@@ -18,6 +18,7 @@ pub enum EventType {
     Account,
     Block,
     Transaction,
+    Withdrawal,
 }
 
 #[derive(FromRow, Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +43,9 @@ impl TryFrom<StoredEvent> for ZkSyncEvent {
             EventType::Transaction => {
                 EventData::Transaction(serde_json::from_value(stored_event.event_data)?)
             }
+            EventType::Withdrawal => {
+                EventData::Withdrawal(serde_json::from_value(stored_event.event_data)?)
+            }
         };
         Ok(Self {
             id,
@@ -56,5 +60,6 @@ pub fn get_event_type(event: &ZkSyncEvent) -> EventType {
         EventData::Account(_) => EventType::Account,
         EventData::Block(_) => EventType::Block,
         EventData::Transaction(_) => EventType::Transaction,
+        EventData::Withdrawal(_) => EventType::Withdrawal,
     }
 }
@@ -8,8 +8,10 @@ use std::{ops::Sub, time::Instant};
 // Local imports
 use crate::{QueryResult, StorageProcessor};
 use zksync_types::forced_exit_requests::{
-    ForcedExitRequest, ForcedExitRequestId, SaveForcedExitRequestQuery,
+    ForcedExitRequest, ForcedExitRequestEvent, ForcedExitRequestId, ForcedExitRequestState,
+    SaveForcedExitRequestQuery,
 };
+use zksync_types::{Address, Nonce};
 
 use zksync_types::tx::TxHash;
 
@@ -17,7 +19,7 @@ pub mod records;
 
 mod utils;
 
-use records::DbForcedExitRequest;
+use records::{DbForcedExitRequest, DbForcedExitRequestEvent};
 
 use crate::utils::address_to_stored_string;
 
@@ -37,6 +39,7 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
 
         let tokens = utils::vec_to_comma_list(request.tokens.clone());
 
+        let mut transaction = self.0.start_transaction().await?;
         let stored_request: DbForcedExitRequest = sqlx::query_as!(
             DbForcedExitRequest,
             r#"
@@ -53,9 +56,20 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
             request.created_at,
             request.valid_until
         )
-        .fetch_one(self.0.conn())
+        .fetch_one(transaction.conn())
         .await?;
 
+        transaction
+            .forced_exit_requests_schema()
+            .store_event(
+                stored_request.id,
+                ForcedExitRequestState::Created,
+                &[],
+                request.created_at,
+            )
+            .await?;
+        transaction.commit().await?;
+
         metrics::histogram!("sql.forced_exit_requests.store_request", start.elapsed());
         Ok(stored_request.into())
     }
@@ -203,7 +217,7 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         sqlx::query!(
             r#"
             DELETE FROM forced_exit_requests
-            WHERE fulfilled_by IS NULL AND valid_until < $1
+            WHERE fulfilled_by IS NULL AND state = 'created' AND valid_until < $1
             "#,
             oldest_allowed
         )
@@ -217,4 +231,171 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
 
         Ok(())
     }
+
+    /// Moves the request to the new state and saves the corresponding event.
+    pub async fn update_state(
+        &mut self,
+        id: ForcedExitRequestId,
+        state: ForcedExitRequestState,
+        tx_hashes: &[TxHash],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE forced_exit_requests
+                SET state = $1
+                WHERE id = $2
+            "#,
+            state.as_str(),
+            id
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction
+            .forced_exit_requests_schema()
+            .store_event(id, state, tx_hashes, Utc::now())
+            .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.forced_exit_requests.update_state", start.elapsed());
+        Ok(())
+    }
+
+    async fn store_event(
+        &mut self,
+        id: ForcedExitRequestId,
+        state: ForcedExitRequestState,
+        tx_hashes: &[TxHash],
+        created_at: DateTime<Utc>,
+    ) -> QueryResult<()> {
+        let tx_hashes = if tx_hashes.is_empty() {
+            None
+        } else {
+            Some(utils::vec_to_comma_list(tx_hashes.to_vec()))
+        };
+
+        sqlx::query!(
+            r#"
+            INSERT INTO forced_exit_request_events ( request_id, state, tx_hashes, created_at )
+            VALUES ( $1, $2, $3, $4 )
+            "#,
+            id,
+            state.as_str(),
+            tx_hashes,
+            created_at
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Loads the history of the request state changes, from the oldest to the newest one.
+    pub async fn get_request_events(
+        &mut self,
+        id: ForcedExitRequestId,
+    ) -> QueryResult<Vec<ForcedExitRequestEvent>> {
+        let start = Instant::now();
+
+        let events = sqlx::query_as!(
+            DbForcedExitRequestEvent,
+            r#"
+            SELECT * FROM forced_exit_request_events
+            WHERE request_id = $1
+            ORDER BY id
+            "#,
+            id
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|event| event.into())
+        .collect();
+
+        metrics::histogram!(
+            "sql.forced_exit_requests.get_request_events",
+            start.elapsed()
+        );
+        Ok(events)
+    }
+
+    pub async fn get_requests_by_state(
+        &mut self,
+        state: ForcedExitRequestState,
+    ) -> QueryResult<Vec<ForcedExitRequest>> {
+        let start = Instant::now();
+
+        let requests = sqlx::query_as!(
+            DbForcedExitRequest,
+            r#"
+            SELECT * FROM forced_exit_requests
+            WHERE state = $1
+            ORDER BY id
+            "#,
+            state.as_str()
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|request| request.into())
+        .collect();
+
+        metrics::histogram!(
+            "sql.forced_exit_requests.get_requests_by_state",
+            start.elapsed()
+        );
+        Ok(requests)
+    }
+
+    pub async fn set_payer(&mut self, id: ForcedExitRequestId, payer: Address) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            UPDATE forced_exit_requests
+                SET payer = $1
+                WHERE id = $2
+            "#,
+            address_to_stored_string(&payer),
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.forced_exit_requests.set_payer", start.elapsed());
+        Ok(())
+    }
+
+    /// Saves the refund transfer for the request. The refund is saved before it is sent,
+    /// so that the nonce it was signed with is known even if the sending has failed.
+    pub async fn set_refund(
+        &mut self,
+        id: ForcedExitRequestId,
+        refund: Option<(TxHash, Nonce)>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        let (tx_hash, nonce) = match refund {
+            Some((tx_hash, nonce)) => (Some(tx_hash.to_string()), Some(*nonce as i64)),
+            None => (None, None),
+        };
+        sqlx::query!(
+            r#"
+            UPDATE forced_exit_requests
+                SET refunded_by = $1, refund_nonce = $2
+                WHERE id = $3
+            "#,
+            tx_hash,
+            nonce,
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.forced_exit_requests.set_refund", start.elapsed());
+        Ok(())
+    }
 }
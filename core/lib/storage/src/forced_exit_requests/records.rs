@@ -2,8 +2,11 @@ use crate::utils::{address_to_stored_string, stored_str_address_to_address};
 use chrono::{DateTime, Utc};
 use num::{bigint::ToBigInt, BigInt};
 use sqlx::types::BigDecimal;
-use zksync_basic_types::TokenId;
-use zksync_types::forced_exit_requests::ForcedExitRequest;
+use std::str::FromStr;
+use zksync_basic_types::{Nonce, TokenId};
+use zksync_types::forced_exit_requests::{
+    ForcedExitRequest, ForcedExitRequestEvent, ForcedExitRequestState,
+};
 use zksync_types::tx::TxHash;
 
 use super::utils;
@@ -18,6 +21,19 @@ pub struct DbForcedExitRequest {
     pub created_at: DateTime<Utc>,
     pub fulfilled_by: Option<String>,
     pub fulfilled_at: Option<DateTime<Utc>>,
+    pub state: String,
+    pub payer: Option<String>,
+    pub refunded_by: Option<String>,
+    pub refund_nonce: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DbForcedExitRequestEvent {
+    pub id: i64,
+    pub request_id: i64,
+    pub state: String,
+    pub tx_hashes: Option<String>,
+    pub created_at: DateTime<Utc>,
 }
 
 impl From<ForcedExitRequest> for DbForcedExitRequest {
@@ -35,6 +51,10 @@ impl From<ForcedExitRequest> for DbForcedExitRequest {
             created_at: request.created_at,
             fulfilled_at: request.fulfilled_at,
             fulfilled_by,
+            state: request.state.as_str().to_owned(),
+            payer: request.payer.as_ref().map(address_to_stored_string),
+            refunded_by: request.refunded_by.map(|hash| hash.to_string()),
+            refund_nonce: request.refund_nonce.map(|nonce| *nonce as i64),
         }
     }
 }
@@ -52,6 +72,11 @@ impl From<DbForcedExitRequest> for ForcedExitRequest {
 
         let tokens: Vec<TokenId> = utils::comma_list_to_vec(val.tokens);
         let fulfilled_by: Option<Vec<TxHash>> = val.fulfilled_by.map(utils::comma_list_to_vec);
+        let state = ForcedExitRequestState::from_str(&val.state)
+            .expect("Invalid forced exit request state has been stored");
+        let refunded_by = val
+            .refunded_by
+            .map(|hash| TxHash::from_str(&hash).expect("Invalid refund hash has been stored"));
 
         ForcedExitRequest {
             id: val.id,
@@ -62,6 +87,25 @@ impl From<DbForcedExitRequest> for ForcedExitRequest {
             valid_until: val.valid_until,
             fulfilled_at: val.fulfilled_at,
             fulfilled_by,
+            state,
+            payer: val.payer.as_deref().map(stored_str_address_to_address),
+            refunded_by,
+            refund_nonce: val.refund_nonce.map(|nonce| Nonce(nonce as u32)),
+        }
+    }
+}
+
+impl From<DbForcedExitRequestEvent> for ForcedExitRequestEvent {
+    fn from(val: DbForcedExitRequestEvent) -> Self {
+        ForcedExitRequestEvent {
+            request_id: val.request_id,
+            state: ForcedExitRequestState::from_str(&val.state)
+                .expect("Invalid forced exit request state has been stored"),
+            tx_hashes: val
+                .tx_hashes
+                .map(utils::comma_list_to_vec)
+                .unwrap_or_default(),
+            created_at: val.created_at,
         }
     }
 }
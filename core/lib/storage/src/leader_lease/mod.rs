@@ -0,0 +1,97 @@
+// Built-in deps
+use std::time::{Duration, Instant};
+// External imports
+// Workspace imports
+// Local imports
+use crate::{QueryResult, StorageProcessor};
+
+/// Schema of the leases used to elect the active instance of the component among several ones.
+///
+/// The lease is held until it expires, so the holder has to renew it in time. Expiration time is
+/// calculated by the database clock, so the clocks of the instances don't have to be in sync.
+#[derive(Debug)]
+pub struct LeaderLeaseSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> LeaderLeaseSchema<'a, 'c> {
+    /// Acquires the lease, or renews it if it's already held by the `holder`.
+    /// Returns `false` if the lease is held by another instance.
+    pub async fn acquire_lease(
+        &mut self,
+        name: &str,
+        holder: &str,
+        duration: Duration,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let acquired = sqlx::query!(
+            r#"
+            INSERT INTO leader_lease (name, holder, expires_at)
+            VALUES ($1, $2, now() + make_interval(secs => $3))
+            ON CONFLICT (name) DO UPDATE
+                SET holder = EXCLUDED.holder, expires_at = EXCLUDED.expires_at
+                WHERE leader_lease.holder = EXCLUDED.holder OR leader_lease.expires_at < now()
+            RETURNING holder
+            "#,
+            name,
+            holder,
+            duration.as_secs_f64()
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .is_some();
+
+        metrics::histogram!("sql.leader_lease.acquire_lease", start.elapsed());
+        Ok(acquired)
+    }
+
+    /// Releases the lease, so another instance can acquire it without waiting for its expiration.
+    /// Does nothing if the lease is not held by the `holder`.
+    pub async fn release_lease(&mut self, name: &str, holder: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "DELETE FROM leader_lease WHERE name = $1 AND holder = $2",
+            name,
+            holder
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.leader_lease.release_lease", start.elapsed());
+        Ok(())
+    }
+
+    /// Returns the current holder of the lease, unless the lease has expired.
+    pub async fn lease_holder(&mut self, name: &str) -> QueryResult<Option<String>> {
+        let start = Instant::now();
+        let holder = sqlx::query!(
+            "SELECT holder FROM leader_lease WHERE name = $1 AND expires_at >= now()",
+            name
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| record.holder);
+
+        metrics::histogram!("sql.leader_lease.lease_holder", start.elapsed());
+        Ok(holder)
+    }
+
+    /// Checks that the lease is held by the `holder` and locks it until the end of the transaction,
+    /// so the lease can't be taken over by another instance before the transaction is committed.
+    pub async fn lock_lease(&mut self, name: &str, holder: &str) -> QueryResult<bool> {
+        let start = Instant::now();
+        let held = sqlx::query!(
+            r#"
+            SELECT holder FROM leader_lease
+            WHERE name = $1 AND holder = $2 AND expires_at >= now()
+            FOR SHARE
+            "#,
+            name,
+            holder
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .is_some();
+
+        metrics::histogram!("sql.leader_lease.lock_lease", start.elapsed());
+        Ok(held)
+    }
+}
@@ -94,11 +94,13 @@ pub mod diff;
 pub mod ethereum;
 pub mod event;
 pub mod forced_exit_requests;
+pub mod leader_lease;
 pub mod listener;
 pub mod prover;
 pub mod test_data;
 pub mod tokens;
 mod utils;
+pub mod withdrawals;
 
 use forced_exit_requests::ForcedExitRequestsSchema;
 
@@ -232,6 +234,16 @@ impl<'a> StorageProcessor<'a> {
         event::EventSchema(self)
     }
 
+    /// Gains access to the `LeaderLease` schema.
+    pub fn leader_lease_schema(&mut self) -> leader_lease::LeaderLeaseSchema<'_, 'a> {
+        leader_lease::LeaderLeaseSchema(self)
+    }
+
+    /// Gains access to the `Withdrawals` schema.
+    pub fn withdrawals_schema(&mut self) -> withdrawals::WithdrawalsSchema<'_, 'a> {
+        withdrawals::WithdrawalsSchema(self)
+    }
+
     fn conn(&mut self) -> &mut PgConnection {
         match &mut self.conn {
             ConnectionHolder::Pooled(conn) => conn,
@@ -1,10 +1,11 @@
 // External imports
 use num::{BigUint, Zero};
 // Workspace imports
+use zksync_api_types::v02::pagination::{PaginationDirection, PaginationQuery};
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID};
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, helpers::apply_updates, AccountId, AccountMap,
-    AccountUpdate, Address, BlockNumber, Nonce, Token, TokenId, TokenKind,
+    aggregated_operations::AggregatedActionType, helpers::apply_updates, Account, AccountId,
+    AccountMap, AccountUpdate, Address, BlockNumber, Nonce, Token, TokenId, TokenKind,
 };
 // Local imports
 use super::block::apply_random_updates;
@@ -528,3 +529,92 @@ async fn test_get_nft_owner(mut storage: StorageProcessor<'_>) -> QueryResult<()
 
     Ok(())
 }
+
+/// Checks the pagination of NFTs owned and minted by the account.
+#[db_test]
+async fn test_account_nfts_pages(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+    let account_id = AccountId(1);
+    let account = Account::default_with_address(&Address::random());
+
+    let mut updates = vec![(
+        account_id,
+        AccountUpdate::Create {
+            address: account.address,
+            nonce: account.nonce,
+        },
+    )];
+    for number in 0..3 {
+        updates.extend(generate_nft(account_id, &account, number, &mut rng));
+    }
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(BlockNumber(1), &updates, 0)
+        .await?;
+    storage
+        .chain()
+        .state_schema()
+        .apply_state_update(BlockNumber(1))
+        .await?;
+
+    let last_id = storage
+        .chain()
+        .account_schema()
+        .get_account_last_nft_id(account_id)
+        .await?;
+    assert_eq!(last_id, Some(TokenId(MIN_NFT_TOKEN_ID + 2)));
+    let count = storage
+        .chain()
+        .account_schema()
+        .get_account_nfts_count(account_id)
+        .await?;
+    assert_eq!(count, 3);
+
+    let query = PaginationQuery {
+        from: TokenId(MIN_NFT_TOKEN_ID + 1),
+        limit: 10,
+        direction: PaginationDirection::Older,
+    };
+    let owned = storage
+        .chain()
+        .account_schema()
+        .load_account_nfts_page(account_id, &query)
+        .await?;
+    let owned_ids: Vec<_> = owned.iter().map(|nft| nft.id).collect();
+    assert_eq!(
+        owned_ids,
+        vec![TokenId(MIN_NFT_TOKEN_ID + 1), TokenId(MIN_NFT_TOKEN_ID)]
+    );
+
+    let query = PaginationQuery {
+        from: TokenId(MIN_NFT_TOKEN_ID + 1),
+        limit: 10,
+        direction: PaginationDirection::Newer,
+    };
+    let minted = storage
+        .tokens_schema()
+        .load_minted_nfts_page(account_id, &query)
+        .await?;
+    let minted_ids: Vec<_> = minted.iter().map(|nft| nft.id).collect();
+    assert_eq!(
+        minted_ids,
+        vec![TokenId(MIN_NFT_TOKEN_ID + 1), TokenId(MIN_NFT_TOKEN_ID + 2)]
+    );
+    assert_eq!(
+        storage
+            .tokens_schema()
+            .get_minted_nfts_count(account_id)
+            .await?,
+        3
+    );
+    assert_eq!(
+        storage
+            .tokens_schema()
+            .get_last_minted_nft_id(AccountId(2))
+            .await?,
+        None
+    );
+
+    Ok(())
+}
@@ -4,5 +4,6 @@ mod mempool;
 mod operations;
 mod operations_ext;
 mod state;
+mod stats;
 
 pub use block::apply_random_updates;
@@ -2,16 +2,23 @@
 use std::collections::HashMap;
 // External imports
 // Workspace imports
-use zksync_api_types::v02::{
-    pagination::{AccountTxsRequest, ApiEither, PaginationDirection, PaginationQuery},
-    transaction::{Receipt, TxInBlockStatus},
+use zksync_api_types::{
+    v02::{
+        pagination::{
+            AccountTxsRequest, ApiEither, Latest, NFTTxsRequest, PaginationDirection,
+            PaginationQuery,
+        },
+        transaction::{Receipt, TxInBlockStatus},
+    },
+    Either,
 };
 use zksync_crypto::{franklin_crypto::bellman::pairing::ff::Field, Fr};
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
     tx::TxHash,
-    AccountId, AccountUpdate, BlockNumber, ExecutedOperations, Nonce, ZkSyncOp, H256,
+    AccountId, AccountUpdate, Address, BlockNumber, ExecutedOperations, Nonce, TokenId, ZkSyncOp,
+    H256, NFT,
 };
 // Local imports
 use self::setup::TransactionsHistoryTestSetup;
@@ -42,6 +49,7 @@ pub async fn commit_schema_data(
         match try_insert_token {
             Ok(..) | Err(StoreTokenError::TokenAlreadyExistsError(..)) => (),
             Err(StoreTokenError::Other(anyhow_err)) => return Err(anyhow_err),
+            Err(err) => return Err(err.into()),
         }
     }
 
@@ -1186,3 +1194,132 @@ async fn test_getting_swap_for_acc(mut storage: StorageProcessor<'_>) -> QueryRe
 
     Ok(())
 }
+
+/// Checks that the NFT history contains all the operations moving the NFT, including
+/// `MintNFT`, `ForcedExit` and `FullExit`, and doesn't contain the other ones.
+#[db_test]
+async fn nft_transactions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block_with_nft_transactions(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    // `MintNFT` transaction is matched with the minted NFT by the creator nonce recorded with it.
+    let creator_id = setup.from_zksync_account.get_account_id().unwrap();
+    let nft = NFT::new(
+        setup.tokens[3].id,
+        0,
+        creator_id,
+        setup.from_zksync_account.address,
+        setup.tokens[3].address,
+        None,
+        H256::default(),
+    );
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(
+            BlockNumber(1),
+            &[(
+                creator_id,
+                AccountUpdate::MintNFT {
+                    token: nft.clone(),
+                    nonce: Nonce(0),
+                },
+            )],
+            0,
+        )
+        .await?;
+    // NFT with the same content minted by the same creator in the same block by another
+    // transaction doesn't share the history of the first one.
+    let other_nft = NFT::new(
+        TokenId(nft.id.0 + 1),
+        1,
+        creator_id,
+        setup.from_zksync_account.address,
+        Address::repeat_byte(0x22),
+        None,
+        H256::default(),
+    );
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(
+            BlockNumber(1),
+            &[(
+                creator_id,
+                AccountUpdate::MintNFT {
+                    token: other_nft.clone(),
+                    nonce: Nonce(1),
+                },
+            )],
+            1,
+        )
+        .await?;
+
+    let expected_hashes: Vec<TxHash> = (0..5).map(|index| setup.get_tx_hash(0, index)).collect();
+    let count = storage
+        .chain()
+        .operations_ext_schema()
+        .get_nft_transactions_count(nft.id)
+        .await?;
+    assert_eq!(count, 5);
+    let last_tx_hash = storage
+        .chain()
+        .operations_ext_schema()
+        .get_nft_last_tx_hash(nft.id)
+        .await?;
+    assert_eq!(last_tx_hash, Some(expected_hashes[4]));
+
+    let txs = storage
+        .chain()
+        .operations_ext_schema()
+        .get_nft_transactions(&PaginationQuery {
+            from: NFTTxsRequest {
+                token_id: nft.id,
+                tx_hash: ApiEither {
+                    inner: Either::Right(Latest),
+                },
+            },
+            limit: 10,
+            direction: PaginationDirection::Older,
+        })
+        .await?
+        .unwrap();
+    let tx_hashes: Vec<_> = txs.iter().map(|tx| tx.tx_hash).collect();
+    let mut expected_older = expected_hashes.clone();
+    expected_older.reverse();
+    assert_eq!(tx_hashes, expected_older);
+
+    let txs = storage
+        .chain()
+        .operations_ext_schema()
+        .get_nft_transactions(&PaginationQuery {
+            from: NFTTxsRequest {
+                token_id: nft.id,
+                tx_hash: ApiEither::from(expected_hashes[1]),
+            },
+            limit: 2,
+            direction: PaginationDirection::Newer,
+        })
+        .await?
+        .unwrap();
+    let tx_hashes: Vec<_> = txs.iter().map(|tx| tx.tx_hash).collect();
+    assert_eq!(tx_hashes, expected_hashes[1..3].to_vec());
+
+    // Other tokens have no history.
+    let count = storage
+        .chain()
+        .operations_ext_schema()
+        .get_nft_transactions_count(other_nft.id)
+        .await?;
+    assert_eq!(count, 0);
+    let count = storage
+        .chain()
+        .operations_ext_schema()
+        .get_nft_transactions_count(TokenId(nft.id.0 + 2))
+        .await?;
+    assert_eq!(count, 0);
+
+    Ok(())
+}
@@ -11,8 +11,9 @@ use zksync_types::{
     operations::{ChangePubKeyOp, ZkSyncOp},
     priority_ops::PriorityOp,
     tx::{ChangePubKeyType, TxHash},
-    AccountId, Address, BlockNumber, CloseOp, Deposit, DepositOp, FullExit, FullExitOp, MintNFTOp,
-    SwapOp, Token, TokenId, TokenKind, TransferOp, TransferToNewOp, WithdrawNFTOp, WithdrawOp,
+    AccountId, Address, BlockNumber, CloseOp, Deposit, DepositOp, ForcedExitOp, FullExit,
+    FullExitOp, MintNFTOp, SwapOp, Token, TokenId, TokenKind, TransferOp, TransferToNewOp,
+    WithdrawNFTOp, WithdrawOp,
 };
 // Local imports
 
@@ -183,6 +184,36 @@ impl TransactionsHistoryTestSetup {
         self.blocks.push(block);
     }
 
+    /// Adds a block with the operations moving the NFT (`self.tokens[3]`): it's minted, transferred,
+    /// force exited, withdrawn and fully exited. The last operation is an unrelated transfer.
+    pub fn add_block_with_nft_transactions(&mut self, block_id: u32) {
+        let prior_op_unique_serial_id = u64::from(block_id * 2);
+        let nft_id = self.tokens[3].id;
+        let operations = vec![
+            self.create_mint_nft_tx(Some(0)),
+            self.create_transfer_tx_for_token(3, Some(1)),
+            self.create_forced_exit_tx(nft_id, Some(2)),
+            self.create_withdraw_nft_tx(Some(3)),
+            self.create_full_exit_op_for_token(nft_id, prior_op_unique_serial_id, block_id, 4),
+            self.create_transfer_tx(Some(5)),
+        ];
+
+        let block = Block::new(
+            BlockNumber(block_id),
+            Fr::zero(),
+            AccountId(0),
+            operations,
+            (0, 0), // Not important
+            100,
+            1_000_000.into(), // Not important
+            1_500_000.into(), // Not important
+            Default::default(),
+            0,
+        );
+
+        self.blocks.push(block);
+    }
+
     fn create_deposit_op(
         &mut self,
         serial_id: u64,
@@ -223,12 +254,22 @@ impl TransactionsHistoryTestSetup {
         serial_id: u64,
         block: u32,
         block_index: u32,
+    ) -> ExecutedOperations {
+        self.create_full_exit_op_for_token(self.tokens[2].id, serial_id, block, block_index)
+    }
+
+    fn create_full_exit_op_for_token(
+        &mut self,
+        token: TokenId,
+        serial_id: u64,
+        block: u32,
+        block_index: u32,
     ) -> ExecutedOperations {
         let full_exit_op = ZkSyncOp::FullExit(Box::new(FullExitOp {
             priority_op: FullExit {
                 account_id: self.from_zksync_account.get_account_id().unwrap(),
                 eth_address: self.from_zksync_account.address,
-                token: self.tokens[2].id,
+                token,
                 is_legacy: false,
             },
             withdraw_amount: Some(self.amount.clone().into()),
@@ -290,12 +331,20 @@ impl TransactionsHistoryTestSetup {
     }
 
     fn create_transfer_tx(&mut self, block_index: Option<u32>) -> ExecutedOperations {
+        self.create_transfer_tx_for_token(1, block_index)
+    }
+
+    fn create_transfer_tx_for_token(
+        &mut self,
+        token_index: usize,
+        block_index: Option<u32>,
+    ) -> ExecutedOperations {
         let transfer_op = ZkSyncOp::Transfer(Box::new(TransferOp {
             tx: self
                 .from_zksync_account
                 .sign_transfer(
-                    self.tokens[1].id,
-                    &self.tokens[1].symbol,
+                    self.tokens[token_index].id,
+                    &self.tokens[token_index].symbol,
                     self.amount.clone(),
                     0u32.into(),
                     &self.to_zksync_account.address,
@@ -321,6 +370,37 @@ impl TransactionsHistoryTestSetup {
         ExecutedOperations::Tx(Box::new(executed_transfer_op))
     }
 
+    fn create_forced_exit_tx(
+        &mut self,
+        token: TokenId,
+        block_index: Option<u32>,
+    ) -> ExecutedOperations {
+        let forced_exit_op = ZkSyncOp::ForcedExit(Box::new(ForcedExitOp {
+            tx: self.from_zksync_account.sign_forced_exit(
+                token,
+                0u32.into(),
+                &self.to_zksync_account.address,
+                None,
+                true,
+                Default::default(),
+            ),
+            target_account_id: self.to_zksync_account.get_account_id().unwrap(),
+            withdraw_amount: Some(self.amount.clone().into()),
+        }));
+
+        let executed_forced_exit_op = ExecutedTx {
+            signed_tx: forced_exit_op.try_get_tx().unwrap().into(),
+            success: true,
+            op: Some(forced_exit_op),
+            fail_reason: None,
+            block_index,
+            created_at: self.get_tx_time(),
+            batch_id: None,
+        };
+
+        ExecutedOperations::Tx(Box::new(executed_forced_exit_op))
+    }
+
     fn create_withdraw_tx(&mut self, block_index: Option<u32>) -> ExecutedOperations {
         let withdraw_op = ZkSyncOp::Withdraw(Box::new(WithdrawOp {
             tx: self
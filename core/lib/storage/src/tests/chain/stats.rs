@@ -0,0 +1,344 @@
+// External imports
+use num::{BigUint, Zero};
+use serde_json::{json, Value};
+// Workspace imports
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_types::{
+    AccountId, AccountUpdate, Address, BlockNumber, Nonce, Token, TokenId, TokenKind,
+};
+// Local imports
+use crate::test_data::gen_sample_block;
+use crate::tests::{db_test, ACCOUNT_MUTEX};
+use crate::{
+    chain::{
+        block::BlockSchema,
+        operations::{
+            records::{NewExecutedPriorityOperation, NewExecutedTransaction},
+            OperationsSchema,
+        },
+        state::StateSchema,
+        stats::{OperationsCount, StatsSchema, TokenAmount},
+    },
+    tokens::TokensSchema,
+    QueryResult, StorageProcessor,
+};
+
+/// Stores the tokens with different decimals: `TKA` with 18 decimals, `TKB` with 6 decimals
+/// and an NFT.
+async fn store_tokens(storage: &mut StorageProcessor<'_>) -> QueryResult<()> {
+    let tokens = vec![
+        Token::new(
+            TokenId(1),
+            Address::from_low_u64_be(1),
+            "TKA",
+            18,
+            TokenKind::ERC20,
+        ),
+        Token::new(
+            TokenId(2),
+            Address::from_low_u64_be(2),
+            "TKB",
+            6,
+            TokenKind::ERC20,
+        ),
+        Token::new_nft(TokenId(MIN_NFT_TOKEN_ID), "NFT-65536"),
+    ];
+    for token in tokens {
+        TokensSchema(storage).store_or_update_token(token).await?;
+    }
+    Ok(())
+}
+
+/// Stores the sample block with the given timestamp.
+async fn save_block(
+    storage: &mut StorageProcessor<'_>,
+    block_number: u32,
+    timestamp: u64,
+) -> QueryResult<()> {
+    let mut block = gen_sample_block(BlockNumber(block_number), 100, Vec::new());
+    block.timestamp = timestamp;
+    BlockSchema(storage).save_block(block).await
+}
+
+/// Creates an account update which creates the account with the given ID.
+fn create_account(account_id: u32) -> (AccountId, AccountUpdate) {
+    (
+        AccountId(account_id),
+        AccountUpdate::Create {
+            address: Address::from_low_u64_be(account_id as u64),
+            nonce: Nonce(0),
+        },
+    )
+}
+
+/// Creates an account update which sets the balance of the account.
+fn set_balance(account_id: u32, token_id: u32, balance: u64) -> (AccountId, AccountUpdate) {
+    (
+        AccountId(account_id),
+        AccountUpdate::UpdateBalance {
+            old_nonce: Nonce(0),
+            new_nonce: Nonce(0),
+            balance_update: (TokenId(token_id), BigUint::zero(), BigUint::from(balance)),
+        },
+    )
+}
+
+/// Creates an executed transaction with the given body. The hash is made of the `id` byte,
+/// so it has to be unique within the test.
+fn executed_tx(id: u8, block_number: i64, tx: Value, success: bool) -> NewExecutedTransaction {
+    NewExecutedTransaction {
+        block_number,
+        tx_hash: vec![id; 32],
+        tx,
+        operation: Default::default(),
+        from_account: Default::default(),
+        to_account: None,
+        success,
+        fail_reason: None,
+        block_index: None,
+        primary_account_address: Default::default(),
+        nonce: Default::default(),
+        created_at: chrono::Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+    }
+}
+
+/// Creates an executed priority operation with the given body. The hashes are made of the `id` byte,
+/// so it has to be unique within the test.
+fn executed_priority_op(
+    id: u8,
+    block_number: i64,
+    operation: Value,
+) -> NewExecutedPriorityOperation {
+    NewExecutedPriorityOperation {
+        block_number,
+        block_index: id as i32,
+        operation,
+        from_account: Default::default(),
+        to_account: Default::default(),
+        priority_op_serialid: id as i64,
+        deadline_block: 100,
+        eth_hash: vec![id; 32],
+        eth_block: 10,
+        created_at: chrono::Utc::now(),
+        tx_hash: vec![id; 32],
+        eth_block_index: Some(1),
+    }
+}
+
+/// Checks that the first block sealed at or after the timestamp is found.
+#[db_test]
+async fn first_block_since(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert_eq!(StatsSchema(&mut storage).first_block_since(0).await?, None);
+
+    for (block_number, timestamp) in vec![(1, 100), (2, 200), (3, 300)] {
+        save_block(&mut storage, block_number, timestamp).await?;
+    }
+
+    let mut stats = StatsSchema(&mut storage);
+    assert_eq!(stats.first_block_since(0).await?, Some(BlockNumber(1)));
+    assert_eq!(stats.first_block_since(150).await?, Some(BlockNumber(2)));
+    assert_eq!(stats.first_block_since(200).await?, Some(BlockNumber(2)));
+    assert_eq!(stats.first_block_since(301).await?, None);
+
+    Ok(())
+}
+
+/// Checks that the balances are summed up over all the accounts and scaled by the token decimals,
+/// while NFTs are not taken into account.
+#[db_test]
+async fn total_value_locked(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    store_tokens(&mut storage).await?;
+    save_block(&mut storage, 1, 100).await?;
+
+    let updates = vec![
+        create_account(1),
+        create_account(2),
+        set_balance(1, 1, 1_500_000_000_000_000_000),
+        set_balance(2, 1, 500_000_000_000_000_000),
+        set_balance(2, 2, 2_500_000),
+        set_balance(2, MIN_NFT_TOKEN_ID, 1),
+    ];
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &updates, 0)
+        .await?;
+    StateSchema(&mut storage)
+        .apply_state_update(BlockNumber(1))
+        .await?;
+
+    let mut tvl = StatsSchema(&mut storage).total_value_locked().await?;
+    tvl.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    assert_eq!(
+        tvl,
+        vec![
+            TokenAmount {
+                symbol: "TKA".to_string(),
+                amount: 2.0,
+            },
+            TokenAmount {
+                symbol: "TKB".to_string(),
+                amount: 2.5,
+            },
+        ]
+    );
+
+    Ok(())
+}
+
+/// Checks that only the accounts created in the sealed blocks starting from the given one are counted.
+#[db_test]
+async fn count_created_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    save_block(&mut storage, 1, 100).await?;
+    save_block(&mut storage, 2, 200).await?;
+
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &[create_account(1), create_account(2)], 0)
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(2), &[create_account(3)], 0)
+        .await?;
+    // Updates of the pending block, which is not sealed yet.
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(3), &[create_account(4)], 0)
+        .await?;
+
+    let mut stats = StatsSchema(&mut storage);
+    assert_eq!(stats.count_created_accounts(BlockNumber(1)).await?, 3);
+    assert_eq!(stats.count_created_accounts(BlockNumber(2)).await?, 1);
+    assert_eq!(stats.count_created_accounts(BlockNumber(3)).await?, 0);
+
+    Ok(())
+}
+
+/// Checks that successful transactions and priority operations of the block are counted by type.
+#[db_test]
+async fn count_block_operations_by_type(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = vec![
+        executed_tx(1, 1, json!({ "type": "Transfer" }), true),
+        executed_tx(2, 1, json!({ "type": "Transfer" }), true),
+        executed_tx(3, 1, json!({ "type": "Withdraw" }), true),
+        // Failed transactions are not counted.
+        executed_tx(4, 1, json!({ "type": "Transfer" }), false),
+        // Transactions of other blocks are not counted.
+        executed_tx(5, 2, json!({ "type": "ChangePubKey" }), true),
+    ];
+    for tx in txs {
+        OperationsSchema(&mut storage).store_executed_tx(tx).await?;
+    }
+    let priority_ops = vec![
+        executed_priority_op(1, 1, json!({ "type": "Deposit" })),
+        executed_priority_op(2, 2, json!({ "type": "FullExit" })),
+    ];
+    for op in priority_ops {
+        OperationsSchema(&mut storage)
+            .store_executed_priority_op(op)
+            .await?;
+    }
+
+    let mut counts = StatsSchema(&mut storage)
+        .count_block_operations_by_type(BlockNumber(1))
+        .await?;
+    counts.sort_by(|a, b| a.op_type.cmp(&b.op_type));
+    let expected: Vec<_> = [("Deposit", 1), ("Transfer", 2), ("Withdraw", 1)]
+        .iter()
+        .map(|(op_type, count)| OperationsCount {
+            op_type: op_type.to_string(),
+            count: *count,
+        })
+        .collect();
+    assert_eq!(counts, expected);
+
+    let counts = StatsSchema(&mut storage)
+        .count_block_operations_by_type(BlockNumber(3))
+        .await?;
+    assert!(counts.is_empty());
+
+    Ok(())
+}
+
+/// Checks that the fees are attributed to the fee token, which is stored either in the `feeToken`
+/// or in the `token` field depending on the transaction type, and scaled by its decimals.
+#[db_test]
+async fn collected_fees(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    store_tokens(&mut storage).await?;
+
+    let txs = vec![
+        // Transactions before the requested block are not taken into account.
+        executed_tx(
+            1,
+            1,
+            json!({ "type": "Transfer", "token": 1, "fee": "1000000000000000000" }),
+            true,
+        ),
+        executed_tx(
+            2,
+            2,
+            json!({ "type": "Transfer", "token": 1, "fee": "1500000000000000000" }),
+            true,
+        ),
+        // Fee is paid in `feeToken` rather than in the withdrawn NFT.
+        executed_tx(
+            3,
+            2,
+            json!({
+                "type": "WithdrawNFT",
+                "token": MIN_NFT_TOKEN_ID,
+                "feeToken": 1,
+                "fee": "500000000000000000"
+            }),
+            true,
+        ),
+        executed_tx(
+            4,
+            2,
+            json!({ "type": "ChangePubKey", "feeToken": 2, "fee": "500000" }),
+            true,
+        ),
+        // Failed transactions don't pay fees.
+        executed_tx(
+            5,
+            2,
+            json!({ "type": "Transfer", "token": 2, "fee": "7000000" }),
+            false,
+        ),
+        // Transactions without fees are skipped.
+        executed_tx(6, 2, json!({ "type": "Close" }), true),
+        executed_tx(
+            7,
+            3,
+            json!({ "type": "ForcedExit", "token": 2, "fee": "1000000" }),
+            true,
+        ),
+    ];
+    for tx in txs {
+        OperationsSchema(&mut storage).store_executed_tx(tx).await?;
+    }
+
+    let mut fees = StatsSchema(&mut storage)
+        .collected_fees(BlockNumber(2))
+        .await?;
+    fees.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    assert_eq!(
+        fees,
+        vec![
+            TokenAmount {
+                symbol: "TKA".to_string(),
+                amount: 2.0,
+            },
+            TokenAmount {
+                symbol: "TKB".to_string(),
+                amount: 1.5,
+            },
+        ]
+    );
+
+    let fees = StatsSchema(&mut storage)
+        .collected_fees(BlockNumber(4))
+        .await?;
+    assert!(fees.is_empty());
+
+    Ok(())
+}
@@ -11,13 +11,13 @@ use chrono::{Duration, Timelike, Utc};
 use num::{BigUint, FromPrimitive};
 use zksync_basic_types::Address;
 use zksync_types::{
-    forced_exit_requests::{ForcedExitRequest, SaveForcedExitRequestQuery},
+    forced_exit_requests::{ForcedExitRequest, ForcedExitRequestState, SaveForcedExitRequestQuery},
     tx::TxHash,
 };
 
 use std::ops::Add;
 
-use zksync_types::TokenId;
+use zksync_types::{Nonce, TokenId};
 
 // Accepts an array of requests and stores them in the db
 pub async fn store_requests(
@@ -201,3 +201,114 @@ async fn delete_old_requests(mut storage: StorageProcessor<'_>) -> QueryResult<(
 
     Ok(())
 }
+
+// Checks that the state changes are saved along with the history of the request
+#[db_test]
+async fn request_state_history(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now().with_nanosecond(0).unwrap();
+
+    let requests = vec![SaveForcedExitRequestQuery {
+        target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
+        tokens: vec![TokenId(1)],
+        price_in_wei: BigUint::from_i32(212).unwrap(),
+        created_at: now,
+        valid_until: now.add(Duration::days(1)),
+    }];
+    let request = store_requests(&mut storage, requests).await.remove(0);
+    assert_eq!(request.state, ForcedExitRequestState::Created);
+
+    let transaction_hash = TxHash::from_str(
+        "sync-tx:796018689b3e323894f44fb0093856ec3832908c626dea357a9bd1b25f9d11bf",
+    )
+    .unwrap();
+    let payer = Address::from_str("2a0a81e257a2f5d6ed4f07b81dbda09f107bd026").unwrap();
+
+    ForcedExitRequestsSchema(&mut storage)
+        .set_payer(request.id, payer)
+        .await?;
+    ForcedExitRequestsSchema(&mut storage)
+        .update_state(request.id, ForcedExitRequestState::Paid, &[])
+        .await?;
+    ForcedExitRequestsSchema(&mut storage)
+        .update_state(
+            request.id,
+            ForcedExitRequestState::Scheduled,
+            &[transaction_hash],
+        )
+        .await?;
+
+    let stored = ForcedExitRequestsSchema(&mut storage)
+        .get_request_by_id(request.id)
+        .await?
+        .unwrap();
+    assert_eq!(stored.state, ForcedExitRequestState::Scheduled);
+    assert_eq!(stored.payer, Some(payer));
+
+    let scheduled = ForcedExitRequestsSchema(&mut storage)
+        .get_requests_by_state(ForcedExitRequestState::Scheduled)
+        .await?;
+    assert_eq!(scheduled.len(), 1);
+    assert_eq!(scheduled[0].id, request.id);
+
+    let events = ForcedExitRequestsSchema(&mut storage)
+        .get_request_events(request.id)
+        .await?;
+    let states: Vec<_> = events.iter().map(|event| event.state).collect();
+    assert_eq!(
+        states,
+        vec![
+            ForcedExitRequestState::Created,
+            ForcedExitRequestState::Paid,
+            ForcedExitRequestState::Scheduled
+        ]
+    );
+    assert!(events[1].tx_hashes.is_empty());
+    assert_eq!(events[2].tx_hashes, vec![transaction_hash]);
+
+    Ok(())
+}
+
+// Checks that the refund is stored along with the nonce it was signed with
+#[db_test]
+async fn set_refund(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now().with_nanosecond(0).unwrap();
+    let requests = vec![SaveForcedExitRequestQuery {
+        target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
+        tokens: vec![TokenId(1)],
+        price_in_wei: BigUint::from_i32(212).unwrap(),
+        created_at: now,
+        valid_until: now.add(Duration::days(1)),
+    }];
+    let request = store_requests(&mut storage, requests).await.remove(0);
+    assert_eq!(request.refunded_by, None);
+    assert_eq!(request.refund_nonce, None);
+
+    let refund_hash = TxHash::from_str(
+        "sync-tx:796018689b3e323894f44fb0093856ec3832908c626dea357a9bd1b25f9d11bf",
+    )
+    .unwrap();
+    ForcedExitRequestsSchema(&mut storage)
+        .set_refund(request.id, Some((refund_hash, Nonce(7))))
+        .await?;
+
+    let stored = ForcedExitRequestsSchema(&mut storage)
+        .get_request_by_id(request.id)
+        .await?
+        .unwrap();
+    assert_eq!(stored.refunded_by, Some(refund_hash));
+    assert_eq!(stored.refund_nonce, Some(Nonce(7)));
+
+    // The refund is cleared together with its nonce.
+    ForcedExitRequestsSchema(&mut storage)
+        .set_refund(request.id, None)
+        .await?;
+
+    let stored = ForcedExitRequestsSchema(&mut storage)
+        .get_request_by_id(request.id)
+        .await?
+        .unwrap();
+    assert_eq!(stored.refunded_by, None);
+    assert_eq!(stored.refund_nonce, None);
+
+    Ok(())
+}
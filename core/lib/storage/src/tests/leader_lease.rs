@@ -0,0 +1,141 @@
+// Built-in imports
+use std::time::Duration;
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+const LEASE_NAME: &str = "zksync_core";
+
+/// Checks that the lease can be held by only one instance until it's released or expired.
+#[db_test]
+async fn acquire_and_release_lease(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let duration = Duration::from_secs(60);
+
+    assert!(storage
+        .leader_lease_schema()
+        .lease_holder(LEASE_NAME)
+        .await?
+        .is_none());
+
+    // The first instance acquires the lease and can renew it.
+    assert!(
+        storage
+            .leader_lease_schema()
+            .acquire_lease(LEASE_NAME, "first", duration)
+            .await?
+    );
+    assert!(
+        storage
+            .leader_lease_schema()
+            .acquire_lease(LEASE_NAME, "first", duration)
+            .await?
+    );
+    assert_eq!(
+        storage
+            .leader_lease_schema()
+            .lease_holder(LEASE_NAME)
+            .await?,
+        Some("first".to_string())
+    );
+
+    // The second one can't acquire the lease while it's held by the first instance.
+    assert!(
+        !storage
+            .leader_lease_schema()
+            .acquire_lease(LEASE_NAME, "second", duration)
+            .await?
+    );
+    // Releasing by the non-holder does nothing.
+    storage
+        .leader_lease_schema()
+        .release_lease(LEASE_NAME, "second")
+        .await?;
+    assert_eq!(
+        storage
+            .leader_lease_schema()
+            .lease_holder(LEASE_NAME)
+            .await?,
+        Some("first".to_string())
+    );
+
+    // Once released, the lease can be acquired by another instance.
+    storage
+        .leader_lease_schema()
+        .release_lease(LEASE_NAME, "first")
+        .await?;
+    assert!(
+        storage
+            .leader_lease_schema()
+            .acquire_lease(LEASE_NAME, "second", duration)
+            .await?
+    );
+
+    Ok(())
+}
+
+/// Checks that the expired lease can be taken over by another instance.
+#[db_test]
+async fn take_over_expired_lease(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(
+        storage
+            .leader_lease_schema()
+            .acquire_lease(LEASE_NAME, "first", Duration::from_secs(60))
+            .await?
+    );
+    // `now()` is fixed within the transaction, so move the expiration time to the past manually.
+    sqlx::query("UPDATE leader_lease SET expires_at = now() - interval '1 second'")
+        .execute(storage.conn())
+        .await?;
+
+    assert!(storage
+        .leader_lease_schema()
+        .lease_holder(LEASE_NAME)
+        .await?
+        .is_none());
+    assert!(
+        storage
+            .leader_lease_schema()
+            .acquire_lease(LEASE_NAME, "second", Duration::from_secs(60))
+            .await?
+    );
+    assert_eq!(
+        storage
+            .leader_lease_schema()
+            .lease_holder(LEASE_NAME)
+            .await?,
+        Some("second".to_string())
+    );
+
+    Ok(())
+}
+
+/// Checks that the lease is locked only by its holder.
+#[db_test]
+async fn lock_lease(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(
+        !storage
+            .leader_lease_schema()
+            .lock_lease(LEASE_NAME, "first")
+            .await?
+    );
+    assert!(
+        storage
+            .leader_lease_schema()
+            .acquire_lease(LEASE_NAME, "first", Duration::from_secs(60))
+            .await?
+    );
+    assert!(
+        storage
+            .leader_lease_schema()
+            .lock_lease(LEASE_NAME, "first")
+            .await?
+    );
+    assert!(
+        !storage
+            .leader_lease_schema()
+            .lock_lease(LEASE_NAME, "second")
+            .await?
+    );
+
+    Ok(())
+}
@@ -29,8 +29,10 @@ mod data_restore;
 mod ethereum;
 mod event;
 mod forced_exit_requests;
+mod leader_lease;
 mod prover;
 mod tokens;
+mod withdrawals;
 
 pub use db_test_macro::test as db_test;
 
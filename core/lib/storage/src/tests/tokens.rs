@@ -6,8 +6,9 @@ use num::{rational::Ratio, BigUint};
 // Workspace imports
 use zksync_test_account::ZkSyncAccount;
 use zksync_types::{
-    tokens::TokenMarketVolume, AccountId, Address, BlockNumber, ExecutedOperations, ExecutedTx,
-    Token, TokenId, TokenKind, TokenLike, TokenPrice, WithdrawNFTOp, ZkSyncOp, H256,
+    tokens::{TokenFeePolicy, TokenMarketVolume, TokenUpdate},
+    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedTx, Token, TokenId, TokenKind,
+    TokenLike, TokenPrice, WithdrawNFTOp, ZkSyncOp, H256,
 };
 use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 // Local imports
@@ -15,7 +16,7 @@ use crate::tests::db_test;
 use crate::{
     chain::account::records::StorageMintNFTUpdate,
     diff::StorageAccountDiff,
-    tokens::{TokensSchema, STORED_USD_PRICE_PRECISION},
+    tokens::{StoreTokenError, TokensSchema, STORED_USD_PRICE_PRECISION},
     QueryResult, StorageProcessor,
 };
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
@@ -334,3 +335,149 @@ async fn test_nfts_with_factories(mut storage: StorageProcessor<'_>) -> QueryRes
 
     Ok(())
 }
+
+/// Checks that each change of the token settings is stored as a new version.
+#[db_test]
+async fn test_token_settings(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let token = Token::new(
+        TokenId(1),
+        Address::from_low_u64_be(1),
+        "ABC",
+        9,
+        TokenKind::ERC20,
+    );
+    storage
+        .tokens_schema()
+        .store_or_update_token(token.clone())
+        .await?;
+    assert!(storage
+        .tokens_schema()
+        .get_token_settings(token.id)
+        .await?
+        .is_none());
+
+    // Unknown tokens can't be updated.
+    let updated = storage
+        .tokens_schema()
+        .update_token_settings(TokenId(100), TokenUpdate::default())
+        .await?;
+    assert!(updated.is_none());
+
+    // Update the metadata, it should be changed in the tokens table as well.
+    let first_version = storage
+        .tokens_schema()
+        .update_token_settings(
+            token.id,
+            TokenUpdate {
+                symbol: Some("XYZ".to_string()),
+                name: Some("XYZ Token".to_string()),
+                ..Default::default()
+            },
+        )
+        .await?
+        .unwrap();
+    assert_eq!(first_version.version, 1);
+    assert_eq!(first_version.decimals, token.decimals);
+    assert_eq!(first_version.fee_policy, TokenFeePolicy::MarketVolume);
+    let stored_token = storage
+        .tokens_schema()
+        .get_token(TokenLike::Id(token.id))
+        .await?
+        .unwrap();
+    assert_eq!(stored_token.symbol, "XYZ");
+
+    // Add the token to the fee allowlist, the previous changes are kept.
+    let second_version = storage
+        .tokens_schema()
+        .update_token_settings(
+            token.id,
+            TokenUpdate {
+                fee_policy: Some(TokenFeePolicy::Allowed),
+                ..Default::default()
+            },
+        )
+        .await?
+        .unwrap();
+    assert_eq!(second_version.version, 2);
+    assert_eq!(second_version.symbol, "XYZ");
+    assert_eq!(second_version.name, Some("XYZ Token".to_string()));
+    assert_eq!(
+        storage.tokens_schema().get_token_settings(token.id).await?,
+        Some(second_version.clone())
+    );
+
+    let allowlist = storage
+        .tokens_schema()
+        .load_tokens_by_fee_policy(TokenFeePolicy::Allowed)
+        .await?;
+    assert_eq!(allowlist, vec![second_version.clone()]);
+
+    let history = storage
+        .tokens_schema()
+        .load_token_settings_history(token.id)
+        .await?;
+    assert_eq!(history, vec![first_version, second_version]);
+
+    // Symbol of another token can't be taken.
+    let result = storage
+        .tokens_schema()
+        .update_token_settings(
+            token.id,
+            TokenUpdate {
+                symbol: Some("ETH".to_string()),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert!(result.is_err());
+
+    // Decimals can be changed only until the token is used.
+    let third_version = storage
+        .tokens_schema()
+        .update_token_settings(
+            token.id,
+            TokenUpdate {
+                decimals: Some(18),
+                ..Default::default()
+            },
+        )
+        .await?
+        .unwrap();
+    assert_eq!(third_version.decimals, 18);
+
+    sqlx::query(
+        "INSERT INTO account_balance_updates
+            (account_id, block_number, coin_id, old_balance, new_balance, old_nonce, new_nonce, update_order_id)
+        VALUES (1, 1, $1, 0, 100, 0, 0, 0)",
+    )
+    .bind(*token.id as i32)
+    .execute(storage.conn())
+    .await?;
+    let result = storage
+        .tokens_schema()
+        .update_token_settings(
+            token.id,
+            TokenUpdate {
+                decimals: Some(6),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert!(matches!(result, Err(StoreTokenError::TokenInUseError(_))));
+    // Other settings can still be changed.
+    let fourth_version = storage
+        .tokens_schema()
+        .update_token_settings(
+            token.id,
+            TokenUpdate {
+                decimals: Some(18),
+                deprecated: Some(true),
+                ..Default::default()
+            },
+        )
+        .await?
+        .unwrap();
+    assert!(fourth_version.deprecated);
+
+    Ok(())
+}
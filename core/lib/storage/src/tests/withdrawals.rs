@@ -0,0 +1,108 @@
+// Built-in imports
+use std::convert::TryFrom;
+// Workspace imports
+use zksync_types::{
+    event::{
+        withdrawal::{WithdrawalEvent, WithdrawalStatus},
+        EventData, EventId, ZkSyncEvent,
+    },
+    tx::TxHash,
+    Address, BlockNumber, TokenId, H256,
+};
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn withdrawal(hash_byte: u8, amount: u32, status: WithdrawalStatus) -> WithdrawalEvent {
+    WithdrawalEvent {
+        tx_hash: TxHash::from_slice(&[hash_byte; 32]).unwrap(),
+        address: Address::from_low_u64_be(1),
+        token_id: TokenId(0),
+        amount: amount.into(),
+        status,
+        eth_tx_hash: H256::from_low_u64_be(1),
+    }
+}
+
+/// Checks that withdrawals statuses are tracked and withdrawal events are created for every change.
+#[db_test]
+async fn withdrawals_tracking(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(storage
+        .withdrawals_schema()
+        .last_tracked_block()
+        .await?
+        .is_none());
+
+    let executed = withdrawal(1, 100, WithdrawalStatus::Executed);
+    let first_pending = withdrawal(2, 200, WithdrawalStatus::PendingBalance);
+    let second_pending = withdrawal(3, 300, WithdrawalStatus::PendingBalance);
+    storage
+        .withdrawals_schema()
+        .store_block_withdrawals(BlockNumber(1), &[executed.clone(), first_pending.clone()])
+        .await?;
+    storage
+        .withdrawals_schema()
+        .store_block_withdrawals(BlockNumber(2), &[second_pending.clone()])
+        .await?;
+    assert_eq!(
+        storage.withdrawals_schema().last_tracked_block().await?,
+        Some(BlockNumber(2))
+    );
+
+    let info = storage
+        .withdrawals_schema()
+        .get_withdrawal(executed.tx_hash)
+        .await?
+        .unwrap();
+    assert_eq!(info.rollup_block, BlockNumber(1));
+    assert_eq!(info.amount, executed.amount);
+    assert_eq!(info.status, WithdrawalStatus::Executed);
+
+    let pending_balances = storage.withdrawals_schema().load_pending_balances().await?;
+    assert_eq!(
+        pending_balances,
+        vec![(executed.address, executed.token_id)]
+    );
+    // The latest withdrawal goes first.
+    let pending = storage
+        .withdrawals_schema()
+        .load_pending_withdrawals(executed.address, executed.token_id)
+        .await?;
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].block_number(), BlockNumber(2));
+
+    // Claim the first pending withdrawal.
+    storage
+        .withdrawals_schema()
+        .mark_withdrawals_claimed(vec![pending[1].clone()])
+        .await?;
+    let info = storage
+        .withdrawals_schema()
+        .get_withdrawal(first_pending.tx_hash)
+        .await?
+        .unwrap();
+    assert_eq!(info.status, WithdrawalStatus::Claimed);
+
+    // Every status change has created an event.
+    let statuses: Vec<_> = storage
+        .event_schema()
+        .fetch_new_events(EventId(0))
+        .await?
+        .into_iter()
+        .map(|event| match ZkSyncEvent::try_from(event).unwrap().data {
+            EventData::Withdrawal(withdrawal) => (withdrawal.tx_hash, withdrawal.status),
+            _ => panic!("unexpected event type"),
+        })
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            (executed.tx_hash, WithdrawalStatus::Executed),
+            (first_pending.tx_hash, WithdrawalStatus::PendingBalance),
+            (second_pending.tx_hash, WithdrawalStatus::PendingBalance),
+            (first_pending.tx_hash, WithdrawalStatus::Claimed),
+        ]
+    );
+
+    Ok(())
+}
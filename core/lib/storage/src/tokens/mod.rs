@@ -2,6 +2,7 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 // External imports
+use chrono::Utc;
 use num::{rational::Ratio, BigUint};
 
 use thiserror::Error;
@@ -13,11 +14,14 @@ use zksync_api_types::v02::{
 use zksync_types::{AccountId, Address, Token, TokenId, TokenLike, TokenPrice, NFT};
 use zksync_utils::ratio_to_big_decimal;
 // Local imports
-use self::records::{DBMarketVolume, DbTickerPrice, DbToken, StorageApiNFT, StorageNFT, TokenKind};
+use self::records::{
+    DBMarketVolume, DbTickerPrice, DbToken, DbTokenSettings, StorageApiNFT, StorageNFT,
+    TokenFeePolicy, TokenKind,
+};
 
 use crate::utils::address_to_stored_string;
 use crate::{QueryResult, StorageProcessor};
-use zksync_types::tokens::TokenMarketVolume;
+use zksync_types::tokens::{TokenMarketVolume, TokenSettings, TokenUpdate};
 
 pub mod records;
 
@@ -34,6 +38,8 @@ pub enum StoreTokenError {
     #[error("{0}")]
     TokenAlreadyExistsError(String),
     #[error("{0}")]
+    TokenInUseError(String),
+    #[error("{0}")]
     Other(anyhow::Error),
 }
 
@@ -125,6 +131,203 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Applies the change of the token metadata and listing settings, storing it as the new version
+    /// of the token settings. Returns `None` if there is no such token.
+    ///
+    /// Decimals can't be changed once the token has any balances, since the amounts stored
+    /// in the transactions and balances would be interpreted differently.
+    pub async fn update_token_settings(
+        &mut self,
+        token_id: TokenId,
+        update: TokenUpdate,
+    ) -> Result<Option<TokenSettings>, StoreTokenError> {
+        let start = Instant::now();
+        let mut transaction = self
+            .0
+            .start_transaction()
+            .await
+            .map_err(StoreTokenError::Other)?;
+
+        let token = TokensSchema(&mut transaction)
+            .get_token(TokenLike::Id(token_id))
+            .await
+            .map_err(StoreTokenError::Other)?;
+        let token = match token {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+        if matches!(update.decimals, Some(decimals) if decimals != token.decimals) {
+            let used = sqlx::query!(
+                r#"
+                SELECT EXISTS (SELECT 1 FROM account_balance_updates WHERE coin_id = $1)
+                    OR EXISTS (SELECT 1 FROM balances WHERE coin_id = $1)
+                    AS "used!"
+                "#,
+                *token_id as i32
+            )
+            .fetch_one(transaction.conn())
+            .await
+            .map_err(|err| StoreTokenError::Other(err.into()))?
+            .used;
+            if used {
+                return Err(StoreTokenError::TokenInUseError(format!(
+                    "decimals of token {} can't be changed since it has balances",
+                    token.symbol
+                )));
+            }
+        }
+
+        let current = TokensSchema(&mut transaction)
+            .get_token_settings(token_id)
+            .await
+            .map_err(StoreTokenError::Other)?;
+
+        let (version, name, deprecated, fee_policy) = match current {
+            Some(current) => (
+                current.version + 1,
+                current.name,
+                current.deprecated,
+                current.fee_policy,
+            ),
+            None => (1, None, false, Default::default()),
+        };
+        let mut settings = TokenSettings {
+            token_id,
+            version,
+            symbol: update.symbol.unwrap_or(token.symbol),
+            decimals: update.decimals.unwrap_or(token.decimals),
+            name: update.name.or(name),
+            deprecated: update.deprecated.unwrap_or(deprecated),
+            fee_policy: update.fee_policy.unwrap_or(fee_policy),
+            // Set by the database below, so it's stored without the loss of precision.
+            updated_at: Utc::now(),
+        };
+
+        let symbol_owner = TokensSchema(&mut transaction)
+            .get_token(TokenLike::Symbol(settings.symbol.clone()))
+            .await
+            .map_err(StoreTokenError::Other)?;
+        if matches!(symbol_owner, Some(owner) if owner.id != token_id) {
+            return Err(StoreTokenError::TokenAlreadyExistsError(format!(
+                "token with symbol {} already exists",
+                settings.symbol
+            )));
+        }
+
+        sqlx::query!(
+            "UPDATE tokens SET symbol = $2, decimals = $3 WHERE id = $1",
+            *token_id as i32,
+            settings.symbol,
+            i16::from(settings.decimals)
+        )
+        .execute(transaction.conn())
+        .await
+        .map_err(|err| StoreTokenError::Other(err.into()))?;
+
+        let fee_policy: TokenFeePolicy = settings.fee_policy.into();
+        settings.updated_at = sqlx::query!(
+            r#"
+            INSERT INTO token_settings ( token_id, version, symbol, decimals, name, deprecated, fee_policy, updated_at )
+            VALUES ( $1, $2, $3, $4, $5, $6, $7, now() )
+            RETURNING updated_at
+            "#,
+            *token_id as i32,
+            i64::from(settings.version),
+            settings.symbol,
+            i16::from(settings.decimals),
+            settings.name,
+            settings.deprecated,
+            fee_policy as TokenFeePolicy
+        )
+        .fetch_one(transaction.conn())
+        .await
+        .map_err(|err| StoreTokenError::Other(err.into()))?
+        .updated_at;
+
+        transaction.commit().await.map_err(StoreTokenError::Other)?;
+
+        metrics::histogram!("sql.token.update_token_settings", start.elapsed());
+        Ok(Some(settings))
+    }
+
+    /// Returns the current settings of the token, or `None` if they have never been changed.
+    pub async fn get_token_settings(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<TokenSettings>> {
+        let start = Instant::now();
+        let settings = sqlx::query_as!(
+            DbTokenSettings,
+            r#"
+            SELECT token_id, version, symbol, decimals, name, deprecated,
+                fee_policy as "fee_policy: _", updated_at
+            FROM token_settings
+            WHERE token_id = $1
+            ORDER BY version DESC
+            LIMIT 1
+            "#,
+            *token_id as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.get_token_settings", start.elapsed());
+        Ok(settings.map(TokenSettings::from))
+    }
+
+    /// Returns all the versions of the token settings in the ascending order.
+    pub async fn load_token_settings_history(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Vec<TokenSettings>> {
+        let start = Instant::now();
+        let history = sqlx::query_as!(
+            DbTokenSettings,
+            r#"
+            SELECT token_id, version, symbol, decimals, name, deprecated,
+                fee_policy as "fee_policy: _", updated_at
+            FROM token_settings
+            WHERE token_id = $1
+            ORDER BY version ASC
+            "#,
+            *token_id as i32
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_token_settings_history", start.elapsed());
+        Ok(history.into_iter().map(TokenSettings::from).collect())
+    }
+
+    /// Returns the current settings of the tokens with the given fee policy, e.g. the fee tokens allowlist.
+    pub async fn load_tokens_by_fee_policy(
+        &mut self,
+        fee_policy: zksync_types::tokens::TokenFeePolicy,
+    ) -> QueryResult<Vec<TokenSettings>> {
+        let start = Instant::now();
+        let fee_policy: TokenFeePolicy = fee_policy.into();
+        let tokens = sqlx::query_as!(
+            DbTokenSettings,
+            r#"
+            SELECT token_id as "token_id!", version as "version!", symbol as "symbol!",
+                decimals as "decimals!", name, deprecated as "deprecated!",
+                fee_policy as "fee_policy!: _", updated_at as "updated_at!"
+            FROM (
+                SELECT DISTINCT ON (token_id) * FROM token_settings
+                ORDER BY token_id, version DESC
+            ) current_settings
+            WHERE fee_policy = $1
+            ORDER BY token_id ASC
+            "#,
+            fee_policy as TokenFeePolicy
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_tokens_by_fee_policy", start.elapsed());
+        Ok(tokens.into_iter().map(TokenSettings::from).collect())
+    }
+
     /// Loads tokens from the database starting from the given id with the given limit in the ascending order.
     pub async fn load_tokens_asc(
         &mut self,
@@ -396,6 +599,92 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(db_token.map(|t| t.into()))
     }
 
+    /// Returns the amount of NFTs minted by the account.
+    pub async fn get_minted_nfts_count(&mut self, creator_id: AccountId) -> QueryResult<u32> {
+        let start = Instant::now();
+        let count = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as "count!" FROM nft WHERE creator_account_id = $1
+            "#,
+            *creator_id as i32
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        metrics::histogram!("sql.token.get_minted_nfts_count", start.elapsed());
+        Ok(count as u32)
+    }
+
+    /// Returns the id of the last NFT minted by the account, if any.
+    pub async fn get_last_minted_nft_id(
+        &mut self,
+        creator_id: AccountId,
+    ) -> QueryResult<Option<TokenId>> {
+        let start = Instant::now();
+        let record = sqlx::query!(
+            r#"
+            SELECT max(token_id) as "token_id" FROM nft WHERE creator_account_id = $1
+            "#,
+            *creator_id as i32
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.get_last_minted_nft_id", start.elapsed());
+        Ok(record.token_id.map(|id| TokenId(id as u32)))
+    }
+
+    /// Loads NFTs minted by the account for the given pagination query.
+    pub async fn load_minted_nfts_page(
+        &mut self,
+        creator_id: AccountId,
+        query: &PaginationQuery<TokenId>,
+    ) -> QueryResult<Vec<NFT>> {
+        let start = Instant::now();
+        let nfts = match query.direction {
+            PaginationDirection::Newer => {
+                sqlx::query_as!(
+                    StorageNFT,
+                    r#"
+                    SELECT nft.*, tokens.symbol FROM nft
+                    INNER JOIN tokens
+                    ON tokens.id = nft.token_id
+                    WHERE creator_account_id = $1 AND token_id >= $2
+                    ORDER BY token_id ASC
+                    LIMIT $3
+                    "#,
+                    *creator_id as i32,
+                    *query.from as i32,
+                    i64::from(query.limit)
+                )
+                .fetch_all(self.0.conn())
+                .await?
+            }
+            PaginationDirection::Older => {
+                sqlx::query_as!(
+                    StorageNFT,
+                    r#"
+                    SELECT nft.*, tokens.symbol FROM nft
+                    INNER JOIN tokens
+                    ON tokens.id = nft.token_id
+                    WHERE creator_account_id = $1 AND token_id <= $2
+                    ORDER BY token_id DESC
+                    LIMIT $3
+                    "#,
+                    *creator_id as i32,
+                    *query.from as i32,
+                    i64::from(query.limit)
+                )
+                .fetch_all(self.0.conn())
+                .await?
+            }
+        };
+
+        metrics::histogram!("sql.token.load_minted_nfts_page", start.elapsed());
+        Ok(nfts.into_iter().map(NFT::from).collect())
+    }
+
     /// Given the numeric token ID, symbol or address, returns token.
     pub async fn get_token(&mut self, token_like: TokenLike) -> QueryResult<Option<Token>> {
         let start = Instant::now();
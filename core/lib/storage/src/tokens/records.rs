@@ -9,7 +9,7 @@ use crate::utils::{address_to_stored_string, stored_str_address_to_address};
 use chrono::{DateTime, Utc};
 use zksync_api_types::v02::token::ApiNFT;
 use zksync_types::{
-    tokens::{TokenMarketVolume, TokenPrice},
+    tokens::{TokenMarketVolume, TokenPrice, TokenSettings},
     AccountId, Address, Token, TokenId, H256, NFT,
 };
 use zksync_utils::big_decimal_to_ratio;
@@ -51,6 +51,61 @@ impl From<zksync_types::TokenKind> for TokenKind {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "token_fee_policy")]
+pub enum TokenFeePolicy {
+    MarketVolume,
+    Allowed,
+    Disallowed,
+}
+
+impl From<TokenFeePolicy> for zksync_types::tokens::TokenFeePolicy {
+    fn from(policy: TokenFeePolicy) -> zksync_types::tokens::TokenFeePolicy {
+        match policy {
+            TokenFeePolicy::MarketVolume => zksync_types::tokens::TokenFeePolicy::MarketVolume,
+            TokenFeePolicy::Allowed => zksync_types::tokens::TokenFeePolicy::Allowed,
+            TokenFeePolicy::Disallowed => zksync_types::tokens::TokenFeePolicy::Disallowed,
+        }
+    }
+}
+
+impl From<zksync_types::tokens::TokenFeePolicy> for TokenFeePolicy {
+    fn from(policy: zksync_types::tokens::TokenFeePolicy) -> TokenFeePolicy {
+        match policy {
+            zksync_types::tokens::TokenFeePolicy::MarketVolume => TokenFeePolicy::MarketVolume,
+            zksync_types::tokens::TokenFeePolicy::Allowed => TokenFeePolicy::Allowed,
+            zksync_types::tokens::TokenFeePolicy::Disallowed => TokenFeePolicy::Disallowed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbTokenSettings {
+    pub token_id: i32,
+    pub version: i64,
+    pub symbol: String,
+    pub decimals: i16,
+    pub name: Option<String>,
+    pub deprecated: bool,
+    pub fee_policy: TokenFeePolicy,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<DbTokenSettings> for TokenSettings {
+    fn from(val: DbTokenSettings) -> Self {
+        Self {
+            token_id: TokenId(val.token_id as u32),
+            version: val.version as u32,
+            symbol: val.symbol,
+            decimals: val.decimals as u8,
+            name: val.name,
+            deprecated: val.deprecated,
+            fee_policy: val.fee_policy.into(),
+            updated_at: val.updated_at,
+        }
+    }
+}
+
 impl From<Token> for DbToken {
     fn from(token: Token) -> Self {
         Self {
@@ -0,0 +1,201 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use num::BigInt;
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_api_types::v02::transaction::WithdrawalInfo;
+use zksync_types::{event::withdrawal::WithdrawalEvent, tx::TxHash, Address, BlockNumber, TokenId};
+// Local imports
+use self::records::{StoredWithdrawal, WithdrawalStatus};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Withdrawals schema tracks the finalization of the fungible withdrawals on L1: whether funds
+/// have been transferred to the recipient by the `executeBlocks` transaction, or have been stored
+/// as the pending balance, which has to be claimed by the recipient.
+///
+/// Every change of the withdrawal status creates a withdrawal event.
+#[derive(Debug)]
+pub struct WithdrawalsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> WithdrawalsSchema<'a, 'c> {
+    /// Returns the last block, which withdrawals have been processed by the tracker.
+    pub async fn last_tracked_block(&mut self) -> QueryResult<Option<BlockNumber>> {
+        let start = Instant::now();
+        let block_number = sqlx::query!("SELECT last_block FROM withdrawals_tracker_state")
+            .fetch_optional(self.0.conn())
+            .await?
+            .map(|record| BlockNumber(record.last_block as u32));
+
+        metrics::histogram!("sql.withdrawals.last_tracked_block", start.elapsed());
+        Ok(block_number)
+    }
+
+    /// Sets the last block, which withdrawals have been processed by the tracker.
+    pub async fn set_last_tracked_block(&mut self, block_number: BlockNumber) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO withdrawals_tracker_state (id, last_block)
+            VALUES (true, $1)
+            ON CONFLICT (id)
+            DO UPDATE SET last_block = $1
+            "#,
+            i64::from(*block_number)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.withdrawals.set_last_tracked_block", start.elapsed());
+        Ok(())
+    }
+
+    /// Stores the withdrawals from the executed block along with the withdrawal events
+    /// and marks the block as processed by the tracker.
+    pub async fn store_block_withdrawals(
+        &mut self,
+        block_number: BlockNumber,
+        withdrawals: &[WithdrawalEvent],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        for withdrawal in withdrawals {
+            let status: WithdrawalStatus = withdrawal.status.into();
+            sqlx::query!(
+                r#"
+                INSERT INTO withdrawals (tx_hash, block_number, address, token_id, amount, status, eth_tx_hash, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, now())
+                ON CONFLICT (tx_hash) DO NOTHING
+                "#,
+                withdrawal.tx_hash.as_ref(),
+                i64::from(*block_number),
+                withdrawal.address.as_bytes(),
+                *withdrawal.token_id as i32,
+                BigDecimal::from(BigInt::from(withdrawal.amount.clone())),
+                status as WithdrawalStatus,
+                withdrawal.eth_tx_hash.as_bytes()
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction
+            .event_schema()
+            .store_withdrawal_events(block_number, withdrawals)
+            .await?;
+        WithdrawalsSchema(&mut transaction)
+            .set_last_tracked_block(block_number)
+            .await?;
+
+        transaction.commit().await?;
+        metrics::histogram!("sql.withdrawals.store_block_withdrawals", start.elapsed());
+        Ok(())
+    }
+
+    /// Returns recipients and tokens of the withdrawals stored as the pending balance.
+    pub async fn load_pending_balances(&mut self) -> QueryResult<Vec<(Address, TokenId)>> {
+        let start = Instant::now();
+        let balances = sqlx::query!(
+            r#"
+            SELECT DISTINCT address, token_id FROM withdrawals
+            WHERE status = 'PendingBalance'
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|record| {
+            (
+                Address::from_slice(&record.address),
+                TokenId(record.token_id as u32),
+            )
+        })
+        .collect();
+
+        metrics::histogram!("sql.withdrawals.load_pending_balances", start.elapsed());
+        Ok(balances)
+    }
+
+    /// Returns the withdrawals of the token to the address stored as the pending balance,
+    /// starting from the latest one.
+    pub async fn load_pending_withdrawals(
+        &mut self,
+        address: Address,
+        token_id: TokenId,
+    ) -> QueryResult<Vec<StoredWithdrawal>> {
+        let start = Instant::now();
+        let withdrawals = sqlx::query_as!(
+            StoredWithdrawal,
+            r#"
+            SELECT tx_hash, block_number, address, token_id, amount,
+                status as "status: _", eth_tx_hash, updated_at
+            FROM withdrawals
+            WHERE address = $1 AND token_id = $2 AND status = 'PendingBalance'
+            ORDER BY block_number DESC
+            "#,
+            address.as_bytes(),
+            *token_id as i32
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.withdrawals.load_pending_withdrawals", start.elapsed());
+        Ok(withdrawals)
+    }
+
+    /// Marks the withdrawals as claimed and stores the withdrawal events.
+    pub async fn mark_withdrawals_claimed(
+        &mut self,
+        withdrawals: Vec<StoredWithdrawal>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        for withdrawal in withdrawals {
+            sqlx::query!(
+                r#"
+                UPDATE withdrawals SET status = 'Claimed', updated_at = now()
+                WHERE tx_hash = $1
+                "#,
+                &withdrawal.tx_hash
+            )
+            .execute(transaction.conn())
+            .await?;
+
+            let block_number = withdrawal.block_number();
+            let mut event = WithdrawalEvent::from(withdrawal);
+            event.status = WithdrawalStatus::Claimed.into();
+            transaction
+                .event_schema()
+                .store_withdrawal_events(block_number, &[event])
+                .await?;
+        }
+
+        transaction.commit().await?;
+        metrics::histogram!("sql.withdrawals.mark_withdrawals_claimed", start.elapsed());
+        Ok(())
+    }
+
+    /// Returns the finalization status of the withdrawal, or `None` if there is no such withdrawal
+    /// or its block hasn't been processed by the tracker yet.
+    pub async fn get_withdrawal(&mut self, tx_hash: TxHash) -> QueryResult<Option<WithdrawalInfo>> {
+        let start = Instant::now();
+        let withdrawal = sqlx::query_as!(
+            StoredWithdrawal,
+            r#"
+            SELECT tx_hash, block_number, address, token_id, amount,
+                status as "status: _", eth_tx_hash, updated_at
+            FROM withdrawals
+            WHERE tx_hash = $1
+            "#,
+            tx_hash.as_ref()
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.withdrawals.get_withdrawal", start.elapsed());
+        Ok(withdrawal.map(WithdrawalInfo::from))
+    }
+}
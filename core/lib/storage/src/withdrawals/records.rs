@@ -0,0 +1,96 @@
+// Built-in imports
+// External imports
+use chrono::{DateTime, Utc};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+use zksync_api_types::v02::transaction::WithdrawalInfo;
+use zksync_types::{
+    event::withdrawal::WithdrawalEvent, tx::TxHash, Address, BlockNumber, TokenId, H256,
+};
+// Local imports
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "withdrawal_status")]
+pub enum WithdrawalStatus {
+    Executed,
+    PendingBalance,
+    Claimed,
+}
+
+impl From<WithdrawalStatus> for zksync_types::event::withdrawal::WithdrawalStatus {
+    fn from(status: WithdrawalStatus) -> Self {
+        match status {
+            WithdrawalStatus::Executed => Self::Executed,
+            WithdrawalStatus::PendingBalance => Self::PendingBalance,
+            WithdrawalStatus::Claimed => Self::Claimed,
+        }
+    }
+}
+
+impl From<zksync_types::event::withdrawal::WithdrawalStatus> for WithdrawalStatus {
+    fn from(status: zksync_types::event::withdrawal::WithdrawalStatus) -> Self {
+        match status {
+            zksync_types::event::withdrawal::WithdrawalStatus::Executed => Self::Executed,
+            zksync_types::event::withdrawal::WithdrawalStatus::PendingBalance => {
+                Self::PendingBalance
+            }
+            zksync_types::event::withdrawal::WithdrawalStatus::Claimed => Self::Claimed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredWithdrawal {
+    pub tx_hash: Vec<u8>,
+    pub block_number: i64,
+    pub address: Vec<u8>,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    pub status: WithdrawalStatus,
+    pub eth_tx_hash: Vec<u8>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StoredWithdrawal {
+    pub fn block_number(&self) -> BlockNumber {
+        BlockNumber(self.block_number as u32)
+    }
+
+    pub fn amount(&self) -> BigUint {
+        self.amount
+            .to_bigint()
+            .and_then(|amount| amount.to_biguint())
+            .expect("Invalid withdrawal amount stored")
+    }
+}
+
+impl From<StoredWithdrawal> for WithdrawalEvent {
+    fn from(val: StoredWithdrawal) -> Self {
+        Self {
+            tx_hash: TxHash::from_slice(&val.tx_hash).expect("Invalid withdrawal hash stored"),
+            address: Address::from_slice(&val.address),
+            token_id: TokenId(val.token_id as u32),
+            amount: val.amount(),
+            status: val.status.into(),
+            eth_tx_hash: H256::from_slice(&val.eth_tx_hash),
+        }
+    }
+}
+
+impl From<StoredWithdrawal> for WithdrawalInfo {
+    fn from(val: StoredWithdrawal) -> Self {
+        let rollup_block = val.block_number();
+        let event = WithdrawalEvent::from(val);
+        Self {
+            tx_hash: event.tx_hash,
+            rollup_block,
+            address: event.address,
+            token_id: event.token_id,
+            amount: event.amount,
+            status: event.status,
+            eth_tx_hash: event.eth_tx_hash,
+        }
+    }
+}
@@ -4,13 +4,17 @@ use serde::Serialize;
 // Workspace uses
 use zksync_basic_types::BlockNumber;
 // Local uses
-use self::{account::AccountEvent, block::BlockEvent, transaction::TransactionEvent};
+use self::{
+    account::AccountEvent, block::BlockEvent, transaction::TransactionEvent,
+    withdrawal::WithdrawalEvent,
+};
 
 pub use crate::EventId;
 
 pub mod account;
 pub mod block;
 pub mod transaction;
+pub mod withdrawal;
 
 pub mod test_data;
 
@@ -21,6 +25,7 @@ pub enum EventData {
     Account(AccountEvent),
     Block(BlockEvent),
     Transaction(TransactionEvent),
+    Withdrawal(WithdrawalEvent),
 }
 
 // An event that happened in the zkSync network.
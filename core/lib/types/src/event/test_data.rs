@@ -5,8 +5,8 @@ use chrono::Utc;
 use once_cell::sync::OnceCell;
 // Workspace uses
 // Local uses
-use super::{account::*, block::*, transaction::*, EventData, EventId, ZkSyncEvent};
-use crate::{AccountId, BlockNumber, Nonce, TokenId};
+use super::{account::*, block::*, transaction::*, withdrawal::*, EventData, EventId, ZkSyncEvent};
+use crate::{tx::TxHash, AccountId, Address, BlockNumber, Nonce, TokenId, H256};
 
 /// Constructs default values for `BlockDetails` struct. Since block events
 /// can only be filtered by status, these fields are not used.
@@ -96,3 +96,24 @@ pub fn get_transaction_event(
         data: EventData::Transaction(tx_event),
     }
 }
+
+/// Construct withdrawal event with the given recipient, token and status.
+pub fn get_withdrawal_event(
+    address: Address,
+    token_id: TokenId,
+    status: WithdrawalStatus,
+) -> ZkSyncEvent {
+    let withdrawal_event = WithdrawalEvent {
+        tx_hash: TxHash::default(),
+        address,
+        token_id,
+        amount: 100u32.into(),
+        status,
+        eth_tx_hash: H256::zero(),
+    };
+    ZkSyncEvent {
+        id: EventId(0),
+        block_number: BlockNumber(0),
+        data: EventData::Withdrawal(withdrawal_event),
+    }
+}
@@ -0,0 +1,34 @@
+// Built-in uses
+// External uses
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+// Workspace uses
+use zksync_utils::BigUintSerdeAsRadix10Str;
+// Local uses
+use crate::{tx::TxHash, Address, TokenId, H256};
+
+/// Status of the withdrawal after the block containing it has been executed on L1.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawalStatus {
+    /// Funds have been transferred to the recipient by the `executeBlocks` transaction.
+    Executed,
+    /// Transfer to the recipient has failed, so funds were stored as the pending balance
+    /// and have to be claimed via `withdrawPendingBalance`.
+    PendingBalance,
+    /// Pending balance has been withdrawn by the recipient.
+    Claimed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithdrawalEvent {
+    /// Hash of the `Withdraw`, `ForcedExit` or `FullExit` operation.
+    pub tx_hash: TxHash,
+    pub address: Address,
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    pub status: WithdrawalStatus,
+    /// Hash of the `executeBlocks` transaction.
+    pub eth_tx_hash: H256,
+}
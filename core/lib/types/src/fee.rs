@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use num::rational::Ratio;
 use num::BigUint;
 use serde::{Deserialize, Serialize};
@@ -37,6 +38,22 @@ pub struct Fee {
     pub zkp_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Quote of the `total_fee` signed by the server, if the server issues quotes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote: Option<FeeQuote>,
+}
+
+/// Fee quoted by the server. Until the expiration the quoted fee is accepted
+/// for the transaction it was requested for, even if the required fee has changed
+/// (within the limit set by the server).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeQuote {
+    /// Server signature of the quote.
+    pub id: String,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub total_fee: BigUint,
+    pub expires_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -84,6 +101,7 @@ impl Fee {
             gas_fee,
             zkp_fee,
             total_fee,
+            quote: None,
         }
     }
 }
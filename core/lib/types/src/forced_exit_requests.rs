@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use num::BigUint;
 use thiserror::Error;
-use zksync_basic_types::{Address, TokenId};
+use zksync_basic_types::{Address, Nonce, TokenId, H256};
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
 use serde::{Deserialize, Serialize};
@@ -9,11 +9,78 @@ use serde::{Deserialize, Serialize};
 pub type ForcedExitRequestId = i64;
 
 use ethabi::{decode, ParamType};
-use std::convert::TryFrom;
+use std::{convert::TryFrom, str::FromStr};
 use zksync_basic_types::Log;
 
 use crate::tx::TxHash;
 
+/// Stage of the forced exit request processing.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum ForcedExitRequestState {
+    /// Request is registered, but the payment for it is not received yet.
+    Created,
+    /// Payment for the request is received.
+    Paid,
+    /// `ForcedExit` transactions for the request are prepared and are about to be sent.
+    Scheduled,
+    /// `ForcedExit` transactions are sent to the mempool.
+    Submitted,
+    /// `ForcedExit` transactions are included into a committed block.
+    Committed,
+    /// Block with the `ForcedExit` transactions is verified.
+    Finalized,
+    /// Payment is received, but the request can't be fulfilled anymore: either it has expired
+    /// before the payment, or the target is not eligible for the forced exit. Payment will be refunded.
+    Expired,
+    /// Payment for the expired request is returned to the payer.
+    Refunded,
+}
+
+impl ForcedExitRequestState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Paid => "paid",
+            Self::Scheduled => "scheduled",
+            Self::Submitted => "submitted",
+            Self::Committed => "committed",
+            Self::Finalized => "finalized",
+            Self::Expired => "expired",
+            Self::Refunded => "refunded",
+        }
+    }
+
+    /// Whether the payment for the request was received, but it is not decided yet what to do with it.
+    /// Such requests can still be fulfilled.
+    pub fn is_awaiting_processing(self) -> bool {
+        matches!(self, Self::Created | Self::Paid)
+    }
+}
+
+impl FromStr for ForcedExitRequestState {
+    type Err = UnknownForcedExitRequestState;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let state = match s {
+            "created" => Self::Created,
+            "paid" => Self::Paid,
+            "scheduled" => Self::Scheduled,
+            "submitted" => Self::Submitted,
+            "committed" => Self::Committed,
+            "finalized" => Self::Finalized,
+            "expired" => Self::Expired,
+            "refunded" => Self::Refunded,
+            _ => return Err(UnknownForcedExitRequestState(s.to_owned())),
+        };
+        Ok(state)
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Unknown forced exit request state: {0}")]
+pub struct UnknownForcedExitRequestState(String);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct ForcedExitRequest {
@@ -26,6 +93,24 @@ pub struct ForcedExitRequest {
     pub created_at: DateTime<Utc>,
     pub fulfilled_by: Option<Vec<TxHash>>,
     pub fulfilled_at: Option<DateTime<Utc>>,
+    pub state: ForcedExitRequestState,
+    /// Ethereum address the payment was sent from.
+    pub payer: Option<Address>,
+    /// Hash of the transfer returning the payment for the expired request.
+    pub refunded_by: Option<TxHash>,
+    /// Nonce of the refund transfer. Only one refund with this nonce can ever be executed.
+    pub refund_nonce: Option<Nonce>,
+}
+
+/// Change of the forced exit request state.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ForcedExitRequestEvent {
+    pub request_id: ForcedExitRequestId,
+    pub state: ForcedExitRequestState,
+    /// Transactions related to the state change, e.g. the sent `ForcedExit` transactions or the refund transfer.
+    pub tx_hashes: Vec<TxHash>,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
@@ -42,6 +127,10 @@ pub struct SaveForcedExitRequestQuery {
 pub struct FundsReceivedEvent {
     pub amount: BigUint,
     pub block_number: u64,
+    /// Hash of the Ethereum transaction with the payment.
+    pub tx_hash: Option<H256>,
+    /// Sender of the payment. It is not a part of the event, so it's loaded separately.
+    pub payer: Option<Address>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -69,6 +158,8 @@ impl TryFrom<Log> for FundsReceivedEvent {
         Ok(FundsReceivedEvent {
             amount: BigUint::from(amount.as_u128()),
             block_number,
+            tx_hash: event.transaction_hash,
+            payer: None,
         })
     }
 }
@@ -62,7 +62,7 @@ mod tests;
 
 pub use self::account::{Account, AccountUpdate, PubKeyHash};
 pub use self::block::{ExecutedOperations, ExecutedPriorityOp, ExecutedTx};
-pub use self::fee::{BatchFee, Fee, OutputFeeType, TotalFee};
+pub use self::fee::{BatchFee, Fee, FeeQuote, OutputFeeType, TotalFee};
 pub use self::operations::{
     ChangePubKeyOp, DepositOp, ForcedExitOp, FullExitOp, MintNFTOp, SwapOp, TransferOp,
     TransferToNewOp, WithdrawNFTOp, WithdrawOp, ZkSyncOp,
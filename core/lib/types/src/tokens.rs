@@ -107,7 +107,7 @@ pub struct Token {
     pub is_nft: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum TokenKind {
     ERC20,
     NFT,
@@ -138,6 +138,63 @@ impl Token {
     }
 }
 
+/// Policy of accepting the token to pay fees in.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TokenFeePolicy {
+    /// Token is accepted if its market volume is high enough.
+    MarketVolume,
+    /// Token is in the allowlist and is accepted regardless of its market volume.
+    Allowed,
+    /// Token is not accepted to pay fees in.
+    Disallowed,
+}
+
+impl Default for TokenFeePolicy {
+    fn default() -> Self {
+        Self::MarketVolume
+    }
+}
+
+/// Version of the token metadata and listing settings, created by each change made by the admin.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TokenSettings {
+    pub token_id: TokenId,
+    /// Version of the settings, starting from 1 for the first change of the token.
+    pub version: u32,
+    pub symbol: String,
+    pub decimals: u8,
+    /// Human-readable name of the token (e.g. "Dai Stablecoin").
+    pub name: Option<String>,
+    /// Deprecated tokens are not accepted to pay fees in, regardless of the fee policy.
+    pub deprecated: bool,
+    pub fee_policy: TokenFeePolicy,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TokenSettings {
+    /// Returns whether the token can be used to pay fees, or `None` if it's decided by the market volume.
+    pub fn fee_acceptance(&self) -> Option<bool> {
+        if self.deprecated {
+            return Some(false);
+        }
+        match self.fee_policy {
+            TokenFeePolicy::MarketVolume => None,
+            TokenFeePolicy::Allowed => Some(true),
+            TokenFeePolicy::Disallowed => Some(false),
+        }
+    }
+}
+
+/// Change of the token metadata and listing settings. Fields set to `None` are left unchanged.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TokenUpdate {
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+    pub name: Option<String>,
+    pub deprecated: Option<bool>,
+    pub fee_policy: Option<TokenFeePolicy>,
+}
+
 /// ERC-20 standard token.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenInfo {
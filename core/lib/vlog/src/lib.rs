@@ -11,14 +11,24 @@
 //! Integration with sentry for catching errors and react on them immediately
 //! https://docs.sentry.io/platforms/rust/
 //!
+//! Spans can be exported to the OpenTelemetry collector, if its URL is set by the `MISC_OTLP_URL` env variable.
+//! Context of the current span can be passed to another service via `current_trace_headers` and
+//! restored there with `set_remote_parent`, so a single trace covers the request in all the services.
+//! Binaries should call `shutdown_exporter` before exiting, so the latest spans are exported.
+//!
 
 use std::{borrow::Cow, str::FromStr};
 
 pub use sentry;
 use sentry::{types::Dsn, ClientInitGuard};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 pub use tracing as __tracing;
-pub use tracing::{debug, info, log, trace};
+pub use tracing::{debug, debug_span, field, info, info_span, log, trace, Instrument, Span};
+
+pub use crate::otlp::{current_trace_headers, set_remote_parent, shutdown_exporter};
+
+mod otlp;
 
 #[macro_export]
 macro_rules! warn {
@@ -56,18 +66,20 @@ fn get_sentry_url() -> Option<Dsn> {
 /// Initialize logging with tracing and set up log format
 ///
 /// If the sentry URL is provided via an environment variable, this function will also initialize sentry.
+/// Same goes for the OpenTelemetry exporter, which requires the function to be called within the tokio runtime.
 /// Returns a sentry client guard. The full description can be found in the official documentation:
 /// https://docs.sentry.io/platforms/rust/#configure
 pub fn init() -> Option<ClientInitGuard> {
     let log_format = std::env::var("MISC_LOG_FORMAT").unwrap_or_else(|_| "plain".to_string());
+    let registry = tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::from_default_env())
+        .with(otlp::layer());
     match log_format.as_str() {
-        "plain" => tracing_subscriber::fmt::init(),
+        "plain" => registry.with(tracing_subscriber::fmt::layer()).init(),
         "json" => {
             let timer = tracing_subscriber::fmt::time::ChronoUtc::rfc3339();
-            tracing_subscriber::fmt::Subscriber::builder()
-                .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-                .with_timer(timer)
-                .json()
+            registry
+                .with(tracing_subscriber::fmt::layer().with_timer(timer).json())
                 .init();
         }
         _ => panic!("MISC_LOG_FORMAT has an unexpected value {}", log_format),
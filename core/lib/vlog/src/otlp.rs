@@ -0,0 +1,87 @@
+//! Export of the spans to the OpenTelemetry collector and propagation of the trace context between services.
+
+use std::collections::HashMap;
+
+use opentelemetry::{
+    global,
+    sdk::{propagation::TraceContextPropagator, trace, Resource},
+    KeyValue,
+};
+use tracing::{Span, Subscriber};
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::registry::LookupSpan;
+
+fn get_otlp_url() -> Option<String> {
+    std::env::var("MISC_OTLP_URL")
+        .ok()
+        .filter(|url| url.starts_with("http://") || url.starts_with("https://"))
+}
+
+/// Name of the service reported to the collector, which is the name of the running binary.
+fn service_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|path| {
+            path.file_stem()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "zksync".to_string())
+}
+
+/// Creates the layer exporting spans to the OpenTelemetry collector, if its URL is provided.
+pub(crate) fn layer<S>() -> Option<OpenTelemetryLayer<S, trace::Tracer>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let otlp_url = get_otlp_url()?;
+    // The batch exporter is run by the tokio runtime.
+    if tokio::runtime::Handle::try_current().is_err() {
+        eprintln!("OpenTelemetry exporter is disabled: logging is initialized outside of the tokio runtime");
+        return None;
+    }
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .with_endpoint(otlp_url)
+        .with_trace_config(
+            trace::config().with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                service_name(),
+            )])),
+        )
+        .with_tonic()
+        .install_batch(opentelemetry::runtime::Tokio)
+        .expect("failed to install OpenTelemetry exporter");
+
+    Some(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Returns the headers carrying the context of the current span, which should be attached
+/// to the request sent to another service.
+///
+/// Returns no headers if the OpenTelemetry exporter is disabled.
+pub fn current_trace_headers() -> HashMap<String, String> {
+    let mut headers = HashMap::new();
+    let context = Span::current().context();
+    global::get_text_map_propagator(|propagator| propagator.inject_context(&context, &mut headers));
+    headers
+}
+
+/// Makes the span a child of the remote span, which context is passed in the request headers.
+pub fn set_remote_parent<'a>(span: &Span, headers: impl IntoIterator<Item = (&'a str, &'a str)>) {
+    let headers: HashMap<String, String> = headers
+        .into_iter()
+        .map(|(name, value)| (name.to_lowercase(), value.to_string()))
+        .collect();
+    let context = global::get_text_map_propagator(|propagator| propagator.extract(&headers));
+    span.set_parent(context);
+}
+
+/// Exports the spans which weren't sent to the collector yet and stops the exporter.
+/// Should be called before the binary exits, otherwise the latest spans are lost.
+pub async fn shutdown_exporter() {
+    // Shutdown blocks until the pending spans are exported, so it's moved out of the async context.
+    tokio::task::spawn_blocking(global::shutdown_tracer_provider)
+        .await
+        .unwrap_or_default();
+}
@@ -26,6 +26,7 @@ impl AccountLifespan {
                     self.build_withdraw(command, Some(nonce)).await?
                 }
                 TxType::ChangePubKey => self.build_change_pubkey(command, Some(nonce)).await?,
+                TxType::MintNFT => self.build_mint_nft(command, Some(nonce)).await?,
                 _ => unreachable!("Other tx types are not suitable for batches"),
             };
 
@@ -1,5 +1,6 @@
 use std::{
     future::Future,
+    sync::Mutex,
     time::{Duration, Instant},
 };
 
@@ -7,20 +8,32 @@ use futures::{channel::mpsc::Sender, SinkExt};
 
 use zksync::{error::ClientError, operations::SyncTransactionHandle, RpcProvider, Wallet};
 use zksync_eth_signer::PrivateKeySigner;
-use zksync_types::{Token, H256};
+use zksync_types::{tx::PackedEthSignature, Order, Token, H256};
 
 use crate::{
     account_pool::{AddressPool, TestWallet},
     command::{Command, ExpectedOutcome, IncorrectnessModifier, TxCommand},
     config::LoadtestConfig,
-    constants::{COMMIT_TIMEOUT, POLLING_INTERVAL},
-    report::{Report, ReportBuilder, ReportLabel},
+    constants::{COMMIT_TIMEOUT, POLLING_INTERVAL, VERIFY_TIMEOUT},
+    pacer::Pacer,
+    report::{OperationLatency, Report, ReportBuilder, ReportLabel},
     rng::LoadtestRng,
+    scenario::Scenario,
 };
 
 mod batch_command_executor;
 mod tx_command_executor;
 
+/// Signed limit order of the swap counterparty.
+///
+/// Limit orders don't change the nonce of the account, so the same order can be used
+/// by all the test accounts as long as the counterparty doesn't send any transactions.
+#[derive(Debug, Clone)]
+pub struct SwapCounterparty {
+    pub order: Order,
+    pub eth_signature: Option<PackedEthSignature>,
+}
+
 /// Account lifespan represents a flow of a single account:
 /// it will send transactions and batches, both correct and incorrect, and will check
 /// whether outcome matches expected one.
@@ -37,20 +50,31 @@ pub struct AccountLifespan {
     /// Rng unique to the account.
     rng: LoadtestRng,
     config: LoadtestConfig,
+    /// Mix of the operations to be generated.
+    scenario: Scenario,
     /// Pool of account addresses, used to generate commands.
     addresses: AddressPool,
     /// ERC-20 token used in the test.
     main_token: Token,
     /// Channel for sending reports about performed operations.
     report_sink: Sender<Report>,
+    /// Rate limiter shared by all the accounts, present if the scenario has a load profile.
+    pacer: Option<Pacer>,
+    /// Counterparty for the swaps, present if swaps are enabled in the scenario.
+    swap_counterparty: Option<SwapCounterparty>,
+    /// Latencies of the operation being executed, filled once it's submitted.
+    latency: Mutex<OperationLatency>,
 }
 
 impl AccountLifespan {
     pub fn new(
         config: &LoadtestConfig,
+        scenario: &Scenario,
         addresses: AddressPool,
         test_account: TestWallet,
         report_sink: Sender<Report>,
+        pacer: Option<Pacer>,
+        swap_counterparty: Option<SwapCounterparty>,
     ) -> Self {
         let main_token = test_account
             .wallet
@@ -63,10 +87,14 @@ impl AccountLifespan {
             eth_pk: test_account.eth_pk,
             rng: test_account.rng,
             config: config.clone(),
+            scenario: scenario.clone(),
             addresses,
             main_token,
 
             report_sink,
+            pacer,
+            swap_counterparty,
+            latency: Mutex::default(),
         }
     }
 
@@ -96,9 +124,24 @@ impl AccountLifespan {
             }
         }
 
-        let command_sequence = self.generate_commands();
-        for command in command_sequence {
-            self.execute_command(command).await;
+        // We start with a CPK just to unlock accounts.
+        let change_pubkey = TxCommand::change_pubkey(self.wallet.address());
+        self.execute_command(Command::SingleTx(change_pubkey)).await;
+
+        match self.pacer.clone() {
+            Some(pacer) => {
+                // Operations are executed until the load profile is over.
+                while pacer.wait_for_slot().await {
+                    let command = self.random_command();
+                    self.execute_command(command).await;
+                }
+            }
+            None => {
+                for _ in 0..self.config.operations_per_account {
+                    let command = self.random_command();
+                    self.execute_command(command).await;
+                }
+            }
         }
     }
 
@@ -114,6 +157,7 @@ impl AccountLifespan {
         let mut attempt = 0;
         loop {
             let start = Instant::now();
+            self.set_latency(OperationLatency::default());
             let result = match &command {
                 Command::SingleTx(tx_command) => self.execute_tx_command(tx_command).await,
                 Command::Batch(tx_commands) => {
@@ -147,7 +191,9 @@ impl AccountLifespan {
             };
 
             // We won't continue the loop unless `continue` was manually called.
-            self.report(label, start.elapsed(), attempt, command).await;
+            let latency = *self.latency.lock().unwrap();
+            self.report(label, start.elapsed(), latency, attempt, command)
+                .await;
             break;
         }
     }
@@ -157,6 +203,7 @@ impl AccountLifespan {
         &mut self,
         label: ReportLabel,
         time: Duration,
+        latency: OperationLatency,
         retries: usize,
         command: Command,
     ) {
@@ -168,6 +215,7 @@ impl AccountLifespan {
             .label(label)
             .reporter(self.wallet.address())
             .time(time)
+            .latency(latency)
             .retries(retries)
             .action(command)
             .finish();
@@ -194,6 +242,7 @@ impl AccountLifespan {
     {
         let expected_outcome = modifier.expected_outcome();
 
+        let submitted_at = Instant::now();
         let send_result = send().await;
         let mut handle = match (expected_outcome, send_result) {
             (ExpectedOutcome::ApiRequestFailed, Ok(_handle)) => {
@@ -217,10 +266,25 @@ impl AccountLifespan {
         };
 
         handle.polling_interval(POLLING_INTERVAL).unwrap();
-        let transaction_receipt = handle
+        let handle = handle
             .commit_timeout(COMMIT_TIMEOUT)
-            .wait_for_commit()
-            .await?;
+            .verify_timeout(VERIFY_TIMEOUT);
+        let transaction_receipt = handle.wait_for_commit().await?;
+        let mut latency = OperationLatency {
+            commit: Some(submitted_at.elapsed()),
+            finalization: None,
+        };
+        self.set_latency(latency);
+
+        if self.config.track_finalization && transaction_receipt.fail_reason.is_none() {
+            // Timeout here must not lead to the retry of the already committed operation.
+            if let Err(err) = handle.wait_for_verify().await {
+                let error = format!("Tx/batch was committed, but not finalized: {}", err);
+                return Ok(ReportLabel::failed(&error));
+            }
+            latency.finalization = Some(submitted_at.elapsed());
+            self.set_latency(latency);
+        }
 
         match expected_outcome {
             ExpectedOutcome::TxSucceed if transaction_receipt.fail_reason.is_none() => {
@@ -242,18 +306,18 @@ impl AccountLifespan {
         }
     }
 
-    /// Prepares a list of random operations to be executed by an account.
-    fn generate_commands(&mut self) -> Vec<Command> {
-        // We start with a CPK just to unlock accounts.
-        let mut commands = vec![Command::SingleTx(TxCommand::change_pubkey(
-            self.wallet.address(),
-        ))];
-
-        for _ in 0..self.config.operations_per_account {
-            let command = Command::random(&mut self.rng, self.wallet.address(), &self.addresses);
-            commands.push(command)
-        }
+    /// Stores the latencies of the operation being executed, so they can be added to its report.
+    fn set_latency(&self, latency: OperationLatency) {
+        *self.latency.lock().unwrap() = latency;
+    }
 
-        commands
+    /// Generates a random operation to be executed by an account.
+    fn random_command(&mut self) -> Command {
+        Command::random(
+            &mut self.rng,
+            self.wallet.address(),
+            &self.addresses,
+            &self.scenario,
+        )
     }
 }
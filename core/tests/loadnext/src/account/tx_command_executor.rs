@@ -1,18 +1,24 @@
-use std::convert::TryInto;
+use std::{convert::TryInto, time::Instant};
 
 use num::{BigUint, Zero};
 use zksync::{
-    error::ClientError, ethereum::PriorityOpHolder, operations::SyncTransactionHandle,
+    error::ClientError,
+    ethereum::PriorityOpHolder,
+    operations::SyncTransactionHandle,
     provider::Provider,
+    types::NFT,
+    utils::{closest_packable_fee_amount, closest_packable_token_amount, private_key_from_seed},
+};
+use zksync_types::{
+    tokens::ETH_TOKEN_ID, tx::PackedEthSignature, Nonce, Order, Swap, TxFeeTypes, ZkSyncTx, H256,
 };
-use zksync_types::{tokens::ETH_TOKEN_ID, tx::PackedEthSignature, Nonce, ZkSyncTx, H256};
 
 use crate::{
     account::AccountLifespan,
     command::{IncorrectnessModifier, TxCommand, TxType},
-    constants::{COMMIT_TIMEOUT, POLLING_INTERVAL},
+    constants::{COMMIT_TIMEOUT, POLLING_INTERVAL, VERIFY_TIMEOUT},
     corrupted_tx::Corrupted,
-    report::ReportLabel,
+    report::{OperationLatency, ReportLabel},
 };
 
 impl AccountLifespan {
@@ -30,6 +36,10 @@ impl AccountLifespan {
             }
             TxType::Deposit => self.execute_deposit(&command).await,
             TxType::FullExit => self.execute_full_exit().await,
+            TxType::MintNFT => self.execute_mint_nft(&command).await,
+            TxType::TransferNFT => self.execute_transfer_nft(&command).await,
+            TxType::WithdrawNFT => self.execute_withdraw_nft(&command).await,
+            TxType::Swap => self.execute_swap(&command).await,
         }
     }
 
@@ -111,6 +121,7 @@ impl AccountLifespan {
             .try_into()
             .unwrap_or_else(|_| u128::max_value())
             .into();
+        let submitted_at = Instant::now();
         let eth_tx_hash = match ethereum
             .deposit(self.main_token.id, amount, self.wallet.address())
             .await
@@ -124,7 +135,7 @@ impl AccountLifespan {
             }
         };
 
-        self.handle_priority_op(eth_tx_hash).await
+        self.handle_priority_op(eth_tx_hash, submitted_at).await
     }
 
     async fn execute_full_exit(&self) -> Result<ReportLabel, ClientError> {
@@ -145,6 +156,7 @@ impl AccountLifespan {
         };
 
         let ethereum = self.wallet.ethereum(&self.config.web3_url).await?;
+        let submitted_at = Instant::now();
         let eth_tx_hash = match ethereum.full_exit(exit_token_id, account_id).await {
             Ok(hash) => hash,
             Err(_err) => {
@@ -154,10 +166,14 @@ impl AccountLifespan {
             }
         };
 
-        self.handle_priority_op(eth_tx_hash).await
+        self.handle_priority_op(eth_tx_hash, submitted_at).await
     }
 
-    async fn handle_priority_op(&self, eth_tx_hash: H256) -> Result<ReportLabel, ClientError> {
+    async fn handle_priority_op(
+        &self,
+        eth_tx_hash: H256,
+        submitted_at: Instant,
+    ) -> Result<ReportLabel, ClientError> {
         let ethereum = self.wallet.ethereum(&self.config.web3_url).await?;
         let receipt = ethereum.wait_for_tx(eth_tx_hash).await?;
 
@@ -175,10 +191,27 @@ impl AccountLifespan {
         priority_op_handle
             .polling_interval(POLLING_INTERVAL)
             .unwrap();
-        priority_op_handle
+        let priority_op_handle = priority_op_handle
             .commit_timeout(COMMIT_TIMEOUT)
-            .wait_for_commit()
-            .await?;
+            .verify_timeout(VERIFY_TIMEOUT);
+        priority_op_handle.wait_for_commit().await?;
+        let mut latency = OperationLatency {
+            commit: Some(submitted_at.elapsed()),
+            finalization: None,
+        };
+        self.set_latency(latency);
+
+        if self.config.track_finalization {
+            if let Err(err) = priority_op_handle.wait_for_verify().await {
+                let error = format!(
+                    "Priority operation was committed, but not finalized: {}",
+                    err
+                );
+                return Ok(ReportLabel::failed(&error));
+            }
+            latency.finalization = Some(submitted_at.elapsed());
+            self.set_latency(latency);
+        }
 
         Ok(ReportLabel::done())
     }
@@ -278,4 +311,183 @@ impl AccountLifespan {
 
         Ok(self.apply_modifier(tx, eth_signature, command.modifier))
     }
+
+    async fn execute_mint_nft(&self, command: &TxCommand) -> Result<ReportLabel, ClientError> {
+        let (tx, eth_signature) = self.build_mint_nft(command, None).await?;
+
+        let provider = self.wallet.provider.clone();
+        self.submit(command.modifier, || async {
+            let tx_hash = provider.send_tx(tx, eth_signature).await?;
+            Ok(SyncTransactionHandle::new(tx_hash, provider))
+        })
+        .await
+    }
+
+    pub(super) async fn build_mint_nft(
+        &self,
+        command: &TxCommand,
+        nonce: Option<Nonce>,
+    ) -> Result<(ZkSyncTx, Option<PackedEthSignature>), ClientError> {
+        let mut builder = self
+            .wallet
+            .start_mint_nft()
+            .recipient(command.to)
+            .content_hash(H256::random())
+            .fee_token(self.config.main_token.as_str())
+            .unwrap();
+        if let Some(nonce) = nonce {
+            builder = builder.nonce(nonce);
+        }
+
+        let (tx, eth_signature) = builder.tx().await.map_err(Self::tx_creation_error)?;
+
+        Ok(self.apply_modifier(tx, eth_signature, command.modifier))
+    }
+
+    /// Returns one of the NFTs owned by the account, if there are any.
+    async fn owned_nft(&self) -> Result<Option<NFT>, ClientError> {
+        let account_info = self.wallet.account_info().await?;
+        Ok(account_info
+            .committed
+            .nfts
+            .into_iter()
+            .map(|(_, nft)| nft)
+            .next())
+    }
+
+    async fn execute_transfer_nft(&self, command: &TxCommand) -> Result<ReportLabel, ClientError> {
+        let nft = match self.owned_nft().await? {
+            Some(nft) => nft,
+            None => return Ok(ReportLabel::skipped("No NFTs to transfer")),
+        };
+
+        // NFT transfer is a batch of the NFT transfer itself and the fee transfer.
+        let (nft_transfer, fee_transfer) = self
+            .wallet
+            .start_transfer_nft()
+            .nft(nft)
+            .to(command.to)
+            .fee_token(self.config.main_token.as_str())
+            .unwrap()
+            .tx()
+            .await
+            .map_err(Self::tx_creation_error)?;
+        let main_hash = nft_transfer.0.hash();
+
+        let provider = self.wallet.provider.clone();
+        self.submit(command.modifier, || async {
+            provider
+                .send_txs_batch(vec![nft_transfer, fee_transfer], None)
+                .await?;
+            Ok(SyncTransactionHandle::new(main_hash, provider))
+        })
+        .await
+    }
+
+    async fn execute_withdraw_nft(&self, command: &TxCommand) -> Result<ReportLabel, ClientError> {
+        let nft = match self.owned_nft().await? {
+            Some(nft) => nft,
+            None => return Ok(ReportLabel::skipped("No NFTs to withdraw")),
+        };
+
+        let (tx, eth_signature) = self
+            .wallet
+            .start_withdraw_nft()
+            .to(command.to)
+            .token(nft.id)
+            .unwrap()
+            .fee_token(self.config.main_token.as_str())
+            .unwrap()
+            .tx()
+            .await
+            .map_err(Self::tx_creation_error)?;
+        let (tx, eth_signature) = self.apply_modifier(tx, eth_signature, command.modifier);
+
+        let provider = self.wallet.provider.clone();
+        self.submit(command.modifier, || async {
+            let tx_hash = provider.send_tx(tx, eth_signature).await?;
+            Ok(SyncTransactionHandle::new(tx_hash, provider))
+        })
+        .await
+    }
+
+    /// Sells the main token for ETH to the swap counterparty.
+    async fn execute_swap(&self, command: &TxCommand) -> Result<ReportLabel, ClientError> {
+        let counterparty = match &self.swap_counterparty {
+            Some(counterparty) => counterparty.clone(),
+            None => return Ok(ReportLabel::skipped("Swap counterparty is not initialized")),
+        };
+        let account_id = match self.wallet.account_id() {
+            Some(id) => id,
+            None => {
+                return Ok(ReportLabel::skipped("L2 account was not initialized yet"));
+            }
+        };
+
+        let nonce = self.wallet.account_info().await?.committed.nonce;
+        let fee = self
+            .wallet
+            .provider
+            .get_tx_fee(TxFeeTypes::Swap, self.wallet.address(), self.main_token.id)
+            .await?
+            .total_fee;
+        let fee = closest_packable_fee_amount(&fee);
+        // Zero amount has a special meaning for orders, so we always sell something.
+        let amount = closest_packable_token_amount(&command.amount).max(BigUint::from(1u8));
+
+        // Counterparty sells ETH for the main token with the 1:1 ratio, our order is the opposite one.
+        let zksync_pk = private_key_from_seed(self.eth_pk.as_bytes())?;
+        let order = Order::new_signed(
+            account_id,
+            self.wallet.address(),
+            nonce,
+            self.main_token.id,
+            ETH_TOKEN_ID,
+            (1u8.into(), 1u8.into()),
+            amount.clone(),
+            Default::default(),
+            &zksync_pk,
+        )
+        .expect("Order should be correct");
+        let order_message = order.get_ethereum_sign_message(
+            &self.main_token.symbol,
+            "ETH",
+            self.main_token.decimals,
+        );
+        let order_signature = PackedEthSignature::sign(&self.eth_pk, order_message.as_bytes())
+            .expect("Signing the order unexpectedly failed");
+
+        let swap = Swap::new_signed(
+            account_id,
+            self.wallet.address(),
+            nonce,
+            (order, counterparty.order),
+            (amount.clone(), amount),
+            fee,
+            self.main_token.id,
+            &zksync_pk,
+        )
+        .expect("Swap should be correct");
+        let swap_message =
+            swap.get_ethereum_sign_message(&self.main_token.symbol, self.main_token.decimals);
+        let eth_signature = PackedEthSignature::sign(&self.eth_pk, swap_message.as_bytes())
+            .expect("Signing the swap unexpectedly failed");
+
+        let (tx, eth_signature) =
+            self.apply_modifier(swap.into(), Some(eth_signature), command.modifier);
+        let swap = match tx {
+            ZkSyncTx::Swap(swap) => *swap,
+            _ => unreachable!("Modifiers don't change the transaction type"),
+        };
+
+        let provider = self.wallet.provider.clone();
+        self.submit(command.modifier, || async {
+            let orders_signatures = (Some(order_signature), counterparty.eth_signature);
+            let tx_hash = provider
+                .send_swap(swap, eth_signature, orders_signatures)
+                .await?;
+            Ok(SyncTransactionHandle::new(tx_hash, provider))
+        })
+        .await
+    }
 }
@@ -77,6 +77,9 @@ pub struct AccountPool {
     pub accounts: VecDeque<TestWallet>,
     /// Pool of addresses of the test accounts.
     pub addresses: AddressPool,
+    /// Wallet that provides liquidity for the swaps made by the test accounts.
+    /// It's only initialized if swaps are enabled in the scenario.
+    pub swap_counterparty: TestWallet,
 }
 
 impl AccountPool {
@@ -119,34 +122,41 @@ impl AccountPool {
         let mut addresses = Vec::with_capacity(config.accounts_amount);
 
         for _ in 0..config.accounts_amount {
-            let eth_credentials = AccountCredentials::random(&mut rng);
-            let zksync_pk = private_key_from_seed(eth_credentials.eth_pk.as_bytes())
-                .expect("Can't generate the zkSync private key");
-            let wallet_credentials = WalletCredentials::<PrivateKeySigner>::from_pk(
-                eth_credentials.address,
-                zksync_pk,
-                Some(eth_credentials.eth_pk),
-            );
-
-            let wallet = Wallet::new(provider.clone(), wallet_credentials)
-                .await
-                .expect("Can't create a wallet");
-
-            addresses.push(wallet.address());
-            let account = TestWallet {
-                wallet,
-                eth_pk: eth_credentials.eth_pk,
-                rng: rng.derive(eth_credentials.eth_pk),
-            };
+            let account = Self::create_test_wallet(&provider, &mut rng).await;
+            addresses.push(account.wallet.address());
             accounts.push_back(account);
         }
+        // Counterparty is not included into the address pool, since it's not an active participant of the test.
+        let swap_counterparty = Self::create_test_wallet(&provider, &mut rng).await;
 
         Ok(Self {
             master_wallet,
             accounts,
             addresses: AddressPool::new(addresses),
+            swap_counterparty,
         })
     }
+
+    async fn create_test_wallet(provider: &RpcProvider, rng: &mut LoadtestRng) -> TestWallet {
+        let eth_credentials = AccountCredentials::random(rng);
+        let zksync_pk = private_key_from_seed(eth_credentials.eth_pk.as_bytes())
+            .expect("Can't generate the zkSync private key");
+        let wallet_credentials = WalletCredentials::<PrivateKeySigner>::from_pk(
+            eth_credentials.address,
+            zksync_pk,
+            Some(eth_credentials.eth_pk),
+        );
+
+        let wallet = Wallet::new(provider.clone(), wallet_credentials)
+            .await
+            .expect("Can't create a wallet");
+
+        TestWallet {
+            wallet,
+            eth_pk: eth_credentials.eth_pk,
+            rng: rng.derive(eth_credentials.eth_pk),
+        }
+    }
 }
 
 fn pk_to_address(eth_pk: &H256) -> Address {
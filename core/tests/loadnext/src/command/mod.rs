@@ -1,12 +1,11 @@
 use rand::Rng;
-use static_assertions::const_assert;
 
 use zksync_types::Address;
 
 use crate::{
     account_pool::AddressPool,
-    constants::MAX_BATCH_SIZE,
-    rng::{LoadtestRng, Random},
+    rng::{choose_weighted, LoadtestRng},
+    scenario::Scenario,
 };
 
 pub use self::{
@@ -32,45 +31,34 @@ pub enum Command {
 enum CommandType {
     SingleTx,
     Batch,
-    ApiRequest,
 }
 
-impl Random for CommandType {
-    fn random(rng: &mut LoadtestRng) -> Self {
-        // Chances of a certain event generation.
-        // You must maintain the sum of these constants to be equal to 1.0f32.
-        const SINGLE_TX_CHANCE: f32 = 0.7;
-        const BATCH_CHANCE: f32 = 0.3;
+impl CommandType {
+    fn random(rng: &mut LoadtestRng, scenario: &Scenario) -> Self {
         // We don't generate API requests at the moment.
-        const _API_REQUEST_CHANCE: f32 = 0.0;
-
-        const _CHANCES_SUM: f32 = SINGLE_TX_CHANCE + BATCH_CHANCE + _API_REQUEST_CHANCE;
-        // Unfortunately. f64::abs()` is not yet a `const` function.
-        const_assert!(
-            -f32::EPSILON <= (_CHANCES_SUM - 1.0f32) && (_CHANCES_SUM - 1.0f32) <= f32::EPSILON
-        );
-        let chance = rng.gen_range(0.0f32..1.0f32);
-
-        if chance <= SINGLE_TX_CHANCE {
-            Self::SingleTx
-        } else if chance <= (SINGLE_TX_CHANCE + BATCH_CHANCE) {
-            Self::Batch
-        } else {
-            Self::ApiRequest
-        }
+        let weighted = [
+            (Self::SingleTx, scenario.commands.single_tx),
+            (Self::Batch, scenario.commands.batch),
+        ];
+        choose_weighted(rng, &weighted)
     }
 }
 
 impl Command {
-    pub fn random(rng: &mut LoadtestRng, own_address: Address, addresses: &AddressPool) -> Self {
-        match CommandType::random(rng) {
-            CommandType::SingleTx => Self::SingleTx(TxCommand::random(rng, own_address, addresses)),
+    pub fn random(
+        rng: &mut LoadtestRng,
+        own_address: Address,
+        addresses: &AddressPool,
+        scenario: &Scenario,
+    ) -> Self {
+        match CommandType::random(rng, scenario) {
+            CommandType::SingleTx => {
+                Self::SingleTx(TxCommand::random(rng, own_address, addresses, scenario))
+            }
             CommandType::Batch => {
-                // TODO: For some reason, batches of size 1 are being rejected because of nonce mistmatch.
-                // It may be either bug in loadtest or server code, thus it should be investigated.
-                let batch_size = rng.gen_range(2..=MAX_BATCH_SIZE);
+                let batch_size = rng.gen_range(scenario.batch_size.min..=scenario.batch_size.max);
                 let mut batch_command: Vec<_> = (0..batch_size)
-                    .map(|_| TxCommand::random_batchable(rng, own_address, addresses))
+                    .map(|_| TxCommand::random_batchable(rng, own_address, addresses, scenario))
                     .collect();
 
                 if batch_command
@@ -88,9 +76,6 @@ impl Command {
 
                 Self::Batch(batch_command)
             }
-            CommandType::ApiRequest => {
-                unreachable!("We don't generate API commands currently")
-            }
         }
     }
 }
@@ -1,12 +1,13 @@
 use num::BigUint;
-use rand::Rng;
+use rand::{seq::SliceRandom, Rng};
 
 use zksync_types::Address;
 
 use crate::{
     account_pool::AddressPool,
-    all::{All, AllWeighted},
-    rng::{LoadtestRng, WeightedRandom},
+    all::All,
+    rng::{choose_weighted, LoadtestRng},
+    scenario::{Scenario, TxWeights},
 };
 
 /// Type of transaction. It doesn't copy the zkSync operation list, because
@@ -20,6 +21,10 @@ pub enum TxType {
     WithdrawToOther,
     FullExit,
     ChangePubKey,
+    MintNFT,
+    TransferNFT,
+    WithdrawNFT,
+    Swap,
 }
 
 impl All for TxType {
@@ -32,52 +37,46 @@ impl All for TxType {
             Self::WithdrawToOther,
             Self::FullExit,
             Self::ChangePubKey,
+            Self::MintNFT,
+            Self::TransferNFT,
+            Self::WithdrawNFT,
+            Self::Swap,
         ]
     }
 }
 
-impl AllWeighted for TxType {
-    fn all_weighted() -> &'static [(Self, f32)] {
-        // All available options together with their weight.
-        // `TransferToNew` and `TransferToExisting` the most likely options.
-        const DEFAULT_WEIGHT: f32 = 1.0;
-        const HIGH_WEIGHT: f32 = 3.0;
-        &[
-            (Self::Deposit, DEFAULT_WEIGHT),
-            (Self::TransferToNew, HIGH_WEIGHT),
-            (Self::TransferToExisting, HIGH_WEIGHT),
-            (Self::WithdrawToSelf, DEFAULT_WEIGHT),
-            (Self::WithdrawToOther, DEFAULT_WEIGHT),
-            (Self::FullExit, DEFAULT_WEIGHT),
-            (Self::ChangePubKey, DEFAULT_WEIGHT),
-        ]
+impl TxType {
+    /// Generates a random transaction type according to the scenario weights.
+    pub fn random(rng: &mut LoadtestRng, weights: &TxWeights) -> Self {
+        choose_weighted(rng, &weights.weighted())
     }
-}
 
-impl TxType {
     /// Generates a random transaction type that can be a part of the batch.
-    pub fn random_batchable(rng: &mut LoadtestRng) -> Self {
-        loop {
-            let output = Self::random(rng);
-
-            // Priority ops cannot be inserted into the batch.
-            if output.is_batchable() {
-                return output;
-            }
-        }
+    pub fn random_batchable(rng: &mut LoadtestRng, weights: &TxWeights) -> Self {
+        choose_weighted(rng, &weights.batchable())
     }
 
     /// Checks whether `TxType` can be used as a part of the batch.
-    fn is_batchable(self) -> bool {
-        !matches!(self, Self::Deposit | Self::FullExit)
+    pub fn is_batchable(self) -> bool {
+        // Priority ops cannot be inserted into the batch.
+        // NFT transfer is a batch on its own, and swaps require signatures of the orders,
+        // which are not supported in batches by the SDK.
+        !matches!(
+            self,
+            Self::Deposit | Self::FullExit | Self::TransferNFT | Self::WithdrawNFT | Self::Swap
+        )
     }
 
     fn is_withdrawal(self) -> bool {
         matches!(self, Self::WithdrawToOther | Self::WithdrawToSelf)
     }
 
-    fn is_change_pubkey(self) -> bool {
-        matches!(self, Self::ChangePubKey)
+    /// Checks whether transaction doesn't have an amount field.
+    fn has_no_amount(self) -> bool {
+        matches!(
+            self,
+            Self::ChangePubKey | Self::MintNFT | Self::TransferNFT | Self::WithdrawNFT
+        )
     }
 
     fn is_priority(self) -> bool {
@@ -85,7 +84,10 @@ impl TxType {
     }
 
     fn is_target_self(self) -> bool {
-        matches!(self, Self::WithdrawToSelf | Self::FullExit)
+        matches!(
+            self,
+            Self::WithdrawToSelf | Self::FullExit | Self::MintNFT | Self::WithdrawNFT
+        )
     }
 }
 
@@ -129,24 +131,16 @@ impl All for IncorrectnessModifier {
     }
 }
 
-impl AllWeighted for IncorrectnessModifier {
-    fn all_weighted() -> &'static [(Self, f32)] {
-        const VARIANT_AMOUNTS: f32 = IncorrectnessModifier::const_all().len() as f32;
-        // No modifier is 9 times probable than all the other variants in sum.
-        // In other words, 90% probability of no modifier.
-        const NONE_PROBABILITY: f32 = (VARIANT_AMOUNTS - 1.0) * 9.0;
-        const DEFAULT_PROBABILITY: f32 = 1.0f32;
+impl IncorrectnessModifier {
+    /// Generates a random modifier: with the probability of `corrupted_ratio` one of the
+    /// incorrectness modifiers is chosen uniformly, otherwise transaction is left intact.
+    pub fn random(rng: &mut LoadtestRng, corrupted_ratio: f32) -> Self {
+        if rng.gen_range(0.0f32..1.0f32) >= corrupted_ratio {
+            return Self::None;
+        }
 
-        &[
-            (Self::ZeroFee, DEFAULT_PROBABILITY),
-            (Self::IncorrectZkSyncSignature, DEFAULT_PROBABILITY),
-            (Self::IncorrectEthSignature, DEFAULT_PROBABILITY),
-            (Self::NonExistentToken, DEFAULT_PROBABILITY),
-            (Self::TooBigAmount, DEFAULT_PROBABILITY),
-            (Self::NotPackableAmount, DEFAULT_PROBABILITY),
-            (Self::NotPackableFeeAmount, DEFAULT_PROBABILITY),
-            (Self::None, NONE_PROBABILITY),
-        ]
+        let corrupting_modifiers = &Self::const_all()[..Self::const_all().len() - 1];
+        *corrupting_modifiers.choose(rng).unwrap()
     }
 }
 
@@ -215,10 +209,15 @@ impl TxCommand {
     }
 
     /// Generates a fully random transaction command.
-    pub fn random(rng: &mut LoadtestRng, own_address: Address, addresses: &AddressPool) -> Self {
-        let command_type = TxType::random(rng);
+    pub fn random(
+        rng: &mut LoadtestRng,
+        own_address: Address,
+        addresses: &AddressPool,
+        scenario: &Scenario,
+    ) -> Self {
+        let command_type = TxType::random(rng, &scenario.txs);
 
-        Self::new_with_type(rng, own_address, addresses, command_type)
+        Self::new_with_type(rng, own_address, addresses, scenario, command_type)
     }
 
     /// Generates a random transaction command that can be a part of the batch.
@@ -226,21 +225,23 @@ impl TxCommand {
         rng: &mut LoadtestRng,
         own_address: Address,
         addresses: &AddressPool,
+        scenario: &Scenario,
     ) -> Self {
-        let command_type = TxType::random_batchable(rng);
+        let command_type = TxType::random_batchable(rng, &scenario.txs);
 
-        Self::new_with_type(rng, own_address, addresses, command_type)
+        Self::new_with_type(rng, own_address, addresses, scenario, command_type)
     }
 
     fn new_with_type(
         rng: &mut LoadtestRng,
         own_address: Address,
         addresses: &AddressPool,
+        scenario: &Scenario,
         command_type: TxType,
     ) -> Self {
         let mut command = Self {
             command_type,
-            modifier: IncorrectnessModifier::random(rng),
+            modifier: IncorrectnessModifier::random(rng, scenario.corrupted_txs.ratio),
             to: addresses.random_address(rng),
             amount: Self::random_amount(rng),
         };
@@ -257,15 +258,17 @@ impl TxCommand {
 
         // Transactions that have no amount field.
         let no_amount_field =
-            command.command_type.is_change_pubkey() && command.modifier.affects_amount();
+            command.command_type.has_no_amount() && command.modifier.affects_amount();
         // It doesn't make sense to fail contract-based functions.
         let incorrect_priority_op = command.command_type.is_priority();
         // Amount doesn't have to be packable for withdrawals.
         let unpackable_withdrawal =
             command.command_type.is_withdrawal() && command.modifier.is_not_packable_amount();
+        // NFT transfer consists of two transactions, so it's sent without modifications.
+        let nft_transfer = matches!(command.command_type, TxType::TransferNFT);
 
         // Check whether generator modifier does not make sense.
-        if no_amount_field || incorrect_priority_op || unpackable_withdrawal {
+        if no_amount_field || incorrect_priority_op || unpackable_withdrawal || nft_transfer {
             command.modifier = IncorrectnessModifier::None;
         }
 
@@ -3,8 +3,9 @@ use serde::Deserialize;
 /// Configuration for the loadtest.
 ///
 /// This structure is meant to provide the least possible amount of parameters:
-/// By the ideology of the test, it is OK for it to be opinionated. Thus we don't provide
-/// kinds of operations we want to perform, do not configure fail or pass criteria.
+/// By the ideology of the test, it is OK for it to be opinionated. Thus the only configurable
+/// pass criteria is the optional comparison with the baseline report, and kinds of operations
+/// we want to perform are only configurable via the optional scenario file.
 ///
 /// It is expected that the user will provide the basic settings, and the loadtest will
 /// take care of everything else.
@@ -43,12 +44,40 @@ pub struct LoadtestConfig {
     /// but you can re-use seed from previous run to reproduce the sequence of operations locally.
     /// Seed must be represented as a hexadecimal string.
     pub seed: Option<String>,
+
+    /// Optional path to the scenario file, which describes the mix of generated operations and the load profile.
+    /// If it's not set, the default scenario is used. See the `Scenario` structure for details.
+    pub scenario_path: Option<String>,
+
+    /// Whether to wait for the finalization of each operation after its commitment.
+    /// Finalization latencies are only collected if this option is set. Note that it makes
+    /// each account wait for the proof of every block it has operations in, and thus
+    /// dramatically reduces the load.
+    #[serde(default)]
+    pub track_finalization: bool,
+
+    /// Optional path to save the final report to. The format is determined by the file
+    /// extension: either `.json` or `.csv`.
+    pub report_path: Option<String>,
+
+    /// Optional path to the JSON report of the previous run to compare the results with.
+    /// If any latency percentile exceeds its baseline value by more than `regression_threshold`
+    /// percent, the test is considered failed.
+    pub baseline_path: Option<String>,
+
+    /// Allowed growth of the latencies in percents, compared to the baseline report.
+    #[serde(default = "LoadtestConfig::default_regression_threshold")]
+    pub regression_threshold: f64,
 }
 
 impl LoadtestConfig {
     pub fn from_env() -> envy::Result<Self> {
         envy::from_env()
     }
+
+    fn default_regression_threshold() -> f64 {
+        20.0
+    }
 }
 
 impl Default for LoadtestConfig {
@@ -66,6 +95,11 @@ impl Default for LoadtestConfig {
             operations_per_account: 40,
             main_token: "DAI".into(),
             seed: None,
+            scenario_path: None,
+            track_finalization: false,
+            report_path: None,
+            baseline_path: None,
+            regression_threshold: Self::default_regression_threshold(),
         }
     }
 }
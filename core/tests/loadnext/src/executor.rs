@@ -3,15 +3,22 @@ use futures::{channel::mpsc, future::join_all};
 use tokio::task::JoinHandle;
 use zksync::{
     error::ClientError, ethereum::PriorityOpHolder, operations::SyncTransactionHandle,
-    provider::Provider, types::TransactionInfo,
+    provider::Provider, types::TransactionInfo, utils::private_key_from_seed,
+};
+use zksync_types::{
+    tokens::ETH_TOKEN_ID, tx::PackedEthSignature, tx::TxHash, Order, TransactionReceipt,
+    TxFeeTypes, U256,
 };
-use zksync_types::{tx::TxHash, TransactionReceipt, TxFeeTypes, U256};
 
+use crate::constants::*;
 use crate::{
-    account::AccountLifespan, account_pool::AccountPool, config::LoadtestConfig,
-    report_collector::LoadtestResult,
+    account::{AccountLifespan, SwapCounterparty},
+    account_pool::AccountPool,
+    config::LoadtestConfig,
+    pacer::Pacer,
+    report_collector::{LoadtestResult, LoadtestSummary, ReportCollector},
+    scenario::Scenario,
 };
-use crate::{constants::*, report_collector::ReportCollector};
 
 /// Executor is the entity capable of running the loadtest flow.
 ///
@@ -19,6 +26,7 @@ use crate::{constants::*, report_collector::ReportCollector};
 ///
 /// - Minting the tokens on L1 for the main account.
 /// - Depositing tokens to the main account in L2 and unlocking it.
+/// - Preparing the swap counterparty, if swaps are enabled in the scenario.
 /// - Spawning the report collector.
 /// - Distributing the funds among the test wallets.
 /// - Spawning account lifespan futures.
@@ -27,15 +35,35 @@ use crate::{constants::*, report_collector::ReportCollector};
 #[derive(Debug)]
 pub struct Executor {
     config: LoadtestConfig,
+    scenario: Scenario,
     pool: AccountPool,
+    swap_counterparty: Option<SwapCounterparty>,
+    /// Summary of the previous run to compare the results with.
+    baseline: Option<LoadtestSummary>,
 }
 
 impl Executor {
     /// Creates a new Executor entity.
     pub async fn new(config: LoadtestConfig) -> anyhow::Result<Self> {
+        let scenario = match &config.scenario_path {
+            Some(path) => Scenario::load(path)?,
+            None => Scenario::default(),
+        };
+        vlog::info!("Using scenario: {:?}", scenario);
+        let baseline = config
+            .baseline_path
+            .as_ref()
+            .map(LoadtestSummary::load)
+            .transpose()?;
         let pool = AccountPool::new(&config).await?;
 
-        Ok(Self { config, pool })
+        Ok(Self {
+            config,
+            scenario,
+            pool,
+            swap_counterparty: None,
+            baseline,
+        })
     }
 
     /// Runs the loadtest until the completion.
@@ -54,6 +82,7 @@ impl Executor {
         self.mint().await?;
         self.deposit_to_master().await?;
         self.set_signing_key().await?;
+        self.prepare_swap_counterparty().await?;
         let (executor_future, account_futures) = self.send_initial_transfers().await?;
         self.wait_account_routines(account_futures).await;
 
@@ -204,6 +233,101 @@ impl Executor {
         Ok(())
     }
 
+    /// Initializes the account that provides ETH for the swaps and signs its limit order.
+    async fn prepare_swap_counterparty(&mut self) -> anyhow::Result<()> {
+        // Amount of ETH (in wei) to be deposited to the swap counterparty, it's enough to cover
+        // many swaps, since each of them buys less than 2^18 wei.
+        const SWAP_LIQUIDITY: u64 = 10u64.pow(17);
+
+        if self.scenario.txs.swap == 0.0 {
+            return Ok(());
+        }
+
+        vlog::info!("Swap Counterparty: Performing an ETH deposit");
+        let counterparty_address = self.pool.swap_counterparty.wallet.address();
+        let mut ethereum = self
+            .pool
+            .master_wallet
+            .ethereum(&self.config.web3_url)
+            .await?;
+        ethereum.set_confirmation_timeout(ETH_CONFIRMATION_TIMEOUT);
+
+        let deposit_tx_hash = ethereum
+            .deposit("ETH", U256::from(SWAP_LIQUIDITY), counterparty_address)
+            .await?;
+        let receipt = ethereum.wait_for_tx(deposit_tx_hash).await?;
+        self.assert_eth_tx_success(&receipt).await;
+        let mut priority_op_handle = receipt
+            .priority_op_handle(self.pool.master_wallet.provider.clone())
+            .unwrap_or_else(|| {
+                panic!(
+                    "Can't get the handle for the deposit operation: {:?}",
+                    receipt
+                );
+            });
+        priority_op_handle
+            .polling_interval(POLLING_INTERVAL)
+            .unwrap();
+        priority_op_handle
+            .commit_timeout(COMMIT_TIMEOUT)
+            .wait_for_commit()
+            .await?;
+
+        vlog::info!("Swap Counterparty: Setting the signing key");
+        let counterparty = &mut self.pool.swap_counterparty;
+        counterparty.wallet.update_account_id().await?;
+        let mut handle = counterparty
+            .wallet
+            .start_change_pubkey()
+            .fee_token("ETH")?
+            .send()
+            .await?;
+        handle.polling_interval(POLLING_INTERVAL).unwrap();
+        let result = handle
+            .commit_timeout(COMMIT_TIMEOUT)
+            .wait_for_commit()
+            .await?;
+        assert!(
+            result.fail_reason.is_none(),
+            "Unable to set signing key on the swap counterparty"
+        );
+
+        let account_id = counterparty
+            .wallet
+            .account_id()
+            .expect("Account ID for swap counterparty was not set");
+        let nonce = counterparty.wallet.account_info().await?.committed.nonce;
+        let main_token = counterparty
+            .wallet
+            .tokens
+            .resolve(self.config.main_token.as_str().into())
+            .expect("Main token is not supported");
+        let zksync_pk = private_key_from_seed(counterparty.eth_pk.as_bytes())?;
+
+        // Limit order (with zero amount) can be filled many times without changing the account nonce.
+        let order = Order::new_signed(
+            account_id,
+            counterparty_address,
+            nonce,
+            ETH_TOKEN_ID,
+            main_token.id,
+            (1u8.into(), 1u8.into()),
+            0u8.into(),
+            Default::default(),
+            &zksync_pk,
+        )?;
+        let message = order.get_ethereum_sign_message("ETH", &main_token.symbol, 18);
+        let eth_signature = PackedEthSignature::sign(&counterparty.eth_pk, message.as_bytes())?;
+
+        self.swap_counterparty = Some(SwapCounterparty {
+            order,
+            eth_signature: Some(eth_signature),
+        });
+
+        vlog::info!("Swap Counterparty: Limit order is signed");
+        Ok(())
+    }
+
     async fn send_initial_transfers_batch(
         &self,
         accounts_to_process: usize,
@@ -328,12 +452,22 @@ impl Executor {
         // Prepare channels for the report collector.
         let (report_sender, report_receiver) = mpsc::channel(256);
 
-        let report_collector = ReportCollector::new(report_receiver);
+        let mut report_collector = ReportCollector::new(report_receiver);
+        if let Some(path) = &self.config.report_path {
+            report_collector = report_collector.summary_path(path);
+        }
+        if let Some(baseline) = self.baseline.take() {
+            report_collector =
+                report_collector.baseline(baseline, self.config.regression_threshold);
+        }
         let report_collector_future = tokio::spawn(report_collector.run());
 
         let config = &self.config;
+        let scenario = &self.scenario;
         let accounts_amount = config.accounts_amount;
         let addresses = self.pool.addresses.clone();
+        let pacer = scenario.load.clone().map(Pacer::new);
+        let swap_counterparty = self.swap_counterparty.clone();
 
         let mut retry_counter = 0;
         let mut accounts_processed = 0;
@@ -395,9 +529,12 @@ impl Executor {
                     .map(|wallet| {
                         let account = AccountLifespan::new(
                             config,
+                            scenario,
                             addresses.clone(),
                             wallet,
                             report_sender.clone(),
+                            pacer.clone(),
+                            swap_counterparty.clone(),
                         );
                         tokio::spawn(account.run())
                     });
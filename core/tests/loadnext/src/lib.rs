@@ -6,6 +6,8 @@ pub mod config;
 pub mod constants;
 pub mod corrupted_tx;
 pub mod executor;
+pub mod pacer;
 pub mod report;
 pub mod report_collector;
 pub mod rng;
+pub mod scenario;
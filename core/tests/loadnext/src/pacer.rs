@@ -0,0 +1,80 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+use crate::scenario::LoadProfile;
+
+/// Shared rate limiter that distributes operation slots among the accounts according to the load profile.
+///
+/// Each account asks the pacer for the next slot before executing an operation. Slots are spaced
+/// according to the target TPS at the corresponding moment, so that the accounts together don't exceed it.
+#[derive(Debug, Clone)]
+pub struct Pacer {
+    profile: LoadProfile,
+    started_at: Instant,
+    /// Time of the next free slot, relative to `started_at`.
+    next_slot: Arc<Mutex<Duration>>,
+}
+
+impl Pacer {
+    pub fn new(profile: LoadProfile) -> Self {
+        Self {
+            profile,
+            started_at: Instant::now(),
+            next_slot: Arc::new(Mutex::new(Duration::from_secs(0))),
+        }
+    }
+
+    /// Waits for the next slot. Returns `false` if the load profile is over and no operations
+    /// should be executed anymore.
+    pub async fn wait_for_slot(&self) -> bool {
+        match self.reserve_slot(self.started_at.elapsed()) {
+            Some(slot) => {
+                tokio::time::sleep_until(self.started_at + slot).await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn reserve_slot(&self, now: Duration) -> Option<Duration> {
+        // At the very beginning of the ramp-up phase target TPS is close to zero,
+        // so we don't let it go below the small share of the target to not stall the test.
+        let min_tps = self.profile.target_tps / 100.0;
+
+        let mut next_slot = self.next_slot.lock().unwrap();
+        let slot = std::cmp::max(*next_slot, now);
+        let tps = self.profile.target_tps_at(slot)?.max(min_tps);
+        *next_slot = slot + Duration::from_secs_f32(1.0 / tps);
+
+        Some(slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slots_follow_profile() {
+        let pacer = Pacer::new(LoadProfile {
+            target_tps: 8.0,
+            ramp_up_secs: 0,
+            steady_secs: 1,
+            ramp_down_secs: 0,
+        });
+
+        // Slots are reserved in advance with the interval corresponding to the target TPS.
+        let slots: Vec<_> = std::iter::from_fn(|| pacer.reserve_slot(Duration::from_secs(0)))
+            .take(20)
+            .collect();
+        assert_eq!(slots.len(), 8);
+        assert_eq!(slots[1] - slots[0], Duration::from_millis(125));
+
+        // Once the profile is over, no slots are provided.
+        assert_eq!(pacer.reserve_slot(Duration::from_secs(2)), None);
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{fmt, time::Duration};
 
 use zksync_types::Address;
 
@@ -27,6 +27,19 @@ pub struct Report {
     pub retries: usize,
     /// Duration of the latest execution attempt.
     pub time: Duration,
+    /// Latencies of the operation measured since its submission.
+    pub latency: OperationLatency,
+}
+
+/// Latencies of the operation measured since it was submitted to the server (or to the Ethereum for
+/// the priority operations). Latencies are only known if the operation was submitted and reached the
+/// corresponding state, and their precision is limited by the polling interval.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OperationLatency {
+    /// Time until the block with the operation was committed.
+    pub commit: Option<Duration>,
+    /// Time until the block with the operation was finalized (verified).
+    pub finalization: Option<Duration>,
 }
 
 /// Builder structure for `Report`.
@@ -50,6 +63,7 @@ impl ReportBuilder {
                 action: ActionType::Tx(TxActionType::Transfer),
                 retries: 0,
                 time: Default::default(),
+                latency: Default::default(),
             },
         }
     }
@@ -79,6 +93,11 @@ impl ReportBuilder {
         self
     }
 
+    pub fn latency(mut self, latency: OperationLatency) -> Self {
+        self.report.latency = latency;
+        self
+    }
+
     pub fn finish(self) -> Report {
         self.report
     }
@@ -119,6 +138,10 @@ pub enum TxActionType {
     ChangePubKey,
     FullExit,
     Deposit,
+    MintNFT,
+    TransferNFT,
+    WithdrawNFT,
+    Swap,
 }
 
 impl All for TxActionType {
@@ -130,6 +153,10 @@ impl All for TxActionType {
             TxActionType::ChangePubKey,
             TxActionType::FullExit,
             TxActionType::Deposit,
+            TxActionType::MintNFT,
+            TxActionType::TransferNFT,
+            TxActionType::WithdrawNFT,
+            TxActionType::Swap,
         ];
 
         ALL
@@ -144,6 +171,10 @@ impl From<TxType> for TxActionType {
             TxType::WithdrawToSelf | TxType::WithdrawToOther => Self::Withdraw,
             TxType::FullExit => Self::FullExit,
             TxType::ChangePubKey => Self::ChangePubKey,
+            TxType::MintNFT => Self::MintNFT,
+            TxType::TransferNFT => Self::TransferNFT,
+            TxType::WithdrawNFT => Self::WithdrawNFT,
+            TxType::Swap => Self::Swap,
         }
     }
 }
@@ -200,6 +231,16 @@ impl From<Command> for ActionType {
     }
 }
 
+impl fmt::Display for ActionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tx(action) => write!(f, "{:?}", action),
+            Self::Api(action) => write!(f, "{:?}", action),
+            Self::Batch { batch_size } => write!(f, "Batch({})", batch_size),
+        }
+    }
+}
+
 impl ActionType {
     /// Returns the vector containing the list of all the supported actions.
     /// May be useful in different collectors to initialize their internal states.
@@ -3,7 +3,10 @@ use std::{
     time::Duration,
 };
 
-use crate::report::ActionType;
+use crate::{
+    report::{ActionType, OperationLatency},
+    report_collector::summary::{ActionSummary, LatencyStats},
+};
 
 #[derive(Debug, Clone)]
 pub struct TimeHistogram {
@@ -13,6 +16,8 @@ pub struct TimeHistogram {
     histogram: BTreeMap<u64, usize>,
     /// Total entries in the histogram.
     total: usize,
+    /// The greatest added entry.
+    max: Duration,
 }
 
 impl Default for TimeHistogram {
@@ -27,10 +32,17 @@ impl TimeHistogram {
         let sub_sec_ranges = (0..10).map(|window_idx| Self::window(window_idx, 100));
         // Ranges from 1 second to 20 seconds with windows of 1 second.
         let sec_ranges = (1..20).map(|window_idx| Self::window(window_idx, 1000));
-        // Range for (20 sec; MAX).
-        let rest_range = std::iter::once((20_000u64, u64::max_value()));
-
-        let ranges: Vec<_> = sub_sec_ranges.chain(sec_ranges).chain(rest_range).collect();
+        // Ranges from 20 seconds to 10 minutes with windows of 10 seconds.
+        // Commitment and especially finalization of the operations may take that long.
+        let long_ranges = (2..60).map(|window_idx| Self::window(window_idx, 10_000));
+        // Range for (10 min; MAX).
+        let rest_range = std::iter::once((600_000u64, u64::max_value()));
+
+        let ranges: Vec<_> = sub_sec_ranges
+            .chain(sec_ranges)
+            .chain(long_ranges)
+            .chain(rest_range)
+            .collect();
         let mut histogram = BTreeMap::new();
 
         for &(start, _) in ranges.iter() {
@@ -41,6 +53,7 @@ impl TimeHistogram {
             ranges,
             histogram,
             total: 0,
+            max: Duration::default(),
         }
     }
 
@@ -49,6 +62,7 @@ impl TimeHistogram {
 
         self.histogram.entry(range).and_modify(|count| *count += 1);
         self.total += 1;
+        self.max = std::cmp::max(self.max, time);
     }
 
     pub fn is_empty(&self) -> bool {
@@ -77,6 +91,29 @@ impl TimeHistogram {
         unreachable!("Range for {} percentile was not found", percentile);
     }
 
+    /// Returns the upper bound of the time range for the requested distribution percentile,
+    /// i.e. the time in which the `percentile` of the entries fit. The bound is limited by
+    /// the greatest entry, since the last range is not bounded.
+    pub fn percentile_bound(&self, percentile: u64) -> Duration {
+        std::cmp::min(self.percentile(percentile).1, self.max)
+    }
+
+    /// Returns the summary of the distribution, or `None` if the histogram is empty.
+    pub fn stats(&self) -> Option<LatencyStats> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let millis = |percentile| self.percentile_bound(percentile).as_millis() as u64;
+        Some(LatencyStats {
+            count: self.total,
+            p50_ms: millis(50),
+            p90_ms: millis(90),
+            p99_ms: millis(99),
+            max_ms: self.max.as_millis() as u64,
+        })
+    }
+
     /// Returns the histogram entry key for the provided duration.
     fn range_for(&self, time: Duration) -> u64 {
         let duration_millis = time.as_millis() as u64;
@@ -106,13 +143,38 @@ impl TimeHistogram {
     }
 }
 
-/// Collector for the execution time metrics.
+/// Time distributions collected for a single type of action.
+#[derive(Debug, Clone, Default)]
+pub struct ActionMetrics {
+    /// Time of the whole action execution, including the preparation of the transactions.
+    pub execution: TimeHistogram,
+    /// Time from the submission until the commitment.
+    pub commit: TimeHistogram,
+    /// Time from the submission until the finalization.
+    pub finalization: TimeHistogram,
+}
+
+impl ActionMetrics {
+    pub fn is_empty(&self) -> bool {
+        self.execution.is_empty() && self.commit.is_empty() && self.finalization.is_empty()
+    }
+
+    pub fn summary(&self) -> ActionSummary {
+        ActionSummary {
+            execution: self.execution.stats(),
+            commit: self.commit.stats(),
+            finalization: self.finalization.stats(),
+        }
+    }
+}
+
+/// Collector for the execution time and latency metrics.
 ///
-/// It builds a distribution histogram for each type of action, thus reported results are represented
-/// by a range window rather than a single concrete number.
+/// It builds distribution histograms for each type of action, thus reported percentiles are represented
+/// by the upper bound of a range window rather than a single concrete number.
 #[derive(Debug, Clone)]
 pub struct MetricsCollector {
-    pub action_stats: HashMap<ActionType, TimeHistogram>,
+    pub action_stats: HashMap<ActionType, ActionMetrics>,
 }
 
 impl Default for MetricsCollector {
@@ -126,29 +188,52 @@ impl MetricsCollector {
         Self {
             action_stats: ActionType::all()
                 .into_iter()
-                .map(|action| (action, TimeHistogram::new()))
+                .map(|action| (action, ActionMetrics::default()))
                 .collect(),
         }
     }
 
-    pub fn add_metric(&mut self, action: ActionType, time: Duration) {
+    pub fn add_metric(&mut self, action: ActionType, time: Duration, latency: OperationLatency) {
+        self.action_stats.entry(action).and_modify(|metrics| {
+            metrics.execution.add_metric(time);
+            if let Some(commit) = latency.commit {
+                metrics.commit.add_metric(commit);
+            }
+            if let Some(finalization) = latency.finalization {
+                metrics.finalization.add_metric(finalization);
+            }
+        });
+    }
+
+    /// Returns the summaries for all the actions that were actually performed.
+    pub fn summary(&self) -> impl Iterator<Item = (ActionType, ActionSummary)> + '_ {
         self.action_stats
-            .entry(action)
-            .and_modify(|hist| hist.add_metric(time));
+            .iter()
+            .filter(|(_, metrics)| !metrics.is_empty())
+            .map(|(action, metrics)| (*action, metrics.summary()))
     }
 
     pub fn report(&self) {
-        vlog::info!("Action: [10 percentile, 50 percentile, 90 percentile]");
-        for (action, histogram) in self.action_stats.iter() {
-            // Only report data that was actually gathered.
-            if !histogram.is_empty() {
-                vlog::info!(
-                    "{:?}: [>{}ms >{}ms >{}ms]",
-                    action,
-                    histogram.percentile(10).0.as_millis(),
-                    histogram.percentile(50).0.as_millis(),
-                    histogram.percentile(90).0.as_millis(),
-                );
+        vlog::info!("Action: [50 percentile, 90 percentile, 99 percentile, max]");
+        for (action, summary) in self.summary() {
+            let metrics = [
+                ("execution", summary.execution),
+                ("commit", summary.commit),
+                ("finalization", summary.finalization),
+            ];
+            for (metric, stats) in metrics.iter() {
+                // Only report data that was actually gathered.
+                if let Some(stats) = stats {
+                    vlog::info!(
+                        "{} {}: [<={}ms <={}ms <={}ms {}ms]",
+                        action,
+                        metric,
+                        stats.p50_ms,
+                        stats.p90_ms,
+                        stats.p99_ms,
+                        stats.max_ms,
+                    );
+                }
             }
         }
     }
@@ -241,4 +326,24 @@ mod tests {
         assert_eq!(histogram.percentile(50), second_range);
         assert_eq!(histogram.percentile(100), third_range);
     }
+
+    #[test]
+    fn histogram_stats() {
+        let mut histogram = TimeHistogram::new();
+        assert_eq!(histogram.stats(), None);
+
+        for millis in 0..99 {
+            histogram.add_metric(Duration::from_millis(millis * 10));
+        }
+        histogram.add_metric(Duration::from_secs(3600));
+
+        let stats = histogram.stats().unwrap();
+        assert_eq!(stats.count, 100);
+        assert_eq!(stats.p50_ms, 499);
+        assert_eq!(stats.p90_ms, 899);
+        // The last entry is in the unbounded range, so the bound is limited by the max value.
+        assert_eq!(stats.p99_ms, 999);
+        assert_eq!(stats.max_ms, 3_600_000);
+        assert_eq!(histogram.percentile_bound(100), Duration::from_secs(3600));
+    }
 }
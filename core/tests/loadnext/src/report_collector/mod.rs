@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use futures::{channel::mpsc::Receiver, StreamExt};
 use operation_results_collector::OperationResultsCollector;
 
@@ -6,8 +8,11 @@ use crate::{
     report_collector::metrics_collector::MetricsCollector,
 };
 
+pub use summary::{ActionSummary, LatencyStats, LoadtestSummary};
+
 mod metrics_collector;
 mod operation_results_collector;
+mod summary;
 
 /// Decision on whether loadtest considered passed or failed.
 #[derive(Debug, Clone, Copy)]
@@ -25,8 +30,10 @@ pub enum LoadtestResult {
 /// Currently, only the following collectors are used:
 ///
 /// - MetricsCollector, which builds time distribution histograms for each kind of performed action.
-/// - OperationResultsCollector, a primitive collector that counts the amount of failures and decides whether
-///   test is passed.
+/// - OperationResultsCollector, a primitive collector that counts the amount of failures grouped by their reasons
+///   and decides whether test is passed.
+///
+/// Once all the reports are received, their summary can be saved to the file and compared with the baseline one.
 ///
 /// Other possible collectors that can be implemented:
 ///
@@ -46,6 +53,10 @@ pub struct ReportCollector {
     reports_stream: Receiver<Report>,
     metrics_collector: MetricsCollector,
     operations_results_collector: OperationResultsCollector,
+    /// Path to save the summary to.
+    summary_path: Option<PathBuf>,
+    /// Summary of the previous run together with the allowed latency growth in percents.
+    baseline: Option<(LoadtestSummary, f64)>,
 }
 
 impl ReportCollector {
@@ -54,9 +65,24 @@ impl ReportCollector {
             reports_stream,
             metrics_collector: MetricsCollector::new(),
             operations_results_collector: OperationResultsCollector::new(),
+            summary_path: None,
+            baseline: None,
         }
     }
 
+    /// Sets the path to save the summary of the test to.
+    pub fn summary_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.summary_path = Some(path.into());
+        self
+    }
+
+    /// Sets the summary to compare the results with. Test is considered failed if any latency
+    /// percentile exceeds the baseline one by more than `regression_threshold` percents.
+    pub fn baseline(mut self, baseline: LoadtestSummary, regression_threshold: f64) -> Self {
+        self.baseline = Some((baseline, regression_threshold));
+        self
+    }
+
     pub async fn run(mut self) -> LoadtestResult {
         while let Some(report) = self.reports_stream.next().await {
             vlog::trace!("Report: {:?}", &report);
@@ -64,7 +90,7 @@ impl ReportCollector {
             if matches!(&report.label, ReportLabel::ActionDone) {
                 // We only count successfully created statistics.
                 self.metrics_collector
-                    .add_metric(report.action, report.time);
+                    .add_metric(report.action, report.time, report.latency);
             }
 
             self.operations_results_collector.add_status(&report.label);
@@ -80,14 +106,48 @@ impl ReportCollector {
         self.metrics_collector.report();
         self.operations_results_collector.report();
 
-        self.final_resolution()
+        let summary = self.summary();
+        if let Some(path) = &self.summary_path {
+            match summary.save(path) {
+                Ok(()) => vlog::info!("Loadtest summary is saved to {}", path.display()),
+                Err(err) => vlog::error!("Unable to save the loadtest summary: {}", err),
+            }
+        }
+
+        self.final_resolution(&summary)
     }
 
-    fn final_resolution(&self) -> LoadtestResult {
+    fn summary(&self) -> LoadtestSummary {
+        let results = &self.operations_results_collector;
+        LoadtestSummary {
+            successes: results.successes(),
+            skipped: results.skipped(),
+            failures: results.failures(),
+            skip_reasons: results.skip_reasons().clone(),
+            errors: results.errors().clone(),
+            actions: self
+                .metrics_collector
+                .summary()
+                .map(|(action, summary)| (action.to_string(), summary))
+                .collect(),
+        }
+    }
+
+    fn final_resolution(&self, summary: &LoadtestSummary) -> LoadtestResult {
         if self.operations_results_collector.failures() > 0 {
-            LoadtestResult::TestFailed
-        } else {
-            LoadtestResult::TestPassed
+            return LoadtestResult::TestFailed;
         }
+
+        if let Some((baseline, regression_threshold)) = &self.baseline {
+            let regressions = summary.regressions(baseline, *regression_threshold);
+            for regression in &regressions {
+                vlog::error!("Latency regression: {}", regression);
+            }
+            if !regressions.is_empty() {
+                return LoadtestResult::TestFailed;
+            }
+        }
+
+        LoadtestResult::TestPassed
     }
 }
@@ -1,12 +1,18 @@
+use std::collections::BTreeMap;
+
 use crate::report::ReportLabel;
 
 /// Collector that analyzes the outcomes of the performed operations.
-/// Currently it's solely capable of deciding whether test was failed or not.
+/// It decides whether test was failed or not and keeps the breakdown of the skip reasons and errors.
 #[derive(Debug, Clone, Default)]
 pub struct OperationResultsCollector {
     successes: u64,
     skipped: u64,
     failures: u64,
+    /// Amount of skipped operations per reason.
+    skip_reasons: BTreeMap<String, u64>,
+    /// Amount of failed operations per error.
+    errors: BTreeMap<String, u64>,
 }
 
 impl OperationResultsCollector {
@@ -17,11 +23,26 @@ impl OperationResultsCollector {
     pub fn add_status(&mut self, status: &ReportLabel) {
         match status {
             ReportLabel::ActionDone => self.successes += 1,
-            ReportLabel::ActionSkipped { .. } => self.skipped += 1,
-            ReportLabel::ActionFailed { .. } => self.failures += 1,
+            ReportLabel::ActionSkipped { reason } => {
+                self.skipped += 1;
+                *self
+                    .skip_reasons
+                    .entry(Self::group_key(reason))
+                    .or_default() += 1;
+            }
+            ReportLabel::ActionFailed { error } => {
+                self.failures += 1;
+                *self.errors.entry(Self::group_key(error)).or_default() += 1;
+            }
         }
     }
 
+    /// Errors may contain the debug output of the whole transaction receipt,
+    /// so only the first line is used to group them.
+    fn group_key(message: &str) -> String {
+        message.lines().next().unwrap_or_default().to_string()
+    }
+
     pub fn successes(&self) -> u64 {
         self.successes
     }
@@ -38,6 +59,14 @@ impl OperationResultsCollector {
         self.successes + self.skipped + self.failures
     }
 
+    pub fn skip_reasons(&self) -> &BTreeMap<String, u64> {
+        &self.skip_reasons
+    }
+
+    pub fn errors(&self) -> &BTreeMap<String, u64> {
+        &self.errors
+    }
+
     pub fn report(&self) {
         vlog::info!(
             "Loadtest status: {} successful operations, {} skipped, {} failures. {} actions total.",
@@ -46,5 +75,31 @@ impl OperationResultsCollector {
             self.failures(),
             self.total()
         );
+        for (reason, amount) in &self.skip_reasons {
+            vlog::info!("Skipped {} times: {}", amount, reason);
+        }
+        for (error, amount) in &self.errors {
+            vlog::info!("Failed {} times: {}", amount, error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcomes_breakdown() {
+        let mut collector = OperationResultsCollector::new();
+        collector.add_status(&ReportLabel::done());
+        collector.add_status(&ReportLabel::skipped("No L1 balance"));
+        collector.add_status(&ReportLabel::skipped("No L1 balance"));
+        collector.add_status(&ReportLabel::failed("Unexpected status\nreceipt 1"));
+        collector.add_status(&ReportLabel::failed("Unexpected status\nreceipt 2"));
+
+        assert_eq!(collector.total(), 5);
+        assert_eq!(collector.skip_reasons()["No L1 balance"], 2);
+        assert_eq!(collector.errors().len(), 1);
+        assert_eq!(collector.errors()["Unexpected status"], 2);
     }
 }
@@ -0,0 +1,209 @@
+use std::{collections::BTreeMap, fmt::Write, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+/// Latency percentiles are represented by the histogram ranges, so a difference within this value
+/// can be caused by an entry moving to the neighbor range and is not considered a regression.
+const REGRESSION_SLACK_MS: u64 = 100;
+
+/// Summary of a time distribution.
+///
+/// Percentiles are the upper bounds of the histogram ranges, i.e. the time in which the corresponding
+/// share of the operations fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyStats {
+    pub count: usize,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+}
+
+/// Time distributions of a single type of action. Distributions without data are omitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionSummary {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution: Option<LatencyStats>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<LatencyStats>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finalization: Option<LatencyStats>,
+}
+
+impl ActionSummary {
+    fn metrics(&self) -> [(&'static str, Option<LatencyStats>); 3] {
+        [
+            ("execution", self.execution),
+            ("commit", self.commit),
+            ("finalization", self.finalization),
+        ]
+    }
+}
+
+/// Machine-readable outcome of the loadtest.
+///
+/// It can be saved as JSON or CSV, and the JSON one can be used as a baseline for the next runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoadtestSummary {
+    pub successes: u64,
+    pub skipped: u64,
+    pub failures: u64,
+    /// Amount of skipped operations per reason.
+    pub skip_reasons: BTreeMap<String, u64>,
+    /// Amount of failed operations per error.
+    pub errors: BTreeMap<String, u64>,
+    /// Time distributions per type of action.
+    pub actions: BTreeMap<String, ActionSummary>,
+}
+
+impl LoadtestSummary {
+    /// Loads the summary from the JSON file.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Saves the summary to the file, the format is chosen by the file extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let contents = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::to_string_pretty(self)?,
+            Some("csv") => self.to_csv(),
+            _ => anyhow::bail!(
+                "Unsupported report format of {}, expected .json or .csv",
+                path.display()
+            ),
+        };
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Represents the time distributions as CSV, one row per action and metric.
+    /// Outcomes breakdown is only available in JSON.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("action,metric,count,p50_ms,p90_ms,p99_ms,max_ms\n");
+        for (action, summary) in &self.actions {
+            for (metric, stats) in summary.metrics().iter() {
+                if let Some(stats) = stats {
+                    writeln!(
+                        csv,
+                        "{},{},{},{},{},{},{}",
+                        action,
+                        metric,
+                        stats.count,
+                        stats.p50_ms,
+                        stats.p90_ms,
+                        stats.p99_ms,
+                        stats.max_ms
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        csv
+    }
+
+    /// Compares the latency percentiles with the baseline ones and returns the description of every one
+    /// that grew by more than `threshold` percents. Maximums are not compared, since they're too volatile.
+    /// Actions and metrics that are not present in any of the summaries are ignored.
+    pub fn regressions(&self, baseline: &Self, threshold: f64) -> Vec<String> {
+        let mut regressions = Vec::new();
+        for (action, summary) in &self.actions {
+            let baseline_summary = match baseline.actions.get(action) {
+                Some(summary) => summary,
+                None => continue,
+            };
+
+            let metrics = summary.metrics();
+            let baseline_metrics = baseline_summary.metrics();
+            for ((metric, stats), (_, baseline_stats)) in metrics.iter().zip(&baseline_metrics) {
+                let (stats, baseline_stats) = match (stats, baseline_stats) {
+                    (Some(stats), Some(baseline_stats)) => (stats, baseline_stats),
+                    _ => continue,
+                };
+
+                let percentiles = [
+                    ("p50", stats.p50_ms, baseline_stats.p50_ms),
+                    ("p90", stats.p90_ms, baseline_stats.p90_ms),
+                    ("p99", stats.p99_ms, baseline_stats.p99_ms),
+                ];
+                for &(percentile, value, baseline_value) in percentiles.iter() {
+                    let allowed = baseline_value as f64 * (1.0 + threshold / 100.0);
+                    if value as f64 > allowed && value > baseline_value + REGRESSION_SLACK_MS {
+                        regressions.push(format!(
+                            "{} {} {}: {}ms, baseline {}ms",
+                            action, metric, percentile, value, baseline_value
+                        ));
+                    }
+                }
+            }
+        }
+        regressions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(p50_ms: u64) -> LatencyStats {
+        LatencyStats {
+            count: 10,
+            p50_ms,
+            p90_ms: 2000,
+            p99_ms: 3000,
+            max_ms: 5000,
+        }
+    }
+
+    fn summary(commit_p50_ms: u64) -> LoadtestSummary {
+        let mut summary = LoadtestSummary::default();
+        summary.actions.insert(
+            "Transfer".into(),
+            ActionSummary {
+                execution: None,
+                commit: Some(stats(commit_p50_ms)),
+                finalization: None,
+            },
+        );
+        summary
+    }
+
+    #[test]
+    fn summary_formats() {
+        let summary = summary(1000);
+
+        let json = serde_json::to_string(&summary).unwrap();
+        assert_eq!(
+            serde_json::from_str::<LoadtestSummary>(&json).unwrap(),
+            summary
+        );
+
+        assert_eq!(
+            summary.to_csv(),
+            "action,metric,count,p50_ms,p90_ms,p99_ms,max_ms\n\
+             Transfer,commit,10,1000,2000,3000,5000\n"
+        );
+    }
+
+    #[test]
+    fn baseline_comparison() {
+        let baseline = summary(1000);
+
+        // Growth within the threshold is allowed.
+        assert!(summary(1199).regressions(&baseline, 20.0).is_empty());
+        // As well as the growth within the precision of the histogram.
+        assert!(summary(99).regressions(&summary(0), 20.0).is_empty());
+
+        let regressions = summary(1300).regressions(&baseline, 20.0);
+        assert_eq!(
+            regressions,
+            vec!["Transfer commit p50: 1300ms, baseline 1000ms".to_string()]
+        );
+
+        // Metrics that are missing in the baseline are not compared.
+        assert!(summary(1300)
+            .regressions(&LoadtestSummary::default(), 20.0)
+            .is_empty());
+    }
+}
@@ -5,8 +5,6 @@ use rand::{rngs::SmallRng, seq::SliceRandom, thread_rng, RngCore, SeedableRng};
 use zksync::web3::signing::keccak256;
 use zksync_types::H256;
 
-use crate::all::AllWeighted;
-
 // SmallRng seed type is [u8; 32].
 const SEED_SIZE: usize = 32;
 
@@ -86,18 +84,11 @@ pub trait Random {
     fn random(rng: &mut LoadtestRng) -> Self;
 }
 
-pub trait WeightedRandom {
-    fn random(rng: &mut LoadtestRng) -> Self;
-}
-
-impl<V> WeightedRandom for V
-where
-    V: 'static + AllWeighted + Sized + Copy,
-{
-    fn random(rng: &mut LoadtestRng) -> Self {
-        V::all_weighted()
-            .choose_weighted(rng, |item| item.1)
-            .unwrap()
-            .0
-    }
+/// Chooses one of the variants according to the provided weights.
+/// Panics if there are no variants with non-zero weight.
+pub fn choose_weighted<V: Copy>(rng: &mut LoadtestRng, weighted: &[(V, f32)]) -> V {
+    weighted
+        .choose_weighted(rng, |item| item.1)
+        .expect("No variants with non-zero weight")
+        .0
 }
@@ -0,0 +1,363 @@
+use std::{path::Path, time::Duration};
+
+use serde::Deserialize;
+
+use crate::{command::TxType, constants::MAX_BATCH_SIZE};
+
+/// Declarative description of the traffic generated by the loadtest.
+///
+/// Scenario is loaded from the TOML file, path to which is provided via `SCENARIO_PATH` variable.
+/// Every section of the file is optional: omitted values are taken from the default scenario,
+/// which corresponds to the random mix loadtest used before scenarios were introduced.
+///
+/// Weights don't have to sum up to any particular value: each weight is divided by the sum of all the
+/// weights in the same section to obtain the probability.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Scenario {
+    /// Weights of the single transactions and batches.
+    pub commands: CommandWeights,
+    /// Weights of the transaction types.
+    /// Batches are filled with the batchable transaction types according to these weights as well.
+    pub txs: TxWeights,
+    /// Amount of transactions in the generated batches.
+    pub batch_size: BatchSize,
+    /// Share of the transactions which are made incorrect on purpose, from 0.0 to 1.0.
+    pub corrupted_txs: CorruptedTxs,
+    /// Load profile of the test. If it's not set, every account executes `OPERATIONS_PER_ACCOUNT` operations
+    /// as fast as it can.
+    pub load: Option<LoadProfile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommandWeights {
+    pub single_tx: f32,
+    pub batch: f32,
+}
+
+impl Default for CommandWeights {
+    fn default() -> Self {
+        Self {
+            single_tx: 0.7,
+            batch: 0.3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TxWeights {
+    pub deposit: f32,
+    pub transfer_to_new: f32,
+    pub transfer_to_existing: f32,
+    pub withdraw_to_self: f32,
+    pub withdraw_to_other: f32,
+    pub full_exit: f32,
+    pub change_pub_key: f32,
+    pub mint_nft: f32,
+    pub transfer_nft: f32,
+    pub withdraw_nft: f32,
+    pub swap: f32,
+}
+
+impl Default for TxWeights {
+    fn default() -> Self {
+        // `TransferToNew` and `TransferToExisting` the most likely options.
+        const DEFAULT_WEIGHT: f32 = 1.0;
+        const HIGH_WEIGHT: f32 = 3.0;
+
+        Self {
+            deposit: DEFAULT_WEIGHT,
+            transfer_to_new: HIGH_WEIGHT,
+            transfer_to_existing: HIGH_WEIGHT,
+            withdraw_to_self: DEFAULT_WEIGHT,
+            withdraw_to_other: DEFAULT_WEIGHT,
+            full_exit: DEFAULT_WEIGHT,
+            change_pub_key: DEFAULT_WEIGHT,
+            // NFT and swap flows are opt-in.
+            mint_nft: 0.0,
+            transfer_nft: 0.0,
+            withdraw_nft: 0.0,
+            swap: 0.0,
+        }
+    }
+}
+
+impl TxWeights {
+    /// Returns all the transaction types together with their weights.
+    pub fn weighted(&self) -> Vec<(TxType, f32)> {
+        vec![
+            (TxType::Deposit, self.deposit),
+            (TxType::TransferToNew, self.transfer_to_new),
+            (TxType::TransferToExisting, self.transfer_to_existing),
+            (TxType::WithdrawToSelf, self.withdraw_to_self),
+            (TxType::WithdrawToOther, self.withdraw_to_other),
+            (TxType::FullExit, self.full_exit),
+            (TxType::ChangePubKey, self.change_pub_key),
+            (TxType::MintNFT, self.mint_nft),
+            (TxType::TransferNFT, self.transfer_nft),
+            (TxType::WithdrawNFT, self.withdraw_nft),
+            (TxType::Swap, self.swap),
+        ]
+    }
+
+    /// Returns the transaction types that can be a part of the batch together with their weights.
+    pub fn batchable(&self) -> Vec<(TxType, f32)> {
+        self.weighted()
+            .into_iter()
+            .filter(|(tx_type, _)| tx_type.is_batchable())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BatchSize {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Default for BatchSize {
+    fn default() -> Self {
+        // TODO: For some reason, batches of size 1 are being rejected because of nonce mistmatch.
+        // It may be either bug in loadtest or server code, thus it should be investigated.
+        Self {
+            min: 2,
+            max: MAX_BATCH_SIZE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorruptedTxs {
+    pub ratio: f32,
+}
+
+impl Default for CorruptedTxs {
+    fn default() -> Self {
+        Self { ratio: 0.1 }
+    }
+}
+
+/// Load profile consisting of the ramp-up, steady and ramp-down phases.
+///
+/// During the ramp-up phase target TPS linearly grows from zero to `target_tps`, then it stays
+/// the same for the steady phase and linearly decreases to zero during the ramp-down phase.
+/// Accounts keep sending transactions until the last phase is over.
+///
+/// Note that target TPS is an upper limit: every account waits for its operation to be committed
+/// before starting the next one, so the amount of accounts must be big enough to reach the target.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoadProfile {
+    pub target_tps: f32,
+    #[serde(default)]
+    pub ramp_up_secs: u64,
+    pub steady_secs: u64,
+    #[serde(default)]
+    pub ramp_down_secs: u64,
+}
+
+impl LoadProfile {
+    /// Returns the target TPS at the moment `elapsed` since the test start or `None` if the test is over.
+    pub fn target_tps_at(&self, elapsed: Duration) -> Option<f32> {
+        let ramp_up = Duration::from_secs(self.ramp_up_secs);
+        let steady = Duration::from_secs(self.steady_secs);
+        let ramp_down = Duration::from_secs(self.ramp_down_secs);
+
+        if elapsed < ramp_up {
+            Some(self.target_tps * elapsed.as_secs_f32() / ramp_up.as_secs_f32())
+        } else if elapsed < ramp_up + steady {
+            Some(self.target_tps)
+        } else if elapsed < ramp_up + steady + ramp_down {
+            let left = ramp_up + steady + ramp_down - elapsed;
+            Some(self.target_tps * left.as_secs_f32() / ramp_down.as_secs_f32())
+        } else {
+            None
+        }
+    }
+
+    /// Total duration of the test.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.ramp_up_secs + self.steady_secs + self.ramp_down_secs)
+    }
+}
+
+impl Scenario {
+    /// Loads the scenario from the TOML file.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| anyhow::anyhow!("Can't read scenario file {:?}: {}", path, err))?;
+        Self::from_toml(&contents)
+    }
+
+    /// Parses and validates the scenario.
+    pub fn from_toml(contents: &str) -> anyhow::Result<Self> {
+        let scenario: Self = toml::from_str(contents)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        let is_valid_weight = |weight: f32| weight.is_finite() && weight >= 0.0;
+
+        let command_weights = [self.commands.single_tx, self.commands.batch];
+        anyhow::ensure!(
+            command_weights.iter().copied().all(is_valid_weight),
+            "Command weights must be non-negative numbers"
+        );
+        anyhow::ensure!(
+            command_weights.iter().sum::<f32>() > 0.0,
+            "At least one command weight must be positive"
+        );
+
+        let tx_weights = self.txs.weighted();
+        anyhow::ensure!(
+            tx_weights
+                .iter()
+                .all(|(_, weight)| is_valid_weight(*weight)),
+            "Transaction weights must be non-negative numbers"
+        );
+        if self.commands.single_tx > 0.0 {
+            anyhow::ensure!(
+                tx_weights.iter().any(|(_, weight)| *weight > 0.0),
+                "At least one transaction weight must be positive"
+            );
+        }
+        if self.commands.batch > 0.0 {
+            anyhow::ensure!(
+                self.txs.batchable().iter().any(|(_, weight)| *weight > 0.0),
+                "Batches are enabled, but all the batchable transaction types have zero weight"
+            );
+        }
+
+        anyhow::ensure!(
+            2 <= self.batch_size.min
+                && self.batch_size.min <= self.batch_size.max
+                && self.batch_size.max <= MAX_BATCH_SIZE,
+            "Batch size must be within [2, {}] and `min` must not exceed `max`",
+            MAX_BATCH_SIZE
+        );
+
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&self.corrupted_txs.ratio),
+            "Ratio of corrupted transactions must be within [0.0, 1.0]"
+        );
+
+        if let Some(load) = &self.load {
+            anyhow::ensure!(
+                load.target_tps.is_finite() && load.target_tps > 0.0,
+                "Target TPS must be positive"
+            );
+            anyhow::ensure!(
+                load.duration() > Duration::from_secs(0),
+                "Load profile must have non-zero duration"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scenario() {
+        let scenario = Scenario::from_toml(
+            r#"
+            corrupted_txs = { ratio = 0.0 }
+
+            [commands]
+            batch = 0.0
+
+            [txs]
+            transfer_to_new = 0.0
+            mint_nft = 2.0
+            swap = 1.5
+
+            [batch_size]
+            max = 10
+
+            [load]
+            target_tps = 50.0
+            ramp_up_secs = 30
+            steady_secs = 120
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(scenario.commands.single_tx, 0.7);
+        assert_eq!(scenario.commands.batch, 0.0);
+        assert_eq!(scenario.txs.transfer_to_new, 0.0);
+        assert_eq!(scenario.txs.transfer_to_existing, 3.0);
+        assert_eq!(scenario.txs.mint_nft, 2.0);
+        assert_eq!(scenario.txs.swap, 1.5);
+        assert_eq!(scenario.batch_size, BatchSize { min: 2, max: 10 });
+        assert_eq!(scenario.corrupted_txs.ratio, 0.0);
+        assert_eq!(
+            scenario.load,
+            Some(LoadProfile {
+                target_tps: 50.0,
+                ramp_up_secs: 30,
+                steady_secs: 120,
+                ramp_down_secs: 0,
+            })
+        );
+
+        // Empty file corresponds to the default scenario.
+        assert_eq!(Scenario::from_toml("").unwrap(), Scenario::default());
+    }
+
+    #[test]
+    fn invalid_scenario() {
+        let invalid_scenarios = [
+            // Unknown field.
+            "[txs]\nunknown = 1.0",
+            // Negative weight.
+            "[txs]\ndeposit = -1.0",
+            // No commands.
+            "[commands]\nsingle_tx = 0.0\nbatch = 0.0",
+            // Batches without batchable transactions.
+            "[txs]\ntransfer_to_new = 0.0\ntransfer_to_existing = 0.0\nwithdraw_to_self = 0.0\n\
+             withdraw_to_other = 0.0\nchange_pub_key = 0.0",
+            // Batch size out of bounds.
+            "[batch_size]\nmin = 1",
+            "[batch_size]\nmax = 1000",
+            // Ratio out of bounds.
+            "[corrupted_txs]\nratio = 1.5",
+            // Zero duration.
+            "[load]\ntarget_tps = 10.0\nsteady_secs = 0",
+        ];
+
+        for scenario in &invalid_scenarios {
+            assert!(
+                Scenario::from_toml(scenario).is_err(),
+                "Scenario must be rejected: {}",
+                scenario
+            );
+        }
+    }
+
+    #[test]
+    fn load_profile_phases() {
+        let load = LoadProfile {
+            target_tps: 100.0,
+            ramp_up_secs: 10,
+            steady_secs: 20,
+            ramp_down_secs: 10,
+        };
+
+        let tps_at = |secs| load.target_tps_at(Duration::from_secs(secs));
+        assert_eq!(tps_at(0), Some(0.0));
+        assert_eq!(tps_at(5), Some(50.0));
+        assert_eq!(tps_at(10), Some(100.0));
+        assert_eq!(tps_at(29), Some(100.0));
+        assert_eq!(tps_at(35), Some(50.0));
+        assert_eq!(tps_at(40), None);
+    }
+}
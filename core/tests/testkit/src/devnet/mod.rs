@@ -0,0 +1,273 @@
+//! In-process zkSync network for the tests.
+//!
+//! Devnet runs the server actors (state keeper, mempool, committer, API, Ethereum sender,
+//! prover server with a dummy prover) in the current process against a mocked Ethereum
+//! and a disposable local Postgres, so tests don't need Docker, geth or deployed contracts.
+//!
+//! Limitations:
+//!
+//! - Mocked Ethereum has no contracts, so there are no priority operations (deposits and full exits)
+//!   and no onchain `ChangePubKey` authorization. Accounts should be funded in the genesis block
+//!   instead, see `DevnetConfig::genesis_balances`. Transactions sent by the Ethereum sender are
+//!   considered executed right away, so blocks are finalized as soon as they're proven.
+//! - Actors rely on the process-wide environment (e.g. `DATABASE_URL`) and can't be stopped
+//!   completely, so there should be only one devnet per process.
+//! - Configuration is loaded from the environment (`zk` tool prepares it), only the ports,
+//!   database and price sources are overridden.
+
+use std::{
+    net::{SocketAddr, TcpListener, TcpStream},
+    time::{Duration, Instant},
+};
+
+use anyhow::format_err;
+use futures::channel::mpsc;
+use num::BigUint;
+use tokio::task::JoinHandle;
+use zksync_api::run_api;
+use zksync_config::ZkSyncConfig;
+use zksync_core::{genesis_init_with_balances, run_core};
+use zksync_eth_client::{clients::mock::MockEthereum, EthereumGateway};
+use zksync_eth_sender::run_eth_sender;
+use zksync_prover::{
+    client::ApiClient,
+    dummy_prover::{DummyProver, DummyProverConfig},
+    prover_work_cycle, ProverImpl, ShutdownRequest,
+};
+use zksync_storage::ConnectionPool;
+use zksync_types::{tokens::get_genesis_token_list, Address, TokenId};
+use zksync_witness_generator::{database::Database, run_prover_server};
+
+use self::{postgres::DisposablePostgres, price_server::start_price_server};
+
+mod postgres;
+mod price_server;
+
+/// Time to wait for the API servers to start accepting connections.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Configuration of the devnet.
+#[derive(Debug, Clone)]
+pub struct DevnetConfig {
+    /// Configuration of the server actors. Ports, database and price sources are overridden on start.
+    pub zksync: ZkSyncConfig,
+    /// Whether to run the prover server with a dummy prover. Dummy prover requires the precomputed proofs
+    /// in the keys directory. Without it blocks are only committed, but never finalized.
+    pub run_prover: bool,
+    /// Balances of the accounts created in the genesis block. Accounts are created in the order
+    /// of the first appearance of their addresses, starting from the account ID 1.
+    pub genesis_balances: Vec<(Address, TokenId, BigUint)>,
+}
+
+impl DevnetConfig {
+    pub fn from_env() -> Self {
+        Self {
+            zksync: ZkSyncConfig::from_env(),
+            run_prover: true,
+            genesis_balances: Vec::new(),
+        }
+    }
+}
+
+/// Running in-process zkSync network. Actors are stopped and the database is removed on drop,
+/// API servers keep running until the end of the process.
+#[derive(Debug)]
+pub struct Devnet {
+    config: ZkSyncConfig,
+    connection_pool: ConnectionPool,
+    eth_gateway: EthereumGateway,
+    task_handles: Vec<JoinHandle<()>>,
+    prover_shutdown: ShutdownRequest,
+    /// Receives the notifications about panics in the actors running in the separate threads.
+    panic_receiver: mpsc::Receiver<bool>,
+    // Declared last, so the database is removed once the actors are stopped.
+    _postgres: DisposablePostgres,
+}
+
+impl Devnet {
+    /// Starts the devnet with the configuration loaded from the environment.
+    pub async fn start() -> anyhow::Result<Self> {
+        Self::start_with_config(DevnetConfig::from_env()).await
+    }
+
+    pub async fn start_with_config(config: DevnetConfig) -> anyhow::Result<Self> {
+        let mut zksync_config = config.zksync;
+
+        let postgres_port = free_port()?;
+        let postgres =
+            tokio::task::spawn_blocking(move || DisposablePostgres::start(postgres_port)).await??;
+        // Storage always takes the database URL from the environment.
+        std::env::set_var("DATABASE_URL", postgres.url());
+        zksync_config.db.url = postgres.url();
+
+        let price_server_addr = SocketAddr::from(([127, 0, 0, 1], free_port()?));
+        let genesis_tokens = get_genesis_token_list(&zksync_config.chain.eth.network.to_string())?;
+        zksync_config.ticker.coingecko_base_url = format!("http://{}", price_server_addr);
+        // There is no Uniswap to check the liquidity of the tokens, so all of them are accepted for fees.
+        zksync_config.ticker.unconditionally_valid_tokens = std::iter::once(Default::default())
+            .chain(genesis_tokens.iter().map(|token| token.address))
+            .collect();
+        start_price_server(price_server_addr, genesis_tokens)?;
+
+        override_ports(&mut zksync_config)?;
+
+        genesis_init_with_balances(&zksync_config, &config.genesis_balances).await;
+        let connection_pool = ConnectionPool::new(Some(zksync_config.db.pool_size as u32));
+        let eth_gateway = EthereumGateway::Mock(MockEthereum::with_auto_confirmations(
+            zksync_config.eth_sender.sender.wait_confirmations,
+        ));
+        let (panic_sender, panic_receiver) = mpsc::channel(256);
+
+        let mut task_handles = run_core(
+            connection_pool.clone(),
+            panic_sender.clone(),
+            eth_gateway.clone(),
+            &zksync_config,
+        )
+        .await?;
+        task_handles.push(run_api(
+            connection_pool.clone(),
+            panic_sender.clone(),
+            eth_gateway.clone(),
+            &zksync_config,
+        ));
+        task_handles.push(run_eth_sender(
+            connection_pool.clone(),
+            eth_gateway.clone(),
+            zksync_config.clone(),
+        ));
+
+        let prover_shutdown = ShutdownRequest::new();
+        if config.run_prover {
+            run_prover_server(
+                Database::new(connection_pool.clone()),
+                panic_sender,
+                zksync_config.clone(),
+            );
+            task_handles.push(run_dummy_prover(&zksync_config, prover_shutdown.clone())?);
+        }
+
+        wait_for_port(zksync_config.api.json_rpc.http_port).await?;
+        wait_for_port(zksync_config.api.rest.port).await?;
+
+        Ok(Self {
+            config: zksync_config,
+            connection_pool,
+            eth_gateway,
+            task_handles,
+            prover_shutdown,
+            panic_receiver,
+            _postgres: postgres,
+        })
+    }
+
+    /// Configuration the actors are running with.
+    pub fn config(&self) -> &ZkSyncConfig {
+        &self.config
+    }
+
+    /// Pool of connections to the devnet database.
+    pub fn connection_pool(&self) -> &ConnectionPool {
+        &self.connection_pool
+    }
+
+    /// Mocked Ethereum client, which can be used to check the sent transactions.
+    pub fn eth_gateway(&self) -> &EthereumGateway {
+        &self.eth_gateway
+    }
+
+    /// Address of the JSON RPC API.
+    pub fn json_rpc_url(&self) -> &str {
+        &self.config.api.json_rpc.http_url
+    }
+
+    /// Address of the REST API.
+    pub fn rest_api_url(&self) -> &str {
+        &self.config.api.rest.url
+    }
+
+    /// Checks whether any of the actors running in the separate threads has panicked.
+    pub fn is_panicked(&mut self) -> bool {
+        matches!(self.panic_receiver.try_next(), Ok(Some(_)))
+    }
+}
+
+impl Drop for Devnet {
+    fn drop(&mut self) {
+        self.prover_shutdown.set();
+        for handle in &self.task_handles {
+            handle.abort();
+        }
+    }
+}
+
+fn run_dummy_prover(
+    config: &ZkSyncConfig,
+    shutdown: ShutdownRequest,
+) -> anyhow::Result<JoinHandle<()>> {
+    let prover = DummyProver::create_from_config(DummyProverConfig {
+        block_sizes: config.chain.block_chunk_sizes_to_seal(),
+    });
+    let client = ApiClient::new(
+        &config.api.prover.url.parse()?,
+        config.prover.prover.request_timeout(),
+        &config.api.prover.secret_auth,
+    );
+    let prover_config = config.prover.clone();
+
+    Ok(tokio::spawn(async move {
+        prover_work_cycle(
+            prover,
+            client,
+            shutdown,
+            prover_config,
+            "devnet_dummy_prover",
+        )
+        .await
+    }))
+}
+
+/// Moves all the servers to the free ports, so the devnet doesn't conflict with
+/// the locally running server or another devnet.
+fn override_ports(config: &mut ZkSyncConfig) -> anyhow::Result<()> {
+    let api = &mut config.api;
+
+    api.admin.port = free_port()?;
+    api.admin.url = local_url("http", api.admin.port);
+    api.rest.port = free_port()?;
+    api.rest.url = local_url("http", api.rest.port);
+    api.json_rpc.http_port = free_port()?;
+    api.json_rpc.http_url = local_url("http", api.json_rpc.http_port);
+    api.json_rpc.ws_port = free_port()?;
+    api.json_rpc.ws_url = local_url("ws", api.json_rpc.ws_port);
+    api.web3.port = free_port()?;
+    api.web3.url = local_url("http", api.web3.port);
+    api.private.port = free_port()?;
+    api.private.url = local_url("http", api.private.port);
+    api.prover.port = free_port()?;
+    api.prover.url = local_url("http", api.prover.port);
+    api.prometheus.port = free_port()?;
+
+    Ok(())
+}
+
+fn local_url(scheme: &str, port: u16) -> String {
+    format!("{}://127.0.0.1:{}", scheme, port)
+}
+
+/// Asks OS for a port that is not used at the moment.
+fn free_port() -> anyhow::Result<u16> {
+    let listener = TcpListener::bind(("127.0.0.1", 0))?;
+    Ok(listener.local_addr()?.port())
+}
+
+async fn wait_for_port(port: u16) -> anyhow::Result<()> {
+    let started_at = Instant::now();
+    while TcpStream::connect(("127.0.0.1", port)).is_err() {
+        if started_at.elapsed() > STARTUP_TIMEOUT {
+            return Err(format_err!("Devnet server on port {} didn't start", port));
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    Ok(())
+}
@@ -0,0 +1,123 @@
+//! Disposable Postgres instance for the devnet.
+//!
+//! Postgres binaries (`initdb`, `pg_ctl`, `createdb` and `psql`) should be in path.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{ensure, format_err};
+
+const DATABASE_NAME: &str = "plasma";
+const USER: &str = "postgres";
+
+/// Postgres cluster created in a temporary directory, which is stopped and removed on drop.
+#[derive(Debug)]
+pub struct DisposablePostgres {
+    data_dir: PathBuf,
+    port: u16,
+}
+
+impl DisposablePostgres {
+    /// Initializes a new cluster, starts it on the provided port and applies the storage migrations
+    /// from `$ZKSYNC_HOME/core/lib/storage/migrations`.
+    pub fn start(port: u16) -> anyhow::Result<Self> {
+        let data_dir =
+            std::env::temp_dir().join(format!("zksync-devnet-{}-{}", std::process::id(), port));
+        let data_dir_str = data_dir.to_string_lossy().to_string();
+
+        run("initdb", &["-D", &data_dir_str, "-U", USER, "--auth=trust"])?;
+        // Created right after the cluster, so the directory is removed even if the start fails.
+        let postgres = Self { data_dir, port };
+
+        let options = format!(
+            "-p {} -k {} -c listen_addresses=127.0.0.1",
+            port, data_dir_str
+        );
+        let log_file = postgres.data_dir.join("postgres.log");
+        run(
+            "pg_ctl",
+            &[
+                "-D",
+                &data_dir_str,
+                "-o",
+                &options,
+                "-l",
+                &log_file.to_string_lossy(),
+                "-w",
+                "start",
+            ],
+        )?;
+
+        let port = port.to_string();
+        run(
+            "createdb",
+            &["-h", "127.0.0.1", "-p", &port, "-U", USER, DATABASE_NAME],
+        )?;
+        postgres.run_migrations()?;
+
+        Ok(postgres)
+    }
+
+    /// URL to connect to the database.
+    pub fn url(&self) -> String {
+        format!(
+            "postgres://{}@127.0.0.1:{}/{}",
+            USER, self.port, DATABASE_NAME
+        )
+    }
+
+    fn run_migrations(&self) -> anyhow::Result<()> {
+        let zksync_home = std::env::var("ZKSYNC_HOME")?;
+        let migrations_dir = Path::new(&zksync_home).join("core/lib/storage/migrations");
+
+        // Migrations are named after their creation time, so they're applied in the alphabetical order.
+        let mut migrations = fs::read_dir(&migrations_dir)?
+            .map(|entry| entry.map(|entry| entry.path().join("up.sql")))
+            .collect::<Result<Vec<_>, _>>()?;
+        migrations.sort();
+
+        let url = self.url();
+        for migration in migrations {
+            run(
+                "psql",
+                &[
+                    &url,
+                    "-q",
+                    "-v",
+                    "ON_ERROR_STOP=1",
+                    "-f",
+                    &migration.to_string_lossy(),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for DisposablePostgres {
+    fn drop(&mut self) {
+        let data_dir = self.data_dir.to_string_lossy().to_string();
+        if let Err(err) = run("pg_ctl", &["-D", &data_dir, "-m", "immediate", "stop"]) {
+            vlog::warn!("Failed to stop the devnet Postgres: {}", err);
+        }
+        fs::remove_dir_all(&self.data_dir).unwrap_or_default();
+    }
+}
+
+fn run(command: &str, args: &[&str]) -> anyhow::Result<()> {
+    let output = Command::new(command)
+        .args(args)
+        .output()
+        .map_err(|err| format_err!("failed to execute command {}: {}", command, err))?;
+    ensure!(
+        output.status.success(),
+        "command {} failed:\nstdout: {}\nstderr: {}",
+        command,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(())
+}
@@ -0,0 +1,66 @@
+//! Minimal implementation of the CoinGecko API used by the fee ticker, so the devnet
+//! doesn't depend on the external price sources.
+//!
+//! Prices are fixed: ETH costs 200 USD and every other token costs 1 USD.
+
+use std::net::SocketAddr;
+
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+use serde_json::json;
+use zksync_types::tokens::TokenInfo;
+
+const ETH_PRICE_USD: &str = "200";
+const TOKEN_PRICE_USD: &str = "1";
+
+async fn coins_list(tokens: web::Data<Vec<TokenInfo>>) -> HttpResponse {
+    let coins: Vec<_> = tokens
+        .iter()
+        .map(|token| {
+            json!({
+                "id": token.symbol.to_lowercase(),
+                "symbol": token.symbol.to_lowercase(),
+                "name": token.symbol,
+                "platforms": { "ethereum": format!("{:?}", token.address) },
+            })
+        })
+        .collect();
+    HttpResponse::Ok().json(coins)
+}
+
+async fn market_chart(req: HttpRequest) -> HttpResponse {
+    let price = match req.match_info().get("coin_id") {
+        Some("ethereum") => ETH_PRICE_USD,
+        _ => TOKEN_PRICE_USD,
+    };
+    let last_updated = chrono::Utc::now().timestamp_millis();
+    HttpResponse::Ok().json(json!({ "prices": [[last_updated, price]] }))
+}
+
+/// Starts the price server in a detached thread.
+pub fn start_price_server(bind_to: SocketAddr, tokens: Vec<TokenInfo>) -> anyhow::Result<()> {
+    // Bind in the current thread to report errors to the caller.
+    let listener = std::net::TcpListener::bind(bind_to)?;
+    std::thread::Builder::new()
+        .name("devnet_price_server".to_string())
+        .spawn(move || {
+            let actix_runtime = actix_rt::System::new();
+            actix_runtime.block_on(async move {
+                HttpServer::new(move || {
+                    App::new()
+                        .app_data(web::Data::new(tokens.clone()))
+                        .route("/api/v3/coins/list", web::get().to(coins_list))
+                        .route(
+                            "/api/v3/coins/{coin_id}/market_chart",
+                            web::get().to(market_chart),
+                        )
+                })
+                .workers(1)
+                .listen(listener)
+                .expect("Failed to start the price server")
+                .run()
+                .await
+                .expect("Price server crashed");
+            });
+        })?;
+    Ok(())
+}
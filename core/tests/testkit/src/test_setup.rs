@@ -315,6 +315,7 @@ impl TestSetup {
         let block = ProposedBlock {
             priority_ops: Vec::new(),
             txs: vec![SignedTxVariant::from(SignedZkSyncTx::from(tx))],
+            spans: Vec::new(),
         };
 
         // Request miniblock execution.
@@ -397,6 +398,7 @@ impl TestSetup {
         let block = ProposedBlock {
             priority_ops: vec![op],
             txs: Vec::new(),
+            spans: Vec::new(),
         };
 
         // Request miniblock execution.
@@ -910,7 +912,7 @@ impl TestSetup {
                 CommitRequest::Block((new_block, _)) => {
                     return new_block;
                 }
-                CommitRequest::PendingBlock(_) => {
+                CommitRequest::PendingBlock(_) | CommitRequest::Flush(_) => {
                     // Pending blocks are ignored.
                 }
             }
@@ -936,6 +938,9 @@ impl TestSetup {
             CommitRequest::PendingBlock(_) => {
                 // Nothing to be done.
             }
+            CommitRequest::Flush(_) => {
+                panic!("Expected pending block, got flush request");
+            }
         }
     }
 
@@ -0,0 +1,92 @@
+//! Tests of the in-process devnet.
+//!
+//! Devnet takes the configuration from the environment and needs Postgres binaries
+//! and the precomputed proofs for the dummy prover, so the tests are run with:
+//!
+//! ```bash
+//! zk test integration devnet
+//! ```
+
+use std::time::Duration;
+
+use num::BigUint;
+
+use zksync::{
+    provider::Provider,
+    types::BlockStatus,
+    web3::types::{Address, H256},
+    zksync_types::tx::PackedEthSignature,
+    RpcProvider, Wallet, WalletCredentials,
+};
+use zksync_eth_signer::PrivateKeySigner;
+use zksync_testkit::devnet::{Devnet, DevnetConfig};
+use zksync_types::TokenId;
+
+const TX_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Funds an account in the genesis block and checks that its transactions are executed
+/// and verified by the devnet.
+#[tokio::test]
+#[ignore]
+async fn transfer_is_verified() -> Result<(), anyhow::Error> {
+    let mut eth_private_key = H256::default();
+    eth_private_key.randomize();
+    let eth_address = PackedEthSignature::address_from_private_key(&eth_private_key)?;
+    let initial_balance = BigUint::from(10u64).pow(18);
+
+    let mut config = DevnetConfig::from_env();
+    config.genesis_balances = vec![(eth_address, TokenId(0), initial_balance.clone())];
+    let mut devnet = Devnet::start_with_config(config).await?;
+
+    let network = devnet.config().chain.eth.network;
+    let provider = RpcProvider::from_addr_and_network(devnet.json_rpc_url(), network);
+    let credentials = WalletCredentials::from_eth_signer(
+        eth_address,
+        PrivateKeySigner::new(eth_private_key),
+        network,
+    )
+    .await?;
+    let wallet = Wallet::new(provider, credentials).await?;
+    assert_eq!(
+        wallet.get_balance(BlockStatus::Committed, "ETH").await?,
+        initial_balance
+    );
+
+    // There are no contracts on the mocked Ethereum, so the signing key is set with the Ethereum signature.
+    wallet
+        .start_change_pubkey()
+        .fee_token("ETH")?
+        .send()
+        .await?
+        .commit_timeout(TX_TIMEOUT)
+        .wait_for_commit()
+        .await?;
+    assert!(wallet.is_signing_key_set().await?);
+
+    let transfer_amount = 1_000_000u64;
+    let recipient = Address::random();
+    wallet
+        .start_transfer()
+        .to(recipient)
+        .token("ETH")?
+        .amount(transfer_amount)
+        .send()
+        .await?
+        .verify_timeout(TX_TIMEOUT)
+        .wait_for_verify()
+        .await?;
+
+    let recipient_balance = wallet
+        .provider
+        .account_info(recipient)
+        .await?
+        .verified
+        .balances
+        .get("ETH")
+        .map(|balance| balance.0.clone())
+        .unwrap_or_default();
+    assert_eq!(recipient_balance, BigUint::from(transfer_amount));
+    assert!(!devnet.is_panicked());
+
+    Ok(())
+}
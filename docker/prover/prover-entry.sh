@@ -10,6 +10,7 @@ echo PROVER_NAME=$PROVER_NAME
 echo CHAIN_CIRCUIT_SUPPORTED_BLOCK_CHUNKS_SIZES=$CHAIN_CIRCUIT_SUPPORTED_BLOCK_CHUNKS_SIZES
 echo CHAIN_CIRCUIT_SUPPORTED_BLOCK_CHUNKS_SIZES_SETUP_POWERS=$CHAIN_CIRCUIT_SUPPORTED_BLOCK_CHUNKS_SIZES_SETUP_POWERS
 echo CHAIN_STATE_KEEPER_BLOCK_CHUNK_SIZES=$CHAIN_STATE_KEEPER_BLOCK_CHUNK_SIZES
+echo CHAIN_STATE_KEEPER_SEAL_INTO_SUPPORTED_SIZES=$CHAIN_STATE_KEEPER_SEAL_INTO_SUPPORTED_SIZES
 
 
 if [ "$MISC_DOCKER_DUMMY_PROVER" == "true" ]; then
@@ -17,13 +18,26 @@ if [ "$MISC_DOCKER_DUMMY_PROVER" == "true" ]; then
   exec dummy_prover "$PROVER_NAME" 2>&1
 fi
 
-# Returns required plonk setup powers based on `CHAIN_STATE_KEEPER_BLOCK_CHUNK_SIZES` used in the environment configuration
+# Returns required plonk setup powers based on `CHAIN_STATE_KEEPER_BLOCK_CHUNK_SIZES` used in the environment configuration.
+# With `CHAIN_STATE_KEEPER_SEAL_INTO_SUPPORTED_SIZES` enabled, blocks may be sealed into any supported size up to the
+# largest configured one, so setups for these sizes are required as well.
 function get_required_plonk_setup_powers() {
    local SUP_CHUNKS_ARR=($(echo $CHAIN_CIRCUIT_SUPPORTED_BLOCK_CHUNKS_SIZES | tr ',' "\n"))
    local SUP_CHUNKS_POW=($(echo $CHAIN_CIRCUIT_SUPPORTED_BLOCK_CHUNKS_SIZES_SETUP_POWERS | tr ',' "\n"))
 
+   local MAX_SIZE=0
+   for my_size in ${CHAIN_STATE_KEEPER_BLOCK_CHUNK_SIZES//,/ }; do
+       if [ "$my_size" -gt "$MAX_SIZE" ]; then
+           MAX_SIZE=$my_size
+       fi
+   done
+
    local REQUIRED_SETUP_POWS=""
    for index in ${!SUP_CHUNKS_ARR[*]}; do
+       if [ "$CHAIN_STATE_KEEPER_SEAL_INTO_SUPPORTED_SIZES" == "true" ] && [ "${SUP_CHUNKS_ARR[$index]}" -le "$MAX_SIZE" ]; then
+           REQUIRED_SETUP_POWS="$REQUIRED_SETUP_POWS${SUP_CHUNKS_POW[$index]},"
+           continue
+       fi
        for my_size in ${CHAIN_STATE_KEEPER_BLOCK_CHUNK_SIZES//,/ }; do
            if [ "$my_size" == "${SUP_CHUNKS_ARR[$index]}" ]; then
                REQUIRED_SETUP_POWS="$REQUIRED_SETUP_POWS${SUP_CHUNKS_POW[$index]},"
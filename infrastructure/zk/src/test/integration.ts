@@ -190,6 +190,10 @@ export async function rustSDK() {
     await utils.spawn('cargo test -p zksync --release -- --ignored --test-threads=1');
 }
 
+export async function devnet() {
+    await utils.spawn('cargo test -p zksync_testkit --release --test devnet -- --ignored --test-threads=1');
+}
+
 export const command = new Command('integration').description('zksync integration tests').alias('i');
 
 command
@@ -240,6 +244,11 @@ command
         cmd.withServer ? await withServer(rustSDK, 1200) : await rustSDK();
     });
 
+command
+    .command('devnet')
+    .description('run integration tests against the in-process devnet')
+    .action(devnet);
+
 command
     .command('api')
     .description('run api integration tests')
@@ -12,6 +12,8 @@ pub enum ClientError {
     RpcError(RpcFailure),
     #[error("Network error: {0}")]
     NetworkError(String),
+    #[error("API error: {0}")]
+    ApiError(String),
 
     #[error("Provided account credentials are incorrect")]
     IncorrectCredentials,
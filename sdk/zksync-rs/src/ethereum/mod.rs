@@ -476,6 +476,69 @@ impl<S: EthereumSigner> EthereumProvider<S> {
         Ok(transaction_hash)
     }
 
+    /// Returns the balance of the token stored on the zkSync contract for the address,
+    /// when the withdrawal couldn't transfer funds to it.
+    pub async fn pending_balance(
+        &self,
+        address: Address,
+        token: impl Into<TokenLike>,
+    ) -> Result<U256, ClientError> {
+        let token = self
+            .tokens_cache
+            .resolve(token.into())
+            .ok_or(ClientError::UnknownToken)?;
+
+        let res = self
+            .eth_client
+            .call_main_contract_function(
+                "getPendingBalance",
+                (address, token.address),
+                None,
+                Options::default(),
+                None,
+            )
+            .await
+            .map_err(|err| ClientError::NetworkError(err.to_string()))?;
+        Ok(res)
+    }
+
+    /// Claims the pending balance of the token, i.e. the funds of the withdrawals
+    /// that were stored on the zkSync contract instead of being transferred to the owner.
+    /// Anyone can send this transaction, the funds are always transferred to the owner.
+    pub async fn withdraw_pending_balance(
+        &self,
+        owner: Address,
+        token: impl Into<TokenLike>,
+        amount: U256,
+    ) -> Result<H256, ClientError> {
+        let token = self
+            .tokens_cache
+            .resolve(token.into())
+            .ok_or(ClientError::UnknownToken)?;
+
+        let options = Options {
+            gas: Some(300_000.into()),
+            ..Default::default()
+        };
+
+        let data = self
+            .client()
+            .encode_tx_data("withdrawPendingBalance", (owner, token.address, amount));
+        let signed_tx = self
+            .client()
+            .sign_prepared_tx(data, options)
+            .await
+            .map_err(|_| ClientError::IncorrectCredentials)?;
+
+        let transaction_hash = self
+            .client()
+            .send_raw_tx(signed_tx.raw_tx)
+            .await
+            .map_err(|err| ClientError::NetworkError(err.to_string()))?;
+
+        Ok(transaction_hash)
+    }
+
     /// Sets the timeout to wait for transactions to appear in the Ethereum network.
     /// By default it is set to 30 seconds.
     pub fn set_confirmation_timeout(&mut self, timeout: Duration) {
@@ -0,0 +1,535 @@
+//! `Provider` implementation on top of the zkSync REST API v0.2.
+//!
+//! Besides the common `Provider` interface, `RestProvider` exposes the functionality
+//! available only in the REST API: paginated account and block history, committed
+//! and finalized account states, batch status and the network status.
+
+// Built-in uses
+use std::collections::HashMap;
+
+// External uses
+use async_trait::async_trait;
+use num::BigUint;
+use serde::{de::DeserializeOwned, Deserialize};
+
+// Workspace uses
+use zksync_api_client::rest::client::{Client, ClientError as RestClientError};
+use zksync_api_types::{
+    v02::{
+        account::AccountState as ApiAccountState,
+        block::BlockInfo as ApiBlockInfo,
+        fee::{ApiFee, TxInBatchFeeRequest},
+        pagination::{ApiEither, Paginated, PaginationDirection, PaginationQuery, MAX_LIMIT},
+        status::NetworkStatus,
+        token::{ApiToken, NFT as ApiNFT},
+        transaction::{
+            ApiTxBatch, L1Transaction, L2Transaction, Receipt, SubmitBatchResponse, Transaction,
+            TransactionData, TxData, TxHashSerializeWrapper, TxInBlockStatus, WithdrawalInfo,
+        },
+        Response,
+    },
+    TxWithSignature,
+};
+use zksync_types::{
+    network::Network,
+    tokens::ChangePubKeyFeeTypeArg,
+    tx::{
+        ChangePubKeyType, EthBatchSignatures, PackedEthSignature, TxEthSignature,
+        TxEthSignatureVariant, TxHash, ZkSyncTx,
+    },
+    Address, BlockNumber, SerialId, Token, TokenId, TokenLike, TxFeeTypes,
+};
+
+// Local uses
+use crate::{
+    error::ClientError,
+    provider::{Provider, ResponseResult},
+    types::*,
+};
+
+/// Returns a corresponding REST API address for a provided network name.
+pub fn get_rest_addr(network: Network) -> &'static str {
+    match network {
+        Network::Mainnet => "https://api.zksync.io",
+        Network::Rinkeby => "https://rinkeby-api.zksync.io",
+        Network::Ropsten => "https://ropsten-api.zksync.io",
+        Network::Localhost => "http://127.0.0.1:3001",
+        Network::Unknown => panic!("Attempt to create a provider from an unknown network"),
+        Network::Test => panic!("Attempt to create a provider from an test network"),
+    }
+}
+
+/// Part of the `/config` response used by the provider.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiConfig {
+    contract: Address,
+    gov_contract: Address,
+    deposit_confirmations: u64,
+}
+
+/// `RestProvider` is capable of interacting with the ZKSync node via its
+/// REST API v0.2.
+#[derive(Debug, Clone)]
+pub struct RestProvider {
+    client: Client,
+    network: Network,
+}
+
+#[async_trait]
+impl Provider for RestProvider {
+    /// Requests and returns information about a ZKSync account given its address.
+    ///
+    /// Ongoing deposits are not reported by the REST API as a part of the account state,
+    /// so they're collected from the pending priority operations of the account.
+    async fn account_info(&self, address: Address) -> ResponseResult<AccountInfo> {
+        let state = self.account_state(address).await?;
+        let depositing = self.depositing_balances(address).await?;
+        let id = state
+            .committed
+            .as_ref()
+            .or_else(|| state.finalized.as_ref())
+            .map(|account| account.account_id);
+
+        Ok(AccountInfo {
+            address,
+            id,
+            depositing,
+            committed: state.committed.map(AccountState::from).unwrap_or_default(),
+            verified: state.finalized.map(AccountState::from).unwrap_or_default(),
+        })
+    }
+
+    async fn tokens(&self) -> ResponseResult<Tokens> {
+        let mut tokens = Tokens::new();
+        let mut from = TokenId(0);
+        loop {
+            let query = PaginationQuery {
+                from: ApiEither::from(from),
+                limit: MAX_LIMIT,
+                direction: PaginationDirection::Newer,
+            };
+            let page = self.tokens_page(&query).await?;
+            let is_last_page = page.list.len() < MAX_LIMIT as usize;
+            let last_id = page.list.last().map(|token| token.id);
+
+            tokens.extend(page.list.into_iter().map(|token| {
+                let token = Token::new(
+                    token.id,
+                    token.address,
+                    &token.symbol,
+                    token.decimals,
+                    token.kind,
+                );
+                (token.symbol.clone(), token)
+            }));
+
+            match last_id {
+                Some(last_id) if !is_last_page => from = TokenId(*last_id + 1),
+                _ => break,
+            }
+        }
+        Ok(tokens)
+    }
+
+    async fn tx_info(&self, tx_hash: TxHash) -> ResponseResult<TransactionInfo> {
+        Ok(self.tx_receipt(tx_hash).await?.into())
+    }
+
+    /// Obtains minimum fee required to process transaction in zkSync network.
+    ///
+    /// REST API doesn't expose the gas parameters of the fee, so `gas_tx_amount`
+    /// and `gas_price_wei` are always zero.
+    async fn get_tx_fee(
+        &self,
+        tx_type: TxFeeTypes,
+        address: Address,
+        token: impl Into<TokenLike> + Send + 'async_trait,
+    ) -> ResponseResult<Fee> {
+        let response = self
+            .client
+            .get_txs_fee(tx_type.into(), address, token.into())
+            .await;
+        let fee: ApiFee = deserialize_response_result(response)?;
+
+        Ok(Fee {
+            fee_type: output_fee_type(tx_type),
+            gas_tx_amount: BigUint::from(0u32),
+            gas_price_wei: BigUint::from(0u32),
+            gas_fee: fee.gas_fee,
+            zkp_fee: fee.zkp_fee,
+            total_fee: fee.total_fee,
+            quote: fee.quote,
+        })
+    }
+
+    async fn get_txs_batch_fee(
+        &self,
+        tx_types: Vec<TxFeeTypes>,
+        addresses: Vec<Address>,
+        token: impl Into<TokenLike> + Send + 'async_trait,
+    ) -> ResponseResult<BigUint> {
+        let transactions = tx_types
+            .into_iter()
+            .zip(addresses)
+            .map(|(tx_type, address)| TxInBatchFeeRequest {
+                tx_type: tx_type.into(),
+                address,
+            })
+            .collect();
+        let response = self.client.get_batch_fee(transactions, token.into()).await;
+        let fee: ApiFee = deserialize_response_result(response)?;
+        Ok(fee.total_fee)
+    }
+
+    /// Priority operations are reported as not executed until they're included in a block.
+    async fn ethop_info(&self, serial_id: u32) -> ResponseResult<EthOpInfo> {
+        let receipt = self.priority_op_receipt(SerialId::from(serial_id)).await?;
+        let block = match receipt {
+            Some(Receipt::L1(receipt)) => receipt.rollup_block.map(|block_number| BlockInfo {
+                block_number: i64::from(*block_number),
+                committed: true,
+                verified: receipt.status == TxInBlockStatus::Finalized,
+            }),
+            _ => None,
+        };
+
+        Ok(EthOpInfo {
+            executed: block.is_some(),
+            block,
+        })
+    }
+
+    async fn get_eth_tx_for_withdrawal(
+        &self,
+        withdrawal_hash: TxHash,
+    ) -> ResponseResult<Option<String>> {
+        let tx_data = self.tx_data(withdrawal_hash).await?;
+        let eth_tx_hash = tx_data.and_then(|data| match data.tx.op {
+            TransactionData::L2(L2Transaction::Withdraw(withdraw)) => withdraw.eth_tx_hash,
+            TransactionData::L2(L2Transaction::ForcedExit(forced_exit)) => forced_exit.eth_tx_hash,
+            TransactionData::L2(L2Transaction::WithdrawNFT(withdraw)) => withdraw.eth_tx_hash,
+            _ => None,
+        });
+        Ok(eth_tx_hash.map(|hash| format!("{:?}", hash)))
+    }
+
+    async fn contract_address(&self) -> ResponseResult<ContractAddress> {
+        let config: ApiConfig = deserialize_response_result(self.client.config().await)?;
+        Ok(ContractAddress {
+            main_contract: format!("{:?}", config.contract),
+            gov_contract: format!("{:?}", config.gov_contract),
+        })
+    }
+
+    async fn send_tx(
+        &self,
+        tx: ZkSyncTx,
+        eth_signature: Option<PackedEthSignature>,
+    ) -> ResponseResult<TxHash> {
+        let signature =
+            TxEthSignatureVariant::Single(eth_signature.map(TxEthSignature::EthereumSignature));
+        let response = self.client.submit_tx(tx, signature).await;
+        let tx_hash: TxHashSerializeWrapper = deserialize_response_result(response)?;
+        Ok(tx_hash.0)
+    }
+
+    async fn send_txs_batch(
+        &self,
+        txs_signed: Vec<(ZkSyncTx, Option<PackedEthSignature>)>,
+        eth_signature: Option<PackedEthSignature>,
+    ) -> ResponseResult<Vec<TxHash>> {
+        let txs = txs_signed
+            .into_iter()
+            .map(|(tx, signature)| TxWithSignature {
+                tx,
+                signature: TxEthSignatureVariant::Single(
+                    signature.map(TxEthSignature::EthereumSignature),
+                ),
+            })
+            .collect();
+        let signature = eth_signature.map(|signature| {
+            EthBatchSignatures::Single(TxEthSignature::EthereumSignature(signature))
+        });
+
+        let response = self.client.submit_batch(txs, signature).await;
+        let response: SubmitBatchResponse = deserialize_response_result(response)?;
+        Ok(response
+            .transaction_hashes
+            .into_iter()
+            .map(|tx_hash| tx_hash.0)
+            .collect())
+    }
+
+    fn network(&self) -> Network {
+        self.network
+    }
+}
+
+impl RestProvider {
+    /// Creates a new `RestProvider` connected to the desired zkSync network.
+    pub fn new(network: Network) -> Self {
+        Self::from_addr_and_network(get_rest_addr(network), network)
+    }
+
+    /// Creates a new `RestProvider` object connected to a custom address.
+    pub fn from_addr(rest_addr: impl Into<String>) -> Self {
+        Self::from_addr_and_network(rest_addr, Network::Unknown)
+    }
+
+    /// Creates a new `RestProvider` object connected to a custom address and the desired zkSync network.
+    pub fn from_addr_and_network(rest_addr: impl Into<String>, network: Network) -> Self {
+        Self {
+            client: Client::new(rest_addr.into()),
+            network,
+        }
+    }
+
+    /// Requests committed and finalized states of the account.
+    pub async fn account_state(&self, address: Address) -> ResponseResult<ApiAccountState> {
+        let response = self
+            .client
+            .account_full_info(&format!("{:?}", address))
+            .await;
+        deserialize_response_result(response)
+    }
+
+    /// Requests a page of transactions of the account.
+    pub async fn account_txs(
+        &self,
+        address: Address,
+        query: &PaginationQuery<ApiEither<TxHash>>,
+    ) -> ResponseResult<Paginated<Transaction, TxHashSerializeWrapper>> {
+        let response = self
+            .client
+            .account_txs(query, &format!("{:?}", address))
+            .await;
+        deserialize_response_result(response)
+    }
+
+    /// Requests a page of priority operations of the account which are not executed yet.
+    pub async fn account_pending_txs(
+        &self,
+        address: Address,
+        query: &PaginationQuery<ApiEither<SerialId>>,
+    ) -> ResponseResult<Paginated<Transaction, SerialId>> {
+        let response = self
+            .client
+            .account_pending_txs(query, &format!("{:?}", address))
+            .await;
+        deserialize_response_result(response)
+    }
+
+    /// Requests a page of NFTs owned by the account.
+    pub async fn account_nfts(
+        &self,
+        address: Address,
+        query: &PaginationQuery<ApiEither<TokenId>>,
+    ) -> ResponseResult<Paginated<ApiNFT, TokenId>> {
+        let response = self
+            .client
+            .account_nfts(query, &format!("{:?}", address))
+            .await;
+        deserialize_response_result(response)
+    }
+
+    /// Requests a page of NFTs minted by the account.
+    pub async fn account_minted_nfts(
+        &self,
+        address: Address,
+        query: &PaginationQuery<ApiEither<TokenId>>,
+    ) -> ResponseResult<Paginated<ApiNFT, TokenId>> {
+        let response = self
+            .client
+            .account_minted_nfts(query, &format!("{:?}", address))
+            .await;
+        deserialize_response_result(response)
+    }
+
+    /// Requests a page of tokens supported by zkSync.
+    pub async fn tokens_page(
+        &self,
+        query: &PaginationQuery<ApiEither<TokenId>>,
+    ) -> ResponseResult<Paginated<ApiToken, TokenId>> {
+        deserialize_response_result(self.client.token_pagination(query).await)
+    }
+
+    /// Requests the receipt of the transaction or priority operation.
+    /// Priority operations can be looked up by the Ethereum transaction hash as well.
+    pub async fn tx_receipt(&self, tx_hash: TxHash) -> ResponseResult<Option<Receipt>> {
+        deserialize_response_result(self.client.tx_status(tx_hash).await)
+    }
+
+    /// Requests the receipt of the executed priority operation.
+    pub async fn priority_op_receipt(
+        &self,
+        serial_id: SerialId,
+    ) -> ResponseResult<Option<Receipt>> {
+        deserialize_response_result(self.client.priority_op_receipt(serial_id).await)
+    }
+
+    /// Requests the transaction together with its execution status.
+    pub async fn tx_data(&self, tx_hash: TxHash) -> ResponseResult<Option<TxData>> {
+        deserialize_response_result(self.client.tx_data(tx_hash).await)
+    }
+
+    /// Requests the finalization status of the withdrawal on L1: whether funds were transferred
+    /// to the recipient, stored as the pending balance or the pending balance was claimed.
+    pub async fn withdrawal_status(
+        &self,
+        tx_hash: TxHash,
+    ) -> ResponseResult<Option<WithdrawalInfo>> {
+        deserialize_response_result(self.client.withdrawal_status(tx_hash).await)
+    }
+
+    /// Requests the status of the transactions batch.
+    pub async fn batch_info(&self, batch_hash: TxHash) -> ResponseResult<Option<ApiTxBatch>> {
+        deserialize_response_result(self.client.get_batch(batch_hash).await)
+    }
+
+    /// Requests the block information.
+    /// Block position is either the block number, `lastCommitted` or `lastFinalized`.
+    pub async fn block_info(&self, block_position: &str) -> ResponseResult<Option<ApiBlockInfo>> {
+        deserialize_response_result(self.client.block_by_position(block_position).await)
+    }
+
+    /// Requests a page of the block transactions.
+    pub async fn block_txs(
+        &self,
+        block_number: BlockNumber,
+        query: &PaginationQuery<ApiEither<TxHash>>,
+    ) -> ResponseResult<Paginated<Transaction, TxHashSerializeWrapper>> {
+        let response = self
+            .client
+            .block_transactions(query, &block_number.to_string())
+            .await;
+        deserialize_response_result(response)
+    }
+
+    /// Requests the last committed and finalized blocks and the mempool size.
+    pub async fn network_status(&self) -> ResponseResult<NetworkStatus> {
+        deserialize_response_result(self.client.status().await)
+    }
+
+    /// Collects the deposits to the account which are not executed yet.
+    async fn depositing_balances(
+        &self,
+        address: Address,
+    ) -> ResponseResult<DepositingAccountBalances> {
+        let mut deposits = Vec::new();
+        let mut from = 0;
+        loop {
+            let query = PaginationQuery {
+                from: ApiEither::from(from),
+                limit: MAX_LIMIT,
+                direction: PaginationDirection::Newer,
+            };
+            let page = self.account_pending_txs(address, &query).await?;
+            let is_last_page = page.list.len() < MAX_LIMIT as usize;
+
+            let mut last_id = None;
+            for tx in page.list {
+                match tx.op {
+                    TransactionData::L1(L1Transaction::Deposit(deposit)) => {
+                        last_id = Some(deposit.id);
+                        if deposit.to == address {
+                            deposits.push(deposit);
+                        }
+                    }
+                    TransactionData::L1(L1Transaction::FullExit(full_exit)) => {
+                        last_id = Some(full_exit.id);
+                    }
+                    TransactionData::L2(_) => {}
+                }
+            }
+
+            match last_id {
+                Some(last_id) if !is_last_page => from = last_id + 1,
+                _ => break,
+            }
+        }
+
+        let mut depositing = DepositingAccountBalances::default();
+        if deposits.is_empty() {
+            return Ok(depositing);
+        }
+
+        let config: ApiConfig = deserialize_response_result(self.client.config().await)?;
+        let tokens: HashMap<_, _> = self
+            .tokens()
+            .await?
+            .into_iter()
+            .map(|(symbol, token)| (token.id, symbol))
+            .collect();
+        for deposit in deposits {
+            // The operation may have been executed after the pending list was requested.
+            let eth_block = match self.tx_receipt(deposit.tx_hash).await? {
+                Some(Receipt::L1(receipt)) if receipt.status == TxInBlockStatus::Queued => {
+                    *receipt.eth_block
+                }
+                _ => continue,
+            };
+            let token_symbol = tokens.get(&deposit.token_id).cloned().ok_or_else(|| {
+                ClientError::MalformedResponse(format!("Unknown token {}", deposit.token_id))
+            })?;
+
+            let expected_accept_block = eth_block + config.deposit_confirmations;
+            let balance = depositing.balances.entry(token_symbol).or_default();
+            balance.amount += deposit.amount;
+            // The greatest block number among all the deposits of a certain token.
+            balance.expected_accept_block =
+                std::cmp::max(balance.expected_accept_block, expected_accept_block);
+        }
+
+        Ok(depositing)
+    }
+}
+
+/// Returns the fee type as it's reported by the JSON RPC API.
+/// Since REST API doesn't distinguish transfers to the new accounts, they're reported as `Transfer`.
+fn output_fee_type(tx_type: TxFeeTypes) -> OutputFeeType {
+    match tx_type {
+        TxFeeTypes::Transfer => OutputFeeType::Transfer,
+        TxFeeTypes::Withdraw => OutputFeeType::Withdraw,
+        TxFeeTypes::FastWithdraw => OutputFeeType::FastWithdraw,
+        TxFeeTypes::MintNFT => OutputFeeType::MintNFT,
+        TxFeeTypes::WithdrawNFT => OutputFeeType::WithdrawNFT,
+        TxFeeTypes::FastWithdrawNFT => OutputFeeType::FastWithdrawNFT,
+        TxFeeTypes::Swap => OutputFeeType::Swap,
+        TxFeeTypes::ChangePubKey(arg) => {
+            let fee_type = match arg {
+                ChangePubKeyFeeTypeArg::PreContracts4Version {
+                    onchain_pubkey_auth: true,
+                }
+                | ChangePubKeyFeeTypeArg::ContractsV4Version(ChangePubKeyType::Onchain) => {
+                    ChangePubKeyFeeType::Onchain
+                }
+                ChangePubKeyFeeTypeArg::PreContracts4Version {
+                    onchain_pubkey_auth: false,
+                }
+                | ChangePubKeyFeeTypeArg::ContractsV4Version(ChangePubKeyType::ECDSA) => {
+                    ChangePubKeyFeeType::ECDSA
+                }
+                ChangePubKeyFeeTypeArg::ContractsV4Version(ChangePubKeyType::CREATE2) => {
+                    ChangePubKeyFeeType::CREATE2
+                }
+            };
+            OutputFeeType::ChangePubKey(fee_type)
+        }
+    }
+}
+
+/// Extracts the result of the successful API call.
+fn deserialize_response_result<T: DeserializeOwned>(
+    response: Result<Response, RestClientError>,
+) -> ResponseResult<T> {
+    let response = response.map_err(|err| match err {
+        RestClientError::Parse(err) => ClientError::MalformedResponse(err.to_string()),
+        err => ClientError::NetworkError(err.to_string()),
+    })?;
+    if let Some(error) = response.error {
+        return Err(ClientError::ApiError(error.to_string()));
+    }
+    serde_json::from_value(response.result.unwrap_or_default())
+        .map_err(|err| ClientError::MalformedResponse(err.to_string()))
+}
@@ -3,7 +3,12 @@ use std::collections::HashMap;
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 
-use zksync_types::{AccountId, Address, Nonce, PubKeyHash, Token, TokenId, H256};
+use zksync_api_types::v02::{
+    account::Account as ApiAccount,
+    token::NFT as ApiNFT,
+    transaction::{Receipt, TxInBlockStatus},
+};
+use zksync_types::{AccountId, Address, BlockNumber, Nonce, PubKeyHash, Token, TokenId, H256};
 use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
 
 pub type Tokens = HashMap<String, Token>;
@@ -17,6 +22,17 @@ pub struct NFT {
     pub content_hash: H256,
 }
 
+impl From<ApiNFT> for NFT {
+    fn from(nft: ApiNFT) -> Self {
+        Self {
+            id: nft.id,
+            symbol: nft.symbol,
+            creator_id: nft.creator_id,
+            content_hash: nft.content_hash,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountState {
@@ -26,18 +42,33 @@ pub struct AccountState {
     pub pub_key_hash: PubKeyHash,
 }
 
+impl From<ApiAccount> for AccountState {
+    fn from(account: ApiAccount) -> Self {
+        Self {
+            balances: account.balances.into_iter().collect(),
+            nfts: account
+                .nfts
+                .into_iter()
+                .map(|(id, nft)| (id, nft.into()))
+                .collect(),
+            nonce: account.nonce,
+            pub_key_hash: account.pub_key_hash,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct DepositingFunds {
     #[serde(with = "BigUintSerdeAsRadix10Str")]
-    amount: BigUint,
-    expected_accept_block: u64,
+    pub amount: BigUint,
+    pub expected_accept_block: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct DepositingAccountBalances {
-    balances: HashMap<String, DepositingFunds>,
+    pub balances: HashMap<String, DepositingFunds>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,6 +112,40 @@ impl TransactionInfo {
     }
 }
 
+impl From<Option<Receipt>> for TransactionInfo {
+    fn from(receipt: Option<Receipt>) -> Self {
+        let (status, rollup_block, fail_reason) = match receipt {
+            Some(Receipt::L1(receipt)) => (receipt.status, receipt.rollup_block, None),
+            Some(Receipt::L2(receipt)) => {
+                (receipt.status, receipt.rollup_block, receipt.fail_reason)
+            }
+            None => (TxInBlockStatus::Queued, None, None),
+        };
+
+        let executed = status != TxInBlockStatus::Queued;
+        let success = match status {
+            TxInBlockStatus::Queued => None,
+            TxInBlockStatus::Rejected => Some(false),
+            TxInBlockStatus::Committed | TxInBlockStatus::Finalized => Some(true),
+        };
+        let block = rollup_block.map(|block_number: BlockNumber| BlockInfo {
+            block_number: i64::from(*block_number),
+            committed: matches!(
+                status,
+                TxInBlockStatus::Committed | TxInBlockStatus::Finalized
+            ),
+            verified: status == TxInBlockStatus::Finalized,
+        });
+
+        Self {
+            executed,
+            success,
+            fail_reason,
+            block,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct EthOpInfo {
@@ -162,6 +227,7 @@ pub enum OutputFeeType {
     MintNFT,
     WithdrawNFT,
     FastWithdrawNFT,
+    Swap,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1,6 +1,11 @@
 use num::BigUint;
+use zksync_api_types::v02::{
+    account::AccountState,
+    pagination::{ApiEither, Paginated, PaginationQuery},
+    transaction::{Transaction, TxHashSerializeWrapper},
+};
 use zksync_eth_signer::EthereumSigner;
-use zksync_types::{AccountId, Address, TokenId, TokenLike};
+use zksync_types::{tx::TxHash, AccountId, Address, TokenId, TokenLike};
 
 use crate::{
     credentials::WalletCredentials,
@@ -8,6 +13,7 @@ use crate::{
     ethereum::EthereumProvider,
     operations::*,
     provider::Provider,
+    rest_provider::RestProvider,
     signer::Signer,
     tokens_cache::TokensCache,
     types::{AccountInfo, BlockStatus, NFT},
@@ -181,3 +187,18 @@ where
         }
     }
 }
+
+impl<S: EthereumSigner> Wallet<S, RestProvider> {
+    /// Returns committed and finalized states of the account.
+    pub async fn account_state(&self) -> Result<AccountState, ClientError> {
+        self.provider.account_state(self.address()).await
+    }
+
+    /// Returns a page of the account transactions.
+    pub async fn account_txs(
+        &self,
+        query: &PaginationQuery<ApiEither<TxHash>>,
+    ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, ClientError> {
+        self.provider.account_txs(self.address(), query).await
+    }
+}
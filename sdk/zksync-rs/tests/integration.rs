@@ -39,8 +39,9 @@ use zksync::{
     zksync_types::{
         tx::PackedEthSignature, PriorityOp, PriorityOpId, Token, TokenLike, TxFeeTypes, ZkSyncTx,
     },
-    EthereumProvider, Network, RpcProvider, Wallet, WalletCredentials,
+    EthereumProvider, Network, RestProvider, RpcProvider, Wallet, WalletCredentials,
 };
+use zksync_api_types::v02::pagination::{ApiEither, PaginationDirection, PaginationQuery};
 use zksync_eth_signer::{EthereumSigner, PrivateKeySigner};
 
 const ETH_ADDR: &str = "36615Cf349d7F6344891B1e7CA7C72883F5dc049";
@@ -520,14 +521,18 @@ where
 /// Auxiliary function that generates a new wallet, performs an initial deposit and changes the public key.
 async fn init_account_with_one_ether(
 ) -> Result<Wallet<PrivateKeySigner, RpcProvider>, anyhow::Error> {
+    init_account_with_one_ether_and_provider(RpcProvider::new(Network::Localhost)).await
+}
+
+async fn init_account_with_one_ether_and_provider<P: Provider + Clone>(
+    provider: P,
+) -> Result<Wallet<PrivateKeySigner, P>, anyhow::Error> {
     let (eth_address, eth_private_key) = eth_random_account_credentials();
 
     // Transfer funds from "rich" account to a randomly created one (so we won't reuse the same
     // account in subsequent test runs).
     transfer_to("ETH", one_ether(), eth_address).await?;
 
-    let provider = RpcProvider::new(Network::Localhost);
-
     let eth_signer = PrivateKeySigner::new(eth_private_key);
     let credentials =
         WalletCredentials::from_eth_signer(eth_address, eth_signer, Network::Localhost)
@@ -907,3 +912,40 @@ async fn batch_transfer() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[tokio::test]
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+async fn rest_provider_test() -> Result<(), anyhow::Error> {
+    let wallet =
+        init_account_with_one_ether_and_provider(RestProvider::new(Network::Localhost)).await?;
+
+    let handle = wallet
+        .start_transfer()
+        .to(wallet.signer.address)
+        .token("ETH")?
+        .amount(1_000_000u64)
+        .send()
+        .await?;
+    let tx_hash = handle.hash();
+    handle
+        .commit_timeout(Duration::from_secs(180))
+        .wait_for_commit()
+        .await?;
+
+    // Committed state is updated right away, the finalized one waits for the block proof.
+    let state = wallet.account_state().await?;
+    let committed = state.committed.expect("Account should be committed");
+    assert!(*committed.nonce > 0);
+
+    let txs = wallet
+        .account_txs(&PaginationQuery {
+            from: ApiEither::from(tx_hash),
+            limit: 1,
+            direction: PaginationDirection::Newer,
+        })
+        .await?;
+    assert_eq!(txs.list.len(), 1);
+    assert_eq!(txs.list[0].tx_hash, tx_hash);
+
+    Ok(())
+}
@@ -1,8 +1,12 @@
 use std::collections::HashMap;
-use zksync::{tokens_cache::TokensCache, utils::*, web3::types::H160};
+use zksync::{tokens_cache::TokensCache, types::TransactionInfo, utils::*, web3::types::H160};
+use zksync_api_types::v02::transaction::{L2Receipt, Receipt, TxInBlockStatus};
 use zksync_config::test_config::unit_vectors::{Config as TestVectorsConfig, TestEntry};
 use zksync_crypto::PrivateKey;
-use zksync_types::{tx::TxSignature, AccountId, Nonce, Token, TokenId, TokenKind};
+use zksync_types::{
+    tx::{TxHash, TxSignature},
+    AccountId, BlockNumber, Nonce, Token, TokenId, TokenKind,
+};
 
 #[test]
 fn test_tokens_cache() {
@@ -81,6 +85,54 @@ fn assert_tx_signature(signature: &TxSignature, expected_pub: &str, expected_sig
     assert_eq!(hex::encode(packed_sig), expected_sig);
 }
 
+#[test]
+fn test_transaction_info_from_receipt() {
+    let receipt = |status, rollup_block, fail_reason| {
+        Some(Receipt::L2(L2Receipt {
+            tx_hash: TxHash::default(),
+            rollup_block,
+            status,
+            fail_reason,
+        }))
+    };
+
+    let info = TransactionInfo::from(None);
+    assert!(!info.executed);
+    assert!(info.success.is_none() && info.block.is_none());
+
+    let info = TransactionInfo::from(receipt(TxInBlockStatus::Queued, None, None));
+    assert!(!info.executed);
+
+    let info = TransactionInfo::from(receipt(
+        TxInBlockStatus::Committed,
+        Some(BlockNumber(5)),
+        None,
+    ));
+    assert!(info.executed);
+    assert_eq!(info.success, Some(true));
+    let block = info.block.as_ref().unwrap();
+    assert_eq!(block.block_number, 5);
+    assert!(block.committed && !block.verified);
+    assert!(!info.is_verified());
+
+    let info = TransactionInfo::from(receipt(
+        TxInBlockStatus::Finalized,
+        Some(BlockNumber(5)),
+        None,
+    ));
+    assert!(info.is_verified());
+
+    let info = TransactionInfo::from(receipt(
+        TxInBlockStatus::Rejected,
+        None,
+        Some("Nonce mismatch".into()),
+    ));
+    assert!(info.executed);
+    assert_eq!(info.success, Some(false));
+    assert_eq!(info.fail_reason.as_deref(), Some("Nonce mismatch"));
+    assert!(info.block.is_none());
+}
+
 #[cfg(test)]
 mod primitives_with_vectors {
     use super::*;
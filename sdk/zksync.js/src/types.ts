@@ -486,6 +486,7 @@ export interface TokenInfo {
     address: Address;
     symbol: string;
     decimals: number;
+    kind: 'ERC20' | 'NFT' | 'None';
     enabledForFees: boolean;
 }
 